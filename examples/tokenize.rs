@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use php_parser_rs::lexer::Lexer;
+
+// Compare tokenizing the PHP files of the given directories into tokens borrowing their
+// values from the files, and into owned tokens, which are the borrowed tokens with their
+// values copied.
+//
+// Only tokenizing is compared. The parser only reads owned tokens, and the AST owns its
+// text, so the time taken by parsing the files, tokenizing included, is shown as is.
+//
+// Defaults to the third-party projects cloned by the third-party tests:
+//
+//     cargo run -r --example tokenize -- [directories]
+fn main() -> Result<()> {
+    let mut directories = env::args().skip(1).map(PathBuf::from).collect::<Vec<_>>();
+    if directories.is_empty() {
+        directories.push(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/third-party"));
+    }
+
+    let mut files = Vec::new();
+    for directory in &directories {
+        collect(directory, &mut files)?;
+    }
+
+    let lexer = Lexer::new();
+    let bytes = files.iter().map(Vec::len).sum::<usize>();
+
+    let mut owned = (Duration::ZERO, 0, 0);
+    let mut borrowed = (Duration::ZERO, 0, 0);
+    let mut parsed = Duration::ZERO;
+    for file in &files {
+        let start = Instant::now();
+        let tokens = lexer.tokens(file).collect::<Vec<_>>();
+        owned.0 += start.elapsed();
+        owned.1 += tokens.len();
+        owned.2 += tokens.iter().map(|token| token.value.len()).sum::<usize>();
+
+        let start = Instant::now();
+        let tokens = lexer.borrowed_tokens(file).collect::<Vec<_>>();
+        borrowed.0 += start.elapsed();
+        borrowed.1 += tokens.len();
+        borrowed.2 += tokens
+            .iter()
+            .map(|token| match &token.value {
+                Cow::Owned(value) => value.len(),
+                Cow::Borrowed(_) => 0,
+            })
+            .sum::<usize>();
+
+        let start = Instant::now();
+        let _ = php_parser_rs::parse(file);
+        parsed += start.elapsed();
+    }
+
+    println!("{} files, {} bytes", files.len(), bytes);
+    for (mode, (elapsed, tokens, copied)) in [("owned", owned), ("borrowed", borrowed)] {
+        println!(
+            "{:>8}: {:>10.2?}, {} tokens, {} bytes copied",
+            mode, elapsed, tokens, copied
+        );
+    }
+    println!("{:>8}: {:>10.2?}", "parse", parsed);
+
+    Ok(())
+}
+
+fn collect(path: &Path, files: &mut Vec<Vec<u8>>) -> Result<()> {
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            collect(&entry?.path(), files)?;
+        }
+    } else if path.extension().is_some_and(|extension| extension == "php") {
+        files.push(fs::read(path)?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1246 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::Write;
+
+use crate::downcast::downcast;
+use crate::node::Node;
+use crate::parser::ast::control_flow::IfStatement;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::functions::MethodBody;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::loops::DoWhileStatement;
+use crate::parser::ast::loops::ForStatement;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatement;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatement;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::try_block::TryStatement;
+use crate::parser::ast::BoolExpression;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MatchExpression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::SwitchStatement;
+use crate::traverser::Visitor;
+
+/// The index of a block in [`ControlFlowGraph::blocks`].
+pub type BlockId = usize;
+
+/// The block every path starts from.
+pub const ENTRY: BlockId = 0;
+
+/// The block every path ends in, whether it returns, throws, exits, or falls off
+/// the end of the body.
+pub const EXIT: BlockId = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// Falling through to the next block.
+    Normal,
+    /// Taken when the condition evaluated last is truthy, a `case` or a `match` arm
+    /// matches, or a `foreach` loop has another element.
+    True,
+    /// Taken when the condition evaluated last is falsy.
+    False,
+    Break,
+    Continue,
+    Goto,
+    Return,
+    /// Taken by a `throw`, or by a `match` without a matching arm.
+    Throw,
+    /// Taken when any instruction of a `try` or `catch` block throws.
+    Exception,
+    /// Taken by `exit`, `die` and `__halt_compiler`.
+    Exit,
+}
+
+impl EdgeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeKind::Normal => "normal",
+            EdgeKind::True => "true",
+            EdgeKind::False => "false",
+            EdgeKind::Break => "break",
+            EdgeKind::Continue => "continue",
+            EdgeKind::Goto => "goto",
+            EdgeKind::Return => "return",
+            EdgeKind::Throw => "throw",
+            EdgeKind::Exception => "exception",
+            EdgeKind::Exit => "exit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub target: BlockId,
+    pub kind: EdgeKind,
+}
+
+/// A step of a basic block.
+///
+/// Control flow statements don't appear as such: they are split into the
+/// expressions they evaluate, and the blocks of their bodies.
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction<'a> {
+    /// A statement without control flow of its own, or one that ends its block,
+    /// such as `return`, `break` or `goto`.
+    Statement(&'a Statement),
+    /// An expression evaluated by a control flow statement, such as the condition
+    /// of an `if`, a `case`, or the body of a `match` arm.
+    Expression(&'a Expression),
+    /// The assignment of the key and value of a `foreach` loop, before each iteration.
+    ///
+    /// The iterated expression is evaluated once, by a preceding `Expression`.
+    Foreach(&'a ForeachStatementIterator),
+    /// The assignment of the caught exception, at the start of a `catch` block.
+    Catch(&'a CatchBlock),
+}
+
+#[derive(Debug, Clone)]
+pub struct BasicBlock<'a> {
+    /// The part of the code the block belongs to, such as `while.body`.
+    pub name: String,
+    pub instructions: Vec<Instruction<'a>>,
+    pub successors: Vec<Edge>,
+}
+
+/// The control flow graph of a function body, or of the top-level code of a file.
+///
+/// `match` expressions are split into blocks when they are a statement of their
+/// own; elsewhere they are evaluated as a whole, as are all other expressions.
+/// Exceptions thrown implicitly, e.g. by a function call, are only modelled within
+/// `try` statements, where every block has an `Exception` edge to each `catch`
+/// block, and to the `finally` block.
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph<'a> {
+    pub blocks: Vec<BasicBlock<'a>>,
+    // the block each statement starts in, in source order.
+    statements: Vec<(&'a Statement, BlockId)>,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    /// Build the graph of the given statements, e.g. the top-level code of a file.
+    pub fn new(statements: &'a [Statement]) -> Self {
+        let mut builder = Builder::new();
+        builder.statements(statements);
+        builder.finish()
+    }
+
+    pub fn from_function(body: &'a FunctionBody) -> Self {
+        Self::new(&body.statements)
+    }
+
+    pub fn from_method(body: &'a MethodBody) -> Self {
+        Self::new(&body.statements)
+    }
+
+    pub fn from_closure(closure: &'a ClosureExpression) -> Self {
+        Self::from_function(&closure.body)
+    }
+
+    pub fn from_arrow_function(function: &'a ArrowFunctionExpression) -> Self {
+        let mut builder = Builder::new();
+        builder.push(Instruction::Expression(&function.body));
+        if !builder.abrupt(&function.body) {
+            builder.edge(ENTRY, EXIT, EdgeKind::Return);
+        }
+
+        Self {
+            blocks: builder.blocks,
+            statements: vec![],
+        }
+    }
+
+    /// The blocks with an edge to `block`, along with the kind of the edge.
+    pub fn predecessors(&self, block: BlockId) -> Vec<(BlockId, EdgeKind)> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(id, source)| {
+                source
+                    .successors
+                    .iter()
+                    .filter(move |edge| edge.target == block)
+                    .map(move |edge| (id, edge.kind))
+            })
+            .collect()
+    }
+
+    /// Whether each block can be reached from the entry block.
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut stack = vec![ENTRY];
+
+        while let Some(block) = stack.pop() {
+            if reachable[block] {
+                continue;
+            }
+
+            reachable[block] = true;
+            stack.extend(self.blocks[block].successors.iter().map(|edge| edge.target));
+        }
+
+        reachable
+    }
+
+    /// The block in which `statement` starts, if it is part of the graph.
+    ///
+    /// Declarations nested in the statements, such as the body of a closure,
+    /// are not part of the graph.
+    pub fn block_of(&self, statement: &Statement) -> Option<BlockId> {
+        self.statements
+            .iter()
+            .find(|(candidate, _)| std::ptr::eq(*candidate, statement))
+            .map(|(_, block)| *block)
+    }
+
+    /// The statements of the graph, along with the block each one starts in, in source order.
+    pub fn statements(&self) -> impl Iterator<Item = (&'a Statement, BlockId)> + '_ {
+        self.statements.iter().copied()
+    }
+
+    /// Export the graph in the DOT format of Graphviz.
+    ///
+    /// Unreachable blocks are dashed.
+    pub fn to_dot(&self, name: &str) -> String {
+        let mut dot = String::new();
+
+        writeln!(dot, "digraph \"{}\" {{", escape(name)).unwrap();
+        writeln!(dot, "  node [shape=box, fontname=\"monospace\"];").unwrap();
+        self.write_dot(&mut dot, "b", "  ");
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String, prefix: &str, indent: &str) {
+        let reachable = self.reachable();
+
+        for (id, block) in self.blocks.iter().enumerate() {
+            let mut label = format!("{}: {}\\l", id, escape(&block.name));
+            for instruction in &block.instructions {
+                label.push_str(&escape(&describe(instruction)));
+                label.push_str("\\l");
+            }
+
+            let style = if reachable[id] { "" } else { ", style=dashed" };
+            writeln!(
+                dot,
+                "{}{}{} [label=\"{}\"{}];",
+                indent, prefix, id, label, style
+            )
+            .unwrap();
+        }
+
+        for (id, block) in self.blocks.iter().enumerate() {
+            for edge in &block.successors {
+                let attributes = match edge.kind {
+                    EdgeKind::Normal => String::new(),
+                    EdgeKind::Exception => " [label=\"exception\", style=dashed]".to_string(),
+                    kind => format!(" [label=\"{}\"]", kind.as_str()),
+                };
+
+                writeln!(
+                    dot,
+                    "{}{}{} -> {}{}{};",
+                    indent, prefix, id, prefix, edge.target, attributes
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+/// Export the graphs of the top-level code of `program`, and of every function,
+/// method, closure and arrow function in it, as a single DOT graph with a cluster
+/// for each of them.
+pub fn to_dot(program: &mut Program) -> String {
+    let mut dot = String::new();
+
+    writeln!(dot, "digraph cfg {{").unwrap();
+    writeln!(dot, "  node [shape=box, fontname=\"monospace\"];").unwrap();
+
+    let mut graphs = vec![(
+        "{main}".to_string(),
+        ControlFlowGraph::new(program).cluster(),
+    )];
+
+    let mut collector = DotCollector { graphs: vec![] };
+    let Ok(()) = collector.visit_node(program);
+    graphs.extend(collector.graphs);
+
+    for (index, (name, cluster)) in graphs.iter().enumerate() {
+        writeln!(dot, "  subgraph cluster_{} {{", index).unwrap();
+        writeln!(dot, "    label=\"{}\";", escape(name)).unwrap();
+        dot.push_str(&cluster.replace("{prefix}", &format!("g{}_", index)));
+        dot.push_str("  }\n");
+    }
+
+    dot.push_str("}\n");
+
+    dot
+}
+
+impl ControlFlowGraph<'_> {
+    // the nodes and edges of the graph, with `{prefix}` in place of the prefix of its cluster.
+    fn cluster(&self) -> String {
+        let mut dot = String::new();
+        self.write_dot(&mut dot, "{prefix}b", "    ");
+
+        dot
+    }
+}
+
+struct DotCollector {
+    graphs: Vec<(String, String)>,
+}
+
+impl Visitor<Infallible> for DotCollector {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        let node: &dyn Node = node;
+
+        let graph = if let Some(function) = downcast::<FunctionStatement>(node) {
+            Some((
+                format!("function {}", function.name),
+                ControlFlowGraph::from_function(&function.body),
+            ))
+        } else if let Some(method) = downcast::<ConcreteMethod>(node) {
+            Some((
+                format!("method {}", method.name),
+                ControlFlowGraph::from_method(&method.body),
+            ))
+        } else if let Some(constructor) = downcast::<ConcreteConstructor>(node) {
+            Some((
+                format!("method {}", constructor.name),
+                ControlFlowGraph::from_method(&constructor.body),
+            ))
+        } else if let Some(closure) = downcast::<ClosureExpression>(node) {
+            Some((
+                format!("closure on line {}", closure.function.line),
+                ControlFlowGraph::from_closure(closure),
+            ))
+        } else {
+            downcast::<ArrowFunctionExpression>(node).map(|function| {
+                (
+                    format!("arrow function on line {}", function.r#fn.line),
+                    ControlFlowGraph::from_arrow_function(function),
+                )
+            })
+        };
+
+        if let Some((name, graph)) = graph {
+            self.graphs.push((name, graph.cluster()));
+        }
+
+        Ok(())
+    }
+}
+
+// the condition and the body of an `if` or `elseif`.
+type Branch<'a> = (&'a Expression, &'a [Statement]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Jump {
+    // the index of the targeted loop in `Builder::frames`.
+    Break(usize),
+    Continue(usize),
+    Return,
+    Throw,
+}
+
+enum Frame {
+    // a loop, or a `switch`, which `break` and `continue` treat as a loop.
+    Loop {
+        r#break: BlockId,
+        r#continue: BlockId,
+    },
+    Try {
+        // the `catch` blocks, while building the `try` block.
+        handlers: Vec<BlockId>,
+        finally: Option<BlockId>,
+        // the jumps that were routed through the `finally` block, and resume after it.
+        pending: Vec<(Jump, EdgeKind)>,
+    },
+}
+
+struct Builder<'a> {
+    blocks: Vec<BasicBlock<'a>>,
+    statements: Vec<(&'a Statement, BlockId)>,
+    current: BlockId,
+    frames: Vec<Frame>,
+    labels: HashMap<String, BlockId>,
+}
+
+impl<'a> Builder<'a> {
+    fn new() -> Self {
+        let mut builder = Self {
+            blocks: vec![],
+            statements: vec![],
+            current: ENTRY,
+            frames: vec![],
+            labels: HashMap::new(),
+        };
+
+        builder.block("entry");
+        builder.block("exit");
+
+        builder
+    }
+
+    fn finish(mut self) -> ControlFlowGraph<'a> {
+        if !self.is_dead() {
+            self.edge(self.current, EXIT, EdgeKind::Normal);
+        }
+
+        ControlFlowGraph {
+            blocks: self.blocks,
+            statements: self.statements,
+        }
+    }
+
+    fn block(&mut self, name: impl ToString) -> BlockId {
+        self.blocks.push(BasicBlock {
+            name: name.to_string(),
+            instructions: vec![],
+            successors: vec![],
+        });
+
+        self.blocks.len() - 1
+    }
+
+    fn edge(&mut self, from: BlockId, to: BlockId, kind: EdgeKind) {
+        let edge = Edge { target: to, kind };
+        if !self.blocks[from].successors.contains(&edge) {
+            self.blocks[from].successors.push(edge);
+        }
+    }
+
+    fn push(&mut self, instruction: Instruction<'a>) {
+        self.blocks[self.current].instructions.push(instruction);
+    }
+
+    // fall through from the current block to `block`, and continue there.
+    fn enter(&mut self, block: BlockId) {
+        self.edge(self.current, block, EdgeKind::Normal);
+        self.current = block;
+    }
+
+    // continue in a new block after a jump, which is unreachable unless it is labeled.
+    fn terminate(&mut self) {
+        self.current = self.block("dead");
+    }
+
+    // whether the current block was started by `terminate`, and nothing jumps to it.
+    fn is_dead(&self) -> bool {
+        self.current != ENTRY
+            && !self
+                .blocks
+                .iter()
+                .any(|block| block.successors.iter().any(|e| e.target == self.current))
+    }
+
+    fn label(&mut self, label: &SimpleIdentifier) -> BlockId {
+        let name = label.value.to_string();
+        if let Some(block) = self.labels.get(&name) {
+            return *block;
+        }
+
+        let block = self.block(format!("label {}", name));
+        self.labels.insert(name, block);
+
+        block
+    }
+
+    fn statements(&mut self, statements: &'a [Statement]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &'a Statement) {
+        // labels start a block of their own.
+        if !matches!(statement, Statement::Label(_)) {
+            self.statements.push((statement, self.current));
+        }
+
+        match statement {
+            Statement::If(r#if) => self.r#if(r#if),
+            Statement::While(r#while) => self.r#while(r#while),
+            Statement::DoWhile(do_while) => self.do_while(do_while),
+            Statement::For(r#for) => self.r#for(r#for),
+            Statement::Foreach(foreach) => self.foreach(foreach),
+            Statement::Switch(switch) => self.switch(switch),
+            Statement::Try(r#try) => self.r#try(r#try),
+            Statement::Block(block) => self.statements(&block.statements),
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                self.statements(&namespace.statements)
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                self.statements(&namespace.body.statements)
+            }
+            Statement::Declare(declare) => match &declare.body {
+                DeclareBody::Braced { statements, .. } | DeclareBody::Block { statements, .. } => {
+                    self.statements(statements)
+                }
+                DeclareBody::Noop { .. } | DeclareBody::Expression { .. } => {
+                    self.push(Instruction::Statement(statement))
+                }
+            },
+            Statement::Label(label) => {
+                let block = self.label(&label.label);
+                self.enter(block);
+                self.statements.push((statement, block));
+            }
+            Statement::Goto(goto) => {
+                self.push(Instruction::Statement(statement));
+
+                let block = self.label(&goto.label);
+                self.edge(self.current, block, EdgeKind::Goto);
+                self.terminate();
+            }
+            Statement::Break(r#break) => {
+                self.push(Instruction::Statement(statement));
+                self.loop_control(&r#break.level, true);
+            }
+            Statement::Continue(r#continue) => {
+                self.push(Instruction::Statement(statement));
+                self.loop_control(&r#continue.level, false);
+            }
+            Statement::Return(_) => {
+                self.push(Instruction::Statement(statement));
+                self.jump(Jump::Return, EdgeKind::Return);
+                self.terminate();
+            }
+            Statement::HaltCompiler(_) => {
+                self.push(Instruction::Statement(statement));
+                self.edge(self.current, EXIT, EdgeKind::Exit);
+                self.terminate();
+            }
+            Statement::Expression(expression) => match &expression.expression {
+                Expression::Match(r#match) => self.r#match(r#match),
+                expression => {
+                    self.push(Instruction::Statement(statement));
+
+                    if self.abrupt(expression) {
+                        self.terminate();
+                    }
+                }
+            },
+            _ => self.push(Instruction::Statement(statement)),
+        }
+    }
+
+    // add the edge taken after evaluating `expression` if it throws or exits,
+    // returning whether it does.
+    fn abrupt(&mut self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Throw(_) => self.jump(Jump::Throw, EdgeKind::Throw),
+            Expression::Exit(_) | Expression::Die(_) => {
+                self.edge(self.current, EXIT, EdgeKind::Exit)
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    // jump from the current block, through the `finally` blocks in between.
+    fn jump(&mut self, jump: Jump, kind: EdgeKind) {
+        let from = self.current;
+
+        for index in (0..self.frames.len()).rev() {
+            match &mut self.frames[index] {
+                Frame::Loop {
+                    r#break,
+                    r#continue,
+                } => match jump {
+                    Jump::Break(target) if target == index => {
+                        let r#break = *r#break;
+                        return self.edge(from, r#break, kind);
+                    }
+                    Jump::Continue(target) if target == index => {
+                        let r#continue = *r#continue;
+                        return self.edge(from, r#continue, kind);
+                    }
+                    _ => {}
+                },
+                Frame::Try {
+                    handlers,
+                    finally,
+                    pending,
+                } => {
+                    // exceptions that aren't caught keep going, to the `finally` block or further.
+                    let handlers = if jump == Jump::Throw {
+                        handlers.clone()
+                    } else {
+                        vec![]
+                    };
+
+                    let finally = *finally;
+                    if finally.is_some() && !pending.contains(&(jump, kind)) {
+                        pending.push((jump, kind));
+                    }
+
+                    for handler in handlers {
+                        self.edge(from, handler, kind);
+                    }
+
+                    if let Some(finally) = finally {
+                        return self.edge(from, finally, kind);
+                    }
+                }
+            }
+        }
+
+        self.edge(from, EXIT, kind);
+    }
+
+    fn loop_control(&mut self, level: &Option<Level>, is_break: bool) {
+        let level = match level {
+            Some(level) => level.value().unwrap_or(0),
+            None => 1,
+        };
+
+        // an invalid level is a compile time error, reported by the validation.
+        let frame = self
+            .frames
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, frame)| matches!(frame, Frame::Loop { .. }))
+            .nth(level.wrapping_sub(1))
+            .map(|(index, _)| index);
+
+        if let Some(frame) = frame {
+            if is_break {
+                self.jump(Jump::Break(frame), EdgeKind::Break);
+            } else {
+                self.jump(Jump::Continue(frame), EdgeKind::Continue);
+            }
+        }
+
+        self.terminate();
+    }
+
+    fn looped(&mut self, r#break: BlockId, r#continue: BlockId, body: &'a [Statement]) {
+        self.frames.push(Frame::Loop {
+            r#break,
+            r#continue,
+        });
+        self.statements(body);
+        self.frames.pop();
+    }
+
+    fn r#if(&mut self, statement: &'a IfStatement) {
+        let (mut branches, r#else): (Vec<Branch>, Option<&[Statement]>) = match &statement.body {
+            IfStatementBody::Statement {
+                statement: then,
+                elseifs,
+                r#else,
+            } => (
+                std::iter::once((&statement.condition, std::slice::from_ref(then.as_ref())))
+                    .chain(elseifs.iter().map(|elseif| {
+                        (
+                            &elseif.condition,
+                            std::slice::from_ref(elseif.statement.as_ref()),
+                        )
+                    }))
+                    .collect(),
+                r#else
+                    .as_ref()
+                    .map(|r#else| std::slice::from_ref(r#else.statement.as_ref())),
+            ),
+            IfStatementBody::Block {
+                statements,
+                elseifs,
+                r#else,
+                ..
+            } => (
+                std::iter::once((&statement.condition, statements.as_slice()))
+                    .chain(
+                        elseifs
+                            .iter()
+                            .map(|elseif| (&elseif.condition, elseif.statements.as_slice())),
+                    )
+                    .collect(),
+                r#else.as_ref().map(|r#else| r#else.statements.as_slice()),
+            ),
+        };
+
+        let after = self.block("if.end");
+        let count = branches.len();
+
+        for (index, (condition, body)) in branches.drain(..).enumerate() {
+            self.push(Instruction::Expression(condition));
+
+            let then = self.block("if.then");
+            let next = if index + 1 < count {
+                self.block("if.elseif")
+            } else if r#else.is_some() {
+                self.block("if.else")
+            } else {
+                after
+            };
+
+            self.condition(condition, then, next);
+
+            self.current = then;
+            self.statements(body);
+            self.edge(self.current, after, EdgeKind::Normal);
+
+            self.current = next;
+        }
+
+        if let Some(body) = r#else {
+            self.statements(body);
+            self.edge(self.current, after, EdgeKind::Normal);
+        }
+
+        self.current = after;
+    }
+
+    fn r#while(&mut self, statement: &'a WhileStatement) {
+        let header = self.block("while.condition");
+        self.enter(header);
+        self.push(Instruction::Expression(&statement.condition));
+
+        let body = self.block("while.body");
+        let after = self.block("while.end");
+        self.condition(&statement.condition, body, after);
+
+        self.current = body;
+        self.looped(
+            after,
+            header,
+            match &statement.body {
+                WhileStatementBody::Statement { statement } => {
+                    std::slice::from_ref(statement.as_ref())
+                }
+                WhileStatementBody::Block { statements, .. } => statements,
+            },
+        );
+
+        self.edge(self.current, header, EdgeKind::Normal);
+        self.current = after;
+    }
+
+    fn do_while(&mut self, statement: &'a DoWhileStatement) {
+        let body = self.block("do.body");
+        let condition = self.block("do.condition");
+        let after = self.block("do.end");
+
+        self.enter(body);
+        self.looped(
+            after,
+            condition,
+            std::slice::from_ref(statement.body.as_ref()),
+        );
+
+        self.enter(condition);
+        self.push(Instruction::Expression(&statement.condition));
+        self.condition(&statement.condition, body, after);
+
+        self.current = after;
+    }
+
+    fn r#for(&mut self, statement: &'a ForStatement) {
+        let iterator = &statement.iterator;
+        for initialization in &iterator.initializations.inner {
+            self.push(Instruction::Expression(initialization));
+        }
+
+        let header = self.block("for.condition");
+        let body = self.block("for.body");
+        let step = self.block("for.step");
+        let after = self.block("for.end");
+
+        self.enter(header);
+        for condition in &iterator.conditions.inner {
+            self.push(Instruction::Expression(condition));
+        }
+
+        // all the conditions are evaluated, but only the last one is checked.
+        match iterator.conditions.inner.last() {
+            Some(condition) => self.condition(condition, body, after),
+            None => self.edge(header, body, EdgeKind::True),
+        }
+
+        self.current = body;
+        self.looped(
+            after,
+            step,
+            match &statement.body {
+                ForStatementBody::Statement { statement } => {
+                    std::slice::from_ref(statement.as_ref())
+                }
+                ForStatementBody::Block { statements, .. } => statements,
+            },
+        );
+
+        self.enter(step);
+        for expression in &iterator.r#loop.inner {
+            self.push(Instruction::Expression(expression));
+        }
+
+        self.edge(step, header, EdgeKind::Normal);
+        self.current = after;
+    }
+
+    fn foreach(&mut self, statement: &'a ForeachStatement) {
+        let expression = match &statement.iterator {
+            ForeachStatementIterator::Value { expression, .. }
+            | ForeachStatementIterator::KeyAndValue { expression, .. } => expression,
+        };
+
+        self.push(Instruction::Expression(expression));
+
+        let header = self.block("foreach.next");
+        let body = self.block("foreach.body");
+        let after = self.block("foreach.end");
+
+        self.enter(header);
+        self.push(Instruction::Foreach(&statement.iterator));
+        self.edge(header, body, EdgeKind::True);
+        self.edge(header, after, EdgeKind::False);
+
+        self.current = body;
+        self.looped(
+            after,
+            header,
+            match &statement.body {
+                ForeachStatementBody::Statement { statement } => {
+                    std::slice::from_ref(statement.as_ref())
+                }
+                ForeachStatementBody::Block { statements, .. } => statements,
+            },
+        );
+
+        self.edge(self.current, header, EdgeKind::Normal);
+        self.current = after;
+    }
+
+    // branch to `then` or `otherwise` after evaluating `condition`, omitting the
+    // branch that a constant condition never takes.
+    fn condition(&mut self, condition: &Expression, then: BlockId, otherwise: BlockId) {
+        let constant = match condition {
+            Expression::Bool(BoolExpression { value, .. }) => Some(*value),
+            _ => None,
+        };
+
+        if constant != Some(false) {
+            self.edge(self.current, then, EdgeKind::True);
+        }
+
+        if constant != Some(true) {
+            self.edge(self.current, otherwise, EdgeKind::False);
+        }
+    }
+
+    fn switch(&mut self, statement: &'a SwitchStatement) {
+        self.push(Instruction::Expression(&statement.condition));
+
+        let bodies = statement
+            .cases
+            .iter()
+            .map(|case| {
+                self.block(if case.condition.is_some() {
+                    "switch.case"
+                } else {
+                    "switch.default"
+                })
+            })
+            .collect::<Vec<BlockId>>();
+        let after = self.block("switch.end");
+
+        // the cases are compared in order, and the default case is taken last,
+        // wherever it is.
+        let default = statement
+            .cases
+            .iter()
+            .position(|case| case.condition.is_none())
+            .map(|index| bodies[index])
+            .unwrap_or(after);
+
+        let tests = statement
+            .cases
+            .iter()
+            .zip(&bodies)
+            .filter_map(|(case, body)| case.condition.as_ref().map(|c| (c, *body)))
+            .collect::<Vec<(&Expression, BlockId)>>();
+
+        for (index, (condition, body)) in tests.iter().enumerate() {
+            self.push(Instruction::Expression(condition));
+
+            let next = if index + 1 < tests.len() {
+                self.block("switch.test")
+            } else {
+                default
+            };
+
+            self.edge(self.current, *body, EdgeKind::True);
+            self.edge(self.current, next, EdgeKind::False);
+            self.current = next;
+        }
+
+        if tests.is_empty() {
+            self.edge(self.current, default, EdgeKind::Normal);
+        }
+
+        self.frames.push(Frame::Loop {
+            r#break: after,
+            r#continue: after,
+        });
+
+        for (index, case) in statement.cases.iter().enumerate() {
+            self.current = bodies[index];
+            self.statements(&case.body);
+
+            // cases without a `break` fall through to the next one.
+            let next = bodies.get(index + 1).copied().unwrap_or(after);
+            self.edge(self.current, next, EdgeKind::Normal);
+        }
+
+        self.frames.pop();
+        self.current = after;
+    }
+
+    fn r#match(&mut self, expression: &'a MatchExpression) {
+        self.push(Instruction::Expression(&expression.condition));
+
+        let after = self.block("match.end");
+
+        for arm in &expression.arms {
+            let body = self.block("match.arm");
+
+            for condition in &arm.conditions {
+                self.push(Instruction::Expression(condition));
+                self.edge(self.current, body, EdgeKind::True);
+
+                let next = self.block("match.test");
+                self.edge(self.current, next, EdgeKind::False);
+                self.current = next;
+            }
+
+            let test = self.current;
+            self.current = body;
+            self.push(Instruction::Expression(&arm.body));
+            if !self.abrupt(&arm.body) {
+                self.edge(self.current, after, EdgeKind::Normal);
+            }
+            self.current = test;
+        }
+
+        match &expression.default {
+            Some(default) => {
+                let body = self.block("match.default");
+                self.enter(body);
+                self.push(Instruction::Expression(&default.body));
+                if !self.abrupt(&default.body) {
+                    self.edge(self.current, after, EdgeKind::Normal);
+                }
+            }
+            // an `UnhandledMatchError` is thrown when no arm matches.
+            None => self.jump(Jump::Throw, EdgeKind::Throw),
+        }
+
+        self.current = after;
+    }
+
+    fn r#try(&mut self, statement: &'a TryStatement) {
+        let handlers = statement
+            .catches
+            .iter()
+            .map(|_| self.block("catch"))
+            .collect::<Vec<BlockId>>();
+        let finally = statement.finally.as_ref().map(|_| self.block("finally"));
+        let after = self.block("try.end");
+
+        let body = self.block("try.body");
+        self.enter(body);
+
+        self.frames.push(Frame::Try {
+            handlers: handlers.clone(),
+            finally,
+            pending: vec![],
+        });
+
+        self.statements(&statement.body);
+        self.exceptions(body..self.blocks.len(), &handlers, finally);
+
+        // whether the `finally` block can be entered without a jump.
+        let mut completes = false;
+        match finally {
+            Some(finally) if !self.is_dead() => {
+                completes = true;
+                self.edge(self.current, finally, EdgeKind::Normal);
+            }
+            Some(_) => {}
+            None => self.edge(self.current, after, EdgeKind::Normal),
+        }
+
+        if let Some(Frame::Try { handlers, .. }) = self.frames.last_mut() {
+            handlers.clear();
+        }
+
+        for (catch, handler) in statement.catches.iter().zip(&handlers) {
+            self.current = *handler;
+            self.push(Instruction::Catch(catch));
+
+            let start = self.blocks.len();
+            self.statements(&catch.body);
+            self.exceptions(start..self.blocks.len(), &[], finally);
+            self.exceptions(*handler..*handler + 1, &[], finally);
+
+            match finally {
+                Some(finally) if !self.is_dead() => {
+                    completes = true;
+                    self.edge(self.current, finally, EdgeKind::Normal);
+                }
+                Some(_) => {}
+                None => self.edge(self.current, after, EdgeKind::Normal),
+            }
+        }
+
+        let Some(Frame::Try { pending, .. }) = self.frames.pop() else {
+            unreachable!("the `try` frame should be the last one");
+        };
+
+        if let (Some(block), Some(finally)) = (finally, &statement.finally) {
+            self.current = block;
+            self.statements(&finally.body);
+
+            let end = self.current;
+            if completes {
+                self.edge(end, after, EdgeKind::Normal);
+            }
+
+            // resume the jumps that were interrupted by the `finally` block.
+            for (jump, kind) in pending {
+                self.current = end;
+                self.jump(jump, kind);
+            }
+        }
+
+        self.current = after;
+    }
+
+    // add the edges taken when any instruction of the given blocks throws.
+    fn exceptions(
+        &mut self,
+        blocks: std::ops::Range<BlockId>,
+        handlers: &[BlockId],
+        finally: Option<BlockId>,
+    ) {
+        if blocks.is_empty() {
+            return;
+        }
+
+        for block in blocks {
+            for handler in handlers {
+                self.edge(block, *handler, EdgeKind::Exception);
+            }
+
+            if let Some(finally) = finally {
+                self.edge(block, finally, EdgeKind::Exception);
+            }
+        }
+
+        if finally.is_some() {
+            if let Some(Frame::Try { pending, .. }) = self.frames.last_mut() {
+                if !pending.contains(&(Jump::Throw, EdgeKind::Throw)) {
+                    pending.push((Jump::Throw, EdgeKind::Throw));
+                }
+            }
+        }
+    }
+}
+
+// a short description of an instruction, e.g. `Echo` or `Call`, for the DOT export.
+fn describe(instruction: &Instruction) -> String {
+    let (kind, debug) = match instruction {
+        Instruction::Statement(statement) => ("statement", format!("{:?}", statement)),
+        Instruction::Expression(expression) => ("expression", format!("{:?}", expression)),
+        Instruction::Foreach(_) => return "foreach".to_string(),
+        Instruction::Catch(_) => return "catch".to_string(),
+    };
+
+    let variant = debug
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+
+    format!("{} {}", kind, variant)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // whether each top-level statement, after the opening tag, is reachable.
+    fn reachable(code: &str) -> Vec<bool> {
+        let program = crate::parse(code).unwrap();
+        let cfg = ControlFlowGraph::new(&program);
+        let reachable = cfg.reachable();
+
+        program[1..]
+            .iter()
+            .map(|statement| reachable[cfg.block_of(statement).unwrap()])
+            .collect()
+    }
+
+    #[test]
+    fn test_branches() {
+        let program =
+            crate::parse("<?php if ($a) { a(); } elseif ($b) { b(); } else { c(); } d();").unwrap();
+        let cfg = ControlFlowGraph::new(&program);
+        let end = cfg.block_of(&program[2]).unwrap();
+
+        assert_eq!(cfg.blocks[end].name, "if.end");
+        assert_eq!(cfg.predecessors(end).len(), 3);
+        assert!(cfg.reachable().iter().all(|reachable| *reachable));
+
+        let program = crate::parse("<?php if (false) { a(); } else { b(); }").unwrap();
+        let cfg = ControlFlowGraph::new(&program);
+        let Statement::If(r#if) = &program[1] else {
+            panic!("expected an if");
+        };
+
+        let IfStatementBody::Statement {
+            statement, r#else, ..
+        } = &r#if.body
+        else {
+            panic!("expected an if with statements");
+        };
+
+        assert!(!cfg.reachable()[cfg.block_of(statement).unwrap()]);
+        assert!(cfg.reachable()[cfg.block_of(&r#else.as_ref().unwrap().statement).unwrap()]);
+    }
+
+    #[test]
+    fn test_loops() {
+        assert_eq!(reachable("<?php while (true) {} a();"), [true, false]);
+        assert_eq!(
+            reachable("<?php while (true) { break; } a();"),
+            [true, true]
+        );
+        assert_eq!(reachable("<?php while ($a) {} a();"), [true, true]);
+        assert_eq!(reachable("<?php while (false) { a(); }"), [true]);
+        assert_eq!(reachable("<?php for (;;) {} a();"), [true, false]);
+        assert_eq!(reachable("<?php do {} while (true); a();"), [true, false]);
+        assert_eq!(
+            reachable("<?php while (true) { foreach ($a as $b) { break 2; } } a();"),
+            [true, true]
+        );
+        assert_eq!(
+            reachable("<?php while (true) { foreach ($a as $b) { continue 2; } } a();"),
+            [true, false]
+        );
+        assert_eq!(
+            reachable("<?php while (true) { switch ($a) { default: continue 2; } } a();"),
+            [true, false]
+        );
+    }
+
+    #[test]
+    fn test_switch_fallthrough() {
+        let program =
+            crate::parse("<?php switch ($a) { case 1: a(); case 2: b(); break; default: c(); }")
+                .unwrap();
+        let cfg = ControlFlowGraph::new(&program);
+
+        let Statement::Switch(switch) = &program[1] else {
+            panic!("expected a switch");
+        };
+
+        let first = cfg.block_of(&switch.cases[0].body[0]).unwrap();
+        let second = cfg.block_of(&switch.cases[1].body[0]).unwrap();
+        assert!(cfg.blocks[first].successors.contains(&Edge {
+            target: second,
+            kind: EdgeKind::Normal
+        }));
+        assert!(cfg.statements().all(|(_, block)| cfg.reachable()[block]));
+    }
+
+    #[test]
+    fn test_jumps() {
+        assert_eq!(reachable("<?php return; a();"), [true, false]);
+        assert_eq!(reachable("<?php exit(1); a();"), [true, false]);
+        assert_eq!(reachable("<?php throw new E(); a();"), [true, false]);
+        assert_eq!(
+            reachable("<?php goto a; b(); a: c();"),
+            [true, false, true, true]
+        );
+        assert_eq!(
+            reachable("<?php match ($a) { 1 => a(), default => throw new E() }; b();"),
+            [true, true]
+        );
+        assert_eq!(
+            reachable("<?php match ($a) { 1 => die(), default => throw new E() }; b();"),
+            [true, false]
+        );
+    }
+
+    #[test]
+    fn test_try() {
+        assert_eq!(
+            reachable("<?php try { return; } finally { a(); } b();"),
+            [true, false]
+        );
+        assert_eq!(
+            reachable("<?php try { a(); } catch (E $e) { return; } b();"),
+            [true, true]
+        );
+        assert_eq!(
+            reachable("<?php try { return; } catch (E $e) { return; } b();"),
+            [true, false]
+        );
+        assert_eq!(
+            reachable("<?php while (true) { try { break; } finally { a(); } } b();"),
+            [true, true]
+        );
+
+        // the `finally` block rethrows the exceptions of the `try` block.
+        let program = crate::parse("<?php try { a(); } finally { b(); }").unwrap();
+        let cfg = ControlFlowGraph::new(&program);
+        let Statement::Try(r#try) = &program[1] else {
+            panic!("expected a try");
+        };
+
+        let finally = cfg
+            .block_of(&r#try.finally.as_ref().unwrap().body[0])
+            .unwrap();
+        assert!(cfg.blocks[finally].successors.contains(&Edge {
+            target: EXIT,
+            kind: EdgeKind::Throw
+        }));
+    }
+
+    #[test]
+    fn test_dot() {
+        let mut program =
+            crate::parse("<?php function a() { if ($a) { return; } } $b = fn() => 1;").unwrap();
+        let dot = to_dot(&mut program);
+
+        assert!(dot.starts_with("digraph cfg {"));
+        assert!(dot.contains("label=\"function a\";"));
+        assert!(dot.contains("label=\"arrow function on line 1\";"));
+        assert!(dot.contains("g1_b0 -> g1_b3 [label=\"true\"];"));
+    }
+}
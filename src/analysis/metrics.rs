@@ -0,0 +1,1196 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::downcast::downcast;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::control_flow::IfStatementElse;
+use crate::parser::ast::control_flow::IfStatementElseBlock;
+use crate::parser::ast::control_flow::IfStatementElseIf;
+use crate::parser::ast::control_flow::IfStatementElseIfBlock;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceExtends;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::namespaces::BracedNamespace;
+use crate::parser::ast::namespaces::UnbracedNamespace;
+use crate::parser::ast::operators::ArithmeticOperationExpression;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::operators::BitwiseOperationExpression;
+use crate::parser::ast::operators::ComparisonOperationExpression;
+use crate::parser::ast::operators::LogicalOperationExpression;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::FinallyBlock;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::Case;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MagicConstantExpression;
+use crate::parser::ast::MatchArm;
+use crate::parser::ast::ParenthesizedExpression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::traverser::Visitor;
+
+/// The metrics of the functions and classes of a file.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Metrics {
+    pub functions: Vec<FunctionMetrics>,
+    pub classes: Vec<ClassMetrics>,
+}
+
+/// The metrics of a function, or of a method with a body.
+///
+/// The closures and arrow functions of a function are measured as part of it.
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionMetrics {
+    /// The name of a function, qualified by its namespace, or the name of a method.
+    pub name: String,
+    /// The line of the `function` keyword.
+    pub line: usize,
+    /// The number of independent paths through the function: one, plus one for each
+    /// `if`, `elseif`, loop, `case`, `catch`, `match` arm condition, and each `&&`, `||`,
+    /// `and`, `or`, `?:`, `?` and `??` operator.
+    pub cyclomatic_complexity: usize,
+    /// How hard the function is to follow, as defined by SonarSource: each break in
+    /// the linear flow of the code counts, and counts more when nested.
+    pub cognitive_complexity: usize,
+    /// The deepest nesting of `if`, loop, `switch`, `match` and `catch` bodies.
+    pub nesting_depth: usize,
+    pub parameters: usize,
+    pub lines: Lines,
+    pub halstead: Halstead,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Lines {
+    /// The lines from the declaration keyword to the closing brace, blank lines
+    /// and comments included.
+    pub physical: usize,
+    /// The number of statements and, for classes, members, not counting blocks,
+    /// comments and empty statements.
+    pub logical: usize,
+}
+
+/// The Halstead measures of a function.
+///
+/// Operators are the operations and keywords of the code, such as `+`, `->`,
+/// calls and `if`, and operands are the variables, literals and names it uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Halstead {
+    pub distinct_operators: usize,
+    pub distinct_operands: usize,
+    pub operators: usize,
+    pub operands: usize,
+    pub vocabulary: usize,
+    pub length: usize,
+    pub volume: f64,
+    pub difficulty: f64,
+    pub effort: f64,
+}
+
+impl Halstead {
+    pub fn new(
+        distinct_operators: usize,
+        distinct_operands: usize,
+        operators: usize,
+        operands: usize,
+    ) -> Self {
+        let vocabulary = distinct_operators + distinct_operands;
+        let length = operators + operands;
+        let volume = match vocabulary {
+            0 => 0.0,
+            _ => length as f64 * (vocabulary as f64).log2(),
+        };
+        let difficulty = match distinct_operands {
+            0 => 0.0,
+            _ => distinct_operators as f64 / 2.0 * operands as f64 / distinct_operands as f64,
+        };
+
+        Self {
+            distinct_operators,
+            distinct_operands,
+            operators,
+            operands,
+            vocabulary,
+            length,
+            volume,
+            difficulty,
+            effort: difficulty * volume,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClassKind {
+    Class,
+    Interface,
+    Trait,
+    Enum,
+}
+
+impl ClassKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClassKind::Class => "class",
+            ClassKind::Interface => "interface",
+            ClassKind::Trait => "trait",
+            ClassKind::Enum => "enum",
+        }
+    }
+}
+
+/// The metrics of a class, interface, trait or enum.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassMetrics {
+    pub kind: ClassKind,
+    /// The name of the class, qualified by its namespace, or `class@anonymous`.
+    pub name: String,
+    /// The line of the `class`, `interface`, `trait` or `enum` keyword.
+    pub line: usize,
+    /// The sum of the cyclomatic complexities of the methods, also known as the
+    /// weighted methods per class.
+    pub cyclomatic_complexity: usize,
+    /// The sum of the cognitive complexities of the methods.
+    pub cognitive_complexity: usize,
+    pub lines: Lines,
+    /// The classes, interfaces, traits and enums the class refers to, through its
+    /// parents, types, instantiations, static accesses, `instanceof` and `catch`.
+    ///
+    /// Names are listed as written, without resolving them against `use` statements.
+    pub coupling: Vec<String>,
+    pub methods: Vec<FunctionMetrics>,
+}
+
+/// Measure every function, class, and method of `program`.
+///
+/// The program is only borrowed mutably because that is what `Visitor` requires;
+/// it is not modified.
+pub fn metrics(program: &mut Program) -> Metrics {
+    let mut collector = Collector {
+        metrics: Metrics::default(),
+        namespace: None,
+        classes: vec![],
+    };
+
+    let Ok(()) = collector.visit_node(program);
+
+    collector.metrics
+}
+
+impl Metrics {
+    /// Render the metrics as a table, with a row for each class, followed by its
+    /// methods, and for each function.
+    pub fn to_table(&self) -> String {
+        let mut rows = vec![[
+            "kind", "name", "line", "cyclo", "cogn", "nest", "params", "lloc", "loc", "volume",
+            "coupling",
+        ]
+        .map(String::from)];
+
+        for class in &self.classes {
+            rows.push([
+                class.kind.as_str().to_string(),
+                class.name.clone(),
+                class.line.to_string(),
+                class.cyclomatic_complexity.to_string(),
+                class.cognitive_complexity.to_string(),
+                "-".to_string(),
+                "-".to_string(),
+                class.lines.logical.to_string(),
+                class.lines.physical.to_string(),
+                "-".to_string(),
+                class.coupling.len().to_string(),
+            ]);
+
+            for method in &class.methods {
+                rows.push(function_row(
+                    "method",
+                    format!("{}::{}", class.name, method.name),
+                    method,
+                ));
+            }
+        }
+
+        for function in &self.functions {
+            rows.push(function_row("function", function.name.clone(), function));
+        }
+
+        let mut widths = [0; 11];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut table = String::new();
+        for row in &rows {
+            let mut line = String::new();
+            for (column, (cell, width)) in row.iter().zip(widths).enumerate() {
+                // the kind and the name are left-aligned, numbers are right-aligned.
+                match column {
+                    0 | 1 => write!(line, "{:<width$}  ", cell).unwrap(),
+                    _ => write!(line, "{:>width$}  ", cell).unwrap(),
+                }
+            }
+
+            writeln!(table, "{}", line.trim_end()).unwrap();
+        }
+
+        table
+    }
+}
+
+fn function_row(kind: &str, name: String, function: &FunctionMetrics) -> [String; 11] {
+    [
+        kind.to_string(),
+        name,
+        function.line.to_string(),
+        function.cyclomatic_complexity.to_string(),
+        function.cognitive_complexity.to_string(),
+        function.nesting_depth.to_string(),
+        function.parameters.to_string(),
+        function.lines.logical.to_string(),
+        function.lines.physical.to_string(),
+        format!("{:.1}", function.halstead.volume),
+        "-".to_string(),
+    ]
+}
+
+struct Collector {
+    metrics: Metrics,
+    namespace: Option<String>,
+    // the indices in `metrics.classes` of the classes being visited.
+    classes: Vec<usize>,
+}
+
+impl Collector {
+    fn qualified(&self, name: &SimpleIdentifier) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}\\{}", namespace, name.value),
+            None => name.value.to_string(),
+        }
+    }
+
+    fn class(&self, node: &mut dyn Node) -> Option<ClassMetrics> {
+        let mut implements: &[SimpleIdentifier] = &[];
+        let (kind, name, keyword, right_brace, members) =
+            if let Some(class) = downcast::<ClassStatement>(node) {
+                (
+                    ClassKind::Class,
+                    self.qualified(&class.name),
+                    class.class,
+                    class.body.right_brace,
+                    class.body.members.len(),
+                )
+            } else if let Some(class) = downcast::<AnonymousClassExpression>(node) {
+                (
+                    ClassKind::Class,
+                    "class@anonymous".to_string(),
+                    class.class,
+                    class.body.right_brace,
+                    class.body.members.len(),
+                )
+            } else if let Some(interface) = downcast::<InterfaceStatement>(node) {
+                (
+                    ClassKind::Interface,
+                    self.qualified(&interface.name),
+                    interface.interface,
+                    interface.body.right_brace,
+                    interface.body.members.len(),
+                )
+            } else if let Some(r#trait) = downcast::<TraitStatement>(node) {
+                (
+                    ClassKind::Trait,
+                    self.qualified(&r#trait.name),
+                    r#trait.r#trait,
+                    r#trait.body.right_brace,
+                    r#trait.body.members.len(),
+                )
+            } else if let Some(r#enum) = downcast::<UnitEnumStatement>(node) {
+                implements = &r#enum.implements;
+                (
+                    ClassKind::Enum,
+                    self.qualified(&r#enum.name),
+                    r#enum.r#enum,
+                    r#enum.body.right_brace,
+                    r#enum.body.members.len(),
+                )
+            } else if let Some(r#enum) = downcast::<BackedEnumStatement>(node) {
+                implements = &r#enum.implements;
+                (
+                    ClassKind::Enum,
+                    self.qualified(&r#enum.name),
+                    r#enum.r#enum,
+                    r#enum.body.right_brace,
+                    r#enum.body.members.len(),
+                )
+            } else {
+                return None;
+            };
+
+        let mut coupling = Coupling {
+            class: name.clone(),
+            names: BTreeSet::new(),
+        };
+        // the interfaces of enums are plain identifiers, indistinguishable from their name.
+        coupling.names(implements);
+        for child in node.children() {
+            let Ok(()) = coupling.visit_node(child);
+        }
+
+        Some(ClassMetrics {
+            kind,
+            name,
+            line: keyword.line,
+            cyclomatic_complexity: 0,
+            cognitive_complexity: 0,
+            lines: Lines {
+                physical: lines(keyword, right_brace),
+                logical: members,
+            },
+            coupling: coupling.names.into_iter().collect(),
+            methods: vec![],
+        })
+    }
+
+    fn function(&self, node: &mut dyn Node) -> Option<(bool, FunctionMetrics)> {
+        let (method, name, keyword, right_brace, parameters) =
+            if let Some(function) = downcast::<FunctionStatement>(node) {
+                (
+                    false,
+                    self.qualified(&function.name),
+                    function.function,
+                    function.body.right_brace,
+                    function.parameters.parameters.inner.len(),
+                )
+            } else if let Some(method) = downcast::<ConcreteMethod>(node) {
+                (
+                    true,
+                    method.name.value.to_string(),
+                    method.function,
+                    method.body.right_brace,
+                    method.parameters.parameters.inner.len(),
+                )
+            } else if let Some(constructor) = downcast::<ConcreteConstructor>(node) {
+                (
+                    true,
+                    constructor.name.value.to_string(),
+                    constructor.function,
+                    constructor.body.right_brace,
+                    constructor.parameters.parameters.inner.len(),
+                )
+            } else {
+                return None;
+            };
+
+        let mut measurer = Measurer::default();
+        for child in node.children() {
+            let Ok(()) = measurer.visit_node(child);
+        }
+
+        Some((
+            method,
+            FunctionMetrics {
+                name,
+                line: keyword.line,
+                cyclomatic_complexity: measurer.cyclomatic + 1,
+                cognitive_complexity: measurer.cognitive,
+                nesting_depth: measurer.max_depth,
+                parameters,
+                lines: Lines {
+                    physical: lines(keyword, right_brace),
+                    logical: measurer.statements,
+                },
+                halstead: Halstead::new(
+                    measurer.operators.len(),
+                    measurer.operands.len(),
+                    measurer.operators.values().sum(),
+                    measurer.operands.values().sum(),
+                ),
+            },
+        ))
+    }
+}
+
+fn lines(start: Span, end: Span) -> usize {
+    end.line + 1 - start.line
+}
+
+impl Visitor<Infallible> for Collector {
+    fn visit_node(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        let namespace = if let Some(namespace) = downcast::<UnbracedNamespace>(node) {
+            Some(Some(namespace.name.value.to_string()))
+        } else {
+            downcast::<BracedNamespace>(node)
+                .map(|namespace| namespace.name.as_ref().map(|name| name.value.to_string()))
+        };
+
+        let namespace =
+            namespace.map(|namespace| std::mem::replace(&mut self.namespace, namespace));
+
+        let class = self.class(node).map(|class| {
+            self.metrics.classes.push(class);
+            self.classes.push(self.metrics.classes.len() - 1);
+        });
+
+        if let Some((method, function)) = self.function(node) {
+            match self.classes.last() {
+                Some(&class) if method => {
+                    let class = &mut self.metrics.classes[class];
+                    class.cyclomatic_complexity += function.cyclomatic_complexity;
+                    class.cognitive_complexity += function.cognitive_complexity;
+                    class.lines.logical += function.lines.logical;
+                    class.methods.push(function);
+                }
+                _ => self.metrics.functions.push(function),
+            }
+        }
+
+        // classes and functions can be declared anywhere, including in functions.
+        for child in node.children() {
+            self.visit_node(child)?;
+        }
+
+        if class.is_some() {
+            self.classes.pop();
+        }
+
+        if let Some(namespace) = namespace {
+            self.namespace = namespace;
+        }
+
+        Ok(())
+    }
+
+    fn visit(&mut self, _: &mut dyn Node) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+// whether the node is measured on its own, rather than as part of its parent.
+fn declaration(node: &dyn Node) -> bool {
+    downcast::<FunctionStatement>(node).is_some()
+        || downcast::<ClassStatement>(node).is_some()
+        || downcast::<AnonymousClassExpression>(node).is_some()
+        || downcast::<InterfaceStatement>(node).is_some()
+        || downcast::<TraitStatement>(node).is_some()
+        || downcast::<UnitEnumStatement>(node).is_some()
+        || downcast::<BackedEnumStatement>(node).is_some()
+}
+
+// whether the node nests its children, for the cognitive complexity and for the
+// nesting depth.
+fn nesting(node: &dyn Node) -> (bool, bool) {
+    if let Some(
+        Statement::If(_)
+        | Statement::While(_)
+        | Statement::DoWhile(_)
+        | Statement::For(_)
+        | Statement::Foreach(_)
+        | Statement::Switch(_),
+    ) = downcast::<Statement>(node)
+    {
+        return (true, true);
+    }
+
+    match downcast::<Expression>(node) {
+        Some(Expression::Match(_)) => (true, true),
+        Some(
+            Expression::Ternary(_)
+            | Expression::ShortTernary(_)
+            | Expression::Closure(_)
+            | Expression::ArrowFunction(_),
+        ) => (true, false),
+        _ => {
+            let catch = downcast::<CatchBlock>(node).is_some();
+            (catch, catch)
+        }
+    }
+}
+
+#[derive(Default)]
+struct Measurer {
+    cyclomatic: usize,
+    cognitive: usize,
+    // the nesting level used by the cognitive complexity.
+    nesting: usize,
+    depth: usize,
+    max_depth: usize,
+    statements: usize,
+    operators: HashMap<&'static str, usize>,
+    operands: HashMap<String, usize>,
+    // the operator of the logical operation the visited node is an operand of.
+    logical: Option<&'static str>,
+}
+
+impl Measurer {
+    fn operator(&mut self, operator: &'static str) {
+        *self.operators.entry(operator).or_default() += 1;
+    }
+
+    fn operand(&mut self, operand: String) {
+        *self.operands.entry(operand).or_default() += 1;
+    }
+
+    // a structure that breaks the linear flow, counting more when nested.
+    fn structure(&mut self) {
+        self.cognitive += 1 + self.nesting;
+    }
+}
+
+impl Visitor<Infallible> for Measurer {
+    fn visit_node(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        if declaration(node) {
+            // an anonymous class still counts as an instantiation.
+            if downcast::<AnonymousClassExpression>(node).is_some() {
+                self.operator("class");
+            }
+
+            return Ok(());
+        }
+
+        self.visit(node)?;
+
+        let (nesting, depth) = nesting(node);
+
+        // the operands of a logical operation are reached through the node of the
+        // operation itself, which is kept transparent along with parentheses.
+        let logical = match downcast::<Expression>(node) {
+            Some(Expression::LogicalOperation(operation)) => logical_operator(operation),
+            Some(Expression::Parenthesized(_)) => self.logical,
+            _ if downcast::<LogicalOperationExpression>(node).is_some()
+                || downcast::<ParenthesizedExpression>(node).is_some() =>
+            {
+                self.logical
+            }
+            _ => None,
+        };
+        let logical = std::mem::replace(&mut self.logical, logical);
+
+        self.nesting += nesting as usize;
+        self.depth += depth as usize;
+        self.max_depth = self.max_depth.max(self.depth);
+
+        for child in node.children() {
+            self.visit_node(child)?;
+        }
+
+        self.nesting -= nesting as usize;
+        self.depth -= depth as usize;
+        self.logical = logical;
+
+        Ok(())
+    }
+
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        let node: &dyn Node = node;
+
+        if let Some(statement) = downcast::<Statement>(node) {
+            self.statement(statement);
+        } else if let Some(expression) = downcast::<Expression>(node) {
+            self.expression(expression);
+        } else if let Some(variable) = downcast::<SimpleVariable>(node) {
+            self.operand(variable.name.to_string());
+        } else if let Some(identifier) = downcast::<SimpleIdentifier>(node) {
+            self.operand(identifier.value.to_string());
+        // the `Variable` and `Identifier` enums don't list their simple variant as a child.
+        } else if let Some(Variable::SimpleVariable(variable)) = downcast::<Variable>(node) {
+            self.operand(variable.name.to_string());
+        } else if let Some(Identifier::SimpleIdentifier(identifier)) = downcast::<Identifier>(node)
+        {
+            self.operand(identifier.value.to_string());
+        } else if let Some(literal) = downcast::<Literal>(node) {
+            let value = match literal {
+                Literal::String(string) => &string.value,
+                Literal::Integer(integer) => &integer.value,
+                Literal::Float(float) => &float.value,
+            };
+
+            self.operand(value.to_string());
+        } else if downcast::<IfStatementElseIf>(node).is_some()
+            || downcast::<IfStatementElseIfBlock>(node).is_some()
+        {
+            self.operator("elseif");
+            self.cyclomatic += 1;
+            self.cognitive += 1;
+        } else if downcast::<IfStatementElse>(node).is_some()
+            || downcast::<IfStatementElseBlock>(node).is_some()
+        {
+            self.operator("else");
+            self.cognitive += 1;
+        } else if let Some(case) = downcast::<Case>(node) {
+            match case.condition {
+                Some(_) => {
+                    self.operator("case");
+                    self.cyclomatic += 1;
+                }
+                None => self.operator("default"),
+            }
+        } else if let Some(arm) = downcast::<MatchArm>(node) {
+            self.operator("=>");
+            self.cyclomatic += arm.conditions.len();
+        } else if downcast::<CatchBlock>(node).is_some() {
+            self.operator("catch");
+            self.cyclomatic += 1;
+            self.structure();
+        } else if downcast::<FinallyBlock>(node).is_some() {
+            self.operator("finally");
+        }
+
+        Ok(())
+    }
+}
+
+impl Measurer {
+    fn statement(&mut self, statement: &Statement) {
+        if !matches!(
+            statement,
+            Statement::FullOpeningTag(_)
+                | Statement::ShortOpeningTag(_)
+                | Statement::EchoOpeningTag(_)
+                | Statement::ClosingTag(_)
+                | Statement::InlineHtml(_)
+                | Statement::Comment(_)
+                | Statement::Block(_)
+                | Statement::Noop(_)
+        ) {
+            self.statements += 1;
+        }
+
+        let operator = match statement {
+            Statement::If(_) => {
+                self.cyclomatic += 1;
+                self.structure();
+                "if"
+            }
+            Statement::While(_) => {
+                self.cyclomatic += 1;
+                self.structure();
+                "while"
+            }
+            Statement::DoWhile(_) => {
+                self.cyclomatic += 1;
+                self.structure();
+                "do"
+            }
+            Statement::For(_) => {
+                self.cyclomatic += 1;
+                self.structure();
+                "for"
+            }
+            Statement::Foreach(_) => {
+                self.cyclomatic += 1;
+                self.structure();
+                "foreach"
+            }
+            Statement::Switch(_) => {
+                self.structure();
+                "switch"
+            }
+            Statement::Break(statement) => {
+                self.jump(&statement.level);
+                "break"
+            }
+            Statement::Continue(statement) => {
+                self.jump(&statement.level);
+                "continue"
+            }
+            Statement::Goto(_) => {
+                self.cognitive += 1;
+                "goto"
+            }
+            Statement::Return(_) => "return",
+            Statement::Echo(_) => "echo",
+            Statement::Global(_) => "global",
+            Statement::Static(_) => "static",
+            Statement::Try(_) => "try",
+            Statement::Constant(_) => "const",
+            Statement::Declare(_) => "declare",
+            _ => return,
+        };
+
+        self.operator(operator);
+    }
+
+    // `break` and `continue` only add to the complexity when leaving several loops.
+    fn jump(&mut self, level: &Option<Level>) {
+        if level.as_ref().and_then(|level| level.value()).unwrap_or(1) > 1 {
+            self.cognitive += 1;
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        let operator = match expression {
+            Expression::Eval(_) => "eval",
+            Expression::Empty(_) => "empty",
+            Expression::Die(_) => "die",
+            Expression::Exit(_) => "exit",
+            Expression::Isset(_) => "isset",
+            Expression::Unset(_) => "unset",
+            Expression::Print(_) => "print",
+            Expression::ArithmeticOperation(operation) => arithmetic_operator(operation),
+            Expression::AssignmentOperation(operation) => {
+                if let AssignmentOperationExpression::Coalesce { .. } = operation {
+                    self.cyclomatic += 1;
+                }
+
+                assignment_operator(operation)
+            }
+            Expression::BitwiseOperation(operation) => bitwise_operator(operation),
+            Expression::ComparisonOperation(operation) => comparison_operator(operation),
+            Expression::LogicalOperation(operation) => {
+                let operator = logical_operator(operation);
+                if operator.is_some() {
+                    // a sequence of the same operator, such as `$a && $b && $c`,
+                    // only counts once.
+                    if operator != self.logical {
+                        self.cognitive += 1;
+                    }
+
+                    if !matches!(operation, LogicalOperationExpression::LogicalXor { .. }) {
+                        self.cyclomatic += 1;
+                    }
+                }
+
+                operator.unwrap_or("!")
+            }
+            Expression::Concat(_) => ".",
+            Expression::Instanceof(_) => "instanceof",
+            Expression::Reference(_) => "&",
+            Expression::ErrorSuppress(_) => "@",
+            Expression::Include(_) => "include",
+            Expression::IncludeOnce(_) => "include_once",
+            Expression::Require(_) => "require",
+            Expression::RequireOnce(_) => "require_once",
+            Expression::FunctionCall(_) => "()",
+            Expression::FunctionClosureCreation(_) => "(...)",
+            Expression::MethodCall(_)
+            | Expression::MethodClosureCreation(_)
+            | Expression::PropertyFetch(_) => "->",
+            Expression::NullsafeMethodCall(_) | Expression::NullsafePropertyFetch(_) => "?->",
+            Expression::StaticMethodCall(_)
+            | Expression::StaticVariableMethodCall(_)
+            | Expression::StaticMethodClosureCreation(_)
+            | Expression::StaticVariableMethodClosureCreation(_)
+            | Expression::StaticPropertyFetch(_)
+            | Expression::ConstantFetch(_) => "::",
+            Expression::ShortArray(_) | Expression::Array(_) => "array",
+            Expression::List(_) => "list",
+            Expression::Closure(_) => "function",
+            Expression::ArrowFunction(_) => "fn",
+            Expression::New(_) => "new",
+            Expression::InterpolatedString(_) => "\"\"",
+            Expression::Heredoc(_) | Expression::Nowdoc(_) => "<<<",
+            Expression::ShellExec(_) => "``",
+            Expression::ArrayIndex(_) => "[]",
+            Expression::ShortTernary(_) => {
+                self.cyclomatic += 1;
+                self.structure();
+                "?:"
+            }
+            Expression::Ternary(_) => {
+                self.cyclomatic += 1;
+                self.structure();
+                "?"
+            }
+            Expression::Coalesce(_) => {
+                self.cyclomatic += 1;
+                "??"
+            }
+            Expression::Clone(_) => "clone",
+            Expression::Match(_) => {
+                self.structure();
+                "match"
+            }
+            Expression::Throw(_) => "throw",
+            Expression::Yield(_) => "yield",
+            Expression::YieldFrom(_) => "yield from",
+            Expression::Cast(cast) => cast_operator(&cast.kind),
+            Expression::Static(_) => return self.operand("static".to_string()),
+            Expression::Self_(_) => return self.operand("self".to_string()),
+            Expression::Parent(_) => return self.operand("parent".to_string()),
+            Expression::Null(_) => return self.operand("null".to_string()),
+            Expression::Bool(bool) => return self.operand(bool.value.to_string()),
+            Expression::MagicConstant(constant) => {
+                return self.operand(magic_constant(constant).to_string())
+            }
+            // operands are counted when visiting the variables, identifiers and literals.
+            Expression::Literal(_)
+            | Expression::Identifier(_)
+            | Expression::Variable(_)
+            | Expression::Parenthesized(_)
+            | Expression::AnonymousClass(_)
+            | Expression::Noop => return,
+        };
+
+        self.operator(operator);
+    }
+}
+
+fn arithmetic_operator(operation: &ArithmeticOperationExpression) -> &'static str {
+    match operation {
+        ArithmeticOperationExpression::Addition { .. } => "+",
+        ArithmeticOperationExpression::Subtraction { .. } => "-",
+        ArithmeticOperationExpression::Multiplication { .. } => "*",
+        ArithmeticOperationExpression::Division { .. } => "/",
+        ArithmeticOperationExpression::Modulo { .. } => "%",
+        ArithmeticOperationExpression::Exponentiation { .. } => "**",
+        ArithmeticOperationExpression::Negative { .. } => "-x",
+        ArithmeticOperationExpression::Positive { .. } => "+x",
+        ArithmeticOperationExpression::PreIncrement { .. }
+        | ArithmeticOperationExpression::PostIncrement { .. } => "++",
+        ArithmeticOperationExpression::PreDecrement { .. }
+        | ArithmeticOperationExpression::PostDecrement { .. } => "--",
+    }
+}
+
+fn assignment_operator(operation: &AssignmentOperationExpression) -> &'static str {
+    match operation {
+        AssignmentOperationExpression::Assign { .. } => "=",
+        AssignmentOperationExpression::Addition { .. } => "+=",
+        AssignmentOperationExpression::Subtraction { .. } => "-=",
+        AssignmentOperationExpression::Multiplication { .. } => "*=",
+        AssignmentOperationExpression::Division { .. } => "/=",
+        AssignmentOperationExpression::Modulo { .. } => "%=",
+        AssignmentOperationExpression::Exponentiation { .. } => "**=",
+        AssignmentOperationExpression::Concat { .. } => ".=",
+        AssignmentOperationExpression::BitwiseAnd { .. } => "&=",
+        AssignmentOperationExpression::BitwiseOr { .. } => "|=",
+        AssignmentOperationExpression::BitwiseXor { .. } => "^=",
+        AssignmentOperationExpression::LeftShift { .. } => "<<=",
+        AssignmentOperationExpression::RightShift { .. } => ">>=",
+        AssignmentOperationExpression::Coalesce { .. } => "??=",
+    }
+}
+
+fn bitwise_operator(operation: &BitwiseOperationExpression) -> &'static str {
+    match operation {
+        BitwiseOperationExpression::And { .. } => "&",
+        BitwiseOperationExpression::Or { .. } => "|",
+        BitwiseOperationExpression::Xor { .. } => "^",
+        BitwiseOperationExpression::LeftShift { .. } => "<<",
+        BitwiseOperationExpression::RightShift { .. } => ">>",
+        BitwiseOperationExpression::Not { .. } => "~",
+    }
+}
+
+fn comparison_operator(operation: &ComparisonOperationExpression) -> &'static str {
+    match operation {
+        ComparisonOperationExpression::Equal { .. } => "==",
+        ComparisonOperationExpression::Identical { .. } => "===",
+        ComparisonOperationExpression::NotEqual { .. } => "!=",
+        ComparisonOperationExpression::AngledNotEqual { .. } => "<>",
+        ComparisonOperationExpression::NotIdentical { .. } => "!==",
+        ComparisonOperationExpression::LessThan { .. } => "<",
+        ComparisonOperationExpression::GreaterThan { .. } => ">",
+        ComparisonOperationExpression::LessThanOrEqual { .. } => "<=",
+        ComparisonOperationExpression::GreaterThanOrEqual { .. } => ">=",
+        ComparisonOperationExpression::Spaceship { .. } => "<=>",
+    }
+}
+
+// the operator of a binary logical operation, `None` for `!`.
+fn logical_operator(operation: &LogicalOperationExpression) -> Option<&'static str> {
+    match operation {
+        LogicalOperationExpression::And { .. } => Some("&&"),
+        LogicalOperationExpression::Or { .. } => Some("||"),
+        LogicalOperationExpression::LogicalAnd { .. } => Some("and"),
+        LogicalOperationExpression::LogicalOr { .. } => Some("or"),
+        LogicalOperationExpression::LogicalXor { .. } => Some("xor"),
+        LogicalOperationExpression::Not { .. } => None,
+    }
+}
+
+fn cast_operator(kind: &CastKind) -> &'static str {
+    match kind {
+        CastKind::Int => "(int)",
+        CastKind::Bool => "(bool)",
+        CastKind::Float => "(float)",
+        CastKind::String => "(string)",
+        CastKind::Array => "(array)",
+        CastKind::Object => "(object)",
+        CastKind::Unset => "(unset)",
+    }
+}
+
+fn magic_constant(constant: &MagicConstantExpression) -> &'static str {
+    match constant {
+        MagicConstantExpression::Directory(_) => "__DIR__",
+        MagicConstantExpression::File(_) => "__FILE__",
+        MagicConstantExpression::Line(_) => "__LINE__",
+        MagicConstantExpression::Class(_) => "__CLASS__",
+        MagicConstantExpression::Function(_) => "__FUNCTION__",
+        MagicConstantExpression::Method(_) => "__METHOD__",
+        MagicConstantExpression::Namespace(_) => "__NAMESPACE__",
+        MagicConstantExpression::Trait(_) => "__TRAIT__",
+        MagicConstantExpression::CompilerHaltOffset(_) => "__COMPILER_HALT_OFFSET__",
+    }
+}
+
+struct Coupling {
+    class: String,
+    names: BTreeSet<String>,
+}
+
+impl Coupling {
+    fn add(&mut self, name: &ByteString) {
+        let name = name.to_string();
+        let name = name.trim_start_matches('\\');
+
+        let own = self.class.rsplit('\\').next().unwrap_or(&self.class);
+        if ["self", "static", "parent", own]
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(name))
+        {
+            return;
+        }
+
+        self.names.insert(name.to_string());
+    }
+
+    fn names<'a>(&mut self, names: impl IntoIterator<Item = &'a SimpleIdentifier>) {
+        names.into_iter().for_each(|name| self.add(&name.value));
+    }
+
+    fn target(&mut self, expression: &Expression) {
+        if let Expression::Identifier(Identifier::SimpleIdentifier(name)) = expression {
+            self.add(&name.value);
+        }
+    }
+}
+
+impl Visitor<Infallible> for Coupling {
+    fn visit_node(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        // nested classes have their own coupling.
+        if downcast::<AnonymousClassExpression>(node).is_some() {
+            return Ok(());
+        }
+
+        self.visit(node)?;
+
+        for child in node.children() {
+            self.visit_node(child)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        let node: &dyn Node = node;
+
+        if let Some(Type::Named(_, name)) = downcast::<Type>(node) {
+            self.add(name);
+        } else if let Some(extends) = downcast::<ClassExtends>(node) {
+            self.add(&extends.parent.value);
+        } else if let Some(implements) = downcast::<ClassImplements>(node) {
+            self.names(&implements.interfaces.inner);
+        } else if let Some(extends) = downcast::<InterfaceExtends>(node) {
+            self.names(&extends.parents.inner);
+        } else if let Some(usage) = downcast::<TraitUsage>(node) {
+            self.names(&usage.traits);
+        } else if let Some(catch) = downcast::<CatchType>(node) {
+            match catch {
+                CatchType::Identifier { identifier } => self.add(&identifier.value),
+                CatchType::Union { identifiers } => self.names(identifiers),
+            }
+        } else if let Some(expression) = downcast::<Expression>(node) {
+            match expression {
+                Expression::New(new) => self.target(&new.target),
+                Expression::StaticMethodCall(call) => self.target(&call.target),
+                Expression::StaticMethodClosureCreation(call) => self.target(&call.target),
+                Expression::StaticPropertyFetch(fetch) => self.target(&fetch.target),
+                Expression::ConstantFetch(fetch) => self.target(&fetch.target),
+                Expression::Instanceof(instanceof) => self.target(&instanceof.right),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(code: &str) -> Metrics {
+        let code = code.to_string();
+
+        // unoptimized builds need more than the default test stack for nested statements.
+        std::thread::Builder::new()
+            .stack_size(16 * 1024 * 1024)
+            .spawn(move || super::metrics(&mut crate::parse(&code).unwrap()))
+            .unwrap()
+            .join()
+            .unwrap()
+    }
+
+    // the metrics of the only function of the given body.
+    fn function(body: &str) -> FunctionMetrics {
+        let mut metrics = metrics(&format!("<?php function f($p) {{\n{}\n}}", body));
+
+        metrics.functions.remove(0)
+    }
+
+    #[test]
+    fn test_complexity() {
+        let simple = function("return $p;");
+        assert_eq!(simple.cyclomatic_complexity, 1);
+        assert_eq!(simple.cognitive_complexity, 0);
+        assert_eq!(simple.nesting_depth, 0);
+
+        let branches = function("if ($p && $p > 1 && $p < 9) {} elseif ($p) {} else {}");
+        assert_eq!(branches.cyclomatic_complexity, 5);
+        assert_eq!(branches.cognitive_complexity, 4);
+        assert_eq!(branches.nesting_depth, 1);
+
+        let nested = function(
+            "foreach ($p as $a) {
+                while ($a) {
+                    if ($a || ($p && $a)) { break 2; }
+                }
+            }
+            try {} catch (E $e) {}",
+        );
+        assert_eq!(nested.cyclomatic_complexity, 7);
+        assert_eq!(nested.cognitive_complexity, 10);
+        assert_eq!(nested.nesting_depth, 3);
+
+        let expressions = function(
+            "$a = $p ?? 1;
+            $b = $p ? ($a ?: 2) : 3;
+            $c = match ($p) { 1, 2 => 3, default => fn () => $a ? 1 : 2 };",
+        );
+        assert_eq!(expressions.cyclomatic_complexity, 7);
+        assert_eq!(expressions.cognitive_complexity, 7);
+        assert_eq!(expressions.nesting_depth, 1);
+    }
+
+    #[test]
+    fn test_size() {
+        let function = function("// comment\n$a = [$p, 1];\n\n{ echo $a; }\nreturn f($a + 1);");
+        assert_eq!(function.parameters, 1);
+        assert_eq!(
+            function.lines,
+            Lines {
+                physical: 7,
+                logical: 3,
+            }
+        );
+
+        // operators: `=`, `array`, `echo`, `return`, `()` and `+`, operands: `$p`,
+        // `$a`, `1` and `f`.
+        let halstead = function.halstead;
+        assert_eq!(halstead.distinct_operators, 6);
+        assert_eq!(halstead.distinct_operands, 4);
+        assert_eq!(halstead.operators, 6);
+        assert_eq!(halstead.operands, 9);
+        assert_eq!(halstead.length, 15);
+        assert_eq!(halstead.volume, 15.0 * 10f64.log2());
+        assert_eq!(halstead.difficulty, 6.0 / 2.0 * 9.0 / 4.0);
+    }
+
+    #[test]
+    fn test_classes() {
+        let metrics = metrics(
+            "<?php
+            namespace App;
+
+            interface I extends \\Countable {}
+
+            class A extends B implements I {
+                use T;
+                const C = 1;
+
+                public function __construct(private D $d) {}
+
+                public function m(E|null $e): static {
+                    try {
+                        return new F(self::C, G::$h, $e instanceof A);
+                    } catch (H $h) {
+                        return new class extends Z {};
+                    }
+                }
+            }
+
+            enum K: string implements L {
+                case M = 'm';
+            }
+
+            function f() {}",
+        );
+
+        let names = metrics
+            .classes
+            .iter()
+            .map(|class| (class.kind, class.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                (ClassKind::Interface, "App\\I"),
+                (ClassKind::Class, "App\\A"),
+                (ClassKind::Class, "class@anonymous"),
+                (ClassKind::Enum, "App\\K"),
+            ]
+        );
+
+        let class = &metrics.classes[1];
+        assert_eq!(class.line, 6);
+        assert_eq!(class.coupling, ["B", "D", "E", "F", "G", "H", "I", "T"]);
+        assert_eq!(class.methods.len(), 2);
+        assert_eq!(class.methods[1].name, "m");
+        assert_eq!(class.cyclomatic_complexity, 3);
+        assert_eq!(
+            class.lines,
+            Lines {
+                physical: 14,
+                logical: 7,
+            }
+        );
+
+        assert_eq!(metrics.classes[0].coupling, ["Countable"]);
+        assert_eq!(metrics.classes[2].coupling, ["Z"]);
+        assert_eq!(metrics.classes[3].coupling, ["L"]);
+
+        assert_eq!(metrics.functions.len(), 1);
+        assert_eq!(metrics.functions[0].name, "App\\f");
+    }
+
+    #[test]
+    fn test_table() {
+        let table = metrics("<?php class A { function m($a) { return $a; } }").to_table();
+
+        assert_eq!(
+            table,
+            "\
+kind    name  line  cyclo  cogn  nest  params  lloc  loc  volume  coupling
+class   A        1      1     0     -       -     2    1       -         0
+method  A::m     1      1     0     0       1     1    1     6.3         -
+"
+        );
+    }
+}
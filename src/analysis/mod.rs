@@ -0,0 +1,4 @@
+pub mod cfg;
+pub mod metrics;
+pub mod scope;
+pub mod unreachable;
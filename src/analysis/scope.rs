@@ -0,0 +1,1403 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::analysis::cfg::BasicBlock;
+use crate::analysis::cfg::ControlFlowGraph;
+use crate::analysis::cfg::EdgeKind;
+use crate::analysis::cfg::Instruction;
+use crate::analysis::cfg::ENTRY;
+use crate::downcast::downcast;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::NamedArgument;
+use crate::parser::ast::arguments::PositionalArgument;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::operators::ArithmeticOperationExpression;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::operators::LogicalOperationExpression;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::BoolExpression;
+use crate::parser::ast::Expression;
+use crate::parser::ast::GlobalStatement;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::MatchExpression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticStatement;
+
+/// The index of a scope in the list returned by [`scopes`].
+pub type ScopeId = usize;
+
+/// The scope of the code outside of any function.
+pub const FILE: ScopeId = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    File,
+    Function,
+    Method,
+    Closure,
+    ArrowFunction,
+}
+
+/// How a variable is accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessKind {
+    /// `$a = ...`, including destructuring with `list()` or `[...]`.
+    Assignment,
+    /// `$a += ...`, `$a ??= ...`, `$a++`, ...: the variable is read, then written.
+    Update,
+    /// `$a[] = ...` or `$a['b'] = ...`, which creates the array if needed.
+    ArrayWrite,
+    /// `&$a`, e.g. both `$a` and `$b` in `$b = &$a`, or a closure capturing `$a` by
+    /// reference. Writing to a variable bound by reference changes another one, so
+    /// the binding counts as a use.
+    Reference,
+    /// A parameter of the function owning the scope.
+    Parameter { by_reference: bool },
+    /// The key or the value of a `foreach`.
+    Foreach,
+    /// The variable of a `catch` block.
+    Catch,
+    /// `global $a;`
+    Global,
+    /// `static $a;`
+    Static,
+    /// A variable imported by the `use` clause of a closure, inside the closure.
+    Capture { by_reference: bool },
+    /// A variable of the parent scope used in an arrow function, inside the arrow function.
+    ArrowCapture,
+    /// `unset($a)`
+    Unset,
+    /// `isset($a)`, `empty($a)` or `$a ?? ...`, which don't require `$a` to be defined.
+    Isset,
+    /// A variable passed as an argument, which the function may take by reference.
+    Argument,
+    /// Any other read.
+    Read,
+}
+
+impl AccessKind {
+    /// Whether the access (possibly) gives the variable a value.
+    pub fn defines(&self) -> bool {
+        !matches!(self, Self::Read | Self::Isset | Self::Unset)
+    }
+
+    /// Whether the access (possibly) reads the value of the variable.
+    pub fn reads(&self) -> bool {
+        matches!(
+            self,
+            Self::Update | Self::Reference | Self::Isset | Self::Argument | Self::Read
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Access {
+    pub span: Span,
+    pub kind: AccessKind,
+    /// Whether the variable is defined on every path reaching the access, before it
+    /// happens. Code that can't be reached counts as defining every variable.
+    pub defined: bool,
+}
+
+/// A variable of a scope, and every access to it.
+///
+/// The accesses are in evaluation order within each basic block of the control flow
+/// graph of the scope, and the blocks are in source order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Symbol {
+    pub accesses: Vec<Access>,
+}
+
+impl Symbol {
+    pub fn definitions(&self) -> impl Iterator<Item = &Access> {
+        self.accesses.iter().filter(|access| access.kind.defines())
+    }
+
+    pub fn uses(&self) -> impl Iterator<Item = &Access> {
+        self.accesses.iter().filter(|access| access.kind.reads())
+    }
+
+    pub fn is_parameter(&self) -> bool {
+        self.accesses
+            .iter()
+            .any(|access| matches!(access.kind, AccessKind::Parameter { .. }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    /// The name of the function or method, if any.
+    pub name: Option<String>,
+    pub parent: Option<ScopeId>,
+    /// The span of the `function` or `fn` keyword, if any.
+    pub span: Option<Span>,
+    /// The variables of the scope, by name, including the `$`.
+    pub variables: BTreeMap<String, Symbol>,
+    /// Whether variables may be accessed by names only known at runtime, through
+    /// `compact()`, `extract()`, `$$a`, `eval()`, `include`, ...
+    pub dynamic: bool,
+}
+
+impl Scope {
+    pub fn variable(&self, name: &str) -> Option<&Symbol> {
+        self.variables.get(name)
+    }
+}
+
+// functions which access the variables of the calling scope without naming them.
+const DYNAMIC_FUNCTIONS: [&str; 6] = [
+    "compact",
+    "extract",
+    "get_defined_vars",
+    "parse_str",
+    "func_get_args",
+    "func_get_arg",
+];
+
+// variables defined by PHP itself.
+const PREDEFINED_VARIABLES: [&str; 11] = [
+    "$this",
+    "$GLOBALS",
+    "$_SERVER",
+    "$_GET",
+    "$_POST",
+    "$_FILES",
+    "$_COOKIE",
+    "$_SESSION",
+    "$_REQUEST",
+    "$_ENV",
+    "$http_response_header",
+];
+
+/// Whether `name` is a variable defined by PHP itself, such as `$this` or `$_GET`.
+pub fn is_predefined(name: &str) -> bool {
+    PREDEFINED_VARIABLES.contains(&name)
+}
+
+/// Build the scopes of `node`, usually a [`Program`]: the file scope, always at index
+/// [`FILE`], followed by a scope for every function, method, closure and arrow function,
+/// in source order.
+///
+/// When `node` is a function, its own scope is the one following the file scope.
+///
+/// Whether variables are defined is found using the [`ControlFlowGraph`] of each
+/// scope.
+pub fn scopes(node: &dyn Node) -> Vec<Scope> {
+    let mut builder = ScopeBuilder {
+        scopes: vec![],
+        stack: vec![],
+        defined: Defined::start(),
+        saved: vec![],
+        recording: true,
+    };
+
+    builder.enter(ScopeKind::File, None, None);
+    match downcast::<Program>(node) {
+        Some(program) => builder.graph(&ControlFlowGraph::new(program)),
+        None => builder.walk(node),
+    }
+
+    builder.scopes
+}
+
+/// Build the scope of a function, method, closure or arrow function on its own,
+/// or return `None` if `node` is none of them.
+pub fn function_scope(node: &dyn Node) -> Option<Scope> {
+    let is_function = downcast::<FunctionStatement>(node).is_some()
+        || downcast::<ConcreteMethod>(node).is_some()
+        || downcast::<ConcreteConstructor>(node).is_some()
+        || downcast::<ClosureExpression>(node).is_some()
+        || downcast::<ArrowFunctionExpression>(node).is_some();
+
+    match is_function {
+        true => scopes(node).into_iter().nth(1),
+        false => None,
+    }
+}
+
+// the variables defined on every path reaching a point of a scope, or `None` if the
+// point can't be reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Defined(Option<BTreeSet<String>>);
+
+impl Defined {
+    fn start() -> Self {
+        Self(Some(BTreeSet::new()))
+    }
+
+    fn unreachable() -> Self {
+        Self(None)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        match &self.0 {
+            Some(defined) => defined.contains(name),
+            None => true,
+        }
+    }
+
+    fn define(&mut self, name: &str) {
+        if let Some(defined) = &mut self.0 {
+            defined.insert(name.to_string());
+        }
+    }
+
+    fn undefine(&mut self, name: &str) {
+        if let Some(defined) = &mut self.0 {
+            defined.remove(name);
+        }
+    }
+
+    // the variables defined where two paths join.
+    fn merge(self, other: Defined) -> Defined {
+        match (self.0, other.0) {
+            (Some(a), Some(b)) => Self(Some(a.intersection(&b).cloned().collect())),
+            (Some(a), None) | (None, Some(a)) => Self(Some(a)),
+            (None, None) => Self(None),
+        }
+    }
+}
+
+// the variables defined when entering a block of the control flow graph.
+//
+// a `finally` block resumes the jumps and the exceptions that entered it, so the path
+// leaving it normally only depends on the paths that entered it normally: the variables
+// defined on those paths are tracked on their own, the `abrupt` ones being the variables
+// defined on every path. Outside of `finally` blocks, both are the same.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct State {
+    normal: Defined,
+    abrupt: Defined,
+}
+
+impl State {
+    fn unreachable() -> Self {
+        Self {
+            normal: Defined::unreachable(),
+            abrupt: Defined::unreachable(),
+        }
+    }
+
+    fn merge(self, other: State) -> State {
+        Self {
+            normal: self.normal.merge(other.normal),
+            abrupt: self.abrupt.merge(other.abrupt),
+        }
+    }
+
+    fn all(&self) -> Defined {
+        self.normal.clone().merge(self.abrupt.clone())
+    }
+}
+
+// whether taking an edge of the given kind interrupts the normal flow of the code.
+fn is_abrupt(kind: EdgeKind) -> bool {
+    !matches!(kind, EdgeKind::Normal | EdgeKind::True | EdgeKind::False)
+}
+
+// the position of an instruction in the source, used to record the accesses of the
+// blocks in source order.
+fn position(instruction: &Instruction) -> Option<usize> {
+    let start = match instruction {
+        Instruction::Statement(statement) => statement.start(),
+        Instruction::Expression(expression) => expression.start(),
+        Instruction::Foreach(iterator) => iterator.start(),
+        Instruction::Catch(catch) => catch.start(),
+    };
+
+    start.map(|span| span.position)
+}
+
+// the variable checked by `isset($a)`, `isset($a['b'])`, `isset($a->b)`, ..., which
+// is defined if the check succeeds.
+fn checked_variable(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::Variable(Variable::SimpleVariable(variable)) => Some(variable.name.to_string()),
+        Expression::ArrayIndex(index) => checked_variable(&index.array),
+        Expression::PropertyFetch(fetch) => checked_variable(&fetch.target),
+        Expression::NullsafePropertyFetch(fetch) => checked_variable(&fetch.target),
+        Expression::Parenthesized(parenthesized) => checked_variable(&parenthesized.expr),
+        _ => None,
+    }
+}
+
+// the values of the given arguments.
+fn values<'a>(arguments: impl Iterator<Item = &'a Argument>) -> Vec<&'a Expression> {
+    arguments
+        .map(|argument| match argument {
+            Argument::Positional(argument) => &argument.value,
+            Argument::Named(argument) => &argument.value,
+        })
+        .collect()
+}
+
+struct ScopeBuilder {
+    scopes: Vec<Scope>,
+    stack: Vec<ScopeId>,
+    defined: Defined,
+    // the variables defined in the enclosing scopes.
+    saved: Vec<Defined>,
+    // whether the accesses are recorded, or only their effect on the defined variables
+    // is computed, in which case nested functions are skipped.
+    recording: bool,
+}
+
+impl ScopeBuilder {
+    fn current(&mut self) -> &mut Scope {
+        let id = *self.stack.last().expect("there should always be a scope");
+
+        &mut self.scopes[id]
+    }
+
+    fn enter(&mut self, kind: ScopeKind, name: Option<String>, span: Option<Span>) {
+        self.scopes.push(Scope {
+            kind,
+            name,
+            parent: self.stack.last().copied(),
+            span,
+            variables: BTreeMap::new(),
+            dynamic: false,
+        });
+        self.stack.push(self.scopes.len() - 1);
+
+        let defined = std::mem::replace(&mut self.defined, Defined::start());
+        self.saved.push(defined);
+    }
+
+    fn leave(&mut self) {
+        self.stack.pop();
+
+        if let Some(defined) = self.saved.pop() {
+            self.defined = defined;
+        }
+    }
+
+    fn record(&mut self, variable: &SimpleVariable, kind: AccessKind) {
+        let name = variable.name.to_string();
+        let defined = self.defined.contains(&name);
+
+        match kind {
+            AccessKind::Unset => self.defined.undefine(&name),
+            kind if kind.defines() => self.defined.define(&name),
+            _ => {}
+        }
+
+        if !self.recording {
+            return;
+        }
+
+        self.current()
+            .variables
+            .entry(name)
+            .or_default()
+            .accesses
+            .push(Access {
+                span: variable.span,
+                kind,
+                defined,
+            });
+    }
+
+    // the current set of defined variables, leaving the current point unreachable.
+    fn take(&mut self) -> Defined {
+        std::mem::replace(&mut self.defined, Defined::unreachable())
+    }
+
+    fn parameters(&mut self, parameters: &FunctionParameterList) {
+        for parameter in parameters.iter() {
+            self.record(
+                &parameter.name,
+                AccessKind::Parameter {
+                    by_reference: parameter.ampersand.is_some(),
+                },
+            );
+        }
+    }
+
+    // record the accesses of the graph of the current scope, starting with the
+    // variables currently defined.
+    fn graph(&mut self, cfg: &ControlFlowGraph) {
+        let entry = self.take();
+        let recording = std::mem::replace(&mut self.recording, false);
+
+        let mut states = vec![State::unreachable(); cfg.blocks.len()];
+        states[ENTRY] = State {
+            normal: entry.clone(),
+            abrupt: entry,
+        };
+
+        // blocks are only ever entered with fewer variables defined, until nothing changes.
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for (id, block) in cfg.blocks.iter().enumerate() {
+                let state = states[id].clone();
+                let normal = self.run(block, state.normal.clone());
+                let abrupt = match state.abrupt == state.normal {
+                    true => normal.clone(),
+                    false => self.run(block, state.abrupt.clone()),
+                };
+
+                for edge in &block.successors {
+                    // an exception may be thrown before anything in the block runs.
+                    let select =
+                        |(truthy, falsy): &(Defined, Defined), entered: &Defined| match edge.kind {
+                            EdgeKind::True => truthy.clone(),
+                            EdgeKind::False => falsy.clone(),
+                            EdgeKind::Exception => entered.clone(),
+                            _ => truthy.clone().merge(falsy.clone()),
+                        };
+                    let normal = select(&normal, &state.normal);
+                    let abrupt = select(&abrupt, &state.abrupt);
+
+                    let target = &cfg.blocks[edge.target].name;
+                    let contribution = if is_abrupt(edge.kind) {
+                        let all = normal.merge(abrupt);
+                        State {
+                            normal: match target.as_str() {
+                                "finally" => Defined::unreachable(),
+                                _ => all.clone(),
+                            },
+                            abrupt: all,
+                        }
+                    } else if target == "try.end" {
+                        State {
+                            normal: normal.clone(),
+                            abrupt: normal,
+                        }
+                    } else {
+                        State { normal, abrupt }
+                    };
+
+                    let merged = states[edge.target].clone().merge(contribution);
+                    if merged != states[edge.target] {
+                        states[edge.target] = merged;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        self.recording = recording;
+
+        let mut blocks = cfg
+            .blocks
+            .iter()
+            .zip(&states)
+            .filter_map(|(block, state)| {
+                let position = block.instructions.first().and_then(position)?;
+
+                Some((position, block, state))
+            })
+            .collect::<Vec<_>>();
+        blocks.sort_by_key(|(position, _, _)| *position);
+
+        for (_, block, state) in blocks {
+            self.run(block, state.all());
+        }
+    }
+
+    // run the instructions of a block, returning the variables defined after its last
+    // instruction when it is truthy, and when it is falsy.
+    fn run(&mut self, block: &BasicBlock, defined: Defined) -> (Defined, Defined) {
+        self.defined = defined;
+
+        let conditional = block
+            .successors
+            .iter()
+            .any(|edge| matches!(edge.kind, EdgeKind::True | EdgeKind::False));
+
+        for (index, instruction) in block.instructions.iter().enumerate() {
+            let last = index + 1 == block.instructions.len();
+
+            match instruction {
+                Instruction::Expression(expression) if conditional && last => {
+                    return self.condition(expression);
+                }
+                Instruction::Expression(expression) => self.evaluate(expression),
+                Instruction::Statement(Statement::Expression(statement)) => {
+                    self.evaluate(&statement.expression)
+                }
+                Instruction::Statement(statement) => self.walk(*statement),
+                // the body of a `foreach` loop may never run.
+                Instruction::Foreach(iterator) => {
+                    let before = self.defined.clone();
+
+                    if let ForeachStatementIterator::KeyAndValue { key, .. } = iterator {
+                        self.target(key, AccessKind::Foreach);
+                    }
+                    let (ForeachStatementIterator::Value { value, .. }
+                    | ForeachStatementIterator::KeyAndValue { value, .. }) = iterator;
+                    self.target(value, AccessKind::Foreach);
+
+                    return (self.take(), before);
+                }
+                Instruction::Catch(catch) => {
+                    if let Some(variable) = &catch.var {
+                        self.record(variable, AccessKind::Catch);
+                    }
+                }
+            }
+        }
+
+        (self.defined.clone(), self.defined.clone())
+    }
+
+    // an expression evaluated by a block: when it throws or exits, the edges leaving
+    // the block are taken with the variables defined at that point.
+    fn evaluate(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Throw(_) | Expression::Exit(_) | Expression::Die(_) => {
+                for child in expression.children_ref() {
+                    self.walk(child);
+                }
+            }
+            _ => self.walk(expression),
+        }
+    }
+
+    // an expression written to, e.g. the left-hand side of an assignment.
+    fn target(&mut self, expression: &Expression, kind: AccessKind) {
+        match expression {
+            Expression::Variable(Variable::SimpleVariable(variable)) => self.record(variable, kind),
+            Expression::ArrayIndex(index) => {
+                if let Some(index) = &index.index {
+                    self.walk(index.as_ref());
+                }
+
+                // `unset($a['k'])` removes an element, not the array itself.
+                let kind = match kind {
+                    AccessKind::Reference => kind,
+                    _ => AccessKind::ArrayWrite,
+                };
+                self.target(&index.array, kind);
+            }
+            Expression::List(list) => {
+                for entry in &list.items {
+                    match entry {
+                        ListEntry::Skipped => {}
+                        ListEntry::Value { value } => self.target(value, kind),
+                        ListEntry::KeyValue { key, value, .. } => {
+                            self.walk(key);
+                            self.target(value, kind);
+                        }
+                    }
+                }
+            }
+            Expression::ShortArray(array) => self.destructure(array.items.inner.iter(), kind),
+            Expression::Array(array) => self.destructure(array.items.inner.iter(), kind),
+            Expression::Parenthesized(parenthesized) => self.target(&parenthesized.expr, kind),
+            _ => self.walk(expression),
+        }
+    }
+
+    fn destructure<'a>(&mut self, items: impl Iterator<Item = &'a ArrayItem>, kind: AccessKind) {
+        for item in items {
+            match item {
+                ArrayItem::Skipped => {}
+                ArrayItem::Value { value } | ArrayItem::SpreadValue { value, .. } => {
+                    self.target(value, kind)
+                }
+                ArrayItem::ReferencedValue { value, .. } => {
+                    self.target(value, AccessKind::Reference)
+                }
+                ArrayItem::KeyValue { key, value, .. } => {
+                    self.walk(key);
+                    self.target(value, kind);
+                }
+                ArrayItem::ReferencedKeyValue { key, value, .. } => {
+                    self.walk(key);
+                    self.target(value, AccessKind::Reference);
+                }
+            }
+        }
+    }
+
+    // an expression checked by `isset()`, `empty()` or `??`, which may not be defined.
+    fn checked(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Variable(Variable::SimpleVariable(variable)) => {
+                self.record(variable, AccessKind::Isset)
+            }
+            Expression::ArrayIndex(index) => {
+                if let Some(index) = &index.index {
+                    self.walk(index.as_ref());
+                }
+
+                self.checked(&index.array);
+            }
+            Expression::PropertyFetch(fetch) => {
+                self.walk(fetch.property.as_ref());
+                self.checked(&fetch.target);
+            }
+            Expression::NullsafePropertyFetch(fetch) => {
+                self.walk(fetch.property.as_ref());
+                self.checked(&fetch.target);
+            }
+            Expression::Parenthesized(parenthesized) => self.checked(&parenthesized.expr),
+            _ => self.walk(expression),
+        }
+    }
+
+    // visit a condition, returning the variables defined when it is truthy and falsy.
+    fn condition(&mut self, expression: &Expression) -> (Defined, Defined) {
+        match expression {
+            Expression::Parenthesized(parenthesized) => self.condition(&parenthesized.expr),
+            Expression::LogicalOperation(LogicalOperationExpression::Not { right, .. }) => {
+                let (truthy, falsy) = self.condition(right);
+
+                (falsy, truthy)
+            }
+            Expression::LogicalOperation(
+                LogicalOperationExpression::And { left, right, .. }
+                | LogicalOperationExpression::LogicalAnd { left, right, .. },
+            ) => {
+                let (truthy, falsy) = self.condition(left);
+                self.defined = truthy;
+                let (right_truthy, right_falsy) = self.condition(right);
+
+                (right_truthy, falsy.merge(right_falsy))
+            }
+            Expression::LogicalOperation(
+                LogicalOperationExpression::Or { left, right, .. }
+                | LogicalOperationExpression::LogicalOr { left, right, .. },
+            ) => {
+                let (truthy, falsy) = self.condition(left);
+                self.defined = falsy;
+                let (right_truthy, right_falsy) = self.condition(right);
+
+                (truthy.merge(right_truthy), right_falsy)
+            }
+            Expression::Bool(BoolExpression { value, .. }) => match value {
+                true => (self.defined.clone(), Defined::unreachable()),
+                false => (Defined::unreachable(), self.defined.clone()),
+            },
+            // the variables checked by `isset()` are defined when it is true, and those
+            // checked by `empty()` when it is false.
+            Expression::Isset(_) | Expression::Empty(_) => {
+                let is_isset = matches!(expression, Expression::Isset(_));
+                let checked = match expression {
+                    Expression::Isset(isset) => values(isset.arguments.arguments.iter()),
+                    Expression::Empty(empty) => values(std::iter::once(&empty.argument.argument)),
+                    _ => unreachable!(),
+                }
+                .into_iter()
+                .filter_map(checked_variable)
+                .collect::<Vec<String>>();
+
+                self.walk(expression);
+
+                let mut guarded = self.defined.clone();
+                for name in &checked {
+                    guarded.define(name);
+                }
+
+                match is_isset {
+                    true => (guarded, self.defined.clone()),
+                    false => (self.defined.clone(), guarded),
+                }
+            }
+            _ => {
+                self.walk(expression);
+
+                (self.defined.clone(), self.defined.clone())
+            }
+        }
+    }
+
+    // a `match` within an expression; the ones that are statements of their own are
+    // split into blocks of the control flow graph.
+    fn r#match(&mut self, expression: &MatchExpression) {
+        self.walk(expression.condition.as_ref());
+        let start = self.take();
+
+        // when no arm matches and there is no default arm, an `UnhandledMatchError`
+        // is thrown.
+        let mut end = Defined::unreachable();
+        for arm in &expression.arms {
+            self.defined = start.clone();
+            for condition in &arm.conditions {
+                self.walk(condition);
+            }
+            self.walk(&arm.body);
+            end = end.merge(self.take());
+        }
+
+        if let Some(default) = &expression.default {
+            self.defined = start;
+            self.walk(&default.body);
+            end = end.merge(self.take());
+        }
+
+        self.defined = end;
+    }
+
+    // returns whether the expression was fully handled.
+    fn expression(&mut self, expression: &Expression) -> bool {
+        if matches!(
+            expression,
+            Expression::LogicalOperation(
+                LogicalOperationExpression::And { .. }
+                    | LogicalOperationExpression::Or { .. }
+                    | LogicalOperationExpression::LogicalAnd { .. }
+                    | LogicalOperationExpression::LogicalOr { .. }
+            )
+        ) {
+            let (truthy, falsy) = self.condition(expression);
+            self.defined = truthy.merge(falsy);
+
+            return true;
+        }
+
+        match expression {
+            Expression::Variable(Variable::SimpleVariable(variable)) => {
+                self.record(variable, AccessKind::Read);
+            }
+            Expression::AssignmentOperation(operation) => {
+                let (left, right, kind) = match operation {
+                    AssignmentOperationExpression::Assign { left, right, .. } => {
+                        // `$b = &$a` binds `$b` by reference.
+                        let kind = match right.as_ref() {
+                            Expression::Reference(_) => AccessKind::Reference,
+                            _ => AccessKind::Assignment,
+                        };
+
+                        (left, right, kind)
+                    }
+                    AssignmentOperationExpression::Coalesce { left, right, .. } => {
+                        self.walk(right.as_ref());
+                        match left.as_ref() {
+                            Expression::Variable(Variable::SimpleVariable(variable)) => {
+                                self.record(variable, AccessKind::Isset);
+                                self.record(variable, AccessKind::Assignment);
+                            }
+                            _ => self.target(left, AccessKind::Update),
+                        }
+
+                        return true;
+                    }
+                    AssignmentOperationExpression::Addition { left, right, .. }
+                    | AssignmentOperationExpression::Subtraction { left, right, .. }
+                    | AssignmentOperationExpression::Multiplication { left, right, .. }
+                    | AssignmentOperationExpression::Division { left, right, .. }
+                    | AssignmentOperationExpression::Modulo { left, right, .. }
+                    | AssignmentOperationExpression::Exponentiation { left, right, .. }
+                    | AssignmentOperationExpression::Concat { left, right, .. }
+                    | AssignmentOperationExpression::BitwiseAnd { left, right, .. }
+                    | AssignmentOperationExpression::BitwiseOr { left, right, .. }
+                    | AssignmentOperationExpression::BitwiseXor { left, right, .. }
+                    | AssignmentOperationExpression::LeftShift { left, right, .. }
+                    | AssignmentOperationExpression::RightShift { left, right, .. } => {
+                        (left, right, AccessKind::Update)
+                    }
+                };
+
+                self.walk(right.as_ref());
+                self.target(left, kind);
+            }
+            Expression::ArithmeticOperation(
+                ArithmeticOperationExpression::PreIncrement { right: operand, .. }
+                | ArithmeticOperationExpression::PreDecrement { right: operand, .. }
+                | ArithmeticOperationExpression::PostIncrement { left: operand, .. }
+                | ArithmeticOperationExpression::PostDecrement { left: operand, .. },
+            ) => self.target(operand, AccessKind::Update),
+            Expression::Reference(reference) => {
+                self.target(&reference.right, AccessKind::Reference)
+            }
+            Expression::Isset(isset) => {
+                for argument in values(isset.arguments.arguments.iter()) {
+                    self.checked(argument);
+                }
+            }
+            Expression::Empty(empty) => {
+                for argument in values(std::iter::once(&empty.argument.argument)) {
+                    self.checked(argument);
+                }
+            }
+            Expression::Unset(unset) => {
+                for argument in values(unset.arguments.arguments.iter()) {
+                    self.target(argument, AccessKind::Unset);
+                }
+            }
+            Expression::Coalesce(coalesce) => {
+                self.checked(&coalesce.lhs);
+                let start = self.defined.clone();
+                self.walk(coalesce.rhs.as_ref());
+                self.defined = start.merge(self.take());
+            }
+            Expression::Ternary(ternary) => {
+                let (truthy, falsy) = self.condition(&ternary.condition);
+                self.defined = truthy;
+                self.walk(ternary.then.as_ref());
+                let then = self.take();
+                self.defined = falsy;
+                self.walk(ternary.r#else.as_ref());
+                self.defined = then.merge(self.take());
+            }
+            Expression::ShortTernary(ternary) => {
+                let (truthy, falsy) = self.condition(&ternary.condition);
+                self.defined = falsy;
+                self.walk(ternary.r#else.as_ref());
+                self.defined = truthy.merge(self.take());
+            }
+            Expression::Match(r#match) => self.r#match(r#match),
+            Expression::Throw(_) | Expression::Exit(_) | Expression::Die(_) => {
+                for child in expression.children_ref() {
+                    self.walk(child);
+                }
+
+                self.defined = Defined::unreachable();
+            }
+            Expression::FunctionCall(call) => {
+                let name = match call.target.as_ref() {
+                    Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => identifier
+                        .value
+                        .to_string()
+                        .trim_start_matches('\\')
+                        .to_ascii_lowercase(),
+                    _ => return false,
+                };
+
+                if !DYNAMIC_FUNCTIONS.contains(&name.as_str()) {
+                    return false;
+                }
+
+                self.current().dynamic = true;
+
+                // `compact('a', 'b')` reads `$a` and `$b`.
+                if name == "compact" {
+                    for argument in call.arguments.iter() {
+                        let (Argument::Positional(PositionalArgument { value, .. })
+                        | Argument::Named(NamedArgument { value, .. })) = argument;
+
+                        if let Expression::Literal(Literal::String(string)) = value {
+                            let variable = SimpleVariable {
+                                span: string.span,
+                                name: format!("${}", string.value).into_bytes().into(),
+                            };
+
+                            self.record(&variable, AccessKind::Read);
+                        }
+                    }
+                }
+
+                return false;
+            }
+            Expression::Eval(_)
+            | Expression::Include(_)
+            | Expression::IncludeOnce(_)
+            | Expression::Require(_)
+            | Expression::RequireOnce(_) => {
+                self.current().dynamic = true;
+
+                return false;
+            }
+            // `Foo::$bar` is a property, not a variable.
+            Expression::StaticPropertyFetch(fetch) => {
+                self.walk(fetch.target.as_ref());
+                if !matches!(fetch.property, Variable::SimpleVariable(_)) {
+                    self.walk(&fetch.property);
+                }
+            }
+            Expression::Closure(closure) => self.closure(closure),
+            Expression::ArrowFunction(function) => self.arrow_function(function),
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn closure(&mut self, closure: &ClosureExpression) {
+        // the captured variables are read, or referenced, when the closure is created.
+        if let Some(uses) = &closure.uses {
+            for variable in uses.variables.iter() {
+                let kind = match variable.ampersand {
+                    Some(_) => AccessKind::Reference,
+                    None => AccessKind::Read,
+                };
+
+                self.record(&variable.variable, kind);
+            }
+        }
+
+        if !self.recording {
+            return;
+        }
+
+        self.enter(ScopeKind::Closure, None, Some(closure.function));
+        self.parameters(&closure.parameters);
+        if let Some(uses) = &closure.uses {
+            for variable in uses.variables.iter() {
+                self.record(
+                    &variable.variable,
+                    AccessKind::Capture {
+                        by_reference: variable.ampersand.is_some(),
+                    },
+                );
+            }
+        }
+        self.graph(&ControlFlowGraph::from_closure(closure));
+        self.leave();
+    }
+
+    fn arrow_function(&mut self, function: &ArrowFunctionExpression) {
+        // the variables captured from the parent scope are only read, which doesn't
+        // change the variables defined in it.
+        if !self.recording {
+            return;
+        }
+
+        self.enter(ScopeKind::ArrowFunction, None, Some(function.r#fn));
+        self.parameters(&function.parameters);
+        self.graph(&ControlFlowGraph::from_arrow_function(function));
+
+        let id = *self.stack.last().unwrap();
+        self.leave();
+
+        // variables of the parent scope are captured by value, so the variables read
+        // before being written in the arrow function are read from the parent scope.
+        let mut captured = vec![];
+        for (name, symbol) in self.scopes[id].variables.iter_mut() {
+            let Some(first) = symbol.accesses.first().copied() else {
+                continue;
+            };
+
+            if symbol.is_parameter() || !first.kind.reads() {
+                continue;
+            }
+
+            for access in &mut symbol.accesses {
+                access.defined = true;
+            }
+            symbol.accesses.insert(
+                0,
+                Access {
+                    span: function.r#fn,
+                    kind: AccessKind::ArrowCapture,
+                    defined: false,
+                },
+            );
+
+            // `isset()` and `??` don't require the variable to be defined in the parent either.
+            let kind = match first.kind {
+                AccessKind::Isset => AccessKind::Isset,
+                _ => AccessKind::Read,
+            };
+            captured.push((name.clone(), first.span, kind));
+        }
+
+        captured.sort_by_key(|(_, span, _)| span.position);
+        for (name, span, kind) in captured {
+            self.record(
+                &SimpleVariable {
+                    span,
+                    name: name.into_bytes().into(),
+                },
+                kind,
+            );
+        }
+    }
+
+    // returns whether the node, which isn't an expression, was fully handled.
+    //
+    // control flow statements never get here, as they are split into the blocks of
+    // the control flow graph of their scope.
+    fn node(&mut self, node: &dyn Node) -> bool {
+        if let Some(function) = downcast::<FunctionStatement>(node) {
+            if self.recording {
+                self.enter(
+                    ScopeKind::Function,
+                    Some(function.name.value.to_string()),
+                    Some(function.function),
+                );
+                self.parameters(&function.parameters);
+                self.graph(&ControlFlowGraph::from_function(&function.body));
+                self.leave();
+            }
+        } else if let Some(method) = downcast::<ConcreteMethod>(node) {
+            if self.recording {
+                self.enter(
+                    ScopeKind::Method,
+                    Some(method.name.value.to_string()),
+                    Some(method.function),
+                );
+                self.parameters(&method.parameters);
+                self.graph(&ControlFlowGraph::from_method(&method.body));
+                self.leave();
+            }
+        } else if let Some(constructor) = downcast::<ConcreteConstructor>(node) {
+            if self.recording {
+                self.enter(
+                    ScopeKind::Method,
+                    Some(constructor.name.value.to_string()),
+                    Some(constructor.function),
+                );
+                for parameter in constructor.parameters.parameters.iter() {
+                    self.record(
+                        &parameter.name,
+                        AccessKind::Parameter {
+                            by_reference: parameter.ampersand.is_some(),
+                        },
+                    );
+                }
+                self.graph(&ControlFlowGraph::from_method(&constructor.body));
+                self.leave();
+            }
+        } else if let Some(closure) = downcast::<ClosureExpression>(node) {
+            self.closure(closure);
+        } else if let Some(function) = downcast::<ArrowFunctionExpression>(node) {
+            self.arrow_function(function);
+        } else if downcast::<FunctionParameter>(node).is_some()
+            || downcast::<ConstructorParameter>(node).is_some()
+        {
+            // parameters are recorded when entering their function, and parameters of
+            // abstract methods don't belong to any scope.
+        } else if let Some(entry) = downcast::<PropertyEntry>(node) {
+            // the name of a property isn't a variable.
+            if let PropertyEntry::Initialized { value, .. } = entry {
+                self.walk(value);
+            }
+        } else if let Some(global) = downcast::<GlobalStatement>(node) {
+            for variable in &global.variables {
+                match variable {
+                    Variable::SimpleVariable(variable) => self.record(variable, AccessKind::Global),
+                    variable => self.walk(variable),
+                }
+            }
+        } else if let Some(statement) = downcast::<StaticStatement>(node) {
+            for var in &statement.vars {
+                if let Some(default) = &var.default {
+                    self.walk(default);
+                }
+
+                match &var.var {
+                    Variable::SimpleVariable(variable) => self.record(variable, AccessKind::Static),
+                    variable => self.walk(variable),
+                }
+            }
+        } else if let Some(argument) = downcast::<PositionalArgument>(node) {
+            let Expression::Variable(Variable::SimpleVariable(variable)) = &argument.value else {
+                return false;
+            };
+
+            self.record(variable, AccessKind::Argument);
+        } else if let Some(argument) = downcast::<NamedArgument>(node) {
+            let Expression::Variable(Variable::SimpleVariable(variable)) = &argument.value else {
+                return false;
+            };
+
+            self.record(variable, AccessKind::Argument);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn walk(&mut self, node: &dyn Node) {
+        let handled = match downcast::<Expression>(node) {
+            Some(expression) => self.expression(expression),
+            None => self.node(node),
+        };
+
+        if handled {
+            return;
+        }
+
+        // any variable not handled above is read, e.g. `$a` in `"{$a}"` or `$$a`.
+        if let Some(variable) = downcast::<Variable>(node) {
+            match variable {
+                Variable::SimpleVariable(variable) => self.record(variable, AccessKind::Read),
+                _ => self.current().dynamic = true,
+            }
+        }
+
+        for child in node.children_ref() {
+            self.walk(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scopes(code: &str) -> Vec<Scope> {
+        super::scopes(&crate::parse(code).unwrap())
+    }
+
+    // the kinds of the accesses to a variable.
+    fn accesses(scope: &Scope, name: &str) -> Vec<AccessKind> {
+        scope
+            .variable(name)
+            .map(|symbol| symbol.accesses.iter().map(|access| access.kind).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_assignments() {
+        let scopes = scopes(
+            "<?php $a = 1; $a += $b; $c[] = ++$a; [$d, [, $e]] = f(); list('k' => $g) = $h ?? []; echo $c[0];",
+        );
+
+        assert_eq!(scopes.len(), 1);
+        let file = &scopes[FILE];
+        assert_eq!(
+            accesses(file, "$a"),
+            [
+                AccessKind::Assignment,
+                AccessKind::Update,
+                AccessKind::Update
+            ]
+        );
+        assert_eq!(accesses(file, "$b"), [AccessKind::Read]);
+        assert_eq!(
+            accesses(file, "$c"),
+            [AccessKind::ArrayWrite, AccessKind::Read]
+        );
+        assert_eq!(accesses(file, "$d"), [AccessKind::Assignment]);
+        assert_eq!(accesses(file, "$e"), [AccessKind::Assignment]);
+        assert_eq!(accesses(file, "$g"), [AccessKind::Assignment]);
+        assert_eq!(accesses(file, "$h"), [AccessKind::Isset]);
+        assert!(!file.dynamic);
+    }
+
+    #[test]
+    fn test_functions() {
+        let scopes = scopes(
+            "<?php
+            function f(&$a, $b = 1) {
+                global $g;
+                static $s = 0;
+                foreach ($b as $k => $v) {}
+                try {} catch (E $e) {}
+                return $undefined;
+            }
+            abstract class A {
+                public $property = 1;
+                public function __construct(private $c) {}
+                public function m() { return A::$property; }
+                abstract function n($p);
+            }",
+        );
+
+        assert_eq!(scopes.len(), 4);
+
+        let function = &scopes[1];
+        assert_eq!(function.kind, ScopeKind::Function);
+        assert_eq!(function.name.as_deref(), Some("f"));
+        assert_eq!(function.parent, Some(FILE));
+        assert_eq!(
+            accesses(function, "$a"),
+            [AccessKind::Parameter { by_reference: true }]
+        );
+        assert_eq!(
+            accesses(function, "$b"),
+            [
+                AccessKind::Parameter {
+                    by_reference: false
+                },
+                AccessKind::Read
+            ]
+        );
+        assert_eq!(accesses(function, "$g"), [AccessKind::Global]);
+        assert_eq!(accesses(function, "$s"), [AccessKind::Static]);
+        assert_eq!(accesses(function, "$k"), [AccessKind::Foreach]);
+        assert_eq!(accesses(function, "$v"), [AccessKind::Foreach]);
+        assert_eq!(accesses(function, "$e"), [AccessKind::Catch]);
+        assert_eq!(
+            function
+                .variable("$undefined")
+                .unwrap()
+                .definitions()
+                .count(),
+            0
+        );
+
+        assert_eq!(scopes[2].kind, ScopeKind::Method);
+        assert_eq!(
+            accesses(&scopes[2], "$c"),
+            [AccessKind::Parameter {
+                by_reference: false
+            }]
+        );
+        assert!(scopes[3].variables.is_empty());
+        assert!(scopes[FILE].variables.is_empty());
+    }
+
+    #[test]
+    fn test_closures() {
+        let scopes = scopes(
+            "<?php
+            $a = 1;
+            $f = function ($p) use ($a, &$b) { return $a + $p; };
+            $g = fn ($p) => $p + $c + fn () => $d;",
+        );
+
+        assert_eq!(scopes.len(), 4);
+        let file = &scopes[FILE];
+        assert_eq!(
+            accesses(file, "$a"),
+            [AccessKind::Assignment, AccessKind::Read]
+        );
+        assert_eq!(accesses(file, "$b"), [AccessKind::Reference]);
+        assert_eq!(accesses(file, "$c"), [AccessKind::Read]);
+        assert_eq!(accesses(file, "$d"), [AccessKind::Read]);
+        assert!(file.variable("$p").is_none());
+
+        let closure = &scopes[1];
+        assert_eq!(closure.kind, ScopeKind::Closure);
+        assert_eq!(
+            accesses(closure, "$a"),
+            [
+                AccessKind::Capture {
+                    by_reference: false
+                },
+                AccessKind::Read
+            ]
+        );
+        assert_eq!(
+            accesses(closure, "$b"),
+            [AccessKind::Capture { by_reference: true }]
+        );
+
+        let arrow = &scopes[2];
+        assert_eq!(arrow.kind, ScopeKind::ArrowFunction);
+        assert_eq!(
+            accesses(arrow, "$c"),
+            [AccessKind::ArrowCapture, AccessKind::Read]
+        );
+        // `$d` is captured by the outer arrow function, for the inner one.
+        assert_eq!(
+            accesses(arrow, "$d"),
+            [AccessKind::ArrowCapture, AccessKind::Read]
+        );
+        assert_eq!(
+            accesses(&scopes[3], "$d"),
+            [AccessKind::ArrowCapture, AccessKind::Read]
+        );
+    }
+
+    // the variables read while they may be undefined, in the first function.
+    fn undefined(code: &str) -> Vec<String> {
+        let code = format!("<?php function f($p) {{ {} }}", code);
+        let scopes = scopes(&code);
+
+        let mut accesses = scopes[1]
+            .variables
+            .iter()
+            .flat_map(|(name, symbol)| symbol.accesses.iter().map(move |access| (name, access)))
+            .filter(|(_, access)| access.kind == AccessKind::Read && !access.defined)
+            .collect::<Vec<_>>();
+        accesses.sort_by_key(|(_, access)| access.span.position);
+
+        accesses.into_iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    #[test]
+    fn test_definite_assignment() {
+        assert_eq!(undefined("$a = $a + $p;"), ["$a"]);
+        assert_eq!(undefined("if ($p) { $a = 1; } return $a;"), ["$a"]);
+        assert!(undefined("if ($p) { $a = 1; } else { $a = 2; } return $a;").is_empty());
+        assert!(undefined("if ($p) { $a = 1; } else { return; } return $a;").is_empty());
+        assert!(undefined("if ($p && ($a = f())) { return $a; }").is_empty());
+        assert_eq!(undefined("if ($p || ($a = f())) { return $a; }"), ["$a"]);
+        assert!(undefined("if (!isset($a)) { return; } return $a;").is_empty());
+        assert!(undefined("return isset($a['b']) ? $a['b'] : $c ?? $p;").is_empty());
+        assert!(undefined("if (empty($a)) { $a = 1; } return $a;").is_empty());
+
+        assert_eq!(
+            undefined("foreach ($p as $v) { $a = $v; } return [$a, $v];"),
+            ["$a", "$v"]
+        );
+        assert!(undefined("while (true) { $a = 1; break; } return $a;").is_empty());
+        assert!(undefined("do { $a = 1; } while ($p); return $a;").is_empty());
+        assert_eq!(
+            undefined("for ($i = 0; $i < 3; $i++) { $a = $i; } return [$a, $i];"),
+            ["$a"]
+        );
+
+        assert!(
+            undefined("switch ($p) { case 1: $a = 1; break; default: $a = 2; } return $a;")
+                .is_empty()
+        );
+        assert_eq!(
+            undefined("switch ($p) { case 1: $a = 1; break; case 2: return; } return $a;"),
+            ["$a"]
+        );
+        assert_eq!(
+            undefined("try { $a = f(); } catch (E $e) { return $e; } finally { $a; } return $a;"),
+            ["$a"]
+        );
+        assert!(
+            undefined("$a = match ($p) { 1 => 1, default => throw $p }; return $a;").is_empty()
+        );
+        assert_eq!(undefined("unset($p); return $p;"), ["$p"]);
+        assert!(undefined("unset($p['k']); return $p;").is_empty());
+
+        assert_eq!(undefined("goto a; $a = 1; a: return $a;"), ["$a"]);
+        assert!(undefined("$a = 1; goto a; $a = f(); a: return $a;").is_empty());
+        assert_eq!(
+            undefined("while ($p) { if ($p) { continue; } $a = 1; } return $a;"),
+            ["$a"]
+        );
+    }
+
+    #[test]
+    fn test_references() {
+        let scopes = scopes("<?php function f() { $x = 1; $y = &$x; $y = 2; return $x; }");
+
+        // writing to `$y` changes `$x`, so the binding is a use of both.
+        assert_eq!(
+            accesses(&scopes[1], "$x"),
+            [
+                AccessKind::Assignment,
+                AccessKind::Reference,
+                AccessKind::Read
+            ]
+        );
+        assert_eq!(
+            accesses(&scopes[1], "$y"),
+            [AccessKind::Reference, AccessKind::Assignment]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_scopes() {
+        let scopes = scopes(
+            "<?php
+            function a() { $a = 1; return compact('a'); }
+            function b() { extract($_GET); }
+            function c() { return $$name; }
+            function d() { return $a; }",
+        );
+
+        assert_eq!(
+            accesses(&scopes[1], "$a"),
+            [AccessKind::Assignment, AccessKind::Read]
+        );
+        assert!(scopes[1].dynamic);
+        assert!(scopes[2].dynamic);
+        assert!(scopes[3].dynamic);
+        assert_eq!(accesses(&scopes[3], "$name"), [AccessKind::Read]);
+        assert!(!scopes[4].dynamic);
+    }
+}
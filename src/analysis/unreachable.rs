@@ -0,0 +1,200 @@
+use std::convert::Infallible;
+
+use crate::analysis::cfg::ControlFlowGraph;
+use crate::downcast::downcast;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::traverser::Visitor;
+
+/// A range of statements that can never run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnreachableCode {
+    /// The span of the first token of the range.
+    pub start: Span,
+    /// The position following the last byte of the range.
+    pub end: usize,
+}
+
+impl UnreachableCode {
+    /// The length of the range in bytes.
+    pub fn length(&self) -> usize {
+        self.end - self.start.position
+    }
+}
+
+/// Find the code that can never run in `program`, i.e. the statements following
+/// an unconditional `return`, `throw`, `exit`, `break`, `continue` or `goto`, and
+/// the branches that a constant condition never takes, such as `if (false)`.
+///
+/// Consecutive statements are reported as a single range, sorted by position.
+///
+/// The program is only borrowed mutably because that is what `Visitor` requires;
+/// it is not modified.
+pub fn unreachable_code(program: &mut Program) -> Vec<UnreachableCode> {
+    let mut unreachable = unreachable(&ControlFlowGraph::new(program));
+
+    let mut collector = Collector {
+        unreachable: vec![],
+    };
+    let Ok(()) = collector.visit_node(program);
+    unreachable.extend(collector.unreachable);
+
+    unreachable.sort_by_key(|code| code.start.position);
+    unreachable
+}
+
+/// Find the code that can never run in the given graph.
+pub fn unreachable(cfg: &ControlFlowGraph) -> Vec<UnreachableCode> {
+    let reachable = cfg.reachable();
+
+    let mut ranges: Vec<UnreachableCode> = vec![];
+    let mut current: Option<UnreachableCode> = None;
+
+    for (statement, block) in cfg.statements() {
+        if reachable[block] {
+            ranges.extend(current.take());
+            continue;
+        }
+
+        if !runs(statement) {
+            continue;
+        }
+
+        let (Some(start), Some(end)) = (statement.start(), statement.end()) else {
+            continue;
+        };
+
+        current = match current {
+            // nested statements are already part of the range of their parent.
+            Some(range) if end <= range.end => Some(range),
+            Some(range) => Some(UnreachableCode {
+                start: range.start,
+                end,
+            }),
+            None => Some(UnreachableCode { start, end }),
+        };
+    }
+
+    ranges.extend(current);
+    ranges
+}
+
+// whether the statement does anything at runtime, where it is written; declarations
+// are hoisted, and tags and inline HTML after a `return` are common in templates.
+fn runs(statement: &Statement) -> bool {
+    !matches!(
+        statement,
+        Statement::FullOpeningTag(_)
+            | Statement::ShortOpeningTag(_)
+            | Statement::EchoOpeningTag(_)
+            | Statement::ClosingTag(_)
+            | Statement::InlineHtml(_)
+            | Statement::HaltCompiler(_)
+            | Statement::Function(_)
+            | Statement::Class(_)
+            | Statement::Trait(_)
+            | Statement::Interface(_)
+            | Statement::UnitEnum(_)
+            | Statement::BackedEnum(_)
+            | Statement::Comment(_)
+            | Statement::Noop(_)
+    )
+}
+
+struct Collector {
+    unreachable: Vec<UnreachableCode>,
+}
+
+impl Visitor<Infallible> for Collector {
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        let node: &dyn Node = node;
+
+        let cfg = if let Some(function) = downcast::<FunctionStatement>(node) {
+            ControlFlowGraph::from_function(&function.body)
+        } else if let Some(method) = downcast::<ConcreteMethod>(node) {
+            ControlFlowGraph::from_method(&method.body)
+        } else if let Some(constructor) = downcast::<ConcreteConstructor>(node) {
+            ControlFlowGraph::from_method(&constructor.body)
+        } else if let Some(closure) = downcast::<ClosureExpression>(node) {
+            ControlFlowGraph::from_closure(closure)
+        } else {
+            return Ok(());
+        };
+
+        self.unreachable.extend(unreachable(&cfg));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the unreachable code of the given program, as source text.
+    fn unreachable(code: &str) -> Vec<&str> {
+        let mut program = crate::parse(code).unwrap();
+
+        unreachable_code(&mut program)
+            .iter()
+            .map(|range| &code[range.start.position..range.start.position + range.length()])
+            .collect()
+    }
+
+    #[test]
+    fn test_jumps() {
+        assert_eq!(unreachable("<?php return; a(); b();"), ["a(); b();"]);
+        assert_eq!(
+            unreachable("<?php function a() { throw new E(); if ($a) { b(); } }"),
+            ["if ($a) { b(); }"]
+        );
+        assert_eq!(
+            unreachable("<?php foreach ($a as $b) { continue; a(); } exit; b();"),
+            ["a();", "b();"]
+        );
+        assert_eq!(
+            unreachable("<?php goto a; b(); a: c(); return; d:"),
+            ["b();", "d:"]
+        );
+        assert_eq!(
+            unreachable("<?php class A { function a() { die(); $a = 1; } }"),
+            ["$a = 1;"]
+        );
+    }
+
+    #[test]
+    fn test_constant_conditions() {
+        assert_eq!(
+            unreachable("<?php if (false) { a(); } else { b(); }"),
+            ["{ a(); }"]
+        );
+        assert_eq!(
+            unreachable("<?php $a = function () { while (true) {} a(); };"),
+            ["a();"]
+        );
+    }
+
+    #[test]
+    fn test_multi_byte_endings() {
+        assert_eq!(unreachable("<?php exit; echo $a ?>\n"), ["echo $a ?>"]);
+        assert_eq!(
+            unreachable("<?php return; if ($a): b(); endif; c();"),
+            ["if ($a): b(); endif; c();"]
+        );
+        assert_eq!(
+            unreachable("<?php function a() { return; while ($a): b(); endwhile ?>\n<?php }"),
+            ["while ($a): b(); endwhile ?>"]
+        );
+    }
+
+    #[test]
+    fn test_declarations_are_not_reported() {
+        assert!(unreachable("<?php return []; function a() {} ?>\n").is_empty());
+    }
+}
@@ -0,0 +1,574 @@
+use std::collections::BTreeMap;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseErrorAnnotationType;
+
+/// The version of the baseline file format.
+pub const BASELINE_VERSION: usize = 1;
+
+/// A set of known errors, which are not reported again.
+///
+/// Errors are identified by their file, their id, and a fingerprint of the code they
+/// point to, so that a baseline keeps matching as code moves around the file.
+/// Identical errors within a file are counted, and a baseline entry only suppresses
+/// as many errors as were recorded.
+///
+/// Files are recorded relative to the directory of the baseline file, so that a baseline
+/// keeps matching whatever the working directory is, and however the files are named.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Baseline {
+    entries: BTreeMap<BaselineKey, usize>,
+    // the absolute directory files are recorded relative to, if any.
+    root: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct BaselineKey {
+    file: String,
+    id: String,
+    fingerprint: String,
+}
+
+/// A baseline entry, as written to the baseline file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BaselineEntry {
+    pub file: String,
+    pub id: String,
+    pub fingerprint: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BaselineFile {
+    version: usize,
+    entries: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Create a baseline recording files as they are named.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a baseline recording files relative to `directory`, usually the one of the
+    /// baseline file.
+    pub fn relative_to(directory: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            entries: BTreeMap::new(),
+            root: Some(absolute(directory)?),
+        })
+    }
+
+    /// Create a baseline recording files relative to the directory of the baseline file
+    /// at `path`.
+    pub fn for_file(path: &Path) -> std::io::Result<Self> {
+        Self::relative_to(path.parent().unwrap_or(Path::new("")))
+    }
+
+    /// Record `error`, reported in `file`, whose contents are `source`.
+    pub fn add(&mut self, file: &str, source: &str, error: &ParseError) {
+        let key = self.key(file, source, error);
+
+        *self.entries.entry(key).or_default() += 1;
+    }
+
+    /// Remove the errors of `file` that are part of the baseline, returning the others.
+    ///
+    /// Every error removed is consumed from the baseline, so that once every file has
+    /// been filtered, the remaining entries are those which no longer occur.
+    pub fn filter(&mut self, file: &str, source: &str, errors: Vec<ParseError>) -> Vec<ParseError> {
+        errors
+            .into_iter()
+            .filter(|error| {
+                let key = self.key(file, source, error);
+
+                match self.entries.get_mut(&key) {
+                    Some(count) => {
+                        *count -= 1;
+                        if *count == 0 {
+                            self.entries.remove(&key);
+                        }
+
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect()
+    }
+
+    /// The entries of the baseline, sorted by file.
+    pub fn entries(&self) -> Vec<BaselineEntry> {
+        self.entries
+            .iter()
+            .map(|(key, count)| BaselineEntry {
+                file: key.file.clone(),
+                id: key.id.clone(),
+                fingerprint: key.fingerprint.clone(),
+                count: *count,
+            })
+            .collect()
+    }
+
+    /// The number of errors recorded in the baseline.
+    pub fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Read a baseline from JSON, recording files as they are named.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let file: BaselineFile = serde_json::from_str(json)?;
+        if file.version != BASELINE_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported baseline version {}, expected {}",
+                file.version, BASELINE_VERSION
+            )));
+        }
+
+        let mut baseline = Self::new();
+        for entry in file.entries {
+            *baseline
+                .entries
+                .entry(BaselineKey {
+                    file: entry.file,
+                    id: entry.id,
+                    fingerprint: entry.fingerprint,
+                })
+                .or_default() += entry.count;
+        }
+
+        Ok(baseline)
+    }
+
+    pub fn to_json(&self) -> String {
+        let file = BaselineFile {
+            version: BASELINE_VERSION,
+            entries: self.entries(),
+        };
+
+        serde_json::to_string_pretty(&file).expect("a baseline should always be serializable")
+    }
+
+    /// Read the baseline file at `path`, whose files are relative to its directory.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            root: Self::for_file(path)?.root,
+            ..Self::from_json(&std::fs::read_to_string(path)?)?
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json() + "\n")
+    }
+
+    fn key(&self, file: &str, source: &str, error: &ParseError) -> BaselineKey {
+        BaselineKey {
+            file: self.file(file),
+            id: error.id.clone(),
+            fingerprint: fingerprint(source, error),
+        }
+    }
+
+    // the name `file` is recorded as, with `/` as separator.
+    fn file(&self, file: &str) -> String {
+        let relative = match &self.root {
+            Some(root) => match absolute(Path::new(file)) {
+                Ok(path) => relative(&path, root),
+                Err(_) => PathBuf::from(file),
+            },
+            None => PathBuf::from(file),
+        };
+
+        relative
+            .components()
+            .filter(|component| *component != Component::CurDir)
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+            .replace('\\', "/")
+    }
+}
+
+// the absolute path of `path`, with `.` and `..` components resolved without following
+// symbolic links.
+fn absolute(path: &Path) -> std::io::Result<PathBuf> {
+    let path = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+
+    let mut absolute = PathBuf::new();
+    for component in std::path::absolute(path)?.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            component => absolute.push(component),
+        }
+    }
+
+    Ok(absolute)
+}
+
+// `path` relative to `root`, both being absolute.
+fn relative(path: &Path, root: &Path) -> PathBuf {
+    let path = path.components().collect::<Vec<_>>();
+    let root = root.components().collect::<Vec<_>>();
+
+    let common = path.iter().zip(&root).take_while(|(a, b)| a == b).count();
+
+    // paths on different drives have nothing in common, and stay absolute.
+    if common == 0 {
+        return path.iter().collect();
+    }
+
+    root[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(path[common..].iter().copied())
+        .collect()
+}
+
+/// Fingerprint an error using its id, the code of the node it points to, ignoring
+/// whitespace, and the symbol enclosing that code, such as `App\User::rename`.
+///
+/// The position of the error isn't part of the fingerprint, so it doesn't change
+/// when lines are added or removed elsewhere in the file, and neither is its message,
+/// so it doesn't change when the message is reworded.
+pub fn fingerprint(source: &str, error: &ParseError) -> String {
+    let (position, length) = error
+        .annotations
+        .iter()
+        .find(|annotation| annotation.r#type == ParseErrorAnnotationType::Error)
+        .map(|annotation| (annotation.position, annotation.length))
+        .unwrap_or((error.span.position, 0));
+
+    let source = source.as_bytes();
+    let start = position.min(source.len());
+    let end = (position + length).min(source.len());
+
+    let (symbol, token) = enclosing_symbol(source, start);
+    // errors pointing to a position rather than to some code, such as the end of a
+    // statement, use the token at that position.
+    let code = if start < end {
+        &source[start..end]
+    } else {
+        token
+    };
+
+    let mut hash = Fnv::default();
+    hash.write(error.id.as_bytes());
+    hash.write(&[0]);
+    hash.write(symbol.as_bytes());
+    for word in code
+        .split(|byte| byte.is_ascii_whitespace())
+        .filter(|word| !word.is_empty())
+    {
+        hash.write(&[0]);
+        hash.write(word);
+    }
+
+    format!("{:016x}", hash.0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Declaration {
+    Namespace,
+    Class,
+    Function,
+}
+
+// The name of the innermost class-like and function declarations enclosing `position`,
+// along with the code of the token at `position`.
+//
+// The declarations are found from the tokens of `source` rather than from its AST, as
+// errors are reported for code which doesn't parse too. Anonymous classes and functions
+// are not symbols, so the code within them belongs to the enclosing declaration.
+fn enclosing_symbol(source: &[u8], position: usize) -> (String, &[u8]) {
+    let mut namespace: Vec<u8> = vec![];
+    // the declarations whose body contains the current token, with the depth of their
+    // opening brace.
+    let mut declarations: Vec<(Declaration, Vec<u8>, usize)> = vec![];
+    // the declaration whose name was read, until its body is opened.
+    let mut pending: Option<(Declaration, Vec<u8>)> = None;
+    // the declaration whose name is expected next.
+    let mut expecting: Option<Declaration> = None;
+    // the kind of the last token read, ignoring comments.
+    let mut last = TokenKind::Eof;
+    let mut depth = 0;
+    let mut token = 0..source.len();
+
+    for next in Lexer::new().borrowed_tokens(source) {
+        if next.kind == TokenKind::Eof || next.span.position > position {
+            token.end = next.span.position.min(source.len());
+            break;
+        }
+
+        token.start = next.span.position;
+
+        if matches!(
+            next.kind,
+            TokenKind::SingleLineComment
+                | TokenKind::HashMarkComment
+                | TokenKind::MultiLineComment
+                | TokenKind::DocumentComment
+        ) {
+            continue;
+        }
+
+        let previous = std::mem::replace(&mut last, next.kind);
+
+        if let Some(declaration) = expecting.take() {
+            let named = match declaration {
+                Declaration::Namespace => matches!(
+                    next.kind,
+                    TokenKind::Identifier | TokenKind::QualifiedIdentifier
+                ),
+                Declaration::Class => next.kind == TokenKind::Identifier,
+                // methods can be named after keywords.
+                Declaration::Function => {
+                    !matches!(next.kind, TokenKind::LeftParen | TokenKind::Ampersand)
+                }
+            };
+
+            if named {
+                pending = Some((declaration, next.value.to_vec()));
+                continue;
+            }
+
+            // a function returning by reference.
+            if declaration == Declaration::Function && next.kind == TokenKind::Ampersand {
+                expecting = Some(declaration);
+                continue;
+            }
+        }
+
+        match next.kind {
+            TokenKind::Namespace => expecting = Some(Declaration::Namespace),
+            TokenKind::Class | TokenKind::Interface | TokenKind::Trait | TokenKind::Enum
+                if previous != TokenKind::DoubleColon =>
+            {
+                expecting = Some(Declaration::Class)
+            }
+            TokenKind::Function => expecting = Some(Declaration::Function),
+            TokenKind::SemiColon => {
+                if let Some((Declaration::Namespace, name)) = pending.take() {
+                    namespace = name;
+                }
+            }
+            TokenKind::LeftBrace | TokenKind::CurlyOpen | TokenKind::DollarLeftBrace => {
+                depth += 1;
+
+                if let Some((declaration, name)) = pending.take() {
+                    if declaration == Declaration::Namespace {
+                        namespace = name.clone();
+                    }
+
+                    declarations.push((declaration, name, depth));
+                }
+            }
+            TokenKind::RightBrace => {
+                if matches!(declarations.last(), Some((_, _, opened)) if *opened == depth) {
+                    if let Some((Declaration::Namespace, _, _)) = declarations.pop() {
+                        namespace.clear();
+                    }
+                }
+
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+
+    let mut symbol = namespace;
+    let mut class = false;
+    for (declaration, name, _) in &declarations {
+        match declaration {
+            Declaration::Namespace => continue,
+            Declaration::Function if class => symbol.extend_from_slice(b"::"),
+            _ if !symbol.is_empty() => symbol.push(b'\\'),
+            _ => {}
+        }
+
+        symbol.extend_from_slice(name);
+        class = *declaration == Declaration::Class;
+    }
+
+    (
+        String::from_utf8_lossy(&symbol).into_owned(),
+        &source[token],
+    )
+}
+
+/// A 64-bit FNV-1a hash, which unlike the hashers of the standard library is stable
+/// across releases, as fingerprints are written to disk.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::options::Options;
+
+    fn errors(source: &str) -> Vec<ParseError> {
+        let options = Options {
+            validate: true,
+            ..Options::default()
+        };
+
+        crate::parse_with(source, &options).err().unwrap().errors
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_position() {
+        let before = "<?php\nbreak;\n";
+        let after = "<?php\n\n// a comment\n  break;\n";
+
+        assert_eq!(
+            fingerprint(before, &errors(before)[0]),
+            fingerprint(after, &errors(after)[0])
+        );
+
+        // the code around the error isn't part of the fingerprint.
+        let other = "<?php\nbreak; // another break\n";
+        assert_eq!(
+            fingerprint(before, &errors(before)[0]),
+            fingerprint(other, &errors(other)[0])
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_of_code() {
+        let a = "<?php function a($a, $a) {}\n";
+        let b = "<?php function a($b, $b) {}\n";
+        assert_ne!(fingerprint(a, &errors(a)[0]), fingerprint(b, &errors(b)[0]));
+
+        let a = "<?php class A { function a() { break; } function b() { break; } }";
+        let (first, second) = (&errors(a)[0], &errors(a)[1]);
+        assert_ne!(fingerprint(a, first), fingerprint(a, second));
+
+        let b = "<?php class A { function b() { break; } }";
+        assert_eq!(fingerprint(a, second), fingerprint(b, &errors(b)[0]));
+    }
+
+    #[test]
+    fn test_enclosing_symbol() {
+        let symbol = |source: &str| {
+            let position = source.find("break").unwrap();
+            let (symbol, token) = enclosing_symbol(source.as_bytes(), position);
+            assert_eq!(token, b"break");
+
+            symbol
+        };
+
+        assert_eq!(symbol("<?php break;"), "");
+        assert_eq!(symbol("<?php function &a() { break; }"), "a");
+        assert_eq!(
+            symbol("<?php namespace App; class User { public function list() { break; } }"),
+            "App\\User::list"
+        );
+        assert_eq!(
+            symbol("<?php namespace App { enum A {} } function a() { $a = \"{$b}\"; break; }"),
+            "a"
+        );
+        assert_eq!(
+            symbol(
+                "<?php interface A { function a(); } class B { function b() { \
+                 $c = A::class; $d = new class { function c() {} }; \
+                 $e = function () { break; }; } }"
+            ),
+            "B::b"
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        let source = "<?php\nbreak;\nbreak;\n";
+        let errors = errors(source);
+
+        let mut baseline = Baseline::new();
+        baseline.add("./src/a.php", source, &errors[0]);
+        assert_eq!(baseline.len(), 1);
+
+        let mut baseline = Baseline::from_json(&baseline.to_json()).unwrap();
+
+        // only one of the two identical errors is part of the baseline.
+        assert_eq!(
+            baseline.filter("src/a.php", source, errors.clone()).len(),
+            1
+        );
+        assert!(baseline.is_empty());
+
+        let mut baseline = Baseline::new();
+        baseline.add("src/a.php", source, &errors[0]);
+        assert_eq!(baseline.filter("src/b.php", source, errors).len(), 2);
+        assert_eq!(
+            baseline.entries(),
+            vec![BaselineEntry {
+                file: "src/a.php".to_string(),
+                id: "E069".to_string(),
+                fingerprint: baseline.entries()[0].fingerprint.clone(),
+                count: 1,
+            }]
+        );
+
+        assert!(Baseline::from_json(r#"{"version": 2, "entries": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_files_are_relative_to_the_baseline() {
+        let source = "<?php\nbreak;\n";
+        let errors = errors(source);
+        let cwd = std::env::current_dir().unwrap();
+
+        let mut baseline = Baseline::for_file(&cwd.join("config/baseline.json")).unwrap();
+        baseline.add("./src/../src/a.php", source, &errors[0]);
+        assert_eq!(baseline.entries()[0].file, "../src/a.php");
+
+        let json = baseline.to_json();
+        for file in ["src/a.php", "./src/a.php"] {
+            let mut baseline = Baseline::from_json(&json).unwrap();
+            baseline.root = Some(cwd.join("config"));
+
+            assert!(baseline.filter(file, source, errors.clone()).is_empty());
+        }
+
+        let mut baseline = Baseline::from_json(&json).unwrap();
+        baseline.root = Some(cwd.join("config"));
+        let file = cwd.join("src/a.php").to_string_lossy().to_string();
+        assert!(baseline.filter(&file, source, errors.clone()).is_empty());
+
+        // with the baseline within `src`.
+        let mut baseline = Baseline::relative_to(&cwd.join("src")).unwrap();
+        baseline.add(&file, source, &errors[0]);
+        assert_eq!(baseline.entries()[0].file, "a.php");
+    }
+}
@@ -0,0 +1,147 @@
+use std::io;
+use std::path::Path;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::parser::ast::Program;
+use crate::parser::error::ParseErrorStack;
+use crate::source::SourceFile;
+
+/// The outcome of parsing one of the sources given to [`parse_sources`] or [`parse_files`].
+#[derive(Debug)]
+pub struct ParsedFile {
+    pub result: Result<Program, ParseErrorStack>,
+    /// The time spent parsing the source, not including reading it.
+    pub duration: Duration,
+}
+
+/// Parse the given sources using a pool of `threads` workers, or one per available core
+/// when `None`, returning the results in the same order as `sources`.
+pub fn parse_sources<S: AsRef<[u8]> + Sync>(
+    sources: &[S],
+    threads: Option<usize>,
+) -> Vec<ParsedFile> {
+    process(sources, threads, |source| parse(source.as_ref()))
+}
+
+/// Read and parse the given files, as [`parse_sources`] does.
+///
+/// The byte order mark of the files is skipped, as [`SourceFile`] does, so the
+/// positions are relative to the end of it.
+pub fn parse_files<P: AsRef<Path> + Sync>(
+    paths: &[P],
+    threads: Option<usize>,
+) -> Vec<io::Result<ParsedFile>> {
+    process(paths, threads, |path| {
+        let source = SourceFile::read(path)?;
+
+        Ok(parse(&source.contents))
+    })
+}
+
+/// Process the given items using a pool of `threads` workers, or one per available core
+/// when `None`, returning the results in the same order as `items`.
+///
+/// The workers have a larger stack than threads have by default, as deeply nested code
+/// makes the parser recurse deeply.
+pub fn process<I: Sync, T: Send>(
+    items: &[I],
+    threads: Option<usize>,
+    process: impl Fn(&I) -> T + Sync,
+) -> Vec<T> {
+    let threads = threads
+        .or_else(|| thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1)
+        .clamp(1, items.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<T>>> = Mutex::new((0..items.len()).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for index in 0..threads {
+            thread::Builder::new()
+                .name(format!("worker-{}", index))
+                .stack_size(16 * 1024 * 1024)
+                .spawn_scoped(scope, || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+
+                    let result = process(item);
+
+                    results.lock().unwrap()[index] = Some(result);
+                })
+                .unwrap_or_else(|e| panic!("failed to spawn thread: {:#?}", e));
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item should have been processed"))
+        .collect()
+}
+
+fn parse(source: &[u8]) -> ParsedFile {
+    let start = Instant::now();
+    let result = crate::parse(source);
+
+    ParsedFile {
+        result,
+        duration: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::lexer::token::Token;
+
+    #[test]
+    fn test_results_are_shareable_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Program>();
+        assert_send_sync::<Token>();
+        assert_send_sync::<ParseErrorStack>();
+    }
+
+    #[test]
+    fn test_parse_sources_keeps_the_order_of_the_sources() {
+        let sources = (0..32)
+            .map(|index| {
+                if index % 3 == 0 {
+                    format!("<?php $a{} = ;", index)
+                } else {
+                    format!("<?php $a{} = {};", index, index)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let parsed = parse_sources(&sources, Some(4));
+
+        assert_eq!(parsed.len(), sources.len());
+        for (index, (source, file)) in sources.iter().zip(&parsed).enumerate() {
+            assert_eq!(file.result.is_err(), index % 3 == 0);
+            assert_eq!(
+                file.result.as_ref().ok(),
+                crate::parse(source).ok().as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_files_reports_unreadable_files() {
+        let parsed = parse_files(&["tests/fixtures/0001/code.php", "missing.php"], None);
+
+        assert!(matches!(&parsed[0], Ok(file) if file.result.is_ok()));
+        assert!(parsed[1].is_err());
+    }
+}
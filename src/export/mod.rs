@@ -0,0 +1,46 @@
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+
+pub mod nikic;
+pub mod php_ast;
+pub mod tokens;
+
+// the position following the given token in the source.
+//
+// the value of some tokens, such as strings, is decoded, and the lexer doesn't always
+// record their position accurately, in which case they are assumed to end where the
+// whitespace preceding the next token starts.
+pub(crate) fn token_end(source: &[u8], tokens: &[Token], index: usize) -> usize {
+    let token = &tokens[index];
+    let start = token.span.position;
+    let end = start + token.value.len();
+    let decoded = matches!(
+        token.kind,
+        TokenKind::LiteralSingleQuotedString | TokenKind::LiteralDoubleQuotedString
+    );
+    if !decoded && !token.value.is_empty() && source.get(start..end) == Some(&token.value[..]) {
+        return end;
+    }
+
+    let next = tokens
+        .get(index + 1)
+        .map(|token| token.span.position)
+        .unwrap_or(source.len())
+        .min(source.len());
+    if next <= start {
+        return end.min(source.len());
+    }
+
+    start
+        + source[start..next]
+            .iter()
+            .rposition(|byte| !byte.is_ascii_whitespace())
+            .map_or(0, |position| position + 1)
+}
+
+// the source code of the given token.
+fn token_text<'a>(source: &'a [u8], tokens: &[Token], index: usize) -> &'a [u8] {
+    let start = tokens[index].span.position.min(source.len());
+
+    &source[start..token_end(source, tokens, index).max(start)]
+}
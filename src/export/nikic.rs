@@ -0,0 +1,3244 @@
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::arguments::ArgumentPlaceholder;
+use crate::parser::ast::arguments::SingleArgument;
+use crate::parser::ast::attributes::Attribute;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::AnonymousClassMember;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::control_flow::IfStatement;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::declares::DeclareStatement;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::literals::LiteralFloat;
+use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::literals::LiteralStringKind;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ClassModifierGroup;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PromotedPropertyModifier;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::operators::ArithmeticOperationExpression;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::operators::BitwiseOperationExpression;
+use crate::parser::ast::operators::ComparisonOperationExpression;
+use crate::parser::ast::operators::LogicalOperationExpression;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::TryStatement;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::Ending;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::MagicConstantExpression;
+use crate::parser::ast::MatchExpression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::Use;
+use crate::parser::ast::UseKind;
+
+// the modifier flags of `PhpParser\Modifiers`.
+const PUBLIC: u64 = 1;
+const PROTECTED: u64 = 2;
+const PRIVATE: u64 = 4;
+const STATIC: u64 = 8;
+const ABSTRACT: u64 = 16;
+const FINAL: u64 = 32;
+const READONLY: u64 = 64;
+
+/// Convert the program to the JSON produced by the `jsonSerialize` methods of
+/// nikic/PHP-Parser 5, so that tools built around its node types can consume it.
+///
+/// `source` must be the code the program was parsed from; it is used to compute the
+/// `startLine`, `endLine`, `startFilePos` and `endFilePos` attributes of every node, along
+/// with the `rawValue` of scalars. Token positions are not exported, as PHP-Parser counts
+/// whitespace tokens, which the lexer doesn't produce, and neither are the positions of the
+/// literal parts of interpolated strings. Comments are not attached to nodes, and names are
+/// not resolved, so `namespacedName` is always `null`.
+pub fn to_json(program: &Program, source: &[u8]) -> Value {
+    let (tokens, _) = Lexer::new().tokenize_with_errors(source);
+    let mut exporter = Exporter {
+        source,
+        tokens: &tokens,
+        cursor: 0,
+        frames: Vec::new(),
+    };
+
+    Value::Array(exporter.statements(program))
+}
+
+struct Exporter<'a> {
+    source: &'a [u8],
+    tokens: &'a [Token],
+    // the index of the first token that hasn't been attributed to a node yet, used to
+    // find the tokens of the nodes the AST doesn't record a span for, such as `true`.
+    cursor: usize,
+    // the first and last token of each node being exported.
+    frames: Vec<Option<(usize, usize)>>,
+}
+
+impl<'a> Exporter<'a> {
+    fn open(&mut self) {
+        self.frames.push(None);
+    }
+
+    fn close(&mut self, kind: &str, fields: Value) -> Value {
+        self.close_with(kind, fields, json!({}))
+    }
+
+    fn close_with(&mut self, kind: &str, fields: Value, attributes: Value) -> Value {
+        let mut node = Map::new();
+        node.insert("nodeType".to_string(), Value::from(kind));
+        if let Value::Object(fields) = fields {
+            node.extend(fields);
+        }
+
+        let mut attributes = match attributes {
+            Value::Object(attributes) => attributes,
+            _ => Map::new(),
+        };
+
+        if let Some((first, last)) = self.frames.pop().flatten() {
+            self.extend(first, last);
+
+            let start = &self.tokens[first];
+            let end = &self.tokens[last];
+            let newlines = self
+                .text(last)
+                .iter()
+                .filter(|byte| **byte == b'\n')
+                .count();
+
+            attributes.insert("startLine".to_string(), Value::from(start.span.line));
+            attributes.insert("startFilePos".to_string(), Value::from(start.span.position));
+            attributes.insert("endLine".to_string(), Value::from(end.span.line + newlines));
+            attributes.insert(
+                "endFilePos".to_string(),
+                Value::from(self.end(last).saturating_sub(1)),
+            );
+        }
+
+        node.insert("attributes".to_string(), Value::Object(attributes));
+
+        Value::Object(node)
+    }
+
+    fn extend(&mut self, first: usize, last: usize) {
+        if let Some(frame) = self.frames.last_mut() {
+            *frame = Some(match *frame {
+                Some((start, end)) => (start.min(first), end.max(last)),
+                None => (first, last),
+            });
+        }
+
+        self.cursor = self.cursor.max(last + 1);
+    }
+
+    fn end(&self, index: usize) -> usize {
+        super::token_end(self.source, self.tokens, index)
+    }
+
+    fn text(&self, index: usize) -> &'a [u8] {
+        super::token_text(self.source, self.tokens, index)
+    }
+
+    // attribute the token starting at the given span to the current node.
+    fn span(&mut self, span: Span) -> Option<usize> {
+        // tokens within interpolated strings might share a position, the last one being
+        // the one the AST refers to.
+        let index = self
+            .tokens
+            .partition_point(|token| token.span.position <= span.position)
+            .checked_sub(1)
+            .filter(|index| self.tokens[*index].span.position == span.position)?;
+
+        self.extend(index, index);
+
+        Some(index)
+    }
+
+    // attribute the next token of the given kind to the current node.
+    fn find(&mut self, predicate: impl Fn(&TokenKind) -> bool) -> Option<usize> {
+        let index = self.cursor
+            + self.tokens[self.cursor.min(self.tokens.len())..]
+                .iter()
+                .position(|token| predicate(&token.kind))?;
+
+        self.extend(index, index);
+
+        Some(index)
+    }
+
+    // attribute the next token to the current node if it is of the given kind.
+    fn next_if(&mut self, predicate: impl Fn(&TokenKind) -> bool) -> Option<usize> {
+        let index = self.cursor;
+        if !predicate(&self.tokens.get(index)?.kind) {
+            return None;
+        }
+
+        self.extend(index, index);
+
+        Some(index)
+    }
+
+    fn raw(&self, index: Option<usize>, default: &ByteString) -> String {
+        match index {
+            Some(index) => String::from_utf8_lossy(self.text(index)).into_owned(),
+            None => text(default),
+        }
+    }
+
+    fn ending(&mut self, ending: &Ending) {
+        match ending {
+            Ending::Semicolon(span) | Ending::CloseTag(span) => self.span(*span),
+        };
+    }
+
+    fn statements(&mut self, statements: &[Statement]) -> Vec<Value> {
+        let mut exported = Vec::new();
+        let mut echo = None;
+
+        for statement in statements {
+            match statement {
+                Statement::EchoOpeningTag(tag) => echo = Some(tag.span),
+                // `<?= $a ?>` is an echo statement.
+                Statement::Expression(statement) if echo.is_some() => {
+                    self.open();
+                    self.span(echo.take().unwrap());
+                    let expression = self.expression(&statement.expression);
+                    self.ending(&statement.ending);
+
+                    exported.push(self.close("Stmt_Echo", json!({ "exprs": [expression] })));
+                }
+                statement => {
+                    echo = None;
+                    exported.extend(self.statement(statement));
+                }
+            }
+        }
+
+        exported
+    }
+
+    // the statements of a loop or conditional body, which are written either as a block
+    // or as a single statement.
+    fn body(&mut self, statement: &Statement) -> Vec<Value> {
+        match statement {
+            Statement::Block(block) => {
+                self.span(block.left_brace);
+                let statements = self.statements(&block.statements);
+                self.span(block.right_brace);
+
+                statements
+            }
+            statement => self.statements(std::slice::from_ref(statement)),
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Option<Value> {
+        let exported = match statement {
+            Statement::FullOpeningTag(_)
+            | Statement::ShortOpeningTag(_)
+            | Statement::EchoOpeningTag(_)
+            | Statement::ClosingTag(_)
+            | Statement::Comment(_)
+            | Statement::Noop(_) => return None,
+            Statement::InlineHtml(statement) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::InlineHtml);
+
+                self.close("Stmt_InlineHTML", json!({ "value": text(&statement.html) }))
+            }
+            Statement::Label(statement) => {
+                self.open();
+                let name = self.identifier(&statement.label);
+                self.span(statement.colon);
+
+                self.close("Stmt_Label", json!({ "name": name }))
+            }
+            Statement::Goto(statement) => {
+                self.open();
+                self.span(statement.keyword);
+                let name = self.identifier(&statement.label);
+                self.span(statement.semicolon);
+
+                self.close("Stmt_Goto", json!({ "name": name }))
+            }
+            Statement::HaltCompiler(statement) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::HaltCompiler);
+                let remaining = statement.content.as_ref().map(text).unwrap_or_default();
+
+                self.close("Stmt_HaltCompiler", json!({ "remaining": remaining }))
+            }
+            Statement::Static(statement) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::Static);
+                let vars = statement
+                    .vars
+                    .iter()
+                    .map(|var| {
+                        self.open();
+                        let name = self.variable(&var.var);
+                        let default = var.default.as_ref().map(|value| self.expression(value));
+
+                        self.close("StaticVar", json!({ "var": name, "default": default }))
+                    })
+                    .collect::<Vec<_>>();
+                self.next_if(|kind| kind == &TokenKind::SemiColon);
+
+                self.close("Stmt_Static", json!({ "vars": vars }))
+            }
+            Statement::DoWhile(statement) => {
+                self.open();
+                self.span(statement.r#do);
+                let stmts = self.body(&statement.body);
+                self.span(statement.r#while);
+                self.span(statement.left_parenthesis);
+                let cond = self.expression(&statement.condition);
+                self.span(statement.right_parenthesis);
+                self.span(statement.semicolon);
+
+                self.close("Stmt_Do", json!({ "stmts": stmts, "cond": cond }))
+            }
+            Statement::While(statement) => {
+                self.open();
+                self.span(statement.r#while);
+                self.span(statement.left_parenthesis);
+                let cond = self.expression(&statement.condition);
+                self.span(statement.right_parenthesis);
+                let stmts = match &statement.body {
+                    WhileStatementBody::Statement { statement } => self.body(statement),
+                    WhileStatementBody::Block {
+                        colon,
+                        statements,
+                        endwhile,
+                        ending,
+                    } => {
+                        self.span(*colon);
+                        let statements = self.statements(statements);
+                        self.span(*endwhile);
+                        self.ending(ending);
+
+                        statements
+                    }
+                };
+
+                self.close("Stmt_While", json!({ "cond": cond, "stmts": stmts }))
+            }
+            Statement::For(statement) => {
+                self.open();
+                self.span(statement.r#for);
+                self.span(statement.left_parenthesis);
+                let iterator = &statement.iterator;
+                let init = self.expressions(&iterator.initializations.inner);
+                self.span(iterator.initializations_semicolon);
+                let cond = self.expressions(&iterator.conditions.inner);
+                self.span(iterator.conditions_semicolon);
+                let r#loop = self.expressions(&iterator.r#loop.inner);
+                self.span(statement.right_parenthesis);
+                let stmts = match &statement.body {
+                    ForStatementBody::Statement { statement } => self.body(statement),
+                    ForStatementBody::Block {
+                        colon,
+                        statements,
+                        endfor,
+                        ending,
+                    } => {
+                        self.span(*colon);
+                        let statements = self.statements(statements);
+                        self.span(*endfor);
+                        self.ending(ending);
+
+                        statements
+                    }
+                };
+
+                self.close(
+                    "Stmt_For",
+                    json!({ "init": init, "cond": cond, "loop": r#loop, "stmts": stmts }),
+                )
+            }
+            Statement::Foreach(statement) => {
+                self.open();
+                self.span(statement.foreach);
+                self.span(statement.left_parenthesis);
+                let (expression, key, by_ref, value) = match &statement.iterator {
+                    ForeachStatementIterator::Value {
+                        expression,
+                        r#as,
+                        ampersand,
+                        value,
+                    } => {
+                        let expression = self.expression(expression);
+                        self.span(*r#as);
+                        let by_ref = self.ampersand(*ampersand);
+                        let value = self.assignable(value);
+
+                        (expression, None, by_ref, value)
+                    }
+                    ForeachStatementIterator::KeyAndValue {
+                        expression,
+                        r#as,
+                        ampersand,
+                        key,
+                        double_arrow,
+                        value,
+                    } => {
+                        let expression = self.expression(expression);
+                        self.span(*r#as);
+                        let key = self.expression(key);
+                        self.span(*double_arrow);
+                        let by_ref = self.ampersand(*ampersand);
+                        let value = self.assignable(value);
+
+                        (expression, Some(key), by_ref, value)
+                    }
+                };
+                self.span(statement.right_parenthesis);
+                let stmts = match &statement.body {
+                    ForeachStatementBody::Statement { statement } => self.body(statement),
+                    ForeachStatementBody::Block {
+                        colon,
+                        statements,
+                        endforeach,
+                        ending,
+                    } => {
+                        self.span(*colon);
+                        let statements = self.statements(statements);
+                        self.span(*endforeach);
+                        self.ending(ending);
+
+                        statements
+                    }
+                };
+
+                self.close(
+                    "Stmt_Foreach",
+                    json!({
+                        "expr": expression,
+                        "keyVar": key,
+                        "byRef": by_ref,
+                        "valueVar": value,
+                        "stmts": stmts,
+                    }),
+                )
+            }
+            Statement::Break(statement) => {
+                self.open();
+                self.span(statement.r#break);
+                let num = statement.level.as_ref().map(|level| self.level(level));
+                self.ending(&statement.ending);
+
+                self.close("Stmt_Break", json!({ "num": num }))
+            }
+            Statement::Continue(statement) => {
+                self.open();
+                self.span(statement.r#continue);
+                let num = statement.level.as_ref().map(|level| self.level(level));
+                self.ending(&statement.ending);
+
+                self.close("Stmt_Continue", json!({ "num": num }))
+            }
+            Statement::Constant(statement) => {
+                self.open();
+                self.span(statement.r#const);
+                let consts = self.constants(&statement.entries);
+                self.span(statement.semicolon);
+
+                self.close("Stmt_Const", json!({ "consts": consts }))
+            }
+            Statement::Function(statement) => self.function(statement),
+            Statement::Class(statement) => self.class(statement),
+            Statement::Trait(statement) => self.r#trait(statement),
+            Statement::Interface(statement) => self.interface(statement),
+            Statement::UnitEnum(statement) => self.unit_enum(statement),
+            Statement::BackedEnum(statement) => self.backed_enum(statement),
+            Statement::If(statement) => self.r#if(statement),
+            Statement::Switch(statement) => {
+                self.open();
+                self.span(statement.switch);
+                self.span(statement.left_parenthesis);
+                let cond = self.expression(&statement.condition);
+                self.span(statement.right_parenthesis);
+                let cases = statement
+                    .cases
+                    .iter()
+                    .map(|case| {
+                        self.open();
+                        self.find(|kind| matches!(kind, TokenKind::Case | TokenKind::Default));
+                        let cond = case
+                            .condition
+                            .as_ref()
+                            .map(|condition| self.expression(condition));
+                        let stmts = self.statements(&case.body);
+
+                        self.close("Stmt_Case", json!({ "cond": cond, "stmts": stmts }))
+                    })
+                    .collect::<Vec<_>>();
+                if let Some(index) =
+                    self.find(|kind| matches!(kind, TokenKind::RightBrace | TokenKind::EndSwitch))
+                {
+                    if self.tokens[index].kind == TokenKind::EndSwitch {
+                        self.next_if(|kind| {
+                            matches!(kind, TokenKind::SemiColon | TokenKind::CloseTag)
+                        });
+                    }
+                }
+
+                self.close("Stmt_Switch", json!({ "cond": cond, "cases": cases }))
+            }
+            Statement::Echo(statement) => {
+                self.open();
+                self.span(statement.echo);
+                let exprs = self.expressions(&statement.values);
+                self.ending(&statement.ending);
+
+                self.close("Stmt_Echo", json!({ "exprs": exprs }))
+            }
+            Statement::Expression(statement) => {
+                self.open();
+                // `unset()` is a statement, even though it is parsed as an expression.
+                let exported = match &statement.expression {
+                    Expression::Unset(unset) => {
+                        self.span(unset.unset);
+                        let vars = self.argument_values(&unset.arguments);
+                        self.ending(&statement.ending);
+
+                        return Some(self.close("Stmt_Unset", json!({ "vars": vars })));
+                    }
+                    expression => self.expression(expression),
+                };
+                self.ending(&statement.ending);
+
+                self.close("Stmt_Expression", json!({ "expr": exported }))
+            }
+            Statement::Return(statement) => {
+                self.open();
+                self.span(statement.r#return);
+                let expr = statement.value.as_ref().map(|value| self.expression(value));
+                self.ending(&statement.ending);
+
+                self.close("Stmt_Return", json!({ "expr": expr }))
+            }
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                // the statements following `namespace Foo;` belong to the namespace, but
+                // are not part of its position.
+                self.open();
+                self.span(namespace.start);
+                let name = self.name(&namespace.name);
+                self.span(namespace.end);
+                let mut exported = self.close_with(
+                    "Stmt_Namespace",
+                    json!({ "name": name }),
+                    json!({ "kind": 1 }),
+                );
+                exported["stmts"] = Value::Array(self.statements(&namespace.statements));
+
+                exported
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                self.open();
+                self.span(namespace.namespace);
+                let name = namespace.name.as_ref().map(|name| self.name(name));
+                self.span(namespace.body.start);
+                let stmts = self.statements(&namespace.body.statements);
+                self.span(namespace.body.end);
+
+                self.close_with(
+                    "Stmt_Namespace",
+                    json!({ "name": name, "stmts": stmts }),
+                    json!({ "kind": 2 }),
+                )
+            }
+            Statement::Use(statement) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::Use);
+                let uses = statement
+                    .uses
+                    .iter()
+                    .map(|r#use| self.use_item(r#use))
+                    .collect::<Vec<_>>();
+                self.next_if(|kind| kind == &TokenKind::SemiColon);
+
+                self.close(
+                    "Stmt_Use",
+                    json!({ "type": use_type(&statement.kind), "uses": uses }),
+                )
+            }
+            Statement::GroupUse(statement) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::Use);
+                let prefix = text(&statement.prefix.value);
+                let prefix = prefix.trim_start_matches('\\').trim_end_matches('\\');
+                self.open();
+                self.span(statement.prefix.span);
+                let prefix = self.close("Name", json!({ "name": prefix }));
+                let uses = statement
+                    .uses
+                    .iter()
+                    .map(|r#use| self.use_item(r#use))
+                    .collect::<Vec<_>>();
+                self.find(|kind| kind == &TokenKind::RightBrace);
+                self.next_if(|kind| kind == &TokenKind::SemiColon);
+
+                self.close(
+                    "Stmt_GroupUse",
+                    json!({
+                        "type": use_type(&statement.kind),
+                        "prefix": prefix,
+                        "uses": uses,
+                    }),
+                )
+            }
+            Statement::Try(statement) => self.r#try(statement),
+            Statement::Block(statement) => {
+                self.open();
+                self.span(statement.left_brace);
+                let stmts = self.statements(&statement.statements);
+                self.span(statement.right_brace);
+
+                self.close("Stmt_Block", json!({ "stmts": stmts }))
+            }
+            Statement::Global(statement) => {
+                self.open();
+                self.span(statement.global);
+                let vars = statement
+                    .variables
+                    .iter()
+                    .map(|variable| self.variable(variable))
+                    .collect::<Vec<_>>();
+                self.next_if(|kind| kind == &TokenKind::SemiColon);
+
+                self.close("Stmt_Global", json!({ "vars": vars }))
+            }
+            Statement::Declare(statement) => self.declare(statement),
+        };
+
+        Some(exported)
+    }
+
+    fn r#if(&mut self, statement: &IfStatement) -> Value {
+        self.open();
+        self.span(statement.r#if);
+        self.span(statement.left_parenthesis);
+        let cond = self.expression(&statement.condition);
+        self.span(statement.right_parenthesis);
+
+        let (stmts, elseifs, r#else) = match &statement.body {
+            IfStatementBody::Statement {
+                statement,
+                elseifs,
+                r#else,
+            } => {
+                let stmts = self.body(statement);
+                let elseifs = elseifs
+                    .iter()
+                    .map(|elseif| {
+                        self.open();
+                        self.span(elseif.elseif);
+                        self.span(elseif.left_parenthesis);
+                        let cond = self.expression(&elseif.condition);
+                        self.span(elseif.right_parenthesis);
+                        let stmts = self.body(&elseif.statement);
+
+                        self.close("Stmt_ElseIf", json!({ "cond": cond, "stmts": stmts }))
+                    })
+                    .collect::<Vec<_>>();
+                let r#else = r#else.as_ref().map(|r#else| {
+                    self.open();
+                    self.span(r#else.r#else);
+                    let stmts = self.body(&r#else.statement);
+
+                    self.close("Stmt_Else", json!({ "stmts": stmts }))
+                });
+
+                (stmts, elseifs, r#else)
+            }
+            IfStatementBody::Block {
+                colon,
+                statements,
+                elseifs,
+                r#else,
+                endif,
+                ending,
+            } => {
+                self.span(*colon);
+                let stmts = self.statements(statements);
+                let elseifs = elseifs
+                    .iter()
+                    .map(|elseif| {
+                        self.open();
+                        self.span(elseif.elseif);
+                        self.span(elseif.left_parenthesis);
+                        let cond = self.expression(&elseif.condition);
+                        self.span(elseif.right_parenthesis);
+                        self.span(elseif.colon);
+                        let stmts = self.statements(&elseif.statements);
+
+                        self.close("Stmt_ElseIf", json!({ "cond": cond, "stmts": stmts }))
+                    })
+                    .collect::<Vec<_>>();
+                let r#else = r#else.as_ref().map(|r#else| {
+                    self.open();
+                    self.span(r#else.r#else);
+                    self.span(r#else.colon);
+                    let stmts = self.statements(&r#else.statements);
+
+                    self.close("Stmt_Else", json!({ "stmts": stmts }))
+                });
+                self.span(*endif);
+                self.ending(ending);
+
+                (stmts, elseifs, r#else)
+            }
+        };
+
+        self.close(
+            "Stmt_If",
+            json!({ "cond": cond, "stmts": stmts, "elseifs": elseifs, "else": r#else }),
+        )
+    }
+
+    fn r#try(&mut self, statement: &TryStatement) -> Value {
+        // the `end` spans of try, catch and finally blocks point at the token following
+        // them, so the closing braces are looked up instead.
+        self.open();
+        self.span(statement.start);
+        let stmts = self.statements(&statement.body);
+        self.find(|kind| kind == &TokenKind::RightBrace);
+
+        let catches = statement
+            .catches
+            .iter()
+            .map(|catch| {
+                self.open();
+                self.span(catch.start);
+                let types = match &catch.types {
+                    CatchType::Identifier { identifier } => vec![self.name(identifier)],
+                    CatchType::Union { identifiers } => identifiers
+                        .iter()
+                        .map(|identifier| self.name(identifier))
+                        .collect(),
+                };
+                let var = catch.var.as_ref().map(|var| self.simple_variable(var));
+                let stmts = self.statements(&catch.body);
+                self.find(|kind| kind == &TokenKind::RightBrace);
+
+                self.close(
+                    "Stmt_Catch",
+                    json!({ "types": types, "var": var, "stmts": stmts }),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let finally = statement.finally.as_ref().map(|finally| {
+            self.open();
+            self.span(finally.start);
+            let stmts = self.statements(&finally.body);
+            self.find(|kind| kind == &TokenKind::RightBrace);
+
+            self.close("Stmt_Finally", json!({ "stmts": stmts }))
+        });
+
+        self.close(
+            "Stmt_TryCatch",
+            json!({ "stmts": stmts, "catches": catches, "finally": finally }),
+        )
+    }
+
+    fn declare(&mut self, statement: &DeclareStatement) -> Value {
+        self.open();
+        self.span(statement.declare);
+        self.span(statement.entries.left_parenthesis);
+        let declares = statement
+            .entries
+            .entries
+            .iter()
+            .map(|entry| {
+                self.open();
+                let key = self.identifier(&entry.key);
+                self.span(entry.equals);
+                let value = self.literal(&entry.value);
+
+                self.close("DeclareItem", json!({ "key": key, "value": value }))
+            })
+            .collect::<Vec<_>>();
+        self.span(statement.entries.right_parenthesis);
+
+        let stmts = match &statement.body {
+            DeclareBody::Noop { semicolon } => {
+                self.span(*semicolon);
+
+                None
+            }
+            DeclareBody::Braced {
+                left_brace,
+                statements,
+                right_brace,
+            } => {
+                self.span(*left_brace);
+                let statements = self.statements(statements);
+                self.span(*right_brace);
+
+                Some(statements)
+            }
+            DeclareBody::Expression {
+                expression,
+                semicolon,
+            } => {
+                self.open();
+                let expression = self.expression(expression);
+                self.span(*semicolon);
+
+                Some(vec![
+                    self.close("Stmt_Expression", json!({ "expr": expression }))
+                ])
+            }
+            DeclareBody::Block {
+                colon,
+                statements,
+                end,
+            } => {
+                self.span(*colon);
+                let statements = self.statements(statements);
+                self.span(end.0);
+                self.span(end.1);
+
+                Some(statements)
+            }
+        };
+
+        self.close(
+            "Stmt_Declare",
+            json!({ "declares": declares, "stmts": stmts }),
+        )
+    }
+
+    fn use_item(&mut self, r#use: &Use) -> Value {
+        self.open();
+        // imported names are always fully qualified, with or without a leading `\`.
+        let name = text(&r#use.name.value);
+        self.open();
+        self.span(r#use.name.span);
+        let name = self.close("Name", json!({ "name": name.trim_start_matches('\\') }));
+        let alias = r#use.alias.as_ref().map(|alias| self.identifier(alias));
+        let r#type = r#use.kind.as_ref().map(use_type).unwrap_or(0);
+
+        self.close(
+            "UseItem",
+            json!({ "type": r#type, "name": name, "alias": alias }),
+        )
+    }
+
+    fn level(&mut self, level: &Level) -> Value {
+        match level {
+            Level::Literal(literal) => self.integer(literal),
+            Level::Parenthesized {
+                left_parenthesis,
+                level,
+                right_parenthesis,
+            } => {
+                self.span(*left_parenthesis);
+                let level = self.level(level);
+                self.span(*right_parenthesis);
+
+                level
+            }
+        }
+    }
+
+    fn constants(&mut self, entries: &[ConstantEntry]) -> Vec<Value> {
+        entries
+            .iter()
+            .map(|entry| {
+                self.open();
+                let name = self.identifier(&entry.name);
+                self.span(entry.equals);
+                let value = self.expression(&entry.value);
+
+                self.close(
+                    "Const",
+                    json!({ "name": name, "value": value, "namespacedName": null }),
+                )
+            })
+            .collect()
+    }
+
+    fn function(&mut self, statement: &FunctionStatement) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&statement.attributes);
+        self.span(statement.function);
+        let by_ref = self.ampersand(statement.ampersand);
+        let name = self.identifier(&statement.name);
+        let params = self.parameters(&statement.parameters);
+        let return_type = self.return_type(&statement.return_type);
+        self.span(statement.body.left_brace);
+        let stmts = self.statements(&statement.body.statements);
+        self.span(statement.body.right_brace);
+
+        self.close(
+            "Stmt_Function",
+            json!({
+                "attrGroups": attr_groups,
+                "byRef": by_ref,
+                "name": name,
+                "params": params,
+                "returnType": return_type,
+                "stmts": stmts,
+                "namespacedName": null,
+            }),
+        )
+    }
+
+    fn class(&mut self, statement: &ClassStatement) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&statement.attributes);
+        let flags = self.class_modifiers(&statement.modifiers);
+        self.span(statement.class);
+        let name = self.identifier(&statement.name);
+        let extends = statement.extends.as_ref().map(|extends| {
+            self.span(extends.extends);
+            self.name(&extends.parent)
+        });
+        let implements = match &statement.implements {
+            Some(implements) => {
+                self.span(implements.implements);
+                self.names(&implements.interfaces.inner)
+            }
+            None => Vec::new(),
+        };
+        self.span(statement.body.left_brace);
+        let stmts = statement
+            .body
+            .members
+            .iter()
+            .map(|member| self.class_member(member))
+            .collect::<Vec<_>>();
+        self.span(statement.body.right_brace);
+
+        self.close(
+            "Stmt_Class",
+            json!({
+                "attrGroups": attr_groups,
+                "flags": flags,
+                "name": name,
+                "extends": extends,
+                "implements": implements,
+                "stmts": stmts,
+                "namespacedName": null,
+            }),
+        )
+    }
+
+    fn anonymous_class(&mut self, expression: &AnonymousClassExpression) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&expression.attributes);
+        self.span(expression.class);
+        let extends = expression.extends.as_ref().map(|extends| {
+            self.span(extends.extends);
+            self.name(&extends.parent)
+        });
+        let implements = match &expression.implements {
+            Some(implements) => {
+                self.span(implements.implements);
+                self.names(&implements.interfaces.inner)
+            }
+            None => Vec::new(),
+        };
+        self.span(expression.body.left_brace);
+        let stmts = expression
+            .body
+            .members
+            .iter()
+            .map(|member| match member {
+                AnonymousClassMember::Constant(constant) => self.class_constant(constant),
+                AnonymousClassMember::TraitUsage(usage) => self.trait_usage(usage),
+                AnonymousClassMember::Property(property) => self.property(property),
+                AnonymousClassMember::VariableProperty(property) => {
+                    self.variable_property(property)
+                }
+                AnonymousClassMember::ConcreteMethod(method) => self.concrete_method(method),
+                AnonymousClassMember::ConcreteConstructor(constructor) => {
+                    self.concrete_constructor(constructor)
+                }
+            })
+            .collect::<Vec<_>>();
+        self.span(expression.body.right_brace);
+
+        self.close(
+            "Stmt_Class",
+            json!({
+                "attrGroups": attr_groups,
+                "flags": 0,
+                "name": null,
+                "extends": extends,
+                "implements": implements,
+                "stmts": stmts,
+                "namespacedName": null,
+            }),
+        )
+    }
+
+    fn r#trait(&mut self, statement: &TraitStatement) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&statement.attributes);
+        self.span(statement.r#trait);
+        let name = self.identifier(&statement.name);
+        self.span(statement.body.left_brace);
+        let stmts = statement
+            .body
+            .members
+            .iter()
+            .map(|member| self.trait_member(member))
+            .collect::<Vec<_>>();
+        self.span(statement.body.right_brace);
+
+        self.close(
+            "Stmt_Trait",
+            json!({
+                "attrGroups": attr_groups,
+                "name": name,
+                "stmts": stmts,
+                "namespacedName": null,
+            }),
+        )
+    }
+
+    fn interface(&mut self, statement: &InterfaceStatement) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&statement.attributes);
+        self.span(statement.interface);
+        let name = self.identifier(&statement.name);
+        let extends = match &statement.extends {
+            Some(extends) => {
+                self.span(extends.extends);
+                self.names(&extends.parents.inner)
+            }
+            None => Vec::new(),
+        };
+        self.span(statement.body.left_brace);
+        let stmts = statement
+            .body
+            .members
+            .iter()
+            .map(|member| match member {
+                InterfaceMember::Constant(constant) => self.class_constant(constant),
+                InterfaceMember::Constructor(constructor) => self.abstract_constructor(constructor),
+                InterfaceMember::Method(method) => self.abstract_method(method),
+            })
+            .collect::<Vec<_>>();
+        self.span(statement.body.right_brace);
+
+        self.close(
+            "Stmt_Interface",
+            json!({
+                "attrGroups": attr_groups,
+                "name": name,
+                "extends": extends,
+                "stmts": stmts,
+                "namespacedName": null,
+            }),
+        )
+    }
+
+    fn unit_enum(&mut self, statement: &UnitEnumStatement) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&statement.attributes);
+        self.span(statement.r#enum);
+        let name = self.identifier(&statement.name);
+        let implements = self.names(&statement.implements);
+        self.span(statement.body.left_brace);
+        let stmts = statement
+            .body
+            .members
+            .iter()
+            .map(|member| match member {
+                UnitEnumMember::Case(case) => {
+                    self.open();
+                    let attr_groups = self.attribute_groups(&case.attributes);
+                    self.span(case.start);
+                    let name = self.identifier(&case.name);
+                    self.span(case.end);
+
+                    self.close(
+                        "Stmt_EnumCase",
+                        json!({ "attrGroups": attr_groups, "name": name, "expr": null }),
+                    )
+                }
+                UnitEnumMember::Method(method) => self.concrete_method(method),
+                UnitEnumMember::Constant(constant) => self.class_constant(constant),
+                UnitEnumMember::TraitUsage(usage) => self.trait_usage(usage),
+            })
+            .collect::<Vec<_>>();
+        self.span(statement.body.right_brace);
+
+        self.close(
+            "Stmt_Enum",
+            json!({
+                "attrGroups": attr_groups,
+                "name": name,
+                "scalarType": null,
+                "implements": implements,
+                "stmts": stmts,
+                "namespacedName": null,
+            }),
+        )
+    }
+
+    fn backed_enum(&mut self, statement: &BackedEnumStatement) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&statement.attributes);
+        self.span(statement.r#enum);
+        let name = self.identifier(&statement.name);
+        let (colon, span, r#type) = match statement.backed_type {
+            BackedEnumType::String(colon, span) => (colon, span, "string"),
+            BackedEnumType::Int(colon, span) => (colon, span, "int"),
+        };
+        self.span(colon);
+        self.open();
+        self.span(span);
+        let scalar_type = self.close("Identifier", json!({ "name": r#type }));
+        let implements = self.names(&statement.implements);
+        self.span(statement.body.left_brace);
+        let stmts = statement
+            .body
+            .members
+            .iter()
+            .map(|member| match member {
+                BackedEnumMember::Case(case) => {
+                    self.open();
+                    let attr_groups = self.attribute_groups(&case.attributes);
+                    self.span(case.case);
+                    let name = self.identifier(&case.name);
+                    self.span(case.equals);
+                    let expr = self.expression(&case.value);
+                    self.span(case.semicolon);
+
+                    self.close(
+                        "Stmt_EnumCase",
+                        json!({ "attrGroups": attr_groups, "name": name, "expr": expr }),
+                    )
+                }
+                BackedEnumMember::Method(method) => self.concrete_method(method),
+                BackedEnumMember::Constant(constant) => self.class_constant(constant),
+                BackedEnumMember::TraitUsage(usage) => self.trait_usage(usage),
+            })
+            .collect::<Vec<_>>();
+        self.span(statement.body.right_brace);
+
+        self.close(
+            "Stmt_Enum",
+            json!({
+                "attrGroups": attr_groups,
+                "name": name,
+                "scalarType": scalar_type,
+                "implements": implements,
+                "stmts": stmts,
+                "namespacedName": null,
+            }),
+        )
+    }
+
+    fn class_member(&mut self, member: &ClassMember) -> Value {
+        match member {
+            ClassMember::Constant(constant) => self.class_constant(constant),
+            ClassMember::TraitUsage(usage) => self.trait_usage(usage),
+            ClassMember::Property(property) => self.property(property),
+            ClassMember::VariableProperty(property) => self.variable_property(property),
+            ClassMember::AbstractMethod(method) => self.abstract_method(method),
+            ClassMember::AbstractConstructor(constructor) => self.abstract_constructor(constructor),
+            ClassMember::ConcreteMethod(method) => self.concrete_method(method),
+            ClassMember::ConcreteConstructor(constructor) => self.concrete_constructor(constructor),
+        }
+    }
+
+    fn trait_member(&mut self, member: &TraitMember) -> Value {
+        match member {
+            TraitMember::Constant(constant) => self.class_constant(constant),
+            TraitMember::TraitUsage(usage) => self.trait_usage(usage),
+            TraitMember::Property(property) => self.property(property),
+            TraitMember::VariableProperty(property) => self.variable_property(property),
+            TraitMember::AbstractMethod(method) => self.abstract_method(method),
+            TraitMember::AbstractConstructor(constructor) => self.abstract_constructor(constructor),
+            TraitMember::ConcreteMethod(method) => self.concrete_method(method),
+            TraitMember::ConcreteConstructor(constructor) => self.concrete_constructor(constructor),
+        }
+    }
+
+    fn class_constant(&mut self, constant: &ClassishConstant) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&constant.attributes);
+        let flags = constant
+            .modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                ConstantModifier::Final(span) => (*span, FINAL),
+                ConstantModifier::Public(span) => (*span, PUBLIC),
+                ConstantModifier::Protected(span) => (*span, PROTECTED),
+                ConstantModifier::Private(span) => (*span, PRIVATE),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum::<u64>();
+        self.span(constant.r#const);
+        let consts = self.constants(&constant.entries);
+        self.span(constant.semicolon);
+
+        self.close(
+            "Stmt_ClassConst",
+            json!({
+                "attrGroups": attr_groups,
+                "flags": flags,
+                "type": null,
+                "consts": consts,
+            }),
+        )
+    }
+
+    fn trait_usage(&mut self, usage: &TraitUsage) -> Value {
+        self.open();
+        self.span(usage.r#use);
+        let traits = self.names(&usage.traits);
+        let adaptations = usage
+            .adaptations
+            .iter()
+            .map(|adaptation| {
+                self.open();
+                match adaptation {
+                    TraitUsageAdaptation::Alias {
+                        r#trait,
+                        method,
+                        alias,
+                        visibility,
+                    } => {
+                        let r#trait = r#trait.as_ref().map(|r#trait| self.name(r#trait));
+                        let method = self.identifier(method);
+                        let modifier = visibility
+                            .as_ref()
+                            .map(|visibility| self.visibility(visibility));
+                        let alias = self.identifier(alias);
+
+                        self.close(
+                            "Stmt_TraitUseAdaptation_Alias",
+                            json!({
+                                "trait": r#trait,
+                                "method": method,
+                                "newModifier": modifier,
+                                "newName": alias,
+                            }),
+                        )
+                    }
+                    TraitUsageAdaptation::Visibility {
+                        r#trait,
+                        method,
+                        visibility,
+                    } => {
+                        let r#trait = r#trait.as_ref().map(|r#trait| self.name(r#trait));
+                        let method = self.identifier(method);
+                        let modifier = self.visibility(visibility);
+
+                        self.close(
+                            "Stmt_TraitUseAdaptation_Alias",
+                            json!({
+                                "trait": r#trait,
+                                "method": method,
+                                "newModifier": modifier,
+                                "newName": null,
+                            }),
+                        )
+                    }
+                    TraitUsageAdaptation::Precedence {
+                        r#trait,
+                        method,
+                        insteadof,
+                    } => {
+                        let r#trait = r#trait.as_ref().map(|r#trait| self.name(r#trait));
+                        let method = self.identifier(method);
+                        let insteadof = self.names(insteadof);
+
+                        self.close(
+                            "Stmt_TraitUseAdaptation_Precedence",
+                            json!({
+                                "trait": r#trait,
+                                "method": method,
+                                "insteadof": insteadof,
+                            }),
+                        )
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // `use Foo;` or `use Foo { ... }`
+        if !usage.adaptations.is_empty()
+            || self.next_if(|kind| kind == &TokenKind::SemiColon).is_none()
+        {
+            self.find(|kind| kind == &TokenKind::RightBrace);
+        }
+
+        self.close(
+            "Stmt_TraitUse",
+            json!({ "traits": traits, "adaptations": adaptations }),
+        )
+    }
+
+    fn property(&mut self, property: &Property) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&property.attributes);
+        let flags = property
+            .modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                PropertyModifier::Public(span) => (*span, PUBLIC),
+                PropertyModifier::Protected(span) => (*span, PROTECTED),
+                PropertyModifier::Private(span) => (*span, PRIVATE),
+                PropertyModifier::Static(span) => (*span, STATIC),
+                PropertyModifier::Readonly(span) => (*span, READONLY),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum::<u64>();
+        let r#type = property.r#type.as_ref().map(|r#type| self.r#type(r#type));
+        let props = self.property_entries(&property.entries);
+        self.span(property.end);
+
+        self.close(
+            "Stmt_Property",
+            json!({
+                "attrGroups": attr_groups,
+                "flags": flags,
+                "type": r#type,
+                "props": props,
+            }),
+        )
+    }
+
+    fn variable_property(&mut self, property: &VariableProperty) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&property.attributes);
+        self.find(|kind| kind == &TokenKind::Var);
+        let r#type = property.r#type.as_ref().map(|r#type| self.r#type(r#type));
+        let props = self.property_entries(&property.entries);
+        self.span(property.end);
+
+        self.close(
+            "Stmt_Property",
+            json!({
+                "attrGroups": attr_groups,
+                "flags": 0,
+                "type": r#type,
+                "props": props,
+            }),
+        )
+    }
+
+    fn property_entries(&mut self, entries: &[PropertyEntry]) -> Vec<Value> {
+        entries
+            .iter()
+            .map(|entry| {
+                self.open();
+                let (name, default) = match entry {
+                    PropertyEntry::Uninitialized { variable } => (self.var_like(variable), None),
+                    PropertyEntry::Initialized {
+                        variable,
+                        equals,
+                        value,
+                    } => {
+                        let name = self.var_like(variable);
+                        self.span(*equals);
+
+                        (name, Some(self.expression(value)))
+                    }
+                };
+
+                self.close("PropertyItem", json!({ "name": name, "default": default }))
+            })
+            .collect()
+    }
+
+    fn method_modifiers(&mut self, modifiers: &MethodModifierGroup) -> u64 {
+        modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                MethodModifier::Final(span) => (*span, FINAL),
+                MethodModifier::Static(span) => (*span, STATIC),
+                MethodModifier::Abstract(span) => (*span, ABSTRACT),
+                MethodModifier::Public(span) => (*span, PUBLIC),
+                MethodModifier::Protected(span) => (*span, PROTECTED),
+                MethodModifier::Private(span) => (*span, PRIVATE),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum()
+    }
+
+    fn class_modifiers(&mut self, modifiers: &ClassModifierGroup) -> u64 {
+        modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                ClassModifier::Final(span) => (*span, FINAL),
+                ClassModifier::Abstract(span) => (*span, ABSTRACT),
+                ClassModifier::Readonly(span) => (*span, READONLY),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum()
+    }
+
+    fn visibility(&mut self, visibility: &VisibilityModifier) -> u64 {
+        match visibility {
+            VisibilityModifier::Public(span) => self.flag(*span, PUBLIC),
+            VisibilityModifier::Protected(span) => self.flag(*span, PROTECTED),
+            VisibilityModifier::Private(span) => self.flag(*span, PRIVATE),
+        }
+    }
+
+    fn flag(&mut self, span: Span, flag: u64) -> u64 {
+        self.span(span);
+
+        flag
+    }
+
+    fn ampersand(&mut self, ampersand: Option<Span>) -> bool {
+        match ampersand {
+            Some(span) => {
+                self.span(span);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn abstract_method(&mut self, method: &AbstractMethod) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&method.attributes);
+        let flags = self.method_modifiers(&method.modifiers);
+        self.span(method.function);
+        let by_ref = self.ampersand(method.ampersand);
+        let name = self.identifier(&method.name);
+        let params = self.parameters(&method.parameters);
+        let return_type = self.return_type(&method.return_type);
+        self.span(method.semicolon);
+
+        self.method(attr_groups, flags, by_ref, name, params, return_type, None)
+    }
+
+    fn abstract_constructor(&mut self, constructor: &AbstractConstructor) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&constructor.attributes);
+        let flags = self.method_modifiers(&constructor.modifiers);
+        self.span(constructor.function);
+        let by_ref = self.ampersand(constructor.ampersand);
+        let name = self.identifier(&constructor.name);
+        let params = self.parameters(&constructor.parameters);
+        self.span(constructor.semicolon);
+
+        self.method(attr_groups, flags, by_ref, name, params, Value::Null, None)
+    }
+
+    fn concrete_method(&mut self, method: &ConcreteMethod) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&method.attributes);
+        let flags = self.method_modifiers(&method.modifiers);
+        self.span(method.function);
+        let by_ref = self.ampersand(method.ampersand);
+        let name = self.identifier(&method.name);
+        let params = self.parameters(&method.parameters);
+        let return_type = self.return_type(&method.return_type);
+        self.span(method.body.left_brace);
+        let stmts = self.statements(&method.body.statements);
+        self.span(method.body.right_brace);
+
+        self.method(
+            attr_groups,
+            flags,
+            by_ref,
+            name,
+            params,
+            return_type,
+            Some(stmts),
+        )
+    }
+
+    fn concrete_constructor(&mut self, constructor: &ConcreteConstructor) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&constructor.attributes);
+        let flags = self.method_modifiers(&constructor.modifiers);
+        self.span(constructor.function);
+        let by_ref = self.ampersand(constructor.ampersand);
+        let name = self.identifier(&constructor.name);
+        self.span(constructor.parameters.left_parenthesis);
+        let params = constructor
+            .parameters
+            .parameters
+            .inner
+            .iter()
+            .map(|parameter| self.constructor_parameter(parameter))
+            .collect();
+        self.span(constructor.parameters.right_parenthesis);
+        self.span(constructor.body.left_brace);
+        let stmts = self.statements(&constructor.body.statements);
+        self.span(constructor.body.right_brace);
+
+        self.method(
+            attr_groups,
+            flags,
+            by_ref,
+            name,
+            params,
+            Value::Null,
+            Some(stmts),
+        )
+    }
+
+    // close the node of a method, opened by the caller.
+    #[allow(clippy::too_many_arguments)]
+    fn method(
+        &mut self,
+        attr_groups: Vec<Value>,
+        flags: u64,
+        by_ref: bool,
+        name: Value,
+        params: Vec<Value>,
+        return_type: Value,
+        stmts: Option<Vec<Value>>,
+    ) -> Value {
+        self.close(
+            "Stmt_ClassMethod",
+            json!({
+                "attrGroups": attr_groups,
+                "flags": flags,
+                "byRef": by_ref,
+                "name": name,
+                "params": params,
+                "returnType": return_type,
+                "stmts": stmts,
+            }),
+        )
+    }
+
+    fn parameters(&mut self, parameters: &FunctionParameterList) -> Vec<Value> {
+        self.span(parameters.left_parenthesis);
+        let exported = parameters
+            .parameters
+            .inner
+            .iter()
+            .map(|parameter| self.parameter(parameter))
+            .collect();
+        self.span(parameters.right_parenthesis);
+
+        exported
+    }
+
+    fn parameter(&mut self, parameter: &FunctionParameter) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&parameter.attributes);
+        let r#type = parameter
+            .data_type
+            .as_ref()
+            .map(|r#type| self.r#type(r#type));
+        let by_ref = self.ampersand(parameter.ampersand);
+        let variadic = self.ampersand(parameter.ellipsis);
+        let var = self.simple_variable(&parameter.name);
+        let default = parameter
+            .default
+            .as_ref()
+            .map(|default| self.expression(default));
+
+        self.close(
+            "Param",
+            json!({
+                "attrGroups": attr_groups,
+                "flags": 0,
+                "type": r#type,
+                "byRef": by_ref,
+                "variadic": variadic,
+                "var": var,
+                "default": default,
+            }),
+        )
+    }
+
+    fn constructor_parameter(&mut self, parameter: &ConstructorParameter) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&parameter.attributes);
+        let flags = parameter
+            .modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                PromotedPropertyModifier::Public(span) => (*span, PUBLIC),
+                PromotedPropertyModifier::Protected(span) => (*span, PROTECTED),
+                PromotedPropertyModifier::Private(span) => (*span, PRIVATE),
+                PromotedPropertyModifier::Readonly(span) => (*span, READONLY),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum::<u64>();
+        let r#type = parameter
+            .data_type
+            .as_ref()
+            .map(|r#type| self.r#type(r#type));
+        let by_ref = self.ampersand(parameter.ampersand);
+        let variadic = self.ampersand(parameter.ellipsis);
+        let var = self.simple_variable(&parameter.name);
+        let default = parameter
+            .default
+            .as_ref()
+            .map(|default| self.expression(default));
+
+        self.close(
+            "Param",
+            json!({
+                "attrGroups": attr_groups,
+                "flags": flags,
+                "type": r#type,
+                "byRef": by_ref,
+                "variadic": variadic,
+                "var": var,
+                "default": default,
+            }),
+        )
+    }
+
+    fn return_type(&mut self, return_type: &Option<ReturnType>) -> Value {
+        match return_type {
+            Some(return_type) => {
+                self.span(return_type.colon);
+                self.r#type(&return_type.data_type)
+            }
+            None => Value::Null,
+        }
+    }
+
+    fn r#type(&mut self, r#type: &Type) -> Value {
+        let (span, name) = match r#type {
+            Type::Named(span, name) => {
+                return self.name(&SimpleIdentifier {
+                    span: *span,
+                    value: name.clone(),
+                })
+            }
+            Type::Nullable(span, inner) => {
+                self.open();
+                self.span(*span);
+                let inner = self.r#type(inner);
+
+                return self.close("NullableType", json!({ "type": inner }));
+            }
+            Type::Union(types) | Type::Intersection(types) => {
+                self.open();
+                let types = types
+                    .iter()
+                    .map(|r#type| self.r#type(r#type))
+                    .collect::<Vec<_>>();
+                let kind = match r#type {
+                    Type::Union(_) => "UnionType",
+                    _ => "IntersectionType",
+                };
+
+                return self.close(kind, json!({ "types": types }));
+            }
+            // `self` and `parent` are class names, while the other types are reserved
+            // identifiers.
+            Type::SelfReference(span) | Type::ParentReference(span) => {
+                self.open();
+                let index = self.span(*span);
+                let name = match r#type {
+                    Type::SelfReference(_) => "self",
+                    _ => "parent",
+                };
+                let name = self.raw(index, &ByteString::from(name));
+
+                return self.close("Name", json!({ "name": name }));
+            }
+            Type::Void(span) => (span, "void"),
+            Type::Null(span) => (span, "null"),
+            Type::True(span) => (span, "true"),
+            Type::False(span) => (span, "false"),
+            Type::Never(span) => (span, "never"),
+            Type::Float(span) => (span, "float"),
+            Type::Boolean(span) => (span, "bool"),
+            Type::Integer(span) => (span, "int"),
+            Type::String(span) => (span, "string"),
+            Type::Array(span) => (span, "array"),
+            Type::Object(span) => (span, "object"),
+            Type::Mixed(span) => (span, "mixed"),
+            Type::Callable(span) => (span, "callable"),
+            Type::Iterable(span) => (span, "iterable"),
+            Type::StaticReference(span) => (span, "static"),
+        };
+
+        self.open();
+        self.span(*span);
+
+        self.close("Identifier", json!({ "name": name }))
+    }
+
+    fn attribute_groups(&mut self, groups: &[AttributeGroup]) -> Vec<Value> {
+        groups
+            .iter()
+            .map(|group| {
+                self.open();
+                self.span(group.start);
+                let attrs = group
+                    .members
+                    .iter()
+                    .map(|attribute| self.attribute(attribute))
+                    .collect::<Vec<_>>();
+                self.span(group.end);
+
+                self.close("AttributeGroup", json!({ "attrs": attrs }))
+            })
+            .collect()
+    }
+
+    fn attribute(&mut self, attribute: &Attribute) -> Value {
+        // the `end` span of an attribute is the end of its group.
+        self.open();
+        let name = self.name(&attribute.name);
+        let args = match &attribute.arguments {
+            Some(arguments) => self.arguments(arguments),
+            None => Vec::new(),
+        };
+
+        self.close("Attribute", json!({ "name": name, "args": args }))
+    }
+
+    fn arguments(&mut self, arguments: &ArgumentList) -> Vec<Value> {
+        self.span(arguments.left_parenthesis);
+        let exported = arguments
+            .arguments
+            .iter()
+            .map(|argument| self.argument(argument))
+            .collect();
+        self.span(arguments.right_parenthesis);
+
+        exported
+    }
+
+    fn argument(&mut self, argument: &Argument) -> Value {
+        self.open();
+        let (name, ellipsis, value) = match argument {
+            Argument::Positional(argument) => (None, argument.ellipsis, &argument.value),
+            Argument::Named(argument) => {
+                let name = self.identifier(&argument.name);
+                self.span(argument.colon);
+
+                (Some(name), argument.ellipsis, &argument.value)
+            }
+        };
+        let unpack = self.ampersand(ellipsis);
+        let value = self.expression(value);
+
+        self.close(
+            "Arg",
+            json!({ "name": name, "value": value, "byRef": false, "unpack": unpack }),
+        )
+    }
+
+    // the arguments of a language construct such as `isset()`, which are plain expressions.
+    fn argument_values(&mut self, arguments: &ArgumentList) -> Vec<Value> {
+        self.span(arguments.left_parenthesis);
+        let exported = arguments
+            .arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::Positional(argument) => self.expression(&argument.value),
+                Argument::Named(argument) => self.expression(&argument.value),
+            })
+            .collect();
+        self.span(arguments.right_parenthesis);
+
+        exported
+    }
+
+    fn single_argument(&mut self, argument: &SingleArgument) -> Value {
+        self.span(argument.left_parenthesis);
+        let exported = match &argument.argument {
+            Argument::Positional(argument) => self.expression(&argument.value),
+            Argument::Named(argument) => self.expression(&argument.value),
+        };
+        self.span(argument.right_parenthesis);
+
+        exported
+    }
+
+    fn placeholder(&mut self, placeholder: &ArgumentPlaceholder) -> Vec<Value> {
+        self.span(placeholder.left_parenthesis);
+        self.open();
+        self.span(placeholder.ellipsis);
+        let exported = self.close("VariadicPlaceholder", json!({}));
+        self.span(placeholder.right_parenthesis);
+
+        vec![exported]
+    }
+
+    fn identifier(&mut self, identifier: &SimpleIdentifier) -> Value {
+        self.open();
+        self.span(identifier.span);
+
+        self.close("Identifier", json!({ "name": text(&identifier.value) }))
+    }
+
+    fn names(&mut self, identifiers: &[SimpleIdentifier]) -> Vec<Value> {
+        identifiers
+            .iter()
+            .map(|identifier| self.name(identifier))
+            .collect()
+    }
+
+    fn name(&mut self, identifier: &SimpleIdentifier) -> Value {
+        self.open();
+        self.span(identifier.span);
+
+        let name = text(&identifier.value);
+        if let Some(name) = name.strip_prefix('\\') {
+            return self.close("Name_FullyQualified", json!({ "name": name }));
+        }
+
+        match name.get(..10) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("namespace\\") => {
+                self.close("Name_Relative", json!({ "name": &name[10..] }))
+            }
+            _ => self.close("Name", json!({ "name": name })),
+        }
+    }
+
+    // a name written as a keyword, such as `true` or `static`, which the AST doesn't
+    // record the position of.
+    fn keyword(&mut self, kind: TokenKind, name: &str) -> Value {
+        self.open();
+        let index = self.find(|candidate| candidate == &kind);
+        let name = self.raw(index, &ByteString::from(name));
+
+        self.close("Name", json!({ "name": name }))
+    }
+
+    fn var_like(&mut self, variable: &SimpleVariable) -> Value {
+        self.open();
+        self.span(variable.span);
+        let name = text(&variable.name);
+
+        self.close(
+            "VarLikeIdentifier",
+            json!({ "name": name.trim_start_matches('$') }),
+        )
+    }
+
+    fn simple_variable(&mut self, variable: &SimpleVariable) -> Value {
+        self.open();
+        self.span(variable.span);
+        let name = text(&variable.name);
+
+        self.close(
+            "Expr_Variable",
+            json!({ "name": name.trim_start_matches('$') }),
+        )
+    }
+
+    fn variable(&mut self, variable: &Variable) -> Value {
+        match variable {
+            Variable::SimpleVariable(variable) => self.simple_variable(variable),
+            Variable::VariableVariable(variable) => {
+                self.open();
+                self.span(variable.span);
+                let name = self.variable(&variable.variable);
+
+                self.close("Expr_Variable", json!({ "name": name }))
+            }
+            Variable::BracedVariableVariable(variable) => {
+                self.open();
+                self.span(variable.start);
+                let name = self.expression(&variable.variable);
+                self.span(variable.end);
+
+                self.close("Expr_Variable", json!({ "name": name }))
+            }
+        }
+    }
+
+    // the name of a static property, such as `$bar` in `Foo::$bar`.
+    fn static_property(&mut self, variable: &Variable) -> Value {
+        match variable {
+            Variable::SimpleVariable(variable) => self.var_like(variable),
+            Variable::VariableVariable(variable) => {
+                self.span(variable.span);
+                self.variable(&variable.variable)
+            }
+            Variable::BracedVariableVariable(variable) => {
+                self.span(variable.start);
+                let name = self.expression(&variable.variable);
+                self.span(variable.end);
+
+                name
+            }
+        }
+    }
+
+    // the class of `new`, `instanceof`, static calls and fetches, and the name of function
+    // calls, where an identifier is a name rather than a constant.
+    fn class_name(&mut self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                self.name(identifier)
+            }
+            Expression::Static(_) => self.keyword(TokenKind::Static, "static"),
+            Expression::Self_(_) => self.keyword(TokenKind::Self_, "self"),
+            Expression::Parent(_) => self.keyword(TokenKind::Parent, "parent"),
+            Expression::AnonymousClass(class) => self.anonymous_class(class),
+            expression => self.expression(expression),
+        }
+    }
+
+    // the name of a method or property, such as `bar` in `$foo->bar()`.
+    fn member(&mut self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Identifier(identifier) => self.member_identifier(identifier),
+            expression => self.expression(expression),
+        }
+    }
+
+    fn member_identifier(&mut self, identifier: &Identifier) -> Value {
+        match identifier {
+            Identifier::SimpleIdentifier(identifier) => self.identifier(identifier),
+            Identifier::DynamicIdentifier(identifier) => {
+                self.span(identifier.start);
+                let name = self.expression(&identifier.expr);
+                self.span(identifier.end);
+
+                name
+            }
+        }
+    }
+
+    fn expressions(&mut self, expressions: &[Expression]) -> Vec<Value> {
+        expressions
+            .iter()
+            .map(|expression| self.expression(expression))
+            .collect()
+    }
+
+    // the target of an assignment, where arrays are lists.
+    fn assignable(&mut self, expression: &Expression) -> Value {
+        match expression {
+            Expression::ShortArray(array) => {
+                self.open();
+                self.span(array.start);
+                let items = self.array_items(&array.items.inner, true);
+                self.span(array.end);
+
+                self.close_with("Expr_List", json!({ "items": items }), json!({ "kind": 2 }))
+            }
+            Expression::List(list) => {
+                self.open();
+                self.span(list.list);
+                self.span(list.start);
+                let items = list
+                    .items
+                    .iter()
+                    .map(|item| {
+                        let (key, double_arrow, value) = match item {
+                            ListEntry::Skipped => return Value::Null,
+                            ListEntry::Value { value } => (None, None, value),
+                            ListEntry::KeyValue {
+                                key,
+                                double_arrow,
+                                value,
+                            } => (Some(key), Some(*double_arrow), value),
+                        };
+
+                        self.open();
+                        let key = key.map(|key| self.expression(key));
+                        if let Some(span) = double_arrow {
+                            self.span(span);
+                        }
+                        let value = self.assignable(value);
+
+                        self.close(
+                            "ArrayItem",
+                            json!({ "key": key, "value": value, "byRef": false, "unpack": false }),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                self.span(list.end);
+
+                self.close_with("Expr_List", json!({ "items": items }), json!({ "kind": 1 }))
+            }
+            expression => self.expression(expression),
+        }
+    }
+
+    fn array_items(&mut self, items: &[ArrayItem], list: bool) -> Vec<Value> {
+        items
+            .iter()
+            .map(|item| {
+                if let ArrayItem::Skipped = item {
+                    return Value::Null;
+                }
+
+                self.open();
+                let (key, by_ref, unpack, value) = match item {
+                    ArrayItem::Skipped => unreachable!(),
+                    ArrayItem::Value { value } => (None, false, false, value),
+                    ArrayItem::ReferencedValue { ampersand, value } => {
+                        self.span(*ampersand);
+
+                        (None, true, false, value)
+                    }
+                    ArrayItem::SpreadValue { ellipsis, value } => {
+                        self.span(*ellipsis);
+
+                        (None, false, true, value)
+                    }
+                    ArrayItem::KeyValue {
+                        key,
+                        double_arrow,
+                        value,
+                    } => {
+                        let key = self.expression(key);
+                        self.span(*double_arrow);
+
+                        (Some(key), false, false, value)
+                    }
+                    ArrayItem::ReferencedKeyValue {
+                        key,
+                        double_arrow,
+                        ampersand,
+                        value,
+                    } => {
+                        let key = self.expression(key);
+                        self.span(*double_arrow);
+                        self.span(*ampersand);
+
+                        (Some(key), true, false, value)
+                    }
+                };
+                let value = if list {
+                    self.assignable(value)
+                } else {
+                    self.expression(value)
+                };
+
+                self.close(
+                    "ArrayItem",
+                    json!({ "key": key, "value": value, "byRef": by_ref, "unpack": unpack }),
+                )
+            })
+            .collect()
+    }
+
+    fn literal(&mut self, literal: &Literal) -> Value {
+        match literal {
+            Literal::String(literal) => {
+                self.open();
+                let index = self.span(literal.span);
+                let raw = self.raw(index, &literal.value);
+                let kind = match literal.kind {
+                    LiteralStringKind::SingleQuoted => 1,
+                    LiteralStringKind::DoubleQuoted => 2,
+                };
+
+                self.close_with(
+                    "Scalar_String",
+                    json!({ "value": text(&literal.value) }),
+                    json!({ "kind": kind, "rawValue": raw }),
+                )
+            }
+            Literal::Integer(literal) => self.integer(literal),
+            Literal::Float(literal) => self.float(literal),
+        }
+    }
+
+    fn integer(&mut self, literal: &LiteralInteger) -> Value {
+        self.open();
+        let index = self.span(literal.span);
+        let raw = self.raw(index, &literal.value);
+
+        let digits = raw.replace('_', "").to_ascii_lowercase();
+        let (kind, digits) = if let Some(digits) = digits.strip_prefix("0x") {
+            (16, digits)
+        } else if let Some(digits) = digits.strip_prefix("0b") {
+            (2, digits)
+        } else if let Some(digits) = digits.strip_prefix("0o") {
+            (8, digits)
+        } else if digits.len() > 1 && digits.starts_with('0') {
+            (8, &digits[1..])
+        } else {
+            (10, digits.as_str())
+        };
+
+        match i64::from_str_radix(digits, kind) {
+            Ok(value) => self.close_with(
+                "Scalar_Int",
+                json!({ "value": value }),
+                json!({ "rawValue": raw, "kind": kind }),
+            ),
+            // integers that overflow are floats.
+            Err(_) => {
+                let value = digits
+                    .chars()
+                    .filter_map(|digit| digit.to_digit(kind))
+                    .fold(0.0, |value, digit| value * kind as f64 + digit as f64);
+
+                self.close_with(
+                    "Scalar_Float",
+                    json!({ "value": value }),
+                    json!({ "rawValue": raw }),
+                )
+            }
+        }
+    }
+
+    fn float(&mut self, literal: &LiteralFloat) -> Value {
+        self.open();
+        let index = self.span(literal.span);
+        let raw = self.raw(index, &literal.value);
+        let value = raw.replace('_', "").parse::<f64>().unwrap_or_default();
+
+        self.close_with(
+            "Scalar_Float",
+            json!({ "value": value }),
+            json!({ "rawValue": raw }),
+        )
+    }
+
+    fn string_parts(&mut self, parts: &[StringPart]) -> Vec<Value> {
+        parts
+            .iter()
+            .map(|part| match part {
+                StringPart::Literal(part) => {
+                    self.open();
+
+                    self.close(
+                        "InterpolatedStringPart",
+                        json!({ "value": text(&part.value) }),
+                    )
+                }
+                StringPart::Expression(part) => self.expression(&part.expression),
+            })
+            .collect()
+    }
+
+    fn binary(
+        &mut self,
+        kind: &str,
+        left: &Expression,
+        operator: Span,
+        right: &Expression,
+    ) -> Value {
+        self.open();
+        let left = self.expression(left);
+        self.span(operator);
+        let right = self.expression(right);
+
+        self.close(kind, json!({ "left": left, "right": right }))
+    }
+
+    fn assign(
+        &mut self,
+        kind: &str,
+        left: &Expression,
+        operator: Span,
+        right: &Expression,
+    ) -> Value {
+        self.open();
+        let var = self.assignable(left);
+        self.span(operator);
+        let expr = self.expression(right);
+
+        self.close(kind, json!({ "var": var, "expr": expr }))
+    }
+
+    fn prefix(&mut self, kind: &str, field: &str, operator: Span, right: &Expression) -> Value {
+        self.open();
+        self.span(operator);
+        let right = self.expression(right);
+
+        self.close(kind, json!({ field: right }))
+    }
+
+    fn postfix(&mut self, kind: &str, left: &Expression, operator: Span) -> Value {
+        self.open();
+        let left = self.expression(left);
+        self.span(operator);
+
+        self.close(kind, json!({ "var": left }))
+    }
+
+    fn expression(&mut self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Eval(expression) => {
+                self.open();
+                self.span(expression.eval);
+                let expr = self.single_argument(&expression.argument);
+
+                self.close("Expr_Eval", json!({ "expr": expr }))
+            }
+            Expression::Empty(expression) => {
+                self.open();
+                self.span(expression.empty);
+                let expr = self.single_argument(&expression.argument);
+
+                self.close("Expr_Empty", json!({ "expr": expr }))
+            }
+            Expression::Die(expression) => {
+                self.open();
+                self.span(expression.die);
+                let expr = expression
+                    .argument
+                    .as_ref()
+                    .map(|argument| self.single_argument(argument));
+
+                self.close_with("Expr_Exit", json!({ "expr": expr }), json!({ "kind": 2 }))
+            }
+            Expression::Exit(expression) => {
+                self.open();
+                self.span(expression.exit);
+                let expr = expression
+                    .argument
+                    .as_ref()
+                    .map(|argument| self.single_argument(argument));
+
+                self.close_with("Expr_Exit", json!({ "expr": expr }), json!({ "kind": 1 }))
+            }
+            Expression::Isset(expression) => {
+                self.open();
+                self.span(expression.isset);
+                let vars = self.argument_values(&expression.arguments);
+
+                self.close("Expr_Isset", json!({ "vars": vars }))
+            }
+            // only valid as a statement, see `statement()`.
+            Expression::Unset(expression) => {
+                self.open();
+                self.span(expression.unset);
+                let vars = self.argument_values(&expression.arguments);
+
+                self.close("Stmt_Unset", json!({ "vars": vars }))
+            }
+            Expression::Print(expression) => {
+                self.open();
+                self.span(expression.print);
+                let expr = match (&expression.value, &expression.argument) {
+                    (Some(value), _) => self.expression(value),
+                    (None, Some(argument)) => self.single_argument(argument),
+                    (None, None) => Value::Null,
+                };
+
+                self.close("Expr_Print", json!({ "expr": expr }))
+            }
+            Expression::Literal(literal) => self.literal(literal),
+            Expression::ArithmeticOperation(operation) => match operation {
+                ArithmeticOperationExpression::Addition { left, plus, right } => {
+                    self.binary("Expr_BinaryOp_Plus", left, *plus, right)
+                }
+                ArithmeticOperationExpression::Subtraction { left, minus, right } => {
+                    self.binary("Expr_BinaryOp_Minus", left, *minus, right)
+                }
+                ArithmeticOperationExpression::Multiplication {
+                    left,
+                    asterisk,
+                    right,
+                } => self.binary("Expr_BinaryOp_Mul", left, *asterisk, right),
+                ArithmeticOperationExpression::Division { left, slash, right } => {
+                    self.binary("Expr_BinaryOp_Div", left, *slash, right)
+                }
+                ArithmeticOperationExpression::Modulo {
+                    left,
+                    percent,
+                    right,
+                } => self.binary("Expr_BinaryOp_Mod", left, *percent, right),
+                ArithmeticOperationExpression::Exponentiation { left, pow, right } => {
+                    self.binary("Expr_BinaryOp_Pow", left, *pow, right)
+                }
+                ArithmeticOperationExpression::Negative { minus, right } => {
+                    self.prefix("Expr_UnaryMinus", "expr", *minus, right)
+                }
+                ArithmeticOperationExpression::Positive { plus, right } => {
+                    self.prefix("Expr_UnaryPlus", "expr", *plus, right)
+                }
+                ArithmeticOperationExpression::PreIncrement { increment, right } => {
+                    self.prefix("Expr_PreInc", "var", *increment, right)
+                }
+                ArithmeticOperationExpression::PostIncrement { left, increment } => {
+                    self.postfix("Expr_PostInc", left, *increment)
+                }
+                ArithmeticOperationExpression::PreDecrement { decrement, right } => {
+                    self.prefix("Expr_PreDec", "var", *decrement, right)
+                }
+                ArithmeticOperationExpression::PostDecrement { left, decrement } => {
+                    self.postfix("Expr_PostDec", left, *decrement)
+                }
+            },
+            Expression::AssignmentOperation(operation) => match operation {
+                AssignmentOperationExpression::Assign {
+                    left,
+                    equals,
+                    right,
+                } => match right.as_ref() {
+                    // `$a = &$b` is an assignment by reference, rather than the assignment
+                    // of a reference.
+                    Expression::Reference(reference) => {
+                        self.open();
+                        let var = self.assignable(left);
+                        self.span(*equals);
+                        self.span(reference.ampersand);
+                        let expr = self.expression(&reference.right);
+
+                        self.close("Expr_AssignRef", json!({ "var": var, "expr": expr }))
+                    }
+                    right => self.assign("Expr_Assign", left, *equals, right),
+                },
+                AssignmentOperationExpression::Addition {
+                    left,
+                    plus_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_Plus", left, *plus_equals, right),
+                AssignmentOperationExpression::Subtraction {
+                    left,
+                    minus_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_Minus", left, *minus_equals, right),
+                AssignmentOperationExpression::Multiplication {
+                    left,
+                    asterisk_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_Mul", left, *asterisk_equals, right),
+                AssignmentOperationExpression::Division {
+                    left,
+                    slash_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_Div", left, *slash_equals, right),
+                AssignmentOperationExpression::Modulo {
+                    left,
+                    percent_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_Mod", left, *percent_equals, right),
+                AssignmentOperationExpression::Exponentiation {
+                    left,
+                    pow_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_Pow", left, *pow_equals, right),
+                AssignmentOperationExpression::Concat {
+                    left,
+                    dot_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_Concat", left, *dot_equals, right),
+                AssignmentOperationExpression::BitwiseAnd {
+                    left,
+                    ampersand_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_BitwiseAnd", left, *ampersand_equals, right),
+                AssignmentOperationExpression::BitwiseOr {
+                    left,
+                    pipe_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_BitwiseOr", left, *pipe_equals, right),
+                AssignmentOperationExpression::BitwiseXor {
+                    left,
+                    caret_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_BitwiseXor", left, *caret_equals, right),
+                AssignmentOperationExpression::LeftShift {
+                    left,
+                    left_shift_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_ShiftLeft", left, *left_shift_equals, right),
+                AssignmentOperationExpression::RightShift {
+                    left,
+                    right_shift_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_ShiftRight", left, *right_shift_equals, right),
+                AssignmentOperationExpression::Coalesce {
+                    left,
+                    coalesce_equals,
+                    right,
+                } => self.assign("Expr_AssignOp_Coalesce", left, *coalesce_equals, right),
+            },
+            Expression::BitwiseOperation(operation) => match operation {
+                BitwiseOperationExpression::And { left, and, right } => {
+                    self.binary("Expr_BinaryOp_BitwiseAnd", left, *and, right)
+                }
+                BitwiseOperationExpression::Or { left, or, right } => {
+                    self.binary("Expr_BinaryOp_BitwiseOr", left, *or, right)
+                }
+                BitwiseOperationExpression::Xor { left, xor, right } => {
+                    self.binary("Expr_BinaryOp_BitwiseXor", left, *xor, right)
+                }
+                BitwiseOperationExpression::LeftShift {
+                    left,
+                    left_shift,
+                    right,
+                } => self.binary("Expr_BinaryOp_ShiftLeft", left, *left_shift, right),
+                BitwiseOperationExpression::RightShift {
+                    left,
+                    right_shift,
+                    right,
+                } => self.binary("Expr_BinaryOp_ShiftRight", left, *right_shift, right),
+                BitwiseOperationExpression::Not { not, right } => {
+                    self.prefix("Expr_BitwiseNot", "expr", *not, right)
+                }
+            },
+            Expression::ComparisonOperation(operation) => match operation {
+                ComparisonOperationExpression::Equal {
+                    left,
+                    double_equals,
+                    right,
+                } => self.binary("Expr_BinaryOp_Equal", left, *double_equals, right),
+                ComparisonOperationExpression::Identical {
+                    left,
+                    triple_equals,
+                    right,
+                } => self.binary("Expr_BinaryOp_Identical", left, *triple_equals, right),
+                ComparisonOperationExpression::NotEqual {
+                    left,
+                    bang_equals,
+                    right,
+                } => self.binary("Expr_BinaryOp_NotEqual", left, *bang_equals, right),
+                ComparisonOperationExpression::AngledNotEqual {
+                    left,
+                    angled_left_right,
+                    right,
+                } => self.binary("Expr_BinaryOp_NotEqual", left, *angled_left_right, right),
+                ComparisonOperationExpression::NotIdentical {
+                    left,
+                    bang_double_equals,
+                    right,
+                } => self.binary(
+                    "Expr_BinaryOp_NotIdentical",
+                    left,
+                    *bang_double_equals,
+                    right,
+                ),
+                ComparisonOperationExpression::LessThan {
+                    left,
+                    less_than,
+                    right,
+                } => self.binary("Expr_BinaryOp_Smaller", left, *less_than, right),
+                ComparisonOperationExpression::GreaterThan {
+                    left,
+                    greater_than,
+                    right,
+                } => self.binary("Expr_BinaryOp_Greater", left, *greater_than, right),
+                ComparisonOperationExpression::LessThanOrEqual {
+                    left,
+                    less_than_equals,
+                    right,
+                } => self.binary(
+                    "Expr_BinaryOp_SmallerOrEqual",
+                    left,
+                    *less_than_equals,
+                    right,
+                ),
+                ComparisonOperationExpression::GreaterThanOrEqual {
+                    left,
+                    greater_than_equals,
+                    right,
+                } => self.binary(
+                    "Expr_BinaryOp_GreaterOrEqual",
+                    left,
+                    *greater_than_equals,
+                    right,
+                ),
+                ComparisonOperationExpression::Spaceship {
+                    left,
+                    spaceship,
+                    right,
+                } => self.binary("Expr_BinaryOp_Spaceship", left, *spaceship, right),
+            },
+            Expression::LogicalOperation(operation) => match operation {
+                LogicalOperationExpression::And {
+                    left,
+                    double_ampersand,
+                    right,
+                } => self.binary("Expr_BinaryOp_BooleanAnd", left, *double_ampersand, right),
+                LogicalOperationExpression::Or {
+                    left,
+                    double_pipe,
+                    right,
+                } => self.binary("Expr_BinaryOp_BooleanOr", left, *double_pipe, right),
+                LogicalOperationExpression::Not { bang, right } => {
+                    self.prefix("Expr_BooleanNot", "expr", *bang, right)
+                }
+                LogicalOperationExpression::LogicalAnd { left, and, right } => {
+                    self.binary("Expr_BinaryOp_LogicalAnd", left, *and, right)
+                }
+                LogicalOperationExpression::LogicalOr { left, or, right } => {
+                    self.binary("Expr_BinaryOp_LogicalOr", left, *or, right)
+                }
+                LogicalOperationExpression::LogicalXor { left, xor, right } => {
+                    self.binary("Expr_BinaryOp_LogicalXor", left, *xor, right)
+                }
+            },
+            Expression::Concat(expression) => self.binary(
+                "Expr_BinaryOp_Concat",
+                &expression.left,
+                expression.dot,
+                &expression.right,
+            ),
+            Expression::Instanceof(expression) => {
+                self.open();
+                let expr = self.expression(&expression.left);
+                self.span(expression.instanceof);
+                let class = self.class_name(&expression.right);
+
+                self.close("Expr_Instanceof", json!({ "expr": expr, "class": class }))
+            }
+            // references are only valid in assignments, arrays and parameters, which
+            // handle them themselves.
+            Expression::Reference(expression) => {
+                self.span(expression.ampersand);
+                self.expression(&expression.right)
+            }
+            // parentheses are not part of the AST, but are part of the enclosing node.
+            Expression::Parenthesized(expression) => {
+                self.span(expression.start);
+                let expr = self.expression(&expression.expr);
+                self.span(expression.end);
+
+                expr
+            }
+            Expression::ErrorSuppress(expression) => self.prefix(
+                "Expr_ErrorSuppress",
+                "expr",
+                expression.at,
+                &expression.expr,
+            ),
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                self.open();
+                let name = self.name(identifier);
+
+                self.close("Expr_ConstFetch", json!({ "name": name }))
+            }
+            Expression::Identifier(identifier) => self.member_identifier(identifier),
+            Expression::Variable(variable) => self.variable(variable),
+            Expression::Include(expression) => {
+                self.include(expression.include, &expression.path, 1)
+            }
+            Expression::IncludeOnce(expression) => {
+                self.include(expression.include_once, &expression.path, 2)
+            }
+            Expression::Require(expression) => {
+                self.include(expression.require, &expression.path, 3)
+            }
+            Expression::RequireOnce(expression) => {
+                self.include(expression.require_once, &expression.path, 4)
+            }
+            Expression::FunctionCall(expression) => {
+                self.open();
+                let name = self.class_name(&expression.target);
+                let args = self.arguments(&expression.arguments);
+
+                self.close("Expr_FuncCall", json!({ "name": name, "args": args }))
+            }
+            Expression::FunctionClosureCreation(expression) => {
+                self.open();
+                let name = self.class_name(&expression.target);
+                let args = self.placeholder(&expression.placeholder);
+
+                self.close("Expr_FuncCall", json!({ "name": name, "args": args }))
+            }
+            Expression::MethodCall(expression) => {
+                self.open();
+                let var = self.expression(&expression.target);
+                self.span(expression.arrow);
+                let name = self.member(&expression.method);
+                let args = self.arguments(&expression.arguments);
+
+                self.close(
+                    "Expr_MethodCall",
+                    json!({ "var": var, "name": name, "args": args }),
+                )
+            }
+            Expression::MethodClosureCreation(expression) => {
+                self.open();
+                let var = self.expression(&expression.target);
+                self.span(expression.arrow);
+                let name = self.member(&expression.method);
+                let args = self.placeholder(&expression.placeholder);
+
+                self.close(
+                    "Expr_MethodCall",
+                    json!({ "var": var, "name": name, "args": args }),
+                )
+            }
+            Expression::NullsafeMethodCall(expression) => {
+                self.open();
+                let var = self.expression(&expression.target);
+                self.span(expression.question_arrow);
+                let name = self.member(&expression.method);
+                let args = self.arguments(&expression.arguments);
+
+                self.close(
+                    "Expr_NullsafeMethodCall",
+                    json!({ "var": var, "name": name, "args": args }),
+                )
+            }
+            Expression::StaticMethodCall(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let name = self.member_identifier(&expression.method);
+                let args = self.arguments(&expression.arguments);
+
+                self.close(
+                    "Expr_StaticCall",
+                    json!({ "class": class, "name": name, "args": args }),
+                )
+            }
+            Expression::StaticVariableMethodCall(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let name = self.variable(&expression.method);
+                let args = self.arguments(&expression.arguments);
+
+                self.close(
+                    "Expr_StaticCall",
+                    json!({ "class": class, "name": name, "args": args }),
+                )
+            }
+            Expression::StaticMethodClosureCreation(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let name = self.member_identifier(&expression.method);
+                let args = self.placeholder(&expression.placeholder);
+
+                self.close(
+                    "Expr_StaticCall",
+                    json!({ "class": class, "name": name, "args": args }),
+                )
+            }
+            Expression::StaticVariableMethodClosureCreation(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let name = self.variable(&expression.method);
+                let args = self.placeholder(&expression.placeholder);
+
+                self.close(
+                    "Expr_StaticCall",
+                    json!({ "class": class, "name": name, "args": args }),
+                )
+            }
+            Expression::PropertyFetch(expression) => {
+                self.open();
+                let var = self.expression(&expression.target);
+                self.span(expression.arrow);
+                let name = self.member(&expression.property);
+
+                self.close("Expr_PropertyFetch", json!({ "var": var, "name": name }))
+            }
+            Expression::NullsafePropertyFetch(expression) => {
+                self.open();
+                let var = self.expression(&expression.target);
+                self.span(expression.question_arrow);
+                let name = self.member(&expression.property);
+
+                self.close(
+                    "Expr_NullsafePropertyFetch",
+                    json!({ "var": var, "name": name }),
+                )
+            }
+            Expression::StaticPropertyFetch(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let name = self.static_property(&expression.property);
+
+                self.close(
+                    "Expr_StaticPropertyFetch",
+                    json!({ "class": class, "name": name }),
+                )
+            }
+            Expression::ConstantFetch(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let name = self.member_identifier(&expression.constant);
+
+                self.close(
+                    "Expr_ClassConstFetch",
+                    json!({ "class": class, "name": name }),
+                )
+            }
+            Expression::Static(_) | Expression::Self_(_) | Expression::Parent(_) => {
+                self.class_name(expression)
+            }
+            Expression::ShortArray(expression) => {
+                self.open();
+                self.span(expression.start);
+                let items = self.array_items(&expression.items.inner, false);
+                self.span(expression.end);
+
+                self.close_with(
+                    "Expr_Array",
+                    json!({ "items": items }),
+                    json!({ "kind": 2 }),
+                )
+            }
+            Expression::Array(expression) => {
+                self.open();
+                self.span(expression.array);
+                self.span(expression.start);
+                let items = self.array_items(&expression.items.inner, false);
+                self.span(expression.end);
+
+                self.close_with(
+                    "Expr_Array",
+                    json!({ "items": items }),
+                    json!({ "kind": 1 }),
+                )
+            }
+            Expression::List(_) => self.assignable(expression),
+            Expression::Closure(expression) => self.closure(expression),
+            Expression::ArrowFunction(expression) => self.arrow_function(expression),
+            Expression::New(expression) => {
+                self.open();
+                self.span(expression.new);
+                // the arguments of an anonymous class come before its body.
+                let (class, args) = match expression.target.as_ref() {
+                    Expression::AnonymousClass(class) => {
+                        let args = expression
+                            .arguments
+                            .as_ref()
+                            .map(|arguments| self.arguments(arguments));
+
+                        (self.anonymous_class(class), args)
+                    }
+                    target => {
+                        let class = self.class_name(target);
+
+                        (
+                            class,
+                            expression
+                                .arguments
+                                .as_ref()
+                                .map(|arguments| self.arguments(arguments)),
+                        )
+                    }
+                };
+
+                self.close(
+                    "Expr_New",
+                    json!({ "class": class, "args": args.unwrap_or_default() }),
+                )
+            }
+            Expression::InterpolatedString(expression) => {
+                // the opening quote is part of the first string part.
+                self.open();
+                self.find(|kind| kind == &TokenKind::StringPart);
+                let parts = self.string_parts(&expression.parts);
+                self.find(|kind| kind == &TokenKind::DoubleQuote);
+
+                self.close_with(
+                    "Scalar_InterpolatedString",
+                    json!({ "parts": parts }),
+                    json!({ "kind": 2 }),
+                )
+            }
+            Expression::Heredoc(expression) => {
+                self.open();
+                self.find(|kind| matches!(kind, TokenKind::StartDocString(_)));
+                let label = doc_label(&expression.label);
+
+                // heredocs without interpolation are plain strings.
+                if expression
+                    .parts
+                    .iter()
+                    .all(|part| matches!(part, StringPart::Literal(_)))
+                {
+                    let value = expression
+                        .parts
+                        .iter()
+                        .map(|part| match part {
+                            StringPart::Literal(part) => text(&part.value),
+                            StringPart::Expression(_) => String::new(),
+                        })
+                        .collect::<String>();
+                    self.find(|kind| matches!(kind, TokenKind::EndDocString(..)));
+
+                    return self.close_with(
+                        "Scalar_String",
+                        json!({ "value": value }),
+                        json!({ "kind": 3, "docLabel": label }),
+                    );
+                }
+
+                let parts = self.string_parts(&expression.parts);
+                self.find(|kind| matches!(kind, TokenKind::EndDocString(..)));
+
+                self.close_with(
+                    "Scalar_InterpolatedString",
+                    json!({ "parts": parts }),
+                    json!({ "kind": 3, "docLabel": label }),
+                )
+            }
+            Expression::Nowdoc(expression) => {
+                self.open();
+                self.find(|kind| matches!(kind, TokenKind::StartDocString(_)));
+                self.find(|kind| matches!(kind, TokenKind::EndDocString(..)));
+
+                self.close_with(
+                    "Scalar_String",
+                    json!({ "value": text(&expression.value) }),
+                    json!({ "kind": 4, "docLabel": doc_label(&expression.label) }),
+                )
+            }
+            Expression::ShellExec(expression) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::Backtick);
+                let parts = self.string_parts(&expression.parts);
+                self.find(|kind| kind == &TokenKind::Backtick);
+
+                self.close("Expr_ShellExec", json!({ "parts": parts }))
+            }
+            Expression::AnonymousClass(class) => self.anonymous_class(class),
+            Expression::Bool(expression) => {
+                self.open();
+                let name = match expression.value {
+                    true => self.keyword(TokenKind::True, "true"),
+                    false => self.keyword(TokenKind::False, "false"),
+                };
+
+                self.close("Expr_ConstFetch", json!({ "name": name }))
+            }
+            Expression::Null(_) => {
+                self.open();
+                let name = self.keyword(TokenKind::Null, "null");
+
+                self.close("Expr_ConstFetch", json!({ "name": name }))
+            }
+            Expression::ArrayIndex(expression) => {
+                self.open();
+                let var = self.expression(&expression.array);
+                self.span(expression.left_bracket);
+                let dim = expression
+                    .index
+                    .as_ref()
+                    .map(|index| self.expression(index));
+                self.span(expression.right_bracket);
+
+                self.close("Expr_ArrayDimFetch", json!({ "var": var, "dim": dim }))
+            }
+            Expression::MagicConstant(constant) => {
+                let (span, kind) = match constant {
+                    MagicConstantExpression::Directory(span) => (span, "Scalar_MagicConst_Dir"),
+                    MagicConstantExpression::File(span) => (span, "Scalar_MagicConst_File"),
+                    MagicConstantExpression::Line(span) => (span, "Scalar_MagicConst_Line"),
+                    MagicConstantExpression::Class(span) => (span, "Scalar_MagicConst_Class"),
+                    MagicConstantExpression::Function(span) => (span, "Scalar_MagicConst_Function"),
+                    MagicConstantExpression::Method(span) => (span, "Scalar_MagicConst_Method"),
+                    MagicConstantExpression::Namespace(span) => {
+                        (span, "Scalar_MagicConst_Namespace")
+                    }
+                    MagicConstantExpression::Trait(span) => (span, "Scalar_MagicConst_Trait"),
+                    // `__COMPILER_HALT_OFFSET__` is a regular constant.
+                    MagicConstantExpression::CompilerHaltOffset(span) => {
+                        self.open();
+                        self.open();
+                        let index = self.span(*span);
+                        let name = self.raw(index, &ByteString::from("__COMPILER_HALT_OFFSET__"));
+                        let name = self.close("Name", json!({ "name": name }));
+
+                        return self.close("Expr_ConstFetch", json!({ "name": name }));
+                    }
+                };
+
+                self.open();
+                self.span(*span);
+
+                self.close(kind, json!({}))
+            }
+            Expression::ShortTernary(expression) => {
+                self.open();
+                let cond = self.expression(&expression.condition);
+                self.span(expression.question_colon);
+                let r#else = self.expression(&expression.r#else);
+
+                self.close(
+                    "Expr_Ternary",
+                    json!({ "cond": cond, "if": null, "else": r#else }),
+                )
+            }
+            Expression::Ternary(expression) => {
+                self.open();
+                let cond = self.expression(&expression.condition);
+                self.span(expression.question);
+                let then = self.expression(&expression.then);
+                self.span(expression.colon);
+                let r#else = self.expression(&expression.r#else);
+
+                self.close(
+                    "Expr_Ternary",
+                    json!({ "cond": cond, "if": then, "else": r#else }),
+                )
+            }
+            Expression::Coalesce(expression) => self.binary(
+                "Expr_BinaryOp_Coalesce",
+                &expression.lhs,
+                expression.double_question,
+                &expression.rhs,
+            ),
+            Expression::Clone(expression) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::Clone);
+                let expr = self.expression(&expression.target);
+
+                self.close("Expr_Clone", json!({ "expr": expr }))
+            }
+            Expression::Match(expression) => self.r#match(expression),
+            Expression::Throw(expression) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::Throw);
+                let expr = self.expression(&expression.value);
+
+                self.close("Expr_Throw", json!({ "expr": expr }))
+            }
+            Expression::Yield(expression) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::Yield);
+                let key = expression.key.as_ref().map(|key| self.expression(key));
+                let value = expression
+                    .value
+                    .as_ref()
+                    .map(|value| self.expression(value));
+
+                self.close("Expr_Yield", json!({ "key": key, "value": value }))
+            }
+            Expression::YieldFrom(expression) => {
+                self.open();
+                self.find(|kind| kind == &TokenKind::Yield);
+                self.next_if(|kind| kind == &TokenKind::From);
+                let expr = self.expression(&expression.value);
+
+                self.close("Expr_YieldFrom", json!({ "expr": expr }))
+            }
+            Expression::Cast(expression) => {
+                self.open();
+                let index = self.span(expression.cast);
+                let expr = self.expression(&expression.value);
+                let kind = match expression.kind {
+                    CastKind::Int => "Expr_Cast_Int",
+                    CastKind::Bool => "Expr_Cast_Bool",
+                    CastKind::Float => "Expr_Cast_Double",
+                    CastKind::String => "Expr_Cast_String",
+                    CastKind::Array => "Expr_Cast_Array",
+                    CastKind::Object => "Expr_Cast_Object",
+                    CastKind::Unset => "Expr_Cast_Unset",
+                };
+
+                // `(double)`, `(float)` and `(real)` are told apart by a `kind` attribute.
+                match index.map(|index| &self.tokens[index].kind) {
+                    Some(TokenKind::DoubleCast) => {
+                        self.close_with(kind, json!({ "expr": expr }), json!({ "kind": 1 }))
+                    }
+                    Some(TokenKind::FloatCast) => {
+                        self.close_with(kind, json!({ "expr": expr }), json!({ "kind": 2 }))
+                    }
+                    Some(TokenKind::RealCast) => {
+                        self.close_with(kind, json!({ "expr": expr }), json!({ "kind": 3 }))
+                    }
+                    _ => self.close(kind, json!({ "expr": expr })),
+                }
+            }
+            Expression::Noop => Value::Null,
+        }
+    }
+
+    fn include(&mut self, keyword: Span, path: &Expression, r#type: u8) -> Value {
+        self.open();
+        self.span(keyword);
+        let expr = self.expression(path);
+
+        self.close("Expr_Include", json!({ "expr": expr, "type": r#type }))
+    }
+
+    fn closure(&mut self, expression: &ClosureExpression) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&expression.attributes);
+        let r#static = self.ampersand(expression.r#static);
+        self.span(expression.function);
+        let by_ref = self.ampersand(expression.ampersand);
+        let params = self.parameters(&expression.parameters);
+        let uses = match &expression.uses {
+            Some(uses) => {
+                self.span(uses.r#use);
+                self.span(uses.left_parenthesis);
+                let variables = uses
+                    .variables
+                    .inner
+                    .iter()
+                    .map(|variable| {
+                        self.open();
+                        let by_ref = self.ampersand(variable.ampersand);
+                        let var = self.simple_variable(&variable.variable);
+
+                        self.close("ClosureUse", json!({ "var": var, "byRef": by_ref }))
+                    })
+                    .collect();
+                self.span(uses.right_parenthesis);
+
+                variables
+            }
+            None => Vec::new(),
+        };
+        let return_type = self.return_type(&expression.return_type);
+        self.span(expression.body.left_brace);
+        let stmts = self.statements(&expression.body.statements);
+        self.span(expression.body.right_brace);
+
+        self.close(
+            "Expr_Closure",
+            json!({
+                "attrGroups": attr_groups,
+                "static": r#static,
+                "byRef": by_ref,
+                "params": params,
+                "uses": uses,
+                "returnType": return_type,
+                "stmts": stmts,
+            }),
+        )
+    }
+
+    fn arrow_function(&mut self, expression: &ArrowFunctionExpression) -> Value {
+        self.open();
+        let attr_groups = self.attribute_groups(&expression.attributes);
+        let r#static = self.ampersand(expression.r#static);
+        self.span(expression.r#fn);
+        let by_ref = self.ampersand(expression.ampersand);
+        let params = self.parameters(&expression.parameters);
+        let return_type = self.return_type(&expression.return_type);
+        self.span(expression.double_arrow);
+        let expr = self.expression(&expression.body);
+
+        self.close(
+            "Expr_ArrowFunction",
+            json!({
+                "attrGroups": attr_groups,
+                "static": r#static,
+                "byRef": by_ref,
+                "params": params,
+                "returnType": return_type,
+                "expr": expr,
+            }),
+        )
+    }
+
+    fn r#match(&mut self, expression: &MatchExpression) -> Value {
+        self.open();
+        self.span(expression.keyword);
+        self.span(expression.left_parenthesis);
+        let cond = self.expression(&expression.condition);
+        self.span(expression.right_parenthesis);
+        self.span(expression.left_brace);
+
+        // the default arm is kept apart from the others, so it is put back in its place.
+        let mut default = expression.default.as_deref();
+        let mut arms = Vec::new();
+        for arm in &expression.arms {
+            if let Some(arm) =
+                default.filter(|default| default.keyword.position < arm.arrow.position)
+            {
+                arms.push(self.default_arm(arm));
+                default = None;
+            }
+
+            self.open();
+            let conds = self.expressions(&arm.conditions);
+            self.span(arm.arrow);
+            let body = self.expression(&arm.body);
+
+            arms.push(self.close("MatchArm", json!({ "conds": conds, "body": body })));
+        }
+        if let Some(arm) = default {
+            arms.push(self.default_arm(arm));
+        }
+
+        self.span(expression.right_brace);
+
+        self.close("Expr_Match", json!({ "cond": cond, "arms": arms }))
+    }
+
+    fn default_arm(&mut self, arm: &crate::parser::ast::DefaultMatchArm) -> Value {
+        self.open();
+        self.span(arm.keyword);
+        self.span(arm.double_arrow);
+        let body = self.expression(&arm.body);
+
+        self.close("MatchArm", json!({ "conds": null, "body": body }))
+    }
+}
+
+fn text(bytes: &ByteString) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn use_type(kind: &UseKind) -> u8 {
+    match kind {
+        UseKind::Normal => 1,
+        UseKind::Function => 2,
+        UseKind::Const => 3,
+    }
+}
+
+// `EOT` in `<<<EOT`, `<<<"EOT"` and `<<<'EOT'`.
+fn doc_label(label: &ByteString) -> String {
+    text(label)
+        .trim_start_matches("<<<")
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(code: &str) -> Value {
+        to_json(&crate::parse(code).unwrap(), code.as_bytes())
+    }
+
+    #[test]
+    fn test_class() {
+        let json = export(
+            "<?php\nfinal class A extends B {\n    public static function f(int $a): ?string {}\n}",
+        );
+        let class = &json[0];
+
+        assert_eq!(class["nodeType"], "Stmt_Class");
+        assert_eq!(class["flags"], FINAL);
+        assert_eq!(class["name"]["nodeType"], "Identifier");
+        assert_eq!(class["name"]["name"], "A");
+        assert_eq!(class["extends"]["nodeType"], "Name");
+        assert_eq!(class["attributes"]["startLine"], 2);
+        assert_eq!(class["attributes"]["startFilePos"], 6);
+        assert_eq!(class["attributes"]["endLine"], 4);
+        assert_eq!(class["attributes"]["endFilePos"], 81);
+
+        let method = &class["stmts"][0];
+        assert_eq!(method["nodeType"], "Stmt_ClassMethod");
+        assert_eq!(method["flags"], PUBLIC | STATIC);
+        assert_eq!(method["params"][0]["type"]["name"], "int");
+        assert_eq!(method["params"][0]["var"]["name"], "a");
+        assert_eq!(method["returnType"]["nodeType"], "NullableType");
+        assert_eq!(method["stmts"], json!([]));
+    }
+
+    #[test]
+    fn test_expressions() {
+        let json = export("<?php [$a, , $b] = f(TRUE, ...$c, x: 0x1F);");
+        let assign = &json[0]["expr"];
+
+        assert_eq!(assign["nodeType"], "Expr_Assign");
+        assert_eq!(assign["var"]["nodeType"], "Expr_List");
+        assert_eq!(assign["var"]["attributes"]["kind"], 2);
+        assert_eq!(assign["var"]["items"][1], Value::Null);
+
+        let args = &assign["expr"]["args"];
+        assert_eq!(args[0]["value"]["nodeType"], "Expr_ConstFetch");
+        assert_eq!(args[0]["value"]["name"]["name"], "TRUE");
+        assert_eq!(args[0]["value"]["attributes"]["startFilePos"], 21);
+        assert_eq!(args[1]["unpack"], true);
+        assert_eq!(args[2]["name"]["name"], "x");
+        assert_eq!(args[2]["value"]["value"], 31);
+        assert_eq!(args[2]["value"]["attributes"]["rawValue"], "0x1F");
+        assert_eq!(args[2]["value"]["attributes"]["kind"], 16);
+    }
+
+    #[test]
+    fn test_strings() {
+        let json = export("<?php echo 'a\\'b', \"a $b\", <<<EOT\n  c\n  EOT;");
+        let exprs = &json[0]["exprs"];
+
+        assert_eq!(exprs[0]["nodeType"], "Scalar_String");
+        assert_eq!(exprs[0]["value"], "a'b");
+        assert_eq!(exprs[0]["attributes"]["rawValue"], "'a\\'b'");
+        assert_eq!(exprs[0]["attributes"]["endFilePos"], 16);
+
+        assert_eq!(exprs[1]["nodeType"], "Scalar_InterpolatedString");
+        assert_eq!(exprs[1]["parts"][0]["value"], "a ");
+        assert_eq!(exprs[1]["parts"][1]["name"], "b");
+        assert_eq!(exprs[1]["attributes"]["startFilePos"], 19);
+        assert_eq!(exprs[1]["attributes"]["endFilePos"], 24);
+
+        assert_eq!(exprs[2]["nodeType"], "Scalar_String");
+        assert_eq!(exprs[2]["value"], "c");
+        assert_eq!(exprs[2]["attributes"]["docLabel"], "EOT");
+        assert_eq!(exprs[2]["attributes"]["endLine"], 3);
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let json = export("<?php\nnamespace A;\nuse B\\C as D;\nnew \\E\\F();");
+        let namespace = &json[0];
+
+        assert_eq!(namespace["nodeType"], "Stmt_Namespace");
+        assert_eq!(namespace["attributes"]["endFilePos"], 17);
+        assert_eq!(namespace["stmts"][0]["uses"][0]["name"]["name"], "B\\C");
+        assert_eq!(namespace["stmts"][0]["uses"][0]["alias"]["name"], "D");
+        assert_eq!(
+            namespace["stmts"][1]["expr"]["class"]["nodeType"],
+            "Name_FullyQualified"
+        );
+        assert_eq!(namespace["stmts"][1]["expr"]["class"]["name"], "E\\F");
+    }
+}
@@ -0,0 +1,3289 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::export::tokens;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::parser::ast::arguments::Argument;
+use crate::parser::ast::arguments::ArgumentList;
+use crate::parser::ast::arguments::ArgumentPlaceholder;
+use crate::parser::ast::arguments::SingleArgument;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::AnonymousClassMember;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::comments::CommentFormat;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::control_flow::IfStatement;
+use crate::parser::ast::control_flow::IfStatementBody;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::declares::DeclareBody;
+use crate::parser::ast::declares::DeclareStatement;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameter;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::literals::Literal;
+use crate::parser::ast::literals::LiteralInteger;
+use crate::parser::ast::loops::ForStatementBody;
+use crate::parser::ast::loops::ForeachStatementBody;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatementBody;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ClassModifierGroup;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PromotedPropertyModifier;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::operators::ArithmeticOperationExpression;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::operators::BitwiseOperationExpression;
+use crate::parser::ast::operators::ComparisonOperationExpression;
+use crate::parser::ast::operators::LogicalOperationExpression;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::TryStatement;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::CastKind;
+use crate::parser::ast::DefaultMatchArm;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::MagicConstantExpression;
+use crate::parser::ast::MatchExpression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::Use;
+use crate::parser::ast::UseKind;
+
+// the values of the `ast\flags\*` constants.
+const NAME_FQ: u64 = 0;
+const NAME_NOT_FQ: u64 = 1;
+const NAME_RELATIVE: u64 = 2;
+const MODIFIER_PUBLIC: u64 = 1;
+const MODIFIER_PROTECTED: u64 = 2;
+const MODIFIER_PRIVATE: u64 = 4;
+const MODIFIER_STATIC: u64 = 16;
+const MODIFIER_FINAL: u64 = 32;
+const MODIFIER_ABSTRACT: u64 = 64;
+const MODIFIER_READONLY: u64 = 128;
+const CLASS_INTERFACE: u64 = 1;
+const CLASS_TRAIT: u64 = 2;
+const CLASS_ANONYMOUS: u64 = 4;
+const CLASS_FINAL: u64 = 32;
+const CLASS_ABSTRACT: u64 = 64;
+const CLASS_READONLY: u64 = 65536;
+const CLASS_ENUM: u64 = 268435456;
+const FUNC_RETURNS_REF: u64 = 4096;
+const PARAM_REF: u64 = 8;
+const PARAM_VARIADIC: u64 = 16;
+const TYPE_NULL: u64 = 1;
+const TYPE_FALSE: u64 = 2;
+const TYPE_TRUE: u64 = 3;
+const TYPE_LONG: u64 = 4;
+const TYPE_DOUBLE: u64 = 5;
+const TYPE_STRING: u64 = 6;
+const TYPE_ARRAY: u64 = 7;
+const TYPE_OBJECT: u64 = 8;
+const TYPE_CALLABLE: u64 = 12;
+const TYPE_ITERABLE: u64 = 13;
+const TYPE_VOID: u64 = 14;
+const TYPE_STATIC: u64 = 15;
+const TYPE_MIXED: u64 = 16;
+const TYPE_NEVER: u64 = 17;
+const TYPE_BOOL: u64 = 18;
+const UNARY_BITWISE_NOT: u64 = 13;
+const UNARY_BOOL_NOT: u64 = 14;
+const UNARY_SILENCE: u64 = 260;
+const UNARY_PLUS: u64 = 261;
+const UNARY_MINUS: u64 = 262;
+const BINARY_ADD: u64 = 1;
+const BINARY_SUB: u64 = 2;
+const BINARY_MUL: u64 = 3;
+const BINARY_DIV: u64 = 4;
+const BINARY_MOD: u64 = 5;
+const BINARY_SHIFT_LEFT: u64 = 6;
+const BINARY_SHIFT_RIGHT: u64 = 7;
+const BINARY_CONCAT: u64 = 8;
+const BINARY_BITWISE_OR: u64 = 9;
+const BINARY_BITWISE_AND: u64 = 10;
+const BINARY_BITWISE_XOR: u64 = 11;
+const BINARY_POW: u64 = 12;
+const BINARY_BOOL_XOR: u64 = 15;
+const BINARY_IS_IDENTICAL: u64 = 16;
+const BINARY_IS_NOT_IDENTICAL: u64 = 17;
+const BINARY_IS_EQUAL: u64 = 18;
+const BINARY_IS_NOT_EQUAL: u64 = 19;
+const BINARY_IS_SMALLER: u64 = 20;
+const BINARY_IS_SMALLER_OR_EQUAL: u64 = 21;
+const BINARY_SPACESHIP: u64 = 170;
+const BINARY_IS_GREATER: u64 = 256;
+const BINARY_IS_GREATER_OR_EQUAL: u64 = 257;
+const BINARY_BOOL_OR: u64 = 258;
+const BINARY_BOOL_AND: u64 = 259;
+const BINARY_COALESCE: u64 = 260;
+const EXEC_EVAL: u64 = 1;
+const EXEC_INCLUDE: u64 = 2;
+const EXEC_INCLUDE_ONCE: u64 = 4;
+const EXEC_REQUIRE: u64 = 8;
+const EXEC_REQUIRE_ONCE: u64 = 16;
+const USE_NORMAL: u64 = 1;
+const USE_FUNCTION: u64 = 2;
+const USE_CONST: u64 = 4;
+const ARRAY_SYNTAX_LIST: u64 = 1;
+const ARRAY_SYNTAX_LONG: u64 = 2;
+const ARRAY_SYNTAX_SHORT: u64 = 3;
+const ARRAY_ELEM_REF: u64 = 1;
+const CLOSURE_USE_REF: u64 = 1;
+
+/// The version of the AST, as passed to php-ast's `ast\parse_code()`.
+///
+/// Only the versions that change the structure of the nodes this parser produces are
+/// supported:
+///
+/// - 80 adds `attributes` children to declarations, and wraps class constants in an
+///   `AST_CLASS_CONST_GROUP` node, which holds their modifiers.
+/// - 85 adds a `type` child to `AST_CLASS`, holding the backing type of enums.
+/// - 90 adds a `type` child to `AST_CLASS_CONST_GROUP`.
+/// - 100 adds a `hooks` child to `AST_PARAM` and `AST_PROP_ELEM`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Version {
+    V70,
+    V80,
+    V85,
+    V90,
+    #[default]
+    V100,
+}
+
+impl Version {
+    pub const ALL: [Version; 5] = [
+        Version::V70,
+        Version::V80,
+        Version::V85,
+        Version::V90,
+        Version::V100,
+    ];
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Version::V70 => 70,
+            Version::V80 => 80,
+            Version::V85 => 85,
+            Version::V90 => 90,
+            Version::V100 => 100,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Version::ALL
+            .into_iter()
+            .find(|version| version.number().to_string() == s.trim())
+            .ok_or_else(|| {
+                format!(
+                    "unsupported php-ast version `{}`, expecting one of: {}",
+                    s,
+                    Version::ALL.map(|version| version.to_string()).join(", ")
+                )
+            })
+    }
+}
+
+/// Convert the program to the nodes `ast\parse_code()` of the php-ast extension returns,
+/// with each `ast\Node` written as an object holding its `kind` (by name), `flags`,
+/// `lineno` and `children`, along with the `endLineno` of declarations.
+///
+/// The children of list nodes, such as `AST_STMT_LIST`, are arrays, while those of other
+/// nodes are objects keyed by child name. Line numbers are those of the first token of
+/// each node, which differ from PHP's for some expressions spanning several lines, and
+/// nodes whose position the AST doesn't record take the line of the preceding token.
+/// `FUNC_GENERATOR` flags are not set.
+pub fn to_json(program: &Program, version: Version) -> Value {
+    let mut exporter = Exporter {
+        version,
+        frames: Vec::new(),
+        line: 1,
+        declarations: 0,
+    };
+
+    exporter.open();
+    let statements = exporter.statements(program);
+
+    exporter.close_list("AST_STMT_LIST", 0, statements)
+}
+
+struct Exporter {
+    version: Version,
+    // the first and last line of each node being exported.
+    frames: Vec<Option<(usize, usize)>>,
+    // the line of the last token seen, used for the nodes without a position.
+    line: usize,
+    // the number of declarations exported, which php-ast gives a `__declId`.
+    declarations: usize,
+}
+
+impl Exporter {
+    fn open(&mut self) {
+        self.frames.push(None);
+    }
+
+    fn span(&mut self, span: Span) {
+        self.line = span.line;
+        self.extend(span.line, span.line);
+    }
+
+    fn extend(&mut self, first: usize, last: usize) {
+        if let Some(frame) = self.frames.last_mut() {
+            *frame = Some(match *frame {
+                Some((start, end)) => (start.min(first), end.max(last)),
+                None => (first, last),
+            });
+        }
+    }
+
+    // pop the lines of the current node, and attribute them to its parent.
+    fn lines(&mut self) -> (usize, usize) {
+        match self.frames.pop().flatten() {
+            Some((start, end)) => {
+                self.extend(start, end);
+
+                (start, end)
+            }
+            None => (self.line, self.line),
+        }
+    }
+
+    fn close(&mut self, kind: &str, flags: u64, children: Value) -> Value {
+        let (lineno, _) = self.lines();
+
+        json!({ "kind": kind, "flags": flags, "lineno": lineno, "children": children })
+    }
+
+    fn close_list(&mut self, kind: &str, flags: u64, children: Vec<Value>) -> Value {
+        self.close(kind, flags, Value::Array(children))
+    }
+
+    // close a function, method, closure or class, which have an end line and an id.
+    fn close_declaration(&mut self, kind: &str, flags: u64, mut children: Value) -> Value {
+        let (lineno, end_lineno) = self.lines();
+        children["__declId"] = Value::from(self.declarations);
+        self.declarations += 1;
+
+        json!({
+            "kind": kind,
+            "flags": flags,
+            "lineno": lineno,
+            "endLineno": end_lineno,
+            "children": children,
+        })
+    }
+
+    // the statements of a block, in which the statements that PHP compiles to a list of
+    // statements, such as `echo $a, $b;`, are flattened.
+    fn statements(&mut self, statements: &[Statement]) -> Vec<Value> {
+        let mut exported = Vec::new();
+        let mut echo = None;
+
+        for statement in statements {
+            match statement {
+                Statement::EchoOpeningTag(tag) => echo = Some(tag.span),
+                // `<?= $a ?>` is an echo statement.
+                Statement::Expression(statement) if echo.is_some() => {
+                    self.open();
+                    self.span(echo.take().unwrap());
+                    let expr = self.expression(&statement.expression);
+
+                    exported.push(self.close("AST_ECHO", 0, json!({ "expr": expr })));
+                }
+                // the statements following `namespace Foo;` are not part of the namespace.
+                Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                    echo = None;
+                    self.open();
+                    self.span(namespace.start);
+                    let name = self.namespace_name(&namespace.name);
+                    self.span(namespace.end);
+
+                    exported.push(self.close(
+                        "AST_NAMESPACE",
+                        0,
+                        json!({ "name": name, "stmts": null }),
+                    ));
+                    exported.extend(self.statements(&namespace.statements));
+                }
+                statement => {
+                    echo = None;
+                    exported.extend(self.statement(statement));
+                }
+            }
+        }
+
+        exported
+    }
+
+    fn statement_list(&mut self, statements: &[Statement]) -> Value {
+        self.open();
+        let statements = self.statements(statements);
+
+        self.close_list("AST_STMT_LIST", 0, statements)
+    }
+
+    // the statements of a loop or conditional body, which are written either as a block
+    // or as a single statement.
+    fn body(&mut self, statement: &Statement) -> Value {
+        match statement {
+            Statement::Block(block) => {
+                self.open();
+                self.span(block.left_brace);
+                let statements = self.statements(&block.statements);
+                self.span(block.right_brace);
+
+                self.close_list("AST_STMT_LIST", 0, statements)
+            }
+            Statement::Echo(_) | Statement::Global(_) | Statement::Static(_) => {
+                self.statement_list(std::slice::from_ref(statement))
+            }
+            Statement::Expression(expression)
+                if matches!(expression.expression, Expression::Unset(_)) =>
+            {
+                self.statement_list(std::slice::from_ref(statement))
+            }
+            statement => self
+                .statement(statement)
+                .into_iter()
+                .next()
+                .unwrap_or(Value::Null),
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Vec<Value> {
+        let exported = match statement {
+            Statement::FullOpeningTag(_)
+            | Statement::ShortOpeningTag(_)
+            | Statement::EchoOpeningTag(_)
+            | Statement::ClosingTag(_)
+            | Statement::Comment(_)
+            | Statement::Noop(_) => return Vec::new(),
+            Statement::InlineHtml(statement) => {
+                self.open();
+
+                self.close("AST_ECHO", 0, json!({ "expr": text(&statement.html) }))
+            }
+            Statement::Label(statement) => {
+                self.open();
+                let name = self.identifier(&statement.label);
+                self.span(statement.colon);
+
+                self.close("AST_LABEL", 0, json!({ "name": name }))
+            }
+            Statement::Goto(statement) => {
+                self.open();
+                self.span(statement.keyword);
+                let label = self.identifier(&statement.label);
+                self.span(statement.semicolon);
+
+                self.close("AST_GOTO", 0, json!({ "label": label }))
+            }
+            Statement::HaltCompiler(_) => {
+                // the offset of the remaining data isn't recorded by the AST.
+                self.open();
+
+                self.close("AST_HALT_COMPILER", 0, json!({ "offset": null }))
+            }
+            Statement::Static(statement) => {
+                return statement
+                    .vars
+                    .iter()
+                    .map(|var| {
+                        self.open();
+                        let name = self.variable(&var.var);
+                        let default = var.default.as_ref().map(|value| self.expression(value));
+
+                        self.close("AST_STATIC", 0, json!({ "var": name, "default": default }))
+                    })
+                    .collect();
+            }
+            Statement::Global(statement) => {
+                return statement
+                    .variables
+                    .iter()
+                    .map(|variable| {
+                        self.open();
+                        self.span(statement.global);
+                        let var = self.variable(variable);
+
+                        self.close("AST_GLOBAL", 0, json!({ "var": var }))
+                    })
+                    .collect();
+            }
+            Statement::Echo(statement) => {
+                self.span(statement.echo);
+
+                return statement
+                    .values
+                    .iter()
+                    .map(|value| {
+                        self.open();
+                        let expr = self.expression(value);
+
+                        self.close("AST_ECHO", 0, json!({ "expr": expr }))
+                    })
+                    .collect();
+            }
+            Statement::Expression(statement) => match &statement.expression {
+                // `unset()` is a statement, even though it is parsed as an expression.
+                Expression::Unset(unset) => {
+                    self.span(unset.unset);
+
+                    return self
+                        .argument_values(&unset.arguments)
+                        .into_iter()
+                        .map(|var| {
+                            self.open();
+                            self.span(unset.unset);
+
+                            self.close("AST_UNSET", 0, json!({ "var": var }))
+                        })
+                        .collect();
+                }
+                expression => self.expression(expression),
+            },
+            Statement::DoWhile(statement) => {
+                self.open();
+                self.span(statement.r#do);
+                let stmts = self.body(&statement.body);
+                self.span(statement.r#while);
+                let cond = self.expression(&statement.condition);
+                self.span(statement.semicolon);
+
+                self.close("AST_DO_WHILE", 0, json!({ "stmts": stmts, "cond": cond }))
+            }
+            Statement::While(statement) => {
+                self.open();
+                self.span(statement.r#while);
+                let cond = self.expression(&statement.condition);
+                let stmts = match &statement.body {
+                    WhileStatementBody::Statement { statement } => self.body(statement),
+                    WhileStatementBody::Block {
+                        statements,
+                        endwhile,
+                        ..
+                    } => {
+                        let statements = self.statement_list(statements);
+                        self.span(*endwhile);
+
+                        statements
+                    }
+                };
+
+                self.close("AST_WHILE", 0, json!({ "cond": cond, "stmts": stmts }))
+            }
+            Statement::For(statement) => {
+                self.open();
+                self.span(statement.r#for);
+                let iterator = &statement.iterator;
+                let init = self.expression_list(&iterator.initializations.inner);
+                let cond = self.expression_list(&iterator.conditions.inner);
+                let r#loop = self.expression_list(&iterator.r#loop.inner);
+                let stmts = match &statement.body {
+                    ForStatementBody::Statement { statement } => self.body(statement),
+                    ForStatementBody::Block {
+                        statements, endfor, ..
+                    } => {
+                        let statements = self.statement_list(statements);
+                        self.span(*endfor);
+
+                        statements
+                    }
+                };
+
+                self.close(
+                    "AST_FOR",
+                    0,
+                    json!({ "init": init, "cond": cond, "loop": r#loop, "stmts": stmts }),
+                )
+            }
+            Statement::Foreach(statement) => {
+                self.open();
+                self.span(statement.foreach);
+                let (expr, key, ampersand, value) = match &statement.iterator {
+                    ForeachStatementIterator::Value {
+                        expression,
+                        ampersand,
+                        value,
+                        ..
+                    } => (self.expression(expression), None, ampersand, value),
+                    ForeachStatementIterator::KeyAndValue {
+                        expression,
+                        ampersand,
+                        key,
+                        value,
+                        ..
+                    } => {
+                        let expression = self.expression(expression);
+                        let key = self.expression(key);
+
+                        (expression, Some(key), ampersand, value)
+                    }
+                };
+                // `&$value` is a reference.
+                let value = match ampersand {
+                    Some(span) => {
+                        self.open();
+                        self.span(*span);
+                        let var = self.assignable(value);
+
+                        self.close("AST_REF", 0, json!({ "var": var }))
+                    }
+                    None => self.assignable(value),
+                };
+                let stmts = match &statement.body {
+                    ForeachStatementBody::Statement { statement } => self.body(statement),
+                    ForeachStatementBody::Block {
+                        statements,
+                        endforeach,
+                        ..
+                    } => {
+                        let statements = self.statement_list(statements);
+                        self.span(*endforeach);
+
+                        statements
+                    }
+                };
+
+                self.close(
+                    "AST_FOREACH",
+                    0,
+                    json!({ "expr": expr, "value": value, "key": key, "stmts": stmts }),
+                )
+            }
+            Statement::Break(statement) => {
+                self.open();
+                self.span(statement.r#break);
+                let depth = statement.level.as_ref().map(|level| self.level(level));
+
+                self.close("AST_BREAK", 0, json!({ "depth": depth }))
+            }
+            Statement::Continue(statement) => {
+                self.open();
+                self.span(statement.r#continue);
+                let depth = statement.level.as_ref().map(|level| self.level(level));
+
+                self.close("AST_CONTINUE", 0, json!({ "depth": depth }))
+            }
+            Statement::Constant(statement) => {
+                self.open();
+                self.span(statement.r#const);
+                let doc_comment = doc_comment(&statement.comments);
+                let constants = self.constants(&statement.entries, &doc_comment);
+
+                self.close_list("AST_CONST_DECL", 0, constants)
+            }
+            Statement::Function(statement) => self.function(statement),
+            Statement::Class(statement) => self.class(statement),
+            Statement::Trait(statement) => self.r#trait(statement),
+            Statement::Interface(statement) => self.interface(statement),
+            Statement::UnitEnum(statement) => self.unit_enum(statement),
+            Statement::BackedEnum(statement) => self.backed_enum(statement),
+            Statement::If(statement) => self.r#if(statement),
+            Statement::Switch(statement) => {
+                self.open();
+                self.span(statement.switch);
+                let cond = self.expression(&statement.condition);
+                self.open();
+                let cases = statement
+                    .cases
+                    .iter()
+                    .map(|case| {
+                        self.open();
+                        let cond = case
+                            .condition
+                            .as_ref()
+                            .map(|condition| self.expression(condition));
+                        let stmts = self.statement_list(&case.body);
+
+                        self.close(
+                            "AST_SWITCH_CASE",
+                            0,
+                            json!({ "cond": cond, "stmts": stmts }),
+                        )
+                    })
+                    .collect();
+                let stmts = self.close_list("AST_SWITCH_LIST", 0, cases);
+
+                self.close("AST_SWITCH", 0, json!({ "cond": cond, "stmts": stmts }))
+            }
+            Statement::Return(statement) => {
+                self.open();
+                self.span(statement.r#return);
+                let expr = statement.value.as_ref().map(|value| self.expression(value));
+
+                self.close("AST_RETURN", 0, json!({ "expr": expr }))
+            }
+            Statement::Namespace(NamespaceStatement::Unbraced(namespace)) => {
+                self.open();
+                self.span(namespace.start);
+                let name = self.namespace_name(&namespace.name);
+                self.span(namespace.end);
+
+                self.close("AST_NAMESPACE", 0, json!({ "name": name, "stmts": null }))
+            }
+            Statement::Namespace(NamespaceStatement::Braced(namespace)) => {
+                self.open();
+                self.span(namespace.namespace);
+                let name = match &namespace.name {
+                    Some(name) => self.namespace_name(name),
+                    None => Value::Null,
+                };
+                self.open();
+                self.span(namespace.body.start);
+                let statements = self.statements(&namespace.body.statements);
+                self.span(namespace.body.end);
+                let stmts = self.close_list("AST_STMT_LIST", 0, statements);
+
+                self.close("AST_NAMESPACE", 0, json!({ "name": name, "stmts": stmts }))
+            }
+            Statement::Use(statement) => {
+                self.open();
+                let uses = statement
+                    .uses
+                    .iter()
+                    .map(|r#use| self.use_item(r#use))
+                    .collect();
+
+                self.close_list("AST_USE", use_flags(&statement.kind), uses)
+            }
+            Statement::GroupUse(statement) => {
+                self.open();
+                let prefix = self.namespace_name(&statement.prefix);
+                self.open();
+                let uses = statement
+                    .uses
+                    .iter()
+                    .map(|r#use| self.use_item(r#use))
+                    .collect();
+                let uses = self.close_list("AST_USE", 0, uses);
+
+                self.close(
+                    "AST_GROUP_USE",
+                    use_flags(&statement.kind),
+                    json!({ "prefix": prefix, "uses": uses }),
+                )
+            }
+            Statement::Try(statement) => self.r#try(statement),
+            Statement::Block(statement) => {
+                // blocks are flattened into the enclosing list of statements.
+                self.span(statement.left_brace);
+                let statements = self.statements(&statement.statements);
+                self.span(statement.right_brace);
+
+                return statements;
+            }
+            Statement::Declare(statement) => self.declare(statement),
+        };
+
+        vec![exported]
+    }
+
+    fn r#if(&mut self, statement: &IfStatement) -> Value {
+        self.open();
+        self.open();
+        self.span(statement.r#if);
+        let cond = self.expression(&statement.condition);
+
+        let mut elements = Vec::new();
+        match &statement.body {
+            IfStatementBody::Statement {
+                statement,
+                elseifs,
+                r#else,
+            } => {
+                let stmts = self.body(statement);
+                elements.push(self.close(
+                    "AST_IF_ELEM",
+                    0,
+                    json!({ "cond": cond, "stmts": stmts }),
+                ));
+
+                for elseif in elseifs {
+                    self.open();
+                    self.span(elseif.elseif);
+                    let cond = self.expression(&elseif.condition);
+                    let stmts = self.body(&elseif.statement);
+
+                    elements.push(self.close(
+                        "AST_IF_ELEM",
+                        0,
+                        json!({ "cond": cond, "stmts": stmts }),
+                    ));
+                }
+
+                if let Some(r#else) = r#else {
+                    self.open();
+                    self.span(r#else.r#else);
+                    let stmts = self.body(&r#else.statement);
+
+                    elements.push(self.close(
+                        "AST_IF_ELEM",
+                        0,
+                        json!({ "cond": null, "stmts": stmts }),
+                    ));
+                }
+            }
+            IfStatementBody::Block {
+                statements,
+                elseifs,
+                r#else,
+                endif,
+                ..
+            } => {
+                let stmts = self.statement_list(statements);
+                elements.push(self.close(
+                    "AST_IF_ELEM",
+                    0,
+                    json!({ "cond": cond, "stmts": stmts }),
+                ));
+
+                for elseif in elseifs {
+                    self.open();
+                    self.span(elseif.elseif);
+                    let cond = self.expression(&elseif.condition);
+                    let stmts = self.statement_list(&elseif.statements);
+
+                    elements.push(self.close(
+                        "AST_IF_ELEM",
+                        0,
+                        json!({ "cond": cond, "stmts": stmts }),
+                    ));
+                }
+
+                if let Some(r#else) = r#else {
+                    self.open();
+                    self.span(r#else.r#else);
+                    let stmts = self.statement_list(&r#else.statements);
+
+                    elements.push(self.close(
+                        "AST_IF_ELEM",
+                        0,
+                        json!({ "cond": null, "stmts": stmts }),
+                    ));
+                }
+
+                self.span(*endif);
+            }
+        }
+
+        self.close_list("AST_IF", 0, elements)
+    }
+
+    fn r#try(&mut self, statement: &TryStatement) -> Value {
+        self.open();
+        self.span(statement.start);
+        let r#try = self.statement_list(&statement.body);
+
+        self.open();
+        let catches = statement
+            .catches
+            .iter()
+            .map(|catch| {
+                self.open();
+                self.span(catch.start);
+                self.open();
+                let names = match &catch.types {
+                    CatchType::Identifier { identifier } => vec![self.name(identifier)],
+                    CatchType::Union { identifiers } => identifiers
+                        .iter()
+                        .map(|identifier| self.name(identifier))
+                        .collect(),
+                };
+                let class = self.close_list("AST_NAME_LIST", 0, names);
+                let var = catch.var.as_ref().map(|var| self.simple_variable(var));
+                let stmts = self.statement_list(&catch.body);
+
+                self.close(
+                    "AST_CATCH",
+                    0,
+                    json!({ "class": class, "var": var, "stmts": stmts }),
+                )
+            })
+            .collect();
+        let catches = self.close_list("AST_CATCH_LIST", 0, catches);
+
+        let finally = statement.finally.as_ref().map(|finally| {
+            self.span(finally.start);
+            self.statement_list(&finally.body)
+        });
+
+        self.close(
+            "AST_TRY",
+            0,
+            json!({ "try": r#try, "catches": catches, "finally": finally }),
+        )
+    }
+
+    fn declare(&mut self, statement: &DeclareStatement) -> Value {
+        self.open();
+        self.span(statement.declare);
+        self.open();
+        let declares = statement
+            .entries
+            .entries
+            .iter()
+            .map(|entry| {
+                self.open();
+                let name = self.identifier(&entry.key);
+                let value = self.literal(&entry.value);
+
+                self.close(
+                    "AST_CONST_ELEM",
+                    0,
+                    json!({ "name": name, "value": value, "docComment": null }),
+                )
+            })
+            .collect();
+        let declares = self.close_list("AST_CONST_DECL", 0, declares);
+
+        let stmts = match &statement.body {
+            DeclareBody::Noop { semicolon } => {
+                self.span(*semicolon);
+
+                Value::Null
+            }
+            DeclareBody::Braced {
+                left_brace,
+                statements,
+                right_brace,
+            } => {
+                self.open();
+                self.span(*left_brace);
+                let statements = self.statements(statements);
+                self.span(*right_brace);
+
+                self.close_list("AST_STMT_LIST", 0, statements)
+            }
+            DeclareBody::Expression { expression, .. } => self.expression(expression),
+            DeclareBody::Block {
+                statements, end, ..
+            } => {
+                let statements = self.statement_list(statements);
+                self.span(end.0);
+
+                statements
+            }
+        };
+
+        self.close(
+            "AST_DECLARE",
+            0,
+            json!({ "declares": declares, "stmts": stmts }),
+        )
+    }
+
+    fn use_item(&mut self, r#use: &Use) -> Value {
+        self.open();
+        let name = self.namespace_name(&r#use.name);
+        let alias = r#use.alias.as_ref().map(|alias| self.identifier(alias));
+        let flags = r#use.kind.as_ref().map(use_flags).unwrap_or(0);
+
+        self.close(
+            "AST_USE_ELEM",
+            flags,
+            json!({ "name": name, "alias": alias }),
+        )
+    }
+
+    fn level(&mut self, level: &Level) -> Value {
+        match level {
+            Level::Literal(literal) => self.integer(literal),
+            Level::Parenthesized { level, .. } => self.level(level),
+        }
+    }
+
+    fn constants(&mut self, entries: &[ConstantEntry], doc_comment: &Value) -> Vec<Value> {
+        entries
+            .iter()
+            .map(|entry| {
+                self.open();
+                let name = self.identifier(&entry.name);
+                let value = self.expression(&entry.value);
+
+                self.close(
+                    "AST_CONST_ELEM",
+                    0,
+                    json!({ "name": name, "value": value, "docComment": doc_comment }),
+                )
+            })
+            .collect()
+    }
+
+    // add the children only present in later versions of the AST.
+    fn since(&self, version: Version, children: &mut Value, name: &str, value: Value) {
+        if self.version >= version {
+            children[name] = value;
+        }
+    }
+
+    fn function(&mut self, statement: &FunctionStatement) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&statement.attributes);
+        self.span(statement.function);
+        let flags = self.returns_ref(statement.ampersand);
+        let name = self.identifier(&statement.name);
+        let params = self.parameters(&statement.parameters);
+        let return_type = self.return_type(&statement.return_type);
+        self.open();
+        self.span(statement.body.left_brace);
+        let statements = self.statements(&statement.body.statements);
+        self.span(statement.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, statements);
+
+        let mut children = json!({
+            "name": name,
+            "docComment": doc_comment(&statement.comments),
+            "params": params,
+            "stmts": stmts,
+            "returnType": return_type,
+        });
+        self.since(Version::V80, &mut children, "attributes", attributes);
+
+        self.close_declaration("AST_FUNC_DECL", flags, children)
+    }
+
+    // close an `AST_CLASS` node, opened by the caller.
+    #[allow(clippy::too_many_arguments)]
+    fn close_class(
+        &mut self,
+        flags: u64,
+        name: Value,
+        extends: Value,
+        implements: Value,
+        stmts: Value,
+        attributes: Value,
+        r#type: Value,
+    ) -> Value {
+        let mut children = json!({
+            "name": name,
+            "docComment": null,
+            "extends": extends,
+            "implements": implements,
+            "stmts": stmts,
+        });
+        self.since(Version::V80, &mut children, "attributes", attributes);
+        self.since(Version::V85, &mut children, "type", r#type);
+
+        self.close_declaration("AST_CLASS", flags, children)
+    }
+
+    fn class(&mut self, statement: &ClassStatement) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&statement.attributes);
+        let flags = self.class_modifiers(&statement.modifiers);
+        self.span(statement.class);
+        let name = self.identifier(&statement.name);
+        let extends = match &statement.extends {
+            Some(extends) => self.name(&extends.parent),
+            None => Value::Null,
+        };
+        let implements = match &statement.implements {
+            Some(implements) => self.name_list(&implements.interfaces.inner),
+            None => Value::Null,
+        };
+        self.open();
+        self.span(statement.body.left_brace);
+        let members = statement
+            .body
+            .members
+            .iter()
+            .map(|member| self.class_member(member))
+            .collect();
+        self.span(statement.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, members);
+
+        self.close_class(
+            flags,
+            name,
+            extends,
+            implements,
+            stmts,
+            attributes,
+            Value::Null,
+        )
+    }
+
+    fn anonymous_class(&mut self, expression: &AnonymousClassExpression) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&expression.attributes);
+        self.span(expression.class);
+        let extends = match &expression.extends {
+            Some(extends) => self.name(&extends.parent),
+            None => Value::Null,
+        };
+        let implements = match &expression.implements {
+            Some(implements) => self.name_list(&implements.interfaces.inner),
+            None => Value::Null,
+        };
+        self.open();
+        self.span(expression.body.left_brace);
+        let members = expression
+            .body
+            .members
+            .iter()
+            .map(|member| match member {
+                AnonymousClassMember::Constant(constant) => self.class_constant(constant),
+                AnonymousClassMember::TraitUsage(usage) => self.trait_usage(usage),
+                AnonymousClassMember::Property(property) => self.property(
+                    &property.attributes,
+                    self.property_modifiers(&property.modifiers.modifiers),
+                    &property.r#type,
+                    &property.entries,
+                ),
+                AnonymousClassMember::VariableProperty(property) => self.property(
+                    &property.attributes,
+                    MODIFIER_PUBLIC,
+                    &property.r#type,
+                    &property.entries,
+                ),
+                AnonymousClassMember::ConcreteMethod(method) => self.concrete_method(method),
+                AnonymousClassMember::ConcreteConstructor(constructor) => {
+                    self.concrete_constructor(constructor)
+                }
+            })
+            .collect();
+        self.span(expression.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, members);
+
+        self.close_class(
+            CLASS_ANONYMOUS,
+            Value::Null,
+            extends,
+            implements,
+            stmts,
+            attributes,
+            Value::Null,
+        )
+    }
+
+    fn r#trait(&mut self, statement: &TraitStatement) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&statement.attributes);
+        self.span(statement.r#trait);
+        let name = self.identifier(&statement.name);
+        self.open();
+        self.span(statement.body.left_brace);
+        let members = statement
+            .body
+            .members
+            .iter()
+            .map(|member| self.trait_member(member))
+            .collect();
+        self.span(statement.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, members);
+
+        self.close_class(
+            CLASS_TRAIT,
+            name,
+            Value::Null,
+            Value::Null,
+            stmts,
+            attributes,
+            Value::Null,
+        )
+    }
+
+    fn interface(&mut self, statement: &InterfaceStatement) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&statement.attributes);
+        self.span(statement.interface);
+        let name = self.identifier(&statement.name);
+        // the interfaces an interface extends are its `implements` child.
+        let implements = match &statement.extends {
+            Some(extends) => self.name_list(&extends.parents.inner),
+            None => Value::Null,
+        };
+        self.open();
+        self.span(statement.body.left_brace);
+        let members = statement
+            .body
+            .members
+            .iter()
+            .map(|member| match member {
+                InterfaceMember::Constant(constant) => self.class_constant(constant),
+                InterfaceMember::Constructor(constructor) => self.abstract_constructor(constructor),
+                InterfaceMember::Method(method) => self.abstract_method(method),
+            })
+            .collect();
+        self.span(statement.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, members);
+
+        self.close_class(
+            CLASS_INTERFACE,
+            name,
+            Value::Null,
+            implements,
+            stmts,
+            attributes,
+            Value::Null,
+        )
+    }
+
+    fn unit_enum(&mut self, statement: &UnitEnumStatement) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&statement.attributes);
+        self.span(statement.r#enum);
+        let name = self.identifier(&statement.name);
+        let implements = match statement.implements.is_empty() {
+            true => Value::Null,
+            false => self.name_list(&statement.implements),
+        };
+        self.open();
+        self.span(statement.body.left_brace);
+        let members = statement
+            .body
+            .members
+            .iter()
+            .map(|member| match member {
+                UnitEnumMember::Case(case) => {
+                    self.open();
+                    let attributes = self.attribute_groups(&case.attributes);
+                    self.span(case.start);
+                    let name = self.identifier(&case.name);
+                    self.span(case.end);
+
+                    self.enum_case(name, Value::Null, attributes)
+                }
+                UnitEnumMember::Method(method) => self.concrete_method(method),
+                UnitEnumMember::Constant(constant) => self.class_constant(constant),
+                UnitEnumMember::TraitUsage(usage) => self.trait_usage(usage),
+            })
+            .collect();
+        self.span(statement.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, members);
+
+        self.close_class(
+            CLASS_ENUM,
+            name,
+            Value::Null,
+            implements,
+            stmts,
+            attributes,
+            Value::Null,
+        )
+    }
+
+    fn backed_enum(&mut self, statement: &BackedEnumStatement) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&statement.attributes);
+        self.span(statement.r#enum);
+        let name = self.identifier(&statement.name);
+        let (span, flags) = match statement.backed_type {
+            BackedEnumType::String(_, span) => (span, TYPE_STRING),
+            BackedEnumType::Int(_, span) => (span, TYPE_LONG),
+        };
+        self.open();
+        self.span(span);
+        let r#type = self.close("AST_TYPE", flags, json!({}));
+        let implements = match statement.implements.is_empty() {
+            true => Value::Null,
+            false => self.name_list(&statement.implements),
+        };
+        self.open();
+        self.span(statement.body.left_brace);
+        let members = statement
+            .body
+            .members
+            .iter()
+            .map(|member| match member {
+                BackedEnumMember::Case(case) => {
+                    self.open();
+                    let attributes = self.attribute_groups(&case.attributes);
+                    self.span(case.case);
+                    let name = self.identifier(&case.name);
+                    let expr = self.expression(&case.value);
+                    self.span(case.semicolon);
+
+                    self.enum_case(name, expr, attributes)
+                }
+                BackedEnumMember::Method(method) => self.concrete_method(method),
+                BackedEnumMember::Constant(constant) => self.class_constant(constant),
+                BackedEnumMember::TraitUsage(usage) => self.trait_usage(usage),
+            })
+            .collect();
+        self.span(statement.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, members);
+
+        self.close_class(
+            CLASS_ENUM,
+            name,
+            Value::Null,
+            implements,
+            stmts,
+            attributes,
+            r#type,
+        )
+    }
+
+    // close an `AST_ENUM_CASE` node, opened by the caller.
+    fn enum_case(&mut self, name: Value, expr: Value, attributes: Value) -> Value {
+        let mut children = json!({ "name": name, "expr": expr, "docComment": null });
+        self.since(Version::V80, &mut children, "attributes", attributes);
+
+        self.close("AST_ENUM_CASE", 0, children)
+    }
+
+    fn class_member(&mut self, member: &ClassMember) -> Value {
+        match member {
+            ClassMember::Constant(constant) => self.class_constant(constant),
+            ClassMember::TraitUsage(usage) => self.trait_usage(usage),
+            ClassMember::Property(property) => self.property(
+                &property.attributes,
+                self.property_modifiers(&property.modifiers.modifiers),
+                &property.r#type,
+                &property.entries,
+            ),
+            ClassMember::VariableProperty(property) => self.property(
+                &property.attributes,
+                MODIFIER_PUBLIC,
+                &property.r#type,
+                &property.entries,
+            ),
+            ClassMember::AbstractMethod(method) => self.abstract_method(method),
+            ClassMember::AbstractConstructor(constructor) => self.abstract_constructor(constructor),
+            ClassMember::ConcreteMethod(method) => self.concrete_method(method),
+            ClassMember::ConcreteConstructor(constructor) => self.concrete_constructor(constructor),
+        }
+    }
+
+    fn trait_member(&mut self, member: &TraitMember) -> Value {
+        match member {
+            TraitMember::Constant(constant) => self.class_constant(constant),
+            TraitMember::TraitUsage(usage) => self.trait_usage(usage),
+            TraitMember::Property(property) => self.property(
+                &property.attributes,
+                self.property_modifiers(&property.modifiers.modifiers),
+                &property.r#type,
+                &property.entries,
+            ),
+            TraitMember::VariableProperty(property) => self.property(
+                &property.attributes,
+                MODIFIER_PUBLIC,
+                &property.r#type,
+                &property.entries,
+            ),
+            TraitMember::AbstractMethod(method) => self.abstract_method(method),
+            TraitMember::AbstractConstructor(constructor) => self.abstract_constructor(constructor),
+            TraitMember::ConcreteMethod(method) => self.concrete_method(method),
+            TraitMember::ConcreteConstructor(constructor) => self.concrete_constructor(constructor),
+        }
+    }
+
+    fn class_constant(&mut self, constant: &ClassishConstant) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&constant.attributes);
+        let flags = constant
+            .modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                ConstantModifier::Final(span) => (*span, MODIFIER_FINAL),
+                ConstantModifier::Public(span) => (*span, MODIFIER_PUBLIC),
+                ConstantModifier::Protected(span) => (*span, MODIFIER_PROTECTED),
+                ConstantModifier::Private(span) => (*span, MODIFIER_PRIVATE),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum::<u64>();
+        self.span(constant.r#const);
+        let doc_comment = doc_comment(&constant.comments);
+        let constants = self.constants(&constant.entries, &doc_comment);
+
+        // prior to version 80, the modifiers are the flags of the declaration.
+        if self.version < Version::V80 {
+            return self.close_list("AST_CLASS_CONST_DECL", flags, constants);
+        }
+
+        self.open();
+        let constants = self.close_list("AST_CLASS_CONST_DECL", 0, constants);
+        let mut children = json!({ "const": constants, "attributes": attributes });
+        self.since(Version::V90, &mut children, "type", Value::Null);
+
+        self.close("AST_CLASS_CONST_GROUP", flags, children)
+    }
+
+    fn trait_usage(&mut self, usage: &TraitUsage) -> Value {
+        self.open();
+        self.span(usage.r#use);
+        let traits = self.name_list(&usage.traits);
+        let adaptations = match usage.adaptations.is_empty() {
+            true => Value::Null,
+            false => {
+                self.open();
+                let adaptations = usage
+                    .adaptations
+                    .iter()
+                    .map(|adaptation| self.trait_adaptation(adaptation))
+                    .collect();
+
+                self.close_list("AST_TRAIT_ADAPTATIONS", 0, adaptations)
+            }
+        };
+
+        self.close(
+            "AST_USE_TRAIT",
+            0,
+            json!({ "traits": traits, "adaptations": adaptations }),
+        )
+    }
+
+    fn trait_adaptation(&mut self, adaptation: &TraitUsageAdaptation) -> Value {
+        self.open();
+        let (r#trait, method) = match adaptation {
+            TraitUsageAdaptation::Alias {
+                r#trait, method, ..
+            }
+            | TraitUsageAdaptation::Visibility {
+                r#trait, method, ..
+            }
+            | TraitUsageAdaptation::Precedence {
+                r#trait, method, ..
+            } => (r#trait, method),
+        };
+        self.open();
+        let class = r#trait.as_ref().map(|r#trait| self.name(r#trait));
+        let name = self.identifier(method);
+        let method = self.close(
+            "AST_METHOD_REFERENCE",
+            0,
+            json!({ "class": class, "method": name }),
+        );
+
+        match adaptation {
+            TraitUsageAdaptation::Alias {
+                alias, visibility, ..
+            } => {
+                let flags = visibility
+                    .as_ref()
+                    .map(|visibility| self.visibility(visibility))
+                    .unwrap_or(0);
+                let alias = self.identifier(alias);
+
+                self.close(
+                    "AST_TRAIT_ALIAS",
+                    flags,
+                    json!({ "method": method, "alias": alias }),
+                )
+            }
+            TraitUsageAdaptation::Visibility { visibility, .. } => {
+                let flags = self.visibility(visibility);
+
+                self.close(
+                    "AST_TRAIT_ALIAS",
+                    flags,
+                    json!({ "method": method, "alias": null }),
+                )
+            }
+            TraitUsageAdaptation::Precedence { insteadof, .. } => {
+                let insteadof = self.name_list(insteadof);
+
+                self.close(
+                    "AST_TRAIT_PRECEDENCE",
+                    0,
+                    json!({ "method": method, "insteadof": insteadof }),
+                )
+            }
+        }
+    }
+
+    fn property_modifiers(&self, modifiers: &[PropertyModifier]) -> u64 {
+        modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                PropertyModifier::Public(_) => MODIFIER_PUBLIC,
+                PropertyModifier::Protected(_) => MODIFIER_PROTECTED,
+                PropertyModifier::Private(_) => MODIFIER_PRIVATE,
+                PropertyModifier::Static(_) => MODIFIER_STATIC,
+                PropertyModifier::Readonly(_) => MODIFIER_READONLY,
+            })
+            .sum()
+    }
+
+    fn property(
+        &mut self,
+        attributes: &[AttributeGroup],
+        flags: u64,
+        r#type: &Option<Type>,
+        entries: &[PropertyEntry],
+    ) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(attributes);
+        let r#type = r#type.as_ref().map(|r#type| self.r#type(r#type));
+        self.open();
+        let props = entries
+            .iter()
+            .map(|entry| {
+                self.open();
+                let (variable, default) = match entry {
+                    PropertyEntry::Uninitialized { variable } => (variable, None),
+                    PropertyEntry::Initialized {
+                        variable, value, ..
+                    } => (variable, Some(value)),
+                };
+                let name = self.variable_name(variable);
+                let default = default.map(|value| self.expression(value));
+
+                let mut children = json!({ "name": name, "default": default, "docComment": null });
+                self.since(Version::V100, &mut children, "hooks", Value::Null);
+
+                self.close("AST_PROP_ELEM", 0, children)
+            })
+            .collect();
+        let props = self.close_list("AST_PROP_DECL", 0, props);
+
+        let mut children = json!({ "type": r#type, "props": props });
+        self.since(Version::V80, &mut children, "attributes", attributes);
+
+        self.close("AST_PROP_GROUP", flags, children)
+    }
+
+    fn method_modifiers(&mut self, modifiers: &MethodModifierGroup) -> u64 {
+        modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                MethodModifier::Final(span) => (*span, MODIFIER_FINAL),
+                MethodModifier::Static(span) => (*span, MODIFIER_STATIC),
+                MethodModifier::Abstract(span) => (*span, MODIFIER_ABSTRACT),
+                MethodModifier::Public(span) => (*span, MODIFIER_PUBLIC),
+                MethodModifier::Protected(span) => (*span, MODIFIER_PROTECTED),
+                MethodModifier::Private(span) => (*span, MODIFIER_PRIVATE),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum()
+    }
+
+    fn class_modifiers(&mut self, modifiers: &ClassModifierGroup) -> u64 {
+        modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                ClassModifier::Final(span) => (*span, CLASS_FINAL),
+                ClassModifier::Abstract(span) => (*span, CLASS_ABSTRACT),
+                ClassModifier::Readonly(span) => (*span, CLASS_READONLY),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum()
+    }
+
+    fn visibility(&mut self, visibility: &VisibilityModifier) -> u64 {
+        match visibility {
+            VisibilityModifier::Public(span) => self.flag(*span, MODIFIER_PUBLIC),
+            VisibilityModifier::Protected(span) => self.flag(*span, MODIFIER_PROTECTED),
+            VisibilityModifier::Private(span) => self.flag(*span, MODIFIER_PRIVATE),
+        }
+    }
+
+    fn flag(&mut self, span: Span, flag: u64) -> u64 {
+        self.span(span);
+
+        flag
+    }
+
+    fn returns_ref(&mut self, ampersand: Option<Span>) -> u64 {
+        match ampersand {
+            Some(span) => self.flag(span, FUNC_RETURNS_REF),
+            None => 0,
+        }
+    }
+
+    fn abstract_method(&mut self, method: &AbstractMethod) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&method.attributes);
+        let flags = self.method_modifiers(&method.modifiers);
+        self.span(method.function);
+        let flags = flags | self.returns_ref(method.ampersand);
+        let name = self.identifier(&method.name);
+        let params = self.parameters(&method.parameters);
+        let return_type = self.return_type(&method.return_type);
+        self.span(method.semicolon);
+
+        self.method(
+            flags,
+            name,
+            doc_comment(&method.comments),
+            params,
+            Value::Null,
+            return_type,
+            attributes,
+        )
+    }
+
+    fn abstract_constructor(&mut self, constructor: &AbstractConstructor) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&constructor.attributes);
+        let flags = self.method_modifiers(&constructor.modifiers);
+        self.span(constructor.function);
+        let flags = flags | self.returns_ref(constructor.ampersand);
+        let name = self.identifier(&constructor.name);
+        let params = self.parameters(&constructor.parameters);
+        self.span(constructor.semicolon);
+
+        self.method(
+            flags,
+            name,
+            doc_comment(&constructor.comments),
+            params,
+            Value::Null,
+            Value::Null,
+            attributes,
+        )
+    }
+
+    fn concrete_method(&mut self, method: &ConcreteMethod) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&method.attributes);
+        let flags = self.method_modifiers(&method.modifiers);
+        self.span(method.function);
+        let flags = flags | self.returns_ref(method.ampersand);
+        let name = self.identifier(&method.name);
+        let params = self.parameters(&method.parameters);
+        let return_type = self.return_type(&method.return_type);
+        self.open();
+        self.span(method.body.left_brace);
+        let statements = self.statements(&method.body.statements);
+        self.span(method.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, statements);
+
+        self.method(
+            flags,
+            name,
+            doc_comment(&method.comments),
+            params,
+            stmts,
+            return_type,
+            attributes,
+        )
+    }
+
+    fn concrete_constructor(&mut self, constructor: &ConcreteConstructor) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&constructor.attributes);
+        let flags = self.method_modifiers(&constructor.modifiers);
+        self.span(constructor.function);
+        let flags = flags | self.returns_ref(constructor.ampersand);
+        let name = self.identifier(&constructor.name);
+        self.open();
+        self.span(constructor.parameters.left_parenthesis);
+        let params = constructor
+            .parameters
+            .parameters
+            .inner
+            .iter()
+            .map(|parameter| self.constructor_parameter(parameter))
+            .collect();
+        self.span(constructor.parameters.right_parenthesis);
+        let params = self.close_list("AST_PARAM_LIST", 0, params);
+        self.open();
+        self.span(constructor.body.left_brace);
+        let statements = self.statements(&constructor.body.statements);
+        self.span(constructor.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, statements);
+
+        self.method(
+            flags,
+            name,
+            doc_comment(&constructor.comments),
+            params,
+            stmts,
+            Value::Null,
+            attributes,
+        )
+    }
+
+    // close the node of a method, opened by the caller.
+    #[allow(clippy::too_many_arguments)]
+    fn method(
+        &mut self,
+        flags: u64,
+        name: Value,
+        doc_comment: Value,
+        params: Value,
+        stmts: Value,
+        return_type: Value,
+        attributes: Value,
+    ) -> Value {
+        let mut children = json!({
+            "name": name,
+            "docComment": doc_comment,
+            "params": params,
+            "stmts": stmts,
+            "returnType": return_type,
+        });
+        self.since(Version::V80, &mut children, "attributes", attributes);
+
+        self.close_declaration("AST_METHOD", flags, children)
+    }
+
+    fn parameters(&mut self, parameters: &FunctionParameterList) -> Value {
+        self.open();
+        self.span(parameters.left_parenthesis);
+        let exported = parameters
+            .parameters
+            .inner
+            .iter()
+            .map(|parameter| self.parameter(parameter))
+            .collect();
+        self.span(parameters.right_parenthesis);
+
+        self.close_list("AST_PARAM_LIST", 0, exported)
+    }
+
+    fn parameter(&mut self, parameter: &FunctionParameter) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&parameter.attributes);
+        let r#type = parameter
+            .data_type
+            .as_ref()
+            .map(|r#type| self.r#type(r#type));
+        let flags = self.parameter_flags(parameter.ampersand, parameter.ellipsis);
+        let name = self.variable_name(&parameter.name);
+        let default = parameter
+            .default
+            .as_ref()
+            .map(|default| self.expression(default));
+
+        self.close_parameter(flags, r#type, name, default, attributes)
+    }
+
+    fn constructor_parameter(&mut self, parameter: &ConstructorParameter) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&parameter.attributes);
+        let modifiers = parameter
+            .modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                PromotedPropertyModifier::Public(span) => (*span, MODIFIER_PUBLIC),
+                PromotedPropertyModifier::Protected(span) => (*span, MODIFIER_PROTECTED),
+                PromotedPropertyModifier::Private(span) => (*span, MODIFIER_PRIVATE),
+                PromotedPropertyModifier::Readonly(span) => (*span, MODIFIER_READONLY),
+            })
+            .map(|(span, flag)| self.flag(span, flag))
+            .sum::<u64>();
+        let r#type = parameter
+            .data_type
+            .as_ref()
+            .map(|r#type| self.r#type(r#type));
+        let flags = modifiers | self.parameter_flags(parameter.ampersand, parameter.ellipsis);
+        let name = self.variable_name(&parameter.name);
+        let default = parameter
+            .default
+            .as_ref()
+            .map(|default| self.expression(default));
+
+        self.close_parameter(flags, r#type, name, default, attributes)
+    }
+
+    fn parameter_flags(&mut self, ampersand: Option<Span>, ellipsis: Option<Span>) -> u64 {
+        let by_ref = ampersand.map_or(0, |span| self.flag(span, PARAM_REF));
+        let variadic = ellipsis.map_or(0, |span| self.flag(span, PARAM_VARIADIC));
+
+        by_ref | variadic
+    }
+
+    // close an `AST_PARAM` node, opened by the caller.
+    fn close_parameter(
+        &mut self,
+        flags: u64,
+        r#type: Option<Value>,
+        name: Value,
+        default: Option<Value>,
+        attributes: Value,
+    ) -> Value {
+        let mut children = json!({ "type": r#type, "name": name, "default": default });
+        self.since(Version::V80, &mut children, "attributes", attributes);
+        self.since(Version::V80, &mut children, "docComment", Value::Null);
+        self.since(Version::V100, &mut children, "hooks", Value::Null);
+
+        self.close("AST_PARAM", flags, children)
+    }
+
+    fn return_type(&mut self, return_type: &Option<ReturnType>) -> Value {
+        match return_type {
+            Some(return_type) => self.r#type(&return_type.data_type),
+            None => Value::Null,
+        }
+    }
+
+    fn r#type(&mut self, r#type: &Type) -> Value {
+        let (span, flags) = match r#type {
+            Type::Named(span, name) => {
+                return self.name(&SimpleIdentifier {
+                    span: *span,
+                    value: name.clone(),
+                })
+            }
+            Type::Nullable(span, inner) => {
+                self.open();
+                self.span(*span);
+                let inner = self.r#type(inner);
+
+                return self.close("AST_NULLABLE_TYPE", 0, json!({ "type": inner }));
+            }
+            Type::Union(types) | Type::Intersection(types) => {
+                self.open();
+                let types = types.iter().map(|r#type| self.r#type(r#type)).collect();
+                let kind = match r#type {
+                    Type::Union(_) => "AST_TYPE_UNION",
+                    _ => "AST_TYPE_INTERSECTION",
+                };
+
+                return self.close_list(kind, 0, types);
+            }
+            // `self` and `parent` are class names, while the other types are reserved.
+            Type::SelfReference(span) => return self.keyword(Some(*span), "self"),
+            Type::ParentReference(span) => return self.keyword(Some(*span), "parent"),
+            Type::Void(span) => (span, TYPE_VOID),
+            Type::Null(span) => (span, TYPE_NULL),
+            Type::True(span) => (span, TYPE_TRUE),
+            Type::False(span) => (span, TYPE_FALSE),
+            Type::Never(span) => (span, TYPE_NEVER),
+            Type::Float(span) => (span, TYPE_DOUBLE),
+            Type::Boolean(span) => (span, TYPE_BOOL),
+            Type::Integer(span) => (span, TYPE_LONG),
+            Type::String(span) => (span, TYPE_STRING),
+            Type::Array(span) => (span, TYPE_ARRAY),
+            Type::Object(span) => (span, TYPE_OBJECT),
+            Type::Mixed(span) => (span, TYPE_MIXED),
+            Type::Callable(span) => (span, TYPE_CALLABLE),
+            Type::Iterable(span) => (span, TYPE_ITERABLE),
+            Type::StaticReference(span) => (span, TYPE_STATIC),
+        };
+
+        self.open();
+        self.span(*span);
+
+        self.close("AST_TYPE", flags, json!({}))
+    }
+
+    // attributes, which are `null` when there are none.
+    fn attribute_groups(&mut self, groups: &[AttributeGroup]) -> Value {
+        if groups.is_empty() {
+            return Value::Null;
+        }
+
+        self.open();
+        let groups = groups
+            .iter()
+            .map(|group| {
+                self.open();
+                self.span(group.start);
+                let attributes = group
+                    .members
+                    .iter()
+                    .map(|attribute| {
+                        self.open();
+                        let class = self.name(&attribute.name);
+                        let args = attribute
+                            .arguments
+                            .as_ref()
+                            .map(|arguments| self.arguments(arguments));
+
+                        self.close("AST_ATTRIBUTE", 0, json!({ "class": class, "args": args }))
+                    })
+                    .collect();
+                self.span(group.end);
+
+                self.close_list("AST_ATTRIBUTE_GROUP", 0, attributes)
+            })
+            .collect();
+
+        self.close_list("AST_ATTRIBUTE_LIST", 0, groups)
+    }
+
+    fn arguments(&mut self, arguments: &ArgumentList) -> Value {
+        self.open();
+        self.span(arguments.left_parenthesis);
+        let exported = arguments
+            .arguments
+            .iter()
+            .map(|argument| self.argument(argument))
+            .collect();
+        self.span(arguments.right_parenthesis);
+
+        self.close_list("AST_ARG_LIST", 0, exported)
+    }
+
+    fn argument(&mut self, argument: &Argument) -> Value {
+        let (name, ellipsis, value) = match argument {
+            Argument::Positional(argument) => (None, argument.ellipsis, &argument.value),
+            Argument::Named(argument) => (Some(&argument.name), argument.ellipsis, &argument.value),
+        };
+
+        if let Some(name) = name {
+            self.open();
+            let name = self.identifier(name);
+            let expr = self.expression(value);
+
+            return self.close("AST_NAMED_ARG", 0, json!({ "name": name, "expr": expr }));
+        }
+
+        match ellipsis {
+            Some(span) => {
+                self.open();
+                self.span(span);
+                let expr = self.expression(value);
+
+                self.close("AST_UNPACK", 0, json!({ "expr": expr }))
+            }
+            None => self.expression(value),
+        }
+    }
+
+    // the arguments of a language construct such as `isset()`, which are plain expressions.
+    fn argument_values(&mut self, arguments: &ArgumentList) -> Vec<Value> {
+        self.span(arguments.left_parenthesis);
+        let exported = arguments
+            .arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::Positional(argument) => self.expression(&argument.value),
+                Argument::Named(argument) => self.expression(&argument.value),
+            })
+            .collect();
+        self.span(arguments.right_parenthesis);
+
+        exported
+    }
+
+    fn single_argument(&mut self, argument: &SingleArgument) -> Value {
+        self.span(argument.left_parenthesis);
+        let exported = match &argument.argument {
+            Argument::Positional(argument) => self.expression(&argument.value),
+            Argument::Named(argument) => self.expression(&argument.value),
+        };
+        self.span(argument.right_parenthesis);
+
+        exported
+    }
+
+    fn placeholder(&mut self, placeholder: &ArgumentPlaceholder) -> Value {
+        self.open();
+        self.span(placeholder.left_parenthesis);
+        self.span(placeholder.right_parenthesis);
+
+        self.close("AST_CALLABLE_CONVERT", 0, json!({}))
+    }
+
+    // a name that is a plain string, such as the name of a function.
+    fn identifier(&mut self, identifier: &SimpleIdentifier) -> Value {
+        self.span(identifier.span);
+
+        Value::from(text(&identifier.value))
+    }
+
+    // a name that is a plain string, without its leading `\`, such as that of a namespace.
+    fn namespace_name(&mut self, identifier: &SimpleIdentifier) -> Value {
+        self.span(identifier.span);
+
+        Value::from(text(&identifier.value).trim_start_matches('\\'))
+    }
+
+    fn name_list(&mut self, identifiers: &[SimpleIdentifier]) -> Value {
+        self.open();
+        let names = identifiers
+            .iter()
+            .map(|identifier| self.name(identifier))
+            .collect();
+
+        self.close_list("AST_NAME_LIST", 0, names)
+    }
+
+    fn name(&mut self, identifier: &SimpleIdentifier) -> Value {
+        self.open();
+        self.span(identifier.span);
+
+        let name = text(&identifier.value);
+        if let Some(name) = name.strip_prefix('\\') {
+            return self.close("AST_NAME", NAME_FQ, json!({ "name": name }));
+        }
+
+        match name.get(..10) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("namespace\\") => {
+                self.close("AST_NAME", NAME_RELATIVE, json!({ "name": &name[10..] }))
+            }
+            _ => self.close("AST_NAME", NAME_NOT_FQ, json!({ "name": name })),
+        }
+    }
+
+    // a name written as a keyword, such as `true` or `static`.
+    fn keyword(&mut self, span: Option<Span>, name: &str) -> Value {
+        self.open();
+        if let Some(span) = span {
+            self.span(span);
+        }
+
+        self.close("AST_NAME", NAME_NOT_FQ, json!({ "name": name }))
+    }
+
+    fn constant(&mut self, span: Option<Span>, name: &str) -> Value {
+        self.open();
+        let name = self.keyword(span, name);
+
+        self.close("AST_CONST", 0, json!({ "name": name }))
+    }
+
+    // the name of a property or parameter, without its `$`.
+    fn variable_name(&mut self, variable: &SimpleVariable) -> Value {
+        self.span(variable.span);
+
+        Value::from(text(&variable.name).trim_start_matches('$'))
+    }
+
+    fn simple_variable(&mut self, variable: &SimpleVariable) -> Value {
+        self.open();
+        let name = self.variable_name(variable);
+
+        self.close("AST_VAR", 0, json!({ "name": name }))
+    }
+
+    fn variable(&mut self, variable: &Variable) -> Value {
+        match variable {
+            Variable::SimpleVariable(variable) => self.simple_variable(variable),
+            Variable::VariableVariable(variable) => {
+                self.open();
+                self.span(variable.span);
+                let name = self.variable(&variable.variable);
+
+                self.close("AST_VAR", 0, json!({ "name": name }))
+            }
+            Variable::BracedVariableVariable(variable) => {
+                self.open();
+                self.span(variable.start);
+                let name = self.expression(&variable.variable);
+                self.span(variable.end);
+
+                self.close("AST_VAR", 0, json!({ "name": name }))
+            }
+        }
+    }
+
+    // the name of a static property, such as `bar` in `Foo::$bar`.
+    fn static_property(&mut self, variable: &Variable) -> Value {
+        match variable {
+            Variable::SimpleVariable(variable) => self.variable_name(variable),
+            Variable::VariableVariable(variable) => {
+                self.span(variable.span);
+                self.variable(&variable.variable)
+            }
+            Variable::BracedVariableVariable(variable) => {
+                self.span(variable.start);
+                let name = self.expression(&variable.variable);
+                self.span(variable.end);
+
+                name
+            }
+        }
+    }
+
+    // the class of `new`, `instanceof`, static calls and fetches, and the name of function
+    // calls, where an identifier is a name rather than a constant.
+    fn class_name(&mut self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                self.name(identifier)
+            }
+            Expression::Static(_) => self.keyword(None, "static"),
+            Expression::Self_(_) => self.keyword(None, "self"),
+            Expression::Parent(_) => self.keyword(None, "parent"),
+            Expression::AnonymousClass(class) => self.anonymous_class(class),
+            expression => self.expression(expression),
+        }
+    }
+
+    // the name of a method or property, such as `bar` in `$foo->bar()`.
+    fn member(&mut self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Identifier(identifier) => self.member_identifier(identifier),
+            expression => self.expression(expression),
+        }
+    }
+
+    fn member_identifier(&mut self, identifier: &Identifier) -> Value {
+        match identifier {
+            Identifier::SimpleIdentifier(identifier) => self.identifier(identifier),
+            Identifier::DynamicIdentifier(identifier) => {
+                self.span(identifier.start);
+                let name = self.expression(&identifier.expr);
+                self.span(identifier.end);
+
+                name
+            }
+        }
+    }
+
+    // the expressions of a `for` loop, which are `null` when there are none.
+    fn expression_list(&mut self, expressions: &[Expression]) -> Value {
+        if expressions.is_empty() {
+            return Value::Null;
+        }
+
+        self.open();
+        let expressions = expressions
+            .iter()
+            .map(|expression| self.expression(expression))
+            .collect();
+
+        self.close_list("AST_EXPR_LIST", 0, expressions)
+    }
+
+    // the target of an assignment, where arrays are lists.
+    fn assignable(&mut self, expression: &Expression) -> Value {
+        match expression {
+            Expression::ShortArray(array) => {
+                self.open();
+                self.span(array.start);
+                let items = self.array_items(&array.items.inner, true);
+                self.span(array.end);
+
+                self.close_list("AST_ARRAY", ARRAY_SYNTAX_SHORT, items)
+            }
+            Expression::List(list) => {
+                self.open();
+                self.span(list.list);
+                let items = list
+                    .items
+                    .iter()
+                    .map(|item| {
+                        let (key, value) = match item {
+                            ListEntry::Skipped => return Value::Null,
+                            ListEntry::Value { value } => (None, value),
+                            ListEntry::KeyValue { key, value, .. } => (Some(key), value),
+                        };
+
+                        self.open();
+                        let key = key.map(|key| self.expression(key));
+                        let value = self.assignable(value);
+
+                        self.close("AST_ARRAY_ELEM", 0, json!({ "value": value, "key": key }))
+                    })
+                    .collect();
+                self.span(list.end);
+
+                self.close_list("AST_ARRAY", ARRAY_SYNTAX_LIST, items)
+            }
+            expression => self.expression(expression),
+        }
+    }
+
+    fn array_items(&mut self, items: &[ArrayItem], list: bool) -> Vec<Value> {
+        items
+            .iter()
+            .map(|item| {
+                if let ArrayItem::Skipped = item {
+                    return Value::Null;
+                }
+
+                self.open();
+                let (key, flags, value) = match item {
+                    ArrayItem::Skipped => unreachable!(),
+                    ArrayItem::Value { value } => (None, 0, value),
+                    ArrayItem::ReferencedValue { ampersand, value } => {
+                        (None, self.flag(*ampersand, ARRAY_ELEM_REF), value)
+                    }
+                    // `...$a` is an unpacked value, rather than an element.
+                    ArrayItem::SpreadValue { ellipsis, value } => {
+                        self.span(*ellipsis);
+                        let expr = self.expression(value);
+
+                        return self.close("AST_UNPACK", 0, json!({ "expr": expr }));
+                    }
+                    ArrayItem::KeyValue { key, value, .. } => {
+                        (Some(self.expression(key)), 0, value)
+                    }
+                    ArrayItem::ReferencedKeyValue {
+                        key,
+                        ampersand,
+                        value,
+                        ..
+                    } => {
+                        let key = self.expression(key);
+
+                        (Some(key), self.flag(*ampersand, ARRAY_ELEM_REF), value)
+                    }
+                };
+                let value = if list {
+                    self.assignable(value)
+                } else {
+                    self.expression(value)
+                };
+
+                self.close(
+                    "AST_ARRAY_ELEM",
+                    flags,
+                    json!({ "value": value, "key": key }),
+                )
+            })
+            .collect()
+    }
+
+    fn literal(&mut self, literal: &Literal) -> Value {
+        match literal {
+            Literal::String(literal) => {
+                self.span(literal.span);
+
+                Value::from(text(&literal.value))
+            }
+            Literal::Integer(literal) => self.integer(literal),
+            Literal::Float(literal) => {
+                self.span(literal.span);
+
+                Value::from(
+                    text(&literal.value)
+                        .replace('_', "")
+                        .parse::<f64>()
+                        .unwrap_or_default(),
+                )
+            }
+        }
+    }
+
+    fn integer(&mut self, literal: &LiteralInteger) -> Value {
+        self.span(literal.span);
+
+        let digits = text(&literal.value).replace('_', "").to_ascii_lowercase();
+        let (radix, digits) = if let Some(digits) = digits.strip_prefix("0x") {
+            (16, digits)
+        } else if let Some(digits) = digits.strip_prefix("0b") {
+            (2, digits)
+        } else if let Some(digits) = digits.strip_prefix("0o") {
+            (8, digits)
+        } else if digits.len() > 1 && digits.starts_with('0') {
+            (8, &digits[1..])
+        } else {
+            (10, digits.as_str())
+        };
+
+        match i64::from_str_radix(digits, radix) {
+            Ok(value) => Value::from(value),
+            // integers that overflow are floats.
+            Err(_) => Value::from(
+                digits
+                    .chars()
+                    .filter_map(|digit| digit.to_digit(radix))
+                    .fold(0.0, |value, digit| value * radix as f64 + digit as f64),
+            ),
+        }
+    }
+
+    fn encaps_list(&mut self, parts: &[StringPart]) -> Value {
+        self.open();
+        let parts = parts
+            .iter()
+            .map(|part| match part {
+                StringPart::Literal(part) => Value::from(text(&part.value)),
+                StringPart::Expression(part) => self.expression(&part.expression),
+            })
+            .collect();
+
+        self.close_list("AST_ENCAPS_LIST", 0, parts)
+    }
+
+    fn binary(
+        &mut self,
+        flags: u64,
+        left: &Expression,
+        operator: Span,
+        right: &Expression,
+    ) -> Value {
+        self.open();
+        let left = self.expression(left);
+        self.span(operator);
+        let right = self.expression(right);
+
+        self.close(
+            "AST_BINARY_OP",
+            flags,
+            json!({ "left": left, "right": right }),
+        )
+    }
+
+    fn assign(
+        &mut self,
+        flags: u64,
+        left: &Expression,
+        operator: Span,
+        right: &Expression,
+    ) -> Value {
+        self.open();
+        let var = self.assignable(left);
+        self.span(operator);
+        let expr = self.expression(right);
+
+        self.close("AST_ASSIGN_OP", flags, json!({ "var": var, "expr": expr }))
+    }
+
+    fn unary(&mut self, flags: u64, operator: Span, right: &Expression) -> Value {
+        self.open();
+        self.span(operator);
+        let expr = self.expression(right);
+
+        self.close("AST_UNARY_OP", flags, json!({ "expr": expr }))
+    }
+
+    fn increment(&mut self, kind: &str, operator: Span, operand: &Expression) -> Value {
+        self.open();
+        self.span(operator);
+        let var = self.expression(operand);
+
+        self.close(kind, 0, json!({ "var": var }))
+    }
+
+    fn expression(&mut self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Eval(expression) => {
+                self.open();
+                self.span(expression.eval);
+                let expr = self.single_argument(&expression.argument);
+
+                self.close("AST_INCLUDE_OR_EVAL", EXEC_EVAL, json!({ "expr": expr }))
+            }
+            Expression::Empty(expression) => {
+                self.open();
+                self.span(expression.empty);
+                let expr = self.single_argument(&expression.argument);
+
+                self.close("AST_EMPTY", 0, json!({ "expr": expr }))
+            }
+            Expression::Die(expression) => {
+                self.open();
+                self.span(expression.die);
+                let expr = expression
+                    .argument
+                    .as_ref()
+                    .map(|argument| self.single_argument(argument));
+
+                self.close("AST_EXIT", 0, json!({ "expr": expr }))
+            }
+            Expression::Exit(expression) => {
+                self.open();
+                self.span(expression.exit);
+                let expr = expression
+                    .argument
+                    .as_ref()
+                    .map(|argument| self.single_argument(argument));
+
+                self.close("AST_EXIT", 0, json!({ "expr": expr }))
+            }
+            // `isset($a, $b)` is `isset($a) && isset($b)`.
+            Expression::Isset(expression) => {
+                self.open();
+                self.span(expression.isset);
+                let line = self.line;
+                let vars = self.argument_values(&expression.arguments);
+                self.lines();
+
+                let mut vars = vars.into_iter().map(|var| {
+                    json!({ "kind": "AST_ISSET", "flags": 0, "lineno": line, "children": { "var": var } })
+                });
+                let first = vars.next().unwrap_or(Value::Null);
+
+                vars.fold(first, |left, right| {
+                    json!({
+                        "kind": "AST_BINARY_OP",
+                        "flags": BINARY_BOOL_AND,
+                        "lineno": line,
+                        "children": { "left": left, "right": right },
+                    })
+                })
+            }
+            // only valid as a statement, see `statement()`.
+            Expression::Unset(expression) => {
+                self.open();
+                self.span(expression.unset);
+                let vars = self.argument_values(&expression.arguments);
+
+                self.close("AST_UNSET", 0, json!({ "var": vars.into_iter().next() }))
+            }
+            Expression::Print(expression) => {
+                self.open();
+                self.span(expression.print);
+                let expr = match (&expression.value, &expression.argument) {
+                    (Some(value), _) => self.expression(value),
+                    (None, Some(argument)) => self.single_argument(argument),
+                    (None, None) => Value::Null,
+                };
+
+                self.close("AST_PRINT", 0, json!({ "expr": expr }))
+            }
+            Expression::Literal(literal) => self.literal(literal),
+            Expression::ArithmeticOperation(operation) => match operation {
+                ArithmeticOperationExpression::Addition { left, plus, right } => {
+                    self.binary(BINARY_ADD, left, *plus, right)
+                }
+                ArithmeticOperationExpression::Subtraction { left, minus, right } => {
+                    self.binary(BINARY_SUB, left, *minus, right)
+                }
+                ArithmeticOperationExpression::Multiplication {
+                    left,
+                    asterisk,
+                    right,
+                } => self.binary(BINARY_MUL, left, *asterisk, right),
+                ArithmeticOperationExpression::Division { left, slash, right } => {
+                    self.binary(BINARY_DIV, left, *slash, right)
+                }
+                ArithmeticOperationExpression::Modulo {
+                    left,
+                    percent,
+                    right,
+                } => self.binary(BINARY_MOD, left, *percent, right),
+                ArithmeticOperationExpression::Exponentiation { left, pow, right } => {
+                    self.binary(BINARY_POW, left, *pow, right)
+                }
+                ArithmeticOperationExpression::Negative { minus, right } => {
+                    self.unary(UNARY_MINUS, *minus, right)
+                }
+                ArithmeticOperationExpression::Positive { plus, right } => {
+                    self.unary(UNARY_PLUS, *plus, right)
+                }
+                ArithmeticOperationExpression::PreIncrement { increment, right } => {
+                    self.increment("AST_PRE_INC", *increment, right)
+                }
+                ArithmeticOperationExpression::PostIncrement { left, increment } => {
+                    self.increment("AST_POST_INC", *increment, left)
+                }
+                ArithmeticOperationExpression::PreDecrement { decrement, right } => {
+                    self.increment("AST_PRE_DEC", *decrement, right)
+                }
+                ArithmeticOperationExpression::PostDecrement { left, decrement } => {
+                    self.increment("AST_POST_DEC", *decrement, left)
+                }
+            },
+            Expression::AssignmentOperation(operation) => match operation {
+                AssignmentOperationExpression::Assign {
+                    left,
+                    equals,
+                    right,
+                } => {
+                    self.open();
+                    let var = self.assignable(left);
+                    self.span(*equals);
+
+                    // `$a = &$b` is an assignment by reference, rather than the assignment
+                    // of a reference.
+                    match right.as_ref() {
+                        Expression::Reference(reference) => {
+                            self.span(reference.ampersand);
+                            let expr = self.expression(&reference.right);
+
+                            self.close("AST_ASSIGN_REF", 0, json!({ "var": var, "expr": expr }))
+                        }
+                        right => {
+                            let expr = self.expression(right);
+
+                            self.close("AST_ASSIGN", 0, json!({ "var": var, "expr": expr }))
+                        }
+                    }
+                }
+                AssignmentOperationExpression::Addition {
+                    left,
+                    plus_equals,
+                    right,
+                } => self.assign(BINARY_ADD, left, *plus_equals, right),
+                AssignmentOperationExpression::Subtraction {
+                    left,
+                    minus_equals,
+                    right,
+                } => self.assign(BINARY_SUB, left, *minus_equals, right),
+                AssignmentOperationExpression::Multiplication {
+                    left,
+                    asterisk_equals,
+                    right,
+                } => self.assign(BINARY_MUL, left, *asterisk_equals, right),
+                AssignmentOperationExpression::Division {
+                    left,
+                    slash_equals,
+                    right,
+                } => self.assign(BINARY_DIV, left, *slash_equals, right),
+                AssignmentOperationExpression::Modulo {
+                    left,
+                    percent_equals,
+                    right,
+                } => self.assign(BINARY_MOD, left, *percent_equals, right),
+                AssignmentOperationExpression::Exponentiation {
+                    left,
+                    pow_equals,
+                    right,
+                } => self.assign(BINARY_POW, left, *pow_equals, right),
+                AssignmentOperationExpression::Concat {
+                    left,
+                    dot_equals,
+                    right,
+                } => self.assign(BINARY_CONCAT, left, *dot_equals, right),
+                AssignmentOperationExpression::BitwiseAnd {
+                    left,
+                    ampersand_equals,
+                    right,
+                } => self.assign(BINARY_BITWISE_AND, left, *ampersand_equals, right),
+                AssignmentOperationExpression::BitwiseOr {
+                    left,
+                    pipe_equals,
+                    right,
+                } => self.assign(BINARY_BITWISE_OR, left, *pipe_equals, right),
+                AssignmentOperationExpression::BitwiseXor {
+                    left,
+                    caret_equals,
+                    right,
+                } => self.assign(BINARY_BITWISE_XOR, left, *caret_equals, right),
+                AssignmentOperationExpression::LeftShift {
+                    left,
+                    left_shift_equals,
+                    right,
+                } => self.assign(BINARY_SHIFT_LEFT, left, *left_shift_equals, right),
+                AssignmentOperationExpression::RightShift {
+                    left,
+                    right_shift_equals,
+                    right,
+                } => self.assign(BINARY_SHIFT_RIGHT, left, *right_shift_equals, right),
+                AssignmentOperationExpression::Coalesce {
+                    left,
+                    coalesce_equals,
+                    right,
+                } => self.assign(BINARY_COALESCE, left, *coalesce_equals, right),
+            },
+            Expression::BitwiseOperation(operation) => match operation {
+                BitwiseOperationExpression::And { left, and, right } => {
+                    self.binary(BINARY_BITWISE_AND, left, *and, right)
+                }
+                BitwiseOperationExpression::Or { left, or, right } => {
+                    self.binary(BINARY_BITWISE_OR, left, *or, right)
+                }
+                BitwiseOperationExpression::Xor { left, xor, right } => {
+                    self.binary(BINARY_BITWISE_XOR, left, *xor, right)
+                }
+                BitwiseOperationExpression::LeftShift {
+                    left,
+                    left_shift,
+                    right,
+                } => self.binary(BINARY_SHIFT_LEFT, left, *left_shift, right),
+                BitwiseOperationExpression::RightShift {
+                    left,
+                    right_shift,
+                    right,
+                } => self.binary(BINARY_SHIFT_RIGHT, left, *right_shift, right),
+                BitwiseOperationExpression::Not { not, right } => {
+                    self.unary(UNARY_BITWISE_NOT, *not, right)
+                }
+            },
+            Expression::ComparisonOperation(operation) => match operation {
+                ComparisonOperationExpression::Equal {
+                    left,
+                    double_equals,
+                    right,
+                } => self.binary(BINARY_IS_EQUAL, left, *double_equals, right),
+                ComparisonOperationExpression::Identical {
+                    left,
+                    triple_equals,
+                    right,
+                } => self.binary(BINARY_IS_IDENTICAL, left, *triple_equals, right),
+                ComparisonOperationExpression::NotEqual {
+                    left,
+                    bang_equals,
+                    right,
+                } => self.binary(BINARY_IS_NOT_EQUAL, left, *bang_equals, right),
+                ComparisonOperationExpression::AngledNotEqual {
+                    left,
+                    angled_left_right,
+                    right,
+                } => self.binary(BINARY_IS_NOT_EQUAL, left, *angled_left_right, right),
+                ComparisonOperationExpression::NotIdentical {
+                    left,
+                    bang_double_equals,
+                    right,
+                } => self.binary(BINARY_IS_NOT_IDENTICAL, left, *bang_double_equals, right),
+                ComparisonOperationExpression::LessThan {
+                    left,
+                    less_than,
+                    right,
+                } => self.binary(BINARY_IS_SMALLER, left, *less_than, right),
+                ComparisonOperationExpression::GreaterThan {
+                    left,
+                    greater_than,
+                    right,
+                } => self.binary(BINARY_IS_GREATER, left, *greater_than, right),
+                ComparisonOperationExpression::LessThanOrEqual {
+                    left,
+                    less_than_equals,
+                    right,
+                } => self.binary(BINARY_IS_SMALLER_OR_EQUAL, left, *less_than_equals, right),
+                ComparisonOperationExpression::GreaterThanOrEqual {
+                    left,
+                    greater_than_equals,
+                    right,
+                } => self.binary(
+                    BINARY_IS_GREATER_OR_EQUAL,
+                    left,
+                    *greater_than_equals,
+                    right,
+                ),
+                ComparisonOperationExpression::Spaceship {
+                    left,
+                    spaceship,
+                    right,
+                } => self.binary(BINARY_SPACESHIP, left, *spaceship, right),
+            },
+            // `and` and `or` are the same operations as `&&` and `||`.
+            Expression::LogicalOperation(operation) => match operation {
+                LogicalOperationExpression::And {
+                    left,
+                    double_ampersand,
+                    right,
+                } => self.binary(BINARY_BOOL_AND, left, *double_ampersand, right),
+                LogicalOperationExpression::Or {
+                    left,
+                    double_pipe,
+                    right,
+                } => self.binary(BINARY_BOOL_OR, left, *double_pipe, right),
+                LogicalOperationExpression::Not { bang, right } => {
+                    self.unary(UNARY_BOOL_NOT, *bang, right)
+                }
+                LogicalOperationExpression::LogicalAnd { left, and, right } => {
+                    self.binary(BINARY_BOOL_AND, left, *and, right)
+                }
+                LogicalOperationExpression::LogicalOr { left, or, right } => {
+                    self.binary(BINARY_BOOL_OR, left, *or, right)
+                }
+                LogicalOperationExpression::LogicalXor { left, xor, right } => {
+                    self.binary(BINARY_BOOL_XOR, left, *xor, right)
+                }
+            },
+            Expression::Concat(expression) => self.binary(
+                BINARY_CONCAT,
+                &expression.left,
+                expression.dot,
+                &expression.right,
+            ),
+            Expression::Instanceof(expression) => {
+                self.open();
+                let expr = self.expression(&expression.left);
+                self.span(expression.instanceof);
+                let class = self.class_name(&expression.right);
+
+                self.close("AST_INSTANCEOF", 0, json!({ "expr": expr, "class": class }))
+            }
+            // references are only valid in assignments, arrays and parameters, which
+            // handle them themselves.
+            Expression::Reference(expression) => {
+                self.span(expression.ampersand);
+                self.expression(&expression.right)
+            }
+            Expression::Parenthesized(expression) => {
+                self.span(expression.start);
+                let expr = self.expression(&expression.expr);
+                self.span(expression.end);
+
+                expr
+            }
+            Expression::ErrorSuppress(expression) => {
+                self.unary(UNARY_SILENCE, expression.at, &expression.expr)
+            }
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                self.open();
+                let name = self.name(identifier);
+
+                self.close("AST_CONST", 0, json!({ "name": name }))
+            }
+            Expression::Identifier(identifier) => self.member_identifier(identifier),
+            Expression::Variable(variable) => self.variable(variable),
+            Expression::Include(expression) => {
+                self.include(expression.include, &expression.path, EXEC_INCLUDE)
+            }
+            Expression::IncludeOnce(expression) => {
+                self.include(expression.include_once, &expression.path, EXEC_INCLUDE_ONCE)
+            }
+            Expression::Require(expression) => {
+                self.include(expression.require, &expression.path, EXEC_REQUIRE)
+            }
+            Expression::RequireOnce(expression) => {
+                self.include(expression.require_once, &expression.path, EXEC_REQUIRE_ONCE)
+            }
+            Expression::FunctionCall(expression) => {
+                self.open();
+                let expr = self.class_name(&expression.target);
+                let args = self.arguments(&expression.arguments);
+
+                self.close("AST_CALL", 0, json!({ "expr": expr, "args": args }))
+            }
+            Expression::FunctionClosureCreation(expression) => {
+                self.open();
+                let expr = self.class_name(&expression.target);
+                let args = self.placeholder(&expression.placeholder);
+
+                self.close("AST_CALL", 0, json!({ "expr": expr, "args": args }))
+            }
+            Expression::MethodCall(expression) => {
+                self.open();
+                let expr = self.expression(&expression.target);
+                self.span(expression.arrow);
+                let method = self.member(&expression.method);
+                let args = self.arguments(&expression.arguments);
+
+                self.close(
+                    "AST_METHOD_CALL",
+                    0,
+                    json!({ "expr": expr, "method": method, "args": args }),
+                )
+            }
+            Expression::MethodClosureCreation(expression) => {
+                self.open();
+                let expr = self.expression(&expression.target);
+                self.span(expression.arrow);
+                let method = self.member(&expression.method);
+                let args = self.placeholder(&expression.placeholder);
+
+                self.close(
+                    "AST_METHOD_CALL",
+                    0,
+                    json!({ "expr": expr, "method": method, "args": args }),
+                )
+            }
+            Expression::NullsafeMethodCall(expression) => {
+                self.open();
+                let expr = self.expression(&expression.target);
+                self.span(expression.question_arrow);
+                let method = self.member(&expression.method);
+                let args = self.arguments(&expression.arguments);
+
+                self.close(
+                    "AST_NULLSAFE_METHOD_CALL",
+                    0,
+                    json!({ "expr": expr, "method": method, "args": args }),
+                )
+            }
+            Expression::StaticMethodCall(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let method = self.member_identifier(&expression.method);
+                let args = self.arguments(&expression.arguments);
+
+                self.close(
+                    "AST_STATIC_CALL",
+                    0,
+                    json!({ "class": class, "method": method, "args": args }),
+                )
+            }
+            Expression::StaticVariableMethodCall(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let method = self.variable(&expression.method);
+                let args = self.arguments(&expression.arguments);
+
+                self.close(
+                    "AST_STATIC_CALL",
+                    0,
+                    json!({ "class": class, "method": method, "args": args }),
+                )
+            }
+            Expression::StaticMethodClosureCreation(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let method = self.member_identifier(&expression.method);
+                let args = self.placeholder(&expression.placeholder);
+
+                self.close(
+                    "AST_STATIC_CALL",
+                    0,
+                    json!({ "class": class, "method": method, "args": args }),
+                )
+            }
+            Expression::StaticVariableMethodClosureCreation(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let method = self.variable(&expression.method);
+                let args = self.placeholder(&expression.placeholder);
+
+                self.close(
+                    "AST_STATIC_CALL",
+                    0,
+                    json!({ "class": class, "method": method, "args": args }),
+                )
+            }
+            Expression::PropertyFetch(expression) => {
+                self.open();
+                let expr = self.expression(&expression.target);
+                self.span(expression.arrow);
+                let prop = self.member(&expression.property);
+
+                self.close("AST_PROP", 0, json!({ "expr": expr, "prop": prop }))
+            }
+            Expression::NullsafePropertyFetch(expression) => {
+                self.open();
+                let expr = self.expression(&expression.target);
+                self.span(expression.question_arrow);
+                let prop = self.member(&expression.property);
+
+                self.close(
+                    "AST_NULLSAFE_PROP",
+                    0,
+                    json!({ "expr": expr, "prop": prop }),
+                )
+            }
+            Expression::StaticPropertyFetch(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+                let prop = self.static_property(&expression.property);
+
+                self.close(
+                    "AST_STATIC_PROP",
+                    0,
+                    json!({ "class": class, "prop": prop }),
+                )
+            }
+            Expression::ConstantFetch(expression) => {
+                self.open();
+                let class = self.class_name(&expression.target);
+                self.span(expression.double_colon);
+
+                // `Foo::class` is the name of the class, rather than a constant.
+                match &expression.constant {
+                    Identifier::SimpleIdentifier(identifier)
+                        if identifier.value.eq_ignore_ascii_case(b"class") =>
+                    {
+                        self.span(identifier.span);
+
+                        self.close("AST_CLASS_NAME", 0, json!({ "class": class }))
+                    }
+                    constant => {
+                        let constant = self.member_identifier(constant);
+
+                        self.close(
+                            "AST_CLASS_CONST",
+                            0,
+                            json!({ "class": class, "const": constant }),
+                        )
+                    }
+                }
+            }
+            Expression::Static(_) | Expression::Self_(_) | Expression::Parent(_) => {
+                self.class_name(expression)
+            }
+            Expression::ShortArray(expression) => {
+                self.open();
+                self.span(expression.start);
+                let items = self.array_items(&expression.items.inner, false);
+                self.span(expression.end);
+
+                self.close_list("AST_ARRAY", ARRAY_SYNTAX_SHORT, items)
+            }
+            Expression::Array(expression) => {
+                self.open();
+                self.span(expression.array);
+                let items = self.array_items(&expression.items.inner, false);
+                self.span(expression.end);
+
+                self.close_list("AST_ARRAY", ARRAY_SYNTAX_LONG, items)
+            }
+            Expression::List(_) => self.assignable(expression),
+            Expression::Closure(expression) => self.closure(expression),
+            Expression::ArrowFunction(expression) => self.arrow_function(expression),
+            Expression::New(expression) => {
+                self.open();
+                self.span(expression.new);
+                // the arguments of an anonymous class come before its body.
+                let (class, args) = match expression.target.as_ref() {
+                    Expression::AnonymousClass(class) => {
+                        let args = expression
+                            .arguments
+                            .as_ref()
+                            .map(|arguments| self.arguments(arguments));
+
+                        (self.anonymous_class(class), args)
+                    }
+                    target => {
+                        let class = self.class_name(target);
+                        let args = expression
+                            .arguments
+                            .as_ref()
+                            .map(|arguments| self.arguments(arguments));
+
+                        (class, args)
+                    }
+                };
+                let args = match args {
+                    Some(args) => args,
+                    None => {
+                        self.open();
+
+                        self.close_list("AST_ARG_LIST", 0, Vec::new())
+                    }
+                };
+
+                self.close("AST_NEW", 0, json!({ "class": class, "args": args }))
+            }
+            Expression::InterpolatedString(expression) => self.encaps_list(&expression.parts),
+            // heredocs without interpolation are plain strings.
+            Expression::Heredoc(expression) => {
+                if expression
+                    .parts
+                    .iter()
+                    .all(|part| matches!(part, StringPart::Literal(_)))
+                {
+                    return Value::from(
+                        expression
+                            .parts
+                            .iter()
+                            .map(|part| match part {
+                                StringPart::Literal(part) => text(&part.value),
+                                StringPart::Expression(_) => String::new(),
+                            })
+                            .collect::<String>(),
+                    );
+                }
+
+                self.encaps_list(&expression.parts)
+            }
+            Expression::Nowdoc(expression) => Value::from(text(&expression.value)),
+            Expression::ShellExec(expression) => {
+                self.open();
+                let expr = self.encaps_list(&expression.parts);
+
+                self.close("AST_SHELL_EXEC", 0, json!({ "expr": expr }))
+            }
+            Expression::AnonymousClass(class) => self.anonymous_class(class),
+            Expression::Bool(expression) => match expression.value {
+                true => self.constant(None, "true"),
+                false => self.constant(None, "false"),
+            },
+            Expression::Null(_) => self.constant(None, "null"),
+            Expression::ArrayIndex(expression) => {
+                self.open();
+                let expr = self.expression(&expression.array);
+                self.span(expression.left_bracket);
+                let dim = expression
+                    .index
+                    .as_ref()
+                    .map(|index| self.expression(index));
+                self.span(expression.right_bracket);
+
+                self.close("AST_DIM", 0, json!({ "expr": expr, "dim": dim }))
+            }
+            Expression::MagicConstant(constant) => {
+                let (span, flags) = match constant {
+                    MagicConstantExpression::Directory(span) => (span, tokens::T_DIR),
+                    MagicConstantExpression::File(span) => (span, tokens::T_FILE),
+                    MagicConstantExpression::Line(span) => (span, tokens::T_LINE),
+                    MagicConstantExpression::Class(span) => (span, tokens::T_CLASS_C),
+                    MagicConstantExpression::Function(span) => (span, tokens::T_FUNC_C),
+                    MagicConstantExpression::Method(span) => (span, tokens::T_METHOD_C),
+                    MagicConstantExpression::Namespace(span) => (span, tokens::T_NS_C),
+                    MagicConstantExpression::Trait(span) => (span, tokens::T_TRAIT_C),
+                    // `__COMPILER_HALT_OFFSET__` is a regular constant.
+                    MagicConstantExpression::CompilerHaltOffset(span) => {
+                        return self.constant(Some(*span), "__COMPILER_HALT_OFFSET__");
+                    }
+                };
+
+                self.open();
+                self.span(*span);
+
+                self.close("AST_MAGIC_CONST", flags as u64, json!({}))
+            }
+            Expression::ShortTernary(expression) => {
+                self.open();
+                let cond = self.expression(&expression.condition);
+                self.span(expression.question_colon);
+                let r#else = self.expression(&expression.r#else);
+
+                self.close(
+                    "AST_CONDITIONAL",
+                    0,
+                    json!({ "cond": cond, "true": null, "false": r#else }),
+                )
+            }
+            Expression::Ternary(expression) => {
+                self.open();
+                let cond = self.expression(&expression.condition);
+                self.span(expression.question);
+                let then = self.expression(&expression.then);
+                self.span(expression.colon);
+                let r#else = self.expression(&expression.r#else);
+
+                self.close(
+                    "AST_CONDITIONAL",
+                    0,
+                    json!({ "cond": cond, "true": then, "false": r#else }),
+                )
+            }
+            Expression::Coalesce(expression) => self.binary(
+                BINARY_COALESCE,
+                &expression.lhs,
+                expression.double_question,
+                &expression.rhs,
+            ),
+            Expression::Clone(expression) => {
+                self.open();
+                let expr = self.expression(&expression.target);
+
+                self.close("AST_CLONE", 0, json!({ "expr": expr }))
+            }
+            Expression::Match(expression) => self.r#match(expression),
+            Expression::Throw(expression) => {
+                self.open();
+                let expr = self.expression(&expression.value);
+
+                self.close("AST_THROW", 0, json!({ "expr": expr }))
+            }
+            Expression::Yield(expression) => {
+                self.open();
+                let key = expression.key.as_ref().map(|key| self.expression(key));
+                let value = expression
+                    .value
+                    .as_ref()
+                    .map(|value| self.expression(value));
+
+                self.close("AST_YIELD", 0, json!({ "value": value, "key": key }))
+            }
+            Expression::YieldFrom(expression) => {
+                self.open();
+                let expr = self.expression(&expression.value);
+
+                self.close("AST_YIELD_FROM", 0, json!({ "expr": expr }))
+            }
+            Expression::Cast(expression) => {
+                self.open();
+                self.span(expression.cast);
+                let expr = self.expression(&expression.value);
+                let flags = match expression.kind {
+                    CastKind::Int => TYPE_LONG,
+                    CastKind::Bool => TYPE_BOOL,
+                    CastKind::Float => TYPE_DOUBLE,
+                    CastKind::String => TYPE_STRING,
+                    CastKind::Array => TYPE_ARRAY,
+                    CastKind::Object => TYPE_OBJECT,
+                    CastKind::Unset => TYPE_NULL,
+                };
+
+                self.close("AST_CAST", flags, json!({ "expr": expr }))
+            }
+            Expression::Noop => Value::Null,
+        }
+    }
+
+    fn include(&mut self, keyword: Span, path: &Expression, flags: u64) -> Value {
+        self.open();
+        self.span(keyword);
+        let expr = self.expression(path);
+
+        self.close("AST_INCLUDE_OR_EVAL", flags, json!({ "expr": expr }))
+    }
+
+    fn closure(&mut self, expression: &ClosureExpression) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&expression.attributes);
+        let flags = expression
+            .r#static
+            .map_or(0, |span| self.flag(span, MODIFIER_STATIC));
+        self.span(expression.function);
+        let flags = flags | self.returns_ref(expression.ampersand);
+        let params = self.parameters(&expression.parameters);
+        let uses = expression.uses.as_ref().map(|uses| {
+            self.open();
+            self.span(uses.r#use);
+            let variables = uses
+                .variables
+                .inner
+                .iter()
+                .map(|variable| {
+                    self.open();
+                    let flags = variable
+                        .ampersand
+                        .map_or(0, |span| self.flag(span, CLOSURE_USE_REF));
+                    let name = self.variable_name(&variable.variable);
+
+                    self.close("AST_CLOSURE_VAR", flags, json!({ "name": name }))
+                })
+                .collect();
+            self.span(uses.right_parenthesis);
+
+            self.close_list("AST_CLOSURE_USES", 0, variables)
+        });
+        let return_type = self.return_type(&expression.return_type);
+        self.open();
+        self.span(expression.body.left_brace);
+        let statements = self.statements(&expression.body.statements);
+        self.span(expression.body.right_brace);
+        let stmts = self.close_list("AST_STMT_LIST", 0, statements);
+
+        let mut children = json!({
+            "name": "{closure}",
+            "docComment": doc_comment(&expression.comments),
+            "params": params,
+            "uses": uses,
+            "stmts": stmts,
+            "returnType": return_type,
+        });
+        self.since(Version::V80, &mut children, "attributes", attributes);
+
+        self.close_declaration("AST_CLOSURE", flags, children)
+    }
+
+    fn arrow_function(&mut self, expression: &ArrowFunctionExpression) -> Value {
+        self.open();
+        let attributes = self.attribute_groups(&expression.attributes);
+        let flags = expression
+            .r#static
+            .map_or(0, |span| self.flag(span, MODIFIER_STATIC));
+        self.span(expression.r#fn);
+        let flags = flags | self.returns_ref(expression.ampersand);
+        let params = self.parameters(&expression.parameters);
+        let return_type = self.return_type(&expression.return_type);
+        self.span(expression.double_arrow);
+        // the body of an arrow function is a return statement.
+        self.open();
+        let expr = self.expression(&expression.body);
+        let stmts = self.close("AST_RETURN", 0, json!({ "expr": expr }));
+
+        let mut children = json!({
+            "name": "{closure}",
+            "docComment": doc_comment(&expression.comments),
+            "params": params,
+            "stmts": stmts,
+            "returnType": return_type,
+        });
+        self.since(Version::V80, &mut children, "attributes", attributes);
+
+        self.close_declaration("AST_ARROW_FUNC", flags, children)
+    }
+
+    fn r#match(&mut self, expression: &MatchExpression) -> Value {
+        self.open();
+        self.span(expression.keyword);
+        let cond = self.expression(&expression.condition);
+        self.open();
+        self.span(expression.left_brace);
+
+        // the default arm is kept apart from the others, so it is put back in its place.
+        let mut default = expression.default.as_deref();
+        let mut arms = Vec::new();
+        for arm in &expression.arms {
+            if let Some(arm) =
+                default.filter(|default| default.keyword.position < arm.arrow.position)
+            {
+                arms.push(self.default_arm(arm));
+                default = None;
+            }
+
+            self.open();
+            self.open();
+            let conditions = arm
+                .conditions
+                .iter()
+                .map(|condition| self.expression(condition))
+                .collect();
+            let conditions = self.close_list("AST_EXPR_LIST", 0, conditions);
+            self.span(arm.arrow);
+            let expr = self.expression(&arm.body);
+
+            arms.push(self.close(
+                "AST_MATCH_ARM",
+                0,
+                json!({ "cond": conditions, "expr": expr }),
+            ));
+        }
+        if let Some(arm) = default {
+            arms.push(self.default_arm(arm));
+        }
+
+        self.span(expression.right_brace);
+        let stmts = self.close_list("AST_MATCH_ARM_LIST", 0, arms);
+
+        self.close("AST_MATCH", 0, json!({ "cond": cond, "stmts": stmts }))
+    }
+
+    fn default_arm(&mut self, arm: &DefaultMatchArm) -> Value {
+        self.open();
+        self.span(arm.keyword);
+        self.span(arm.double_arrow);
+        let expr = self.expression(&arm.body);
+
+        self.close("AST_MATCH_ARM", 0, json!({ "cond": null, "expr": expr }))
+    }
+}
+
+fn text(bytes: &ByteString) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn use_flags(kind: &UseKind) -> u64 {
+    match kind {
+        UseKind::Normal => USE_NORMAL,
+        UseKind::Function => USE_FUNCTION,
+        UseKind::Const => USE_CONST,
+    }
+}
+
+// the last document comment preceding a declaration.
+fn doc_comment(comments: &CommentGroup) -> Value {
+    comments
+        .iter()
+        .rev()
+        .find(|comment| comment.format == CommentFormat::Document)
+        .map_or(Value::Null, |comment| Value::from(text(&comment.content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(code: &str, version: Version) -> Value {
+        to_json(&crate::parse(code).unwrap(), version)
+    }
+
+    #[test]
+    fn test_class() {
+        let json = export(
+            "<?php\nfinal class A extends B {\n    const C = 1;\n    public static function &f(int ...$a): ?string {}\n}",
+            Version::default(),
+        );
+        let class = &json["children"][0];
+
+        assert_eq!(json["kind"], "AST_STMT_LIST");
+        assert_eq!(class["kind"], "AST_CLASS");
+        assert_eq!(class["flags"], CLASS_FINAL);
+        assert_eq!(class["lineno"], 2);
+        assert_eq!(class["endLineno"], 5);
+        assert_eq!(class["children"]["name"], "A");
+        assert_eq!(class["children"]["extends"]["kind"], "AST_NAME");
+        assert_eq!(class["children"]["extends"]["flags"], NAME_NOT_FQ);
+        assert_eq!(class["children"]["attributes"], Value::Null);
+
+        let constant = &class["children"]["stmts"]["children"][0];
+        assert_eq!(constant["kind"], "AST_CLASS_CONST_GROUP");
+        assert_eq!(
+            constant["children"]["const"]["kind"],
+            "AST_CLASS_CONST_DECL"
+        );
+        assert_eq!(
+            constant["children"]["const"]["children"][0]["children"]["value"],
+            1
+        );
+
+        let method = &class["children"]["stmts"]["children"][1];
+        assert_eq!(method["kind"], "AST_METHOD");
+        assert_eq!(
+            method["flags"],
+            MODIFIER_PUBLIC | MODIFIER_STATIC | FUNC_RETURNS_REF
+        );
+        assert_eq!(method["lineno"], 4);
+
+        let param = &method["children"]["params"]["children"][0];
+        assert_eq!(param["flags"], PARAM_VARIADIC);
+        assert_eq!(param["children"]["type"]["kind"], "AST_TYPE");
+        assert_eq!(param["children"]["type"]["flags"], TYPE_LONG);
+        assert_eq!(param["children"]["name"], "a");
+        assert_eq!(
+            method["children"]["returnType"]["kind"],
+            "AST_NULLABLE_TYPE"
+        );
+    }
+
+    #[test]
+    fn test_versions() {
+        let code = "<?php enum A: string { public const B = 'b'; }";
+
+        let json = export(code, Version::V70);
+        let class = &json["children"][0];
+        assert_eq!(class["flags"], CLASS_ENUM);
+        assert_eq!(class["children"].get("attributes"), None);
+        assert_eq!(class["children"].get("type"), None);
+        let constant = &class["children"]["stmts"]["children"][0];
+        assert_eq!(constant["kind"], "AST_CLASS_CONST_DECL");
+        assert_eq!(constant["flags"], MODIFIER_PUBLIC);
+
+        let json = export(code, Version::V85);
+        let class = &json["children"][0];
+        assert_eq!(class["children"]["type"]["flags"], TYPE_STRING);
+        let constant = &class["children"]["stmts"]["children"][0];
+        assert_eq!(constant["kind"], "AST_CLASS_CONST_GROUP");
+        assert_eq!(constant["flags"], MODIFIER_PUBLIC);
+        assert_eq!(constant["children"].get("type"), None);
+
+        assert_eq!("90".parse(), Ok(Version::V90));
+        assert!("60".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn test_expressions() {
+        let json = export(
+            "<?php\necho $a, \"b $c\";\n[$d, , $e] = f(TRUE, ...$g, x: 0x1F) ?? A::class;",
+            Version::default(),
+        );
+        let statements = &json["children"];
+
+        assert_eq!(statements[0]["kind"], "AST_ECHO");
+        assert_eq!(statements[0]["children"]["expr"]["kind"], "AST_VAR");
+        assert_eq!(statements[1]["children"]["expr"]["kind"], "AST_ENCAPS_LIST");
+        assert_eq!(statements[1]["children"]["expr"]["children"][0], "b ");
+
+        let assign = &statements[2];
+        assert_eq!(assign["kind"], "AST_ASSIGN");
+        assert_eq!(assign["lineno"], 3);
+        assert_eq!(assign["children"]["var"]["flags"], ARRAY_SYNTAX_SHORT);
+        assert_eq!(assign["children"]["var"]["children"][1], Value::Null);
+
+        let coalesce = &assign["children"]["expr"];
+        assert_eq!(coalesce["flags"], BINARY_COALESCE);
+        assert_eq!(coalesce["children"]["right"]["kind"], "AST_CLASS_NAME");
+
+        let args = &coalesce["children"]["left"]["children"]["args"]["children"];
+        assert_eq!(args[0]["kind"], "AST_CONST");
+        assert_eq!(args[0]["children"]["name"]["children"]["name"], "true");
+        assert_eq!(args[1]["kind"], "AST_UNPACK");
+        assert_eq!(args[2]["kind"], "AST_NAMED_ARG");
+        assert_eq!(args[2]["children"]["expr"], 31);
+    }
+}
@@ -0,0 +1,723 @@
+use serde::Serialize;
+
+use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+
+macro_rules! php_tokens {
+    ($($name:ident = $id:literal,)*) => {
+        $(pub const $name: u16 = $id;)*
+
+        const NAMES: &[(u16, &str)] = &[$(($id, stringify!($name)),)*];
+    };
+}
+
+// the ids of the `T_*` constants of PHP 8.1 to 8.3.
+php_tokens! {
+    T_LNUMBER = 260,
+    T_DNUMBER = 261,
+    T_STRING = 262,
+    T_NAME_FULLY_QUALIFIED = 263,
+    T_NAME_RELATIVE = 264,
+    T_NAME_QUALIFIED = 265,
+    T_VARIABLE = 266,
+    T_INLINE_HTML = 267,
+    T_ENCAPSED_AND_WHITESPACE = 268,
+    T_CONSTANT_ENCAPSED_STRING = 269,
+    T_STRING_VARNAME = 270,
+    T_NUM_STRING = 271,
+    T_INCLUDE = 272,
+    T_INCLUDE_ONCE = 273,
+    T_EVAL = 274,
+    T_REQUIRE = 275,
+    T_REQUIRE_ONCE = 276,
+    T_LOGICAL_OR = 277,
+    T_LOGICAL_XOR = 278,
+    T_LOGICAL_AND = 279,
+    T_PRINT = 280,
+    T_YIELD = 281,
+    T_YIELD_FROM = 282,
+    T_INSTANCEOF = 283,
+    T_NEW = 284,
+    T_CLONE = 285,
+    T_EXIT = 286,
+    T_IF = 287,
+    T_ELSEIF = 288,
+    T_ELSE = 289,
+    T_ENDIF = 290,
+    T_ECHO = 291,
+    T_DO = 292,
+    T_WHILE = 293,
+    T_ENDWHILE = 294,
+    T_FOR = 295,
+    T_ENDFOR = 296,
+    T_FOREACH = 297,
+    T_ENDFOREACH = 298,
+    T_DECLARE = 299,
+    T_ENDDECLARE = 300,
+    T_AS = 301,
+    T_SWITCH = 302,
+    T_ENDSWITCH = 303,
+    T_CASE = 304,
+    T_DEFAULT = 305,
+    T_MATCH = 306,
+    T_BREAK = 307,
+    T_CONTINUE = 308,
+    T_GOTO = 309,
+    T_FUNCTION = 310,
+    T_FN = 311,
+    T_CONST = 312,
+    T_RETURN = 313,
+    T_TRY = 314,
+    T_CATCH = 315,
+    T_FINALLY = 316,
+    T_THROW = 317,
+    T_USE = 318,
+    T_INSTEADOF = 319,
+    T_GLOBAL = 320,
+    T_STATIC = 321,
+    T_ABSTRACT = 322,
+    T_FINAL = 323,
+    T_PRIVATE = 324,
+    T_PROTECTED = 325,
+    T_PUBLIC = 326,
+    T_READONLY = 327,
+    T_VAR = 328,
+    T_UNSET = 329,
+    T_ISSET = 330,
+    T_EMPTY = 331,
+    T_HALT_COMPILER = 332,
+    T_CLASS = 333,
+    T_TRAIT = 334,
+    T_INTERFACE = 335,
+    T_ENUM = 336,
+    T_EXTENDS = 337,
+    T_IMPLEMENTS = 338,
+    T_NAMESPACE = 339,
+    T_LIST = 340,
+    T_ARRAY = 341,
+    T_CALLABLE = 342,
+    T_LINE = 343,
+    T_FILE = 344,
+    T_DIR = 345,
+    T_CLASS_C = 346,
+    T_TRAIT_C = 347,
+    T_METHOD_C = 348,
+    T_FUNC_C = 349,
+    T_NS_C = 350,
+    T_ATTRIBUTE = 351,
+    T_PLUS_EQUAL = 352,
+    T_MINUS_EQUAL = 353,
+    T_MUL_EQUAL = 354,
+    T_DIV_EQUAL = 355,
+    T_CONCAT_EQUAL = 356,
+    T_MOD_EQUAL = 357,
+    T_AND_EQUAL = 358,
+    T_OR_EQUAL = 359,
+    T_XOR_EQUAL = 360,
+    T_SL_EQUAL = 361,
+    T_SR_EQUAL = 362,
+    T_COALESCE_EQUAL = 363,
+    T_BOOLEAN_OR = 364,
+    T_BOOLEAN_AND = 365,
+    T_IS_EQUAL = 366,
+    T_IS_NOT_EQUAL = 367,
+    T_IS_IDENTICAL = 368,
+    T_IS_NOT_IDENTICAL = 369,
+    T_IS_SMALLER_OR_EQUAL = 370,
+    T_IS_GREATER_OR_EQUAL = 371,
+    T_SPACESHIP = 372,
+    T_SL = 373,
+    T_SR = 374,
+    T_INC = 375,
+    T_DEC = 376,
+    T_INT_CAST = 377,
+    T_DOUBLE_CAST = 378,
+    T_STRING_CAST = 379,
+    T_ARRAY_CAST = 380,
+    T_OBJECT_CAST = 381,
+    T_BOOL_CAST = 382,
+    T_UNSET_CAST = 383,
+    T_OBJECT_OPERATOR = 384,
+    T_NULLSAFE_OBJECT_OPERATOR = 385,
+    T_DOUBLE_ARROW = 386,
+    T_COMMENT = 387,
+    T_DOC_COMMENT = 388,
+    T_OPEN_TAG = 389,
+    T_OPEN_TAG_WITH_ECHO = 390,
+    T_CLOSE_TAG = 391,
+    T_WHITESPACE = 392,
+    T_START_HEREDOC = 393,
+    T_END_HEREDOC = 394,
+    T_DOLLAR_OPEN_CURLY_BRACES = 395,
+    T_CURLY_OPEN = 396,
+    T_PAAMAYIM_NEKUDOTAYIM = 397,
+    T_NS_SEPARATOR = 398,
+    T_ELLIPSIS = 399,
+    T_COALESCE = 400,
+    T_POW = 401,
+    T_POW_EQUAL = 402,
+    T_AMPERSAND_FOLLOWED_BY_VAR_OR_VARARG = 403,
+    T_AMPERSAND_NOT_FOLLOWED_BY_VAR_OR_VARARG = 404,
+    T_BAD_CHARACTER = 405,
+}
+
+/// The kind of a token, as reported by PHP's tokenizer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PhpTokenKind {
+    /// A token identified by one of the `T_*` constants, such as `T_STRING`.
+    Named(u16),
+    /// A single character token, such as `;`.
+    Char(char),
+}
+
+impl PhpTokenKind {
+    /// The name of the token, such as `T_STRING`, or `None` for single character tokens.
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            PhpTokenKind::Named(id) => token_name(*id),
+            PhpTokenKind::Char(_) => None,
+        }
+    }
+}
+
+/// A token as returned by PHP's `token_get_all()`, which serializes to either
+/// `[id, text, line]` or the character of single character tokens.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum PhpToken {
+    Named(u16, String, usize),
+    Char(char),
+}
+
+/// Get the name of the token with the given id, like PHP's `token_name()`.
+pub fn token_name(id: u16) -> Option<&'static str> {
+    NAMES
+        .binary_search_by_key(&id, |(id, _)| *id)
+        .ok()
+        .map(|index| NAMES[index].1)
+}
+
+/// Get the kind PHP gives to tokens of the given kind, or `None` for the end of file.
+///
+/// Some tokens depend on their surroundings, and are only told apart by `token_get_all()`:
+/// `&` is assumed not to be followed by a variable, qualified names not to be relative,
+/// integers not to overflow, and `?:`, which PHP reads as two tokens, is reported as `?`.
+pub fn php_kind(kind: &TokenKind) -> Option<PhpTokenKind> {
+    let id = match kind {
+        TokenKind::Eof => return None,
+        TokenKind::Backtick => return Some(PhpTokenKind::Char('`')),
+        TokenKind::DoubleQuote => return Some(PhpTokenKind::Char('"')),
+        TokenKind::Dollar => return Some(PhpTokenKind::Char('$')),
+        TokenKind::At => return Some(PhpTokenKind::Char('@')),
+        TokenKind::Asterisk => return Some(PhpTokenKind::Char('*')),
+        TokenKind::Bang => return Some(PhpTokenKind::Char('!')),
+        TokenKind::BitwiseNot => return Some(PhpTokenKind::Char('~')),
+        TokenKind::Caret => return Some(PhpTokenKind::Char('^')),
+        TokenKind::Colon => return Some(PhpTokenKind::Char(':')),
+        TokenKind::Comma => return Some(PhpTokenKind::Char(',')),
+        TokenKind::Dot => return Some(PhpTokenKind::Char('.')),
+        TokenKind::Equals => return Some(PhpTokenKind::Char('=')),
+        TokenKind::GreaterThan => return Some(PhpTokenKind::Char('>')),
+        TokenKind::LessThan => return Some(PhpTokenKind::Char('<')),
+        TokenKind::LeftBrace => return Some(PhpTokenKind::Char('{')),
+        TokenKind::LeftBracket => return Some(PhpTokenKind::Char('[')),
+        TokenKind::LeftParen => return Some(PhpTokenKind::Char('(')),
+        TokenKind::RightBrace => return Some(PhpTokenKind::Char('}')),
+        TokenKind::RightBracket => return Some(PhpTokenKind::Char(']')),
+        TokenKind::RightParen => return Some(PhpTokenKind::Char(')')),
+        TokenKind::Minus => return Some(PhpTokenKind::Char('-')),
+        TokenKind::Percent => return Some(PhpTokenKind::Char('%')),
+        TokenKind::Pipe => return Some(PhpTokenKind::Char('|')),
+        TokenKind::Plus => return Some(PhpTokenKind::Char('+')),
+        TokenKind::Question | TokenKind::QuestionColon => return Some(PhpTokenKind::Char('?')),
+        TokenKind::SemiColon => return Some(PhpTokenKind::Char(';')),
+        TokenKind::Slash => return Some(PhpTokenKind::Char('/')),
+        TokenKind::Die | TokenKind::Exit => T_EXIT,
+        TokenKind::Self_
+        | TokenKind::Parent
+        | TokenKind::From
+        | TokenKind::True
+        | TokenKind::False
+        | TokenKind::Null
+        | TokenKind::Identifier
+        | TokenKind::CompilerHaltOffsetConstant => T_STRING,
+        TokenKind::StartDocString(_) => T_START_HEREDOC,
+        TokenKind::EndDocString(..) => T_END_HEREDOC,
+        TokenKind::Print => T_PRINT,
+        TokenKind::HaltCompiler => T_HALT_COMPILER,
+        TokenKind::Readonly => T_READONLY,
+        TokenKind::Global => T_GLOBAL,
+        TokenKind::Abstract => T_ABSTRACT,
+        TokenKind::Ampersand => T_AMPERSAND_NOT_FOLLOWED_BY_VAR_OR_VARARG,
+        TokenKind::AmpersandEquals | TokenKind::AndEquals => T_AND_EQUAL,
+        TokenKind::And | TokenKind::BooleanAnd => T_BOOLEAN_AND,
+        TokenKind::BooleanOr => T_BOOLEAN_OR,
+        TokenKind::Array => T_ARRAY,
+        TokenKind::ArrayCast => T_ARRAY_CAST,
+        TokenKind::Arrow => T_OBJECT_OPERATOR,
+        TokenKind::QuestionArrow => T_NULLSAFE_OBJECT_OPERATOR,
+        TokenKind::As => T_AS,
+        TokenKind::Attribute => T_ATTRIBUTE,
+        TokenKind::BangEquals | TokenKind::AngledLeftRight => T_IS_NOT_EQUAL,
+        TokenKind::BangDoubleEquals => T_IS_NOT_IDENTICAL,
+        TokenKind::Spaceship => T_SPACESHIP,
+        TokenKind::BoolCast | TokenKind::BooleanCast => T_BOOL_CAST,
+        TokenKind::Break => T_BREAK,
+        TokenKind::Callable => T_CALLABLE,
+        TokenKind::CaretEquals => T_XOR_EQUAL,
+        TokenKind::Case => T_CASE,
+        TokenKind::Catch => T_CATCH,
+        TokenKind::Class => T_CLASS,
+        TokenKind::ClassConstant => T_CLASS_C,
+        TokenKind::TraitConstant => T_TRAIT_C,
+        TokenKind::FunctionConstant => T_FUNC_C,
+        TokenKind::MethodConstant => T_METHOD_C,
+        TokenKind::LineConstant => T_LINE,
+        TokenKind::FileConstant => T_FILE,
+        TokenKind::DirConstant => T_DIR,
+        TokenKind::NamespaceConstant => T_NS_C,
+        TokenKind::Clone => T_CLONE,
+        TokenKind::MinusEquals => T_MINUS_EQUAL,
+        TokenKind::CloseTag => T_CLOSE_TAG,
+        TokenKind::DoubleQuestion => T_COALESCE,
+        TokenKind::DoubleQuestionEquals => T_COALESCE_EQUAL,
+        TokenKind::AsteriskEquals => T_MUL_EQUAL,
+        TokenKind::SingleLineComment | TokenKind::HashMarkComment | TokenKind::MultiLineComment => {
+            T_COMMENT
+        }
+        TokenKind::DocumentComment => T_DOC_COMMENT,
+        TokenKind::Const => T_CONST,
+        TokenKind::LiteralSingleQuotedString | TokenKind::LiteralDoubleQuotedString => {
+            T_CONSTANT_ENCAPSED_STRING
+        }
+        TokenKind::Continue => T_CONTINUE,
+        TokenKind::CurlyOpen => T_CURLY_OPEN,
+        TokenKind::Declare => T_DECLARE,
+        TokenKind::Decrement => T_DEC,
+        TokenKind::Default => T_DEFAULT,
+        TokenKind::DivEquals | TokenKind::SlashEquals => T_DIV_EQUAL,
+        TokenKind::Do => T_DO,
+        TokenKind::DollarLeftBrace => T_DOLLAR_OPEN_CURLY_BRACES,
+        TokenKind::DotEquals => T_CONCAT_EQUAL,
+        TokenKind::DoubleArrow => T_DOUBLE_ARROW,
+        TokenKind::DoubleCast | TokenKind::RealCast | TokenKind::FloatCast => T_DOUBLE_CAST,
+        TokenKind::DoubleColon => T_PAAMAYIM_NEKUDOTAYIM,
+        TokenKind::DoubleEquals => T_IS_EQUAL,
+        TokenKind::Echo => T_ECHO,
+        TokenKind::Ellipsis => T_ELLIPSIS,
+        TokenKind::Else => T_ELSE,
+        TokenKind::ElseIf => T_ELSEIF,
+        TokenKind::Empty => T_EMPTY,
+        TokenKind::EndDeclare => T_ENDDECLARE,
+        TokenKind::EndFor => T_ENDFOR,
+        TokenKind::EndForeach => T_ENDFOREACH,
+        TokenKind::EndIf => T_ENDIF,
+        TokenKind::EndSwitch => T_ENDSWITCH,
+        TokenKind::EndWhile => T_ENDWHILE,
+        TokenKind::Enum => T_ENUM,
+        TokenKind::Error => T_BAD_CHARACTER,
+        TokenKind::Extends => T_EXTENDS,
+        TokenKind::Final => T_FINAL,
+        TokenKind::Finally => T_FINALLY,
+        TokenKind::LiteralFloat => T_DNUMBER,
+        TokenKind::Fn => T_FN,
+        TokenKind::For => T_FOR,
+        TokenKind::Foreach => T_FOREACH,
+        TokenKind::FullyQualifiedIdentifier => T_NAME_FULLY_QUALIFIED,
+        TokenKind::Function => T_FUNCTION,
+        TokenKind::Goto => T_GOTO,
+        TokenKind::GreaterThanEquals => T_IS_GREATER_OR_EQUAL,
+        TokenKind::If => T_IF,
+        TokenKind::Implements => T_IMPLEMENTS,
+        TokenKind::Include => T_INCLUDE,
+        TokenKind::IncludeOnce => T_INCLUDE_ONCE,
+        TokenKind::Increment => T_INC,
+        TokenKind::InlineHtml => T_INLINE_HTML,
+        TokenKind::Instanceof => T_INSTANCEOF,
+        TokenKind::Insteadof => T_INSTEADOF,
+        TokenKind::Eval => T_EVAL,
+        TokenKind::Unset => T_UNSET,
+        TokenKind::Isset => T_ISSET,
+        TokenKind::List => T_LIST,
+        TokenKind::LiteralInteger => T_LNUMBER,
+        TokenKind::IntCast | TokenKind::IntegerCast => T_INT_CAST,
+        TokenKind::Interface => T_INTERFACE,
+        TokenKind::LeftShift => T_SL,
+        TokenKind::LeftShiftEquals => T_SL_EQUAL,
+        TokenKind::RightShift => T_SR,
+        TokenKind::RightShiftEquals => T_SR_EQUAL,
+        TokenKind::LessThanEquals => T_IS_SMALLER_OR_EQUAL,
+        TokenKind::Match => T_MATCH,
+        TokenKind::Namespace => T_NAMESPACE,
+        TokenKind::NamespaceSeparator => T_NS_SEPARATOR,
+        TokenKind::New => T_NEW,
+        TokenKind::ObjectCast => T_OBJECT_CAST,
+        TokenKind::UnsetCast => T_UNSET_CAST,
+        TokenKind::OpenTag(OpenTagKind::Echo) => T_OPEN_TAG_WITH_ECHO,
+        TokenKind::OpenTag(_) => T_OPEN_TAG,
+        TokenKind::PercentEquals => T_MOD_EQUAL,
+        TokenKind::PipeEquals => T_OR_EQUAL,
+        TokenKind::PlusEquals => T_PLUS_EQUAL,
+        TokenKind::Pow => T_POW,
+        TokenKind::PowEquals => T_POW_EQUAL,
+        TokenKind::Private => T_PRIVATE,
+        TokenKind::Protected => T_PROTECTED,
+        TokenKind::Public => T_PUBLIC,
+        TokenKind::QualifiedIdentifier => T_NAME_QUALIFIED,
+        TokenKind::Require => T_REQUIRE,
+        TokenKind::RequireOnce => T_REQUIRE_ONCE,
+        TokenKind::Return => T_RETURN,
+        TokenKind::Static => T_STATIC,
+        TokenKind::StringCast | TokenKind::BinaryCast => T_STRING_CAST,
+        TokenKind::StringPart => T_ENCAPSED_AND_WHITESPACE,
+        TokenKind::Switch => T_SWITCH,
+        TokenKind::Throw => T_THROW,
+        TokenKind::Trait => T_TRAIT,
+        TokenKind::TripleEquals => T_IS_IDENTICAL,
+        TokenKind::Try => T_TRY,
+        TokenKind::Use => T_USE,
+        TokenKind::Var => T_VAR,
+        TokenKind::Variable => T_VARIABLE,
+        TokenKind::Yield => T_YIELD,
+        TokenKind::While => T_WHILE,
+        TokenKind::LogicalAnd => T_LOGICAL_AND,
+        TokenKind::LogicalOr => T_LOGICAL_OR,
+        TokenKind::LogicalXor => T_LOGICAL_XOR,
+    };
+
+    Some(PhpTokenKind::Named(id))
+}
+
+/// Tokenize the source the way PHP's `token_get_all()` does.
+///
+/// The lexer doesn't produce whitespace tokens, so `T_WHITESPACE` never appears in the
+/// output. The text of the tokens within interpolated strings and heredocs is the decoded
+/// value the lexer produces, rather than the source code.
+pub fn token_get_all(source: &[u8]) -> Vec<PhpToken> {
+    let (tokens, _) = Lexer::new().tokenize_with_errors(source);
+    let mut output = Vec::with_capacity(tokens.len());
+
+    // whether the tokens are within a string, and within how many braces of an
+    // interpolation such as `{$a}`.
+    let mut string = false;
+    let mut braces = 0;
+    // the length of the line break following a closing tag, which PHP makes part of it
+    // while the lexer makes it part of the following inline HTML.
+    let mut line_break = 0;
+
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        let line = token.span.line;
+        let text = if string && braces == 0 {
+            String::from_utf8_lossy(&token.value).into_owned()
+        } else {
+            String::from_utf8_lossy(super::token_text(source, &tokens, index)).into_owned()
+        };
+        let consumed = std::mem::take(&mut line_break);
+
+        let id = match &token.kind {
+            TokenKind::Eof => break,
+            TokenKind::StringPart => {
+                // the opening quote of an interpolated string is part of its first part.
+                if !string && source.get(token.span.position) == Some(&b'"') {
+                    output.push(PhpToken::Char('"'));
+                    string = true;
+                }
+
+                if !token.value.is_empty() {
+                    output.push(PhpToken::Named(
+                        T_ENCAPSED_AND_WHITESPACE,
+                        String::from_utf8_lossy(&token.value).into_owned(),
+                        line,
+                    ));
+                }
+
+                index += 1;
+                continue;
+            }
+            TokenKind::DoubleQuote | TokenKind::Backtick => {
+                string = !string;
+                braces = 0;
+                None
+            }
+            TokenKind::StartDocString(_) => {
+                string = true;
+                braces = 0;
+
+                // the opening label includes the line break following it.
+                let start = token.span.position;
+                let end = source[start..]
+                    .iter()
+                    .position(|byte| *byte == b'\n')
+                    .map_or(source.len(), |position| start + position + 1);
+                output.push(PhpToken::Named(
+                    T_START_HEREDOC,
+                    String::from_utf8_lossy(&source[start..end]).into_owned(),
+                    line,
+                ));
+
+                index += 1;
+                continue;
+            }
+            TokenKind::EndDocString(_, indentation) => {
+                string = false;
+
+                // the closing label includes its indentation.
+                let start = token.span.position.saturating_sub(*indentation);
+                let end = super::token_end(source, &tokens, index);
+                output.push(PhpToken::Named(
+                    T_END_HEREDOC,
+                    String::from_utf8_lossy(&source[start.min(end)..end]).into_owned(),
+                    line,
+                ));
+
+                index += 1;
+                continue;
+            }
+            TokenKind::LeftBrace if string && braces == 0 => {
+                braces = 1;
+                Some(T_CURLY_OPEN)
+            }
+            TokenKind::LeftBrace if braces > 0 => {
+                braces += 1;
+                None
+            }
+            TokenKind::RightBrace if braces > 0 => {
+                braces -= 1;
+                None
+            }
+            TokenKind::DollarLeftBrace => {
+                braces += 1;
+                None
+            }
+            // `a` in `"${a}"`.
+            TokenKind::Identifier
+                if braces > 0
+                    && index > 0
+                    && tokens[index - 1].kind == TokenKind::DollarLeftBrace
+                    && tokens.get(index + 1).map(|token| &token.kind)
+                        == Some(&TokenKind::RightBrace) =>
+            {
+                Some(T_STRING_VARNAME)
+            }
+            // `0` in `"$a[0]"`.
+            TokenKind::LiteralInteger if string && braces == 0 => Some(T_NUM_STRING),
+            TokenKind::LiteralInteger if integer_overflows(&text) => Some(T_DNUMBER),
+            TokenKind::QualifiedIdentifier
+                if text
+                    .get(..10)
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case("namespace\\")) =>
+            {
+                Some(T_NAME_RELATIVE)
+            }
+            TokenKind::Ampersand => {
+                let next = tokens[index + 1..].iter().find(|token| !is_comment(token));
+
+                match next.map(|token| &token.kind) {
+                    Some(TokenKind::Variable | TokenKind::Ellipsis) => {
+                        Some(T_AMPERSAND_FOLLOWED_BY_VAR_OR_VARARG)
+                    }
+                    _ => None,
+                }
+            }
+            TokenKind::Yield
+                if tokens.get(index + 1).map(|token| &token.kind) == Some(&TokenKind::From) =>
+            {
+                let end = super::token_end(source, &tokens, index + 1);
+                output.push(PhpToken::Named(
+                    T_YIELD_FROM,
+                    String::from_utf8_lossy(&source[token.span.position..end]).into_owned(),
+                    line,
+                ));
+
+                index += 2;
+                continue;
+            }
+            TokenKind::QuestionColon => {
+                output.push(PhpToken::Char('?'));
+                output.push(PhpToken::Char(':'));
+
+                index += 1;
+                continue;
+            }
+            TokenKind::OpenTag(OpenTagKind::Full) | TokenKind::CloseTag => {
+                // `<?php` includes the whitespace following it, and `?>` the line break.
+                let end = token.span.position + text.len();
+                let length = match source.get(end..).unwrap_or_default() {
+                    [b'\r', b'\n', ..] => 2,
+                    [b'\n' | b'\r', ..] => 1,
+                    [b' ' | b'\t', ..] if token.kind != TokenKind::CloseTag => 1,
+                    _ => 0,
+                };
+                if token.kind == TokenKind::CloseTag {
+                    line_break = length;
+                }
+
+                output.push(PhpToken::Named(
+                    if token.kind == TokenKind::CloseTag {
+                        T_CLOSE_TAG
+                    } else {
+                        T_OPEN_TAG
+                    },
+                    String::from_utf8_lossy(&source[token.span.position..end + length])
+                        .into_owned(),
+                    line,
+                ));
+
+                index += 1;
+                continue;
+            }
+            TokenKind::InlineHtml if consumed > 0 => {
+                if text.len() > consumed {
+                    output.push(PhpToken::Named(
+                        T_INLINE_HTML,
+                        text[consumed..].to_string(),
+                        line + 1,
+                    ));
+                }
+
+                index += 1;
+                continue;
+            }
+            _ => None,
+        };
+
+        let kind = match id {
+            Some(id) => PhpTokenKind::Named(id),
+            None => match php_kind(&token.kind) {
+                Some(kind) => kind,
+                None => break,
+            },
+        };
+
+        output.push(match kind {
+            PhpTokenKind::Named(id) => PhpToken::Named(id, text, line),
+            PhpTokenKind::Char(char) => PhpToken::Char(char),
+        });
+
+        index += 1;
+    }
+
+    output
+}
+
+fn is_comment(token: &Token) -> bool {
+    matches!(
+        token.kind,
+        TokenKind::SingleLineComment
+            | TokenKind::HashMarkComment
+            | TokenKind::MultiLineComment
+            | TokenKind::DocumentComment
+    )
+}
+
+// integer literals that don't fit in an integer are floats.
+fn integer_overflows(literal: &str) -> bool {
+    let digits = literal.replace('_', "").to_ascii_lowercase();
+    let (radix, digits) = if let Some(digits) = digits.strip_prefix("0x") {
+        (16, digits)
+    } else if let Some(digits) = digits.strip_prefix("0b") {
+        (2, digits)
+    } else if let Some(digits) = digits.strip_prefix("0o") {
+        (8, digits)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (8, &digits[1..])
+    } else {
+        (10, digits.as_str())
+    };
+
+    i64::from_str_radix(digits, radix).is_err()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(id: u16, text: &str, line: usize) -> PhpToken {
+        PhpToken::Named(id, text.to_string(), line)
+    }
+
+    #[test]
+    fn test_token_names() {
+        assert_eq!(token_name(T_STRING), Some("T_STRING"));
+        assert_eq!(token_name(T_BAD_CHARACTER), Some("T_BAD_CHARACTER"));
+        assert_eq!(token_name(0), None);
+
+        assert_eq!(
+            php_kind(&TokenKind::Identifier).and_then(|kind| kind.name()),
+            Some("T_STRING")
+        );
+        assert_eq!(
+            php_kind(&TokenKind::SemiColon),
+            Some(PhpTokenKind::Char(';'))
+        );
+        assert_eq!(php_kind(&TokenKind::Eof), None);
+    }
+
+    #[test]
+    fn test_token_get_all() {
+        assert_eq!(
+            token_get_all(b"<?php\n$a = &$b ?: 'c';\n?>\n<b>"),
+            vec![
+                named(T_OPEN_TAG, "<?php\n", 1),
+                named(T_VARIABLE, "$a", 2),
+                PhpToken::Char('='),
+                named(T_AMPERSAND_FOLLOWED_BY_VAR_OR_VARARG, "&", 2),
+                named(T_VARIABLE, "$b", 2),
+                PhpToken::Char('?'),
+                PhpToken::Char(':'),
+                named(T_CONSTANT_ENCAPSED_STRING, "'c'", 2),
+                PhpToken::Char(';'),
+                named(T_CLOSE_TAG, "?>\n", 3),
+                named(T_INLINE_HTML, "<b>", 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strings() {
+        assert_eq!(
+            token_get_all(b"<?php \"a $b[0] {$c}\"; <<<EOT\n  d\n  EOT;"),
+            vec![
+                named(T_OPEN_TAG, "<?php ", 1),
+                PhpToken::Char('"'),
+                named(T_ENCAPSED_AND_WHITESPACE, "a ", 1),
+                named(T_VARIABLE, "$b", 1),
+                PhpToken::Char('['),
+                named(T_NUM_STRING, "0", 1),
+                PhpToken::Char(']'),
+                named(T_ENCAPSED_AND_WHITESPACE, " ", 1),
+                named(T_CURLY_OPEN, "{", 1),
+                named(T_VARIABLE, "$c", 1),
+                PhpToken::Char('}'),
+                PhpToken::Char('"'),
+                PhpToken::Char(';'),
+                named(T_START_HEREDOC, "<<<EOT\n", 1),
+                named(T_ENCAPSED_AND_WHITESPACE, "  d", 2),
+                named(T_END_HEREDOC, "  EOT", 3),
+                PhpToken::Char(';'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(
+            token_get_all(b"<?php yield from namespace\\A(\\B\\C, 0x7FFFFFFFFFFFFFFFF);"),
+            vec![
+                named(T_OPEN_TAG, "<?php ", 1),
+                named(T_YIELD_FROM, "yield from", 1),
+                named(T_NAME_RELATIVE, "namespace\\A", 1),
+                PhpToken::Char('('),
+                named(T_NAME_FULLY_QUALIFIED, "\\B\\C", 1),
+                PhpToken::Char(','),
+                named(T_DNUMBER, "0x7FFFFFFFFFFFFFFFF", 1),
+                PhpToken::Char(')'),
+                PhpToken::Char(';'),
+            ]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseErrorEdit;
+use crate::parser::error::ParseErrorFix;
+use crate::parser::options::Options;
+
+/// Parsing stops at the first unrecoverable error, so fixing it might reveal
+/// more errors; this limits how many times a file is re-parsed.
+const MAX_PASSES: usize = 16;
+
+/// The result of fixing a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedSource {
+    /// The fixed source code.
+    pub source: Vec<u8>,
+    /// The number of fixes that have been applied.
+    pub applied: usize,
+    /// The errors that remain after applying the fixes.
+    pub errors: Vec<ParseError>,
+}
+
+/// Apply the first applicable fix of each of the given errors to `source`.
+///
+/// Only safe fixes are applied unless `unsafe_fixes` is `true`. A fix is skipped
+/// if any of its edits is out of bounds or overlaps an edit of a fix that has
+/// already been selected, so that the result never depends on the order of edits.
+///
+/// Returns the new source, along with the number of applied fixes.
+pub fn apply(source: &[u8], errors: &[ParseError], unsafe_fixes: bool) -> (Vec<u8>, usize) {
+    let mut selected: Vec<&ParseErrorFix> = vec![];
+
+    for error in errors {
+        let fix = error.fixes.iter().find(|fix| {
+            (fix.safe || unsafe_fixes)
+                && fix.edits.iter().all(|edit| {
+                    edit.position + edit.length <= source.len()
+                        && !selected
+                            .iter()
+                            .flat_map(|fix| &fix.edits)
+                            .any(|other| overlaps(edit, other))
+                })
+        });
+
+        if let Some(fix) = fix {
+            selected.push(fix);
+        }
+    }
+
+    let mut edits = selected
+        .iter()
+        .flat_map(|fix| &fix.edits)
+        .collect::<Vec<&ParseErrorEdit>>();
+
+    // apply the edits from the end of the file, so that positions remain valid.
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.position));
+
+    let mut fixed = source.to_vec();
+    for edit in edits {
+        fixed.splice(
+            edit.position..edit.position + edit.length,
+            edit.replacement.bytes(),
+        );
+    }
+
+    (fixed, selected.len())
+}
+
+/// Parse `source` using the given options, and apply fixes until it parses successfully,
+/// or no more fixes can be applied.
+pub fn fix(source: &[u8], unsafe_fixes: bool, options: &Options) -> FixedSource {
+    let mut source = source.to_vec();
+    let mut applied = 0;
+    let mut errors = vec![];
+
+    for _ in 0..MAX_PASSES {
+        errors = match crate::parse_with(&source, options) {
+            Ok(_) => vec![],
+            Err(stack) => stack.errors,
+        };
+
+        let (fixed, count) = apply(&source, &errors, unsafe_fixes);
+        if count == 0 {
+            break;
+        }
+
+        source = fixed;
+        applied += count;
+    }
+
+    FixedSource {
+        source,
+        applied,
+        errors,
+    }
+}
+
+fn overlaps(a: &ParseErrorEdit, b: &ParseErrorEdit) -> bool {
+    a.position == b.position
+        || (a.position < b.position + b.length && b.position < a.position + a.length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::token::Span;
+
+    fn fixed(source: &str, unsafe_fixes: bool) -> String {
+        String::from_utf8(fix(source.as_bytes(), unsafe_fixes, &Options::default()).source).unwrap()
+    }
+
+    #[test]
+    fn test_safe_fixes() {
+        assert_eq!(
+            fixed("<?php class A { public public function a() {} }", false),
+            "<?php class A { public function a() {} }"
+        );
+        assert_eq!(
+            fixed("<?php function a(): ?mixed {}", false),
+            "<?php function a(): mixed {}"
+        );
+        assert_eq!(
+            fixed("<?php class A { final private const B = 1; }", false),
+            "<?php class A { private const B = 1; }"
+        );
+        assert_eq!(
+            fixed("<?php class A { static static static $a; }", false),
+            "<?php class A { static $a; }"
+        );
+    }
+
+    #[test]
+    fn test_unsafe_fixes_are_opt_in() {
+        let source = "<?php abstract final class A {}";
+
+        assert_eq!(fixed(source, false), source);
+        assert_eq!(fixed(source, true), "<?php final class A {}");
+
+        assert_eq!(
+            fixed("<?php class A { public readonly int $a = 1, $b; }", true),
+            "<?php class A { public readonly int $a, $b; }"
+        );
+        assert_eq!(
+            fixed("<?php try { a(); }", true),
+            "<?php try { a(); } finally {}"
+        );
+    }
+
+    #[test]
+    fn test_fixes_of_validation_errors() {
+        let source = b"<?php function a(): void { return 1; }";
+        let options = Options {
+            validate: true,
+            ..Options::default()
+        };
+
+        assert_eq!(fix(source, true, &Options::default()).applied, 0);
+        assert_eq!(
+            fix(source, true, &options).source,
+            b"<?php function a(): void { return; }"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_edits_are_skipped() {
+        let error = ParseError::new("E007", "", Span::new(1, 1, 0))
+            .fix("a", vec![ParseErrorEdit::replace(0, 2, "a")])
+            .fix("b", vec![ParseErrorEdit::replace(1, 2, "b")]);
+
+        assert_eq!(
+            apply(b"xyz", &[error.clone(), error], false),
+            (b"az".to_vec(), 1)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use crate::lexer::byte_string::ByteString;
 use crate::lexer::token::Span;
 
 pub type SyntaxResult<T> = Result<T, SyntaxError>;
@@ -17,9 +18,39 @@ pub enum SyntaxError {
     InvalidDocIndentation(Span),
     InvalidDocBodyIndentationLevel(usize, Span),
     UnrecognisedToken(u8, Span),
+    /// A string, or a shell command, that was never closed: the delimiter, where it starts,
+    /// and where the end of file was reached.
+    UnterminatedString(u8, Span, Span),
+    /// A heredoc or a nowdoc that was never closed: its label, where it starts, and where
+    /// the end of file was reached.
+    UnterminatedDocString(ByteString, Span, Span),
+    UnterminatedComment(Span, Span),
+    /// An interpolation (`{$` or `${`) inside of a string that was never closed.
+    UnterminatedInterpolation(Span, Span),
 }
 
 impl SyntaxError {
+    /// The stable error code of this error, see [`crate::parser::codes`].
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::UnexpectedEndOfFile(_) => "E001",
+            Self::UnexpectedError(_) => "E052",
+            Self::UnexpectedCharacter(_, _) => "E053",
+            Self::InvalidHaltCompiler(_) => "E054",
+            Self::InvalidOctalEscape(_) => "E055",
+            Self::InvalidOctalLiteral(_) => "E056",
+            Self::InvalidUnicodeEscape(_) => "E057",
+            Self::UnpredictableState(_) => "E058",
+            Self::InvalidDocIndentation(_) => "E059",
+            Self::InvalidDocBodyIndentationLevel(_, _) => "E060",
+            Self::UnrecognisedToken(_, _) => "E061",
+            Self::UnterminatedString(_, _, _) => "E062",
+            Self::UnterminatedDocString(_, _, _) => "E063",
+            Self::UnterminatedComment(_, _) => "E064",
+            Self::UnterminatedInterpolation(_, _) => "E065",
+        }
+    }
+
     pub fn span(&self) -> Span {
         match self {
             Self::UnexpectedEndOfFile(span) => *span,
@@ -33,6 +64,10 @@ impl SyntaxError {
             Self::InvalidDocIndentation(span) => *span,
             Self::InvalidDocBodyIndentationLevel(_, span) => *span,
             Self::UnrecognisedToken(_, span) => *span,
+            Self::UnterminatedString(_, span, _) => *span,
+            Self::UnterminatedDocString(_, span, _) => *span,
+            Self::UnterminatedComment(span, _) => *span,
+            Self::UnterminatedInterpolation(span, _) => *span,
         }
     }
 }
@@ -97,7 +132,27 @@ impl Display for SyntaxError {
                 token,
                 span.line,
                 span.column
-            )
+            ),
+            Self::UnterminatedString(delimiter, span, _) => write!(
+                f,
+                "Syntax Error: unterminated string, starting with `{}` on line {} column {}",
+                *delimiter as char, span.line, span.column
+            ),
+            Self::UnterminatedDocString(label, span, _) => write!(
+                f,
+                "Syntax Error: unterminated heredoc `{}` starting on line {} column {}",
+                label, span.line, span.column
+            ),
+            Self::UnterminatedComment(span, _) => write!(
+                f,
+                "Syntax Error: unterminated comment starting on line {} column {}",
+                span.line, span.column
+            ),
+            Self::UnterminatedInterpolation(span, _) => write!(
+                f,
+                "Syntax Error: unterminated interpolation starting on line {} column {}",
+                span.line, span.column
+            ),
         }
     }
 }
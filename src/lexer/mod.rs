@@ -1,3 +1,6 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
 use crate::ident;
 use crate::ident_start;
 use crate::lexer::byte_string::ByteString;
@@ -6,9 +9,11 @@ use crate::lexer::error::SyntaxResult;
 use crate::lexer::state::source::Source;
 use crate::lexer::state::StackFrame;
 use crate::lexer::state::State;
+use crate::lexer::token::BorrowedToken;
 use crate::lexer::token::DocStringIndentationKind;
 use crate::lexer::token::DocStringKind;
 use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::Span;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 
@@ -29,186 +34,243 @@ impl Lexer {
         Self {}
     }
 
+    /// Tokenize the input, failing with the first error found.
     pub fn tokenize<B: ?Sized + AsRef<[u8]>>(&self, input: &B) -> SyntaxResult<Vec<Token>> {
-        let mut state = State::new(Source::new(input.as_ref()));
-        let mut tokens = Vec::new();
-
-        while !state.source.eof() {
-            match state.frame()? {
-                // The "Initial" state is used to parse inline HTML. It is essentially a catch-all
-                // state that will build up a single token buffer until it encounters an open tag
-                // of some description.
-                StackFrame::Initial => self.initial(&mut state, &mut tokens)?,
-                // The scripting state is entered when an open tag is encountered in the source code.
-                // This tells the lexer to start analysing characters at PHP tokens instead of inline HTML.
-                StackFrame::Scripting => {
-                    self.skip_whitespace(&mut state);
-
-                    // If we have consumed whitespace and then reached the end of the file, we should break.
-                    if state.source.eof() {
-                        break;
-                    }
+        let (tokens, mut errors) = self.tokenize_with_errors(input);
 
-                    tokens.push(self.scripting(&mut state)?);
-                }
-                // The "Halted" state is entered when the `__halt_compiler` token is encountered.
-                // In this state, all the text that follows is no longer parsed as PHP as is collected
-                // into a single "InlineHtml" token (kind of cheating, oh well).
-                StackFrame::Halted => {
-                    tokens.push(Token {
-                        kind: TokenKind::InlineHtml,
-                        span: state.source.span(),
-                        value: state.source.read_remaining().into(),
-                    });
-                    break;
-                }
-                // The double quote state is entered when inside a double-quoted string that
-                // contains variables.
-                StackFrame::DoubleQuote => self.double_quote(&mut state, &mut tokens)?,
-                // The shell exec state is entered when inside of a execution string (`).
-                StackFrame::ShellExec => self.shell_exec(&mut state, &mut tokens)?,
-                // The doc string state is entered when tokenizing heredocs and nowdocs.
-                StackFrame::DocString(kind, label, ..) => {
-                    let label = label.clone();
-
-                    match kind {
-                        DocStringKind::Heredoc => self.heredoc(&mut state, &mut tokens, label)?,
-                        DocStringKind::Nowdoc => self.nowdoc(&mut state, &mut tokens, label)?,
-                    }
-                }
-                // LookingForProperty is entered inside double quotes,
-                // backticks, or a heredoc, expecting a variable name.
-                // If one isn't found, it switches to scripting.
-                StackFrame::LookingForVarname => {
-                    if let Some(token) = self.looking_for_varname(&mut state)? {
-                        tokens.push(token);
-                    }
-                }
-                // LookingForProperty is entered inside double quotes,
-                // backticks, or a heredoc, expecting an arrow followed by a
-                // property name.
-                StackFrame::LookingForProperty => {
-                    tokens.push(self.looking_for_property(&mut state)?);
-                }
-                StackFrame::VarOffset => {
-                    if state.source.eof() {
-                        break;
-                    }
-
-                    tokens.push(self.var_offset(&mut state)?);
-                }
-            }
+        if errors.is_empty() {
+            Ok(tokens)
+        } else {
+            Err(errors.remove(0))
         }
+    }
 
-        tokens.push(Token {
-            kind: TokenKind::Eof,
-            span: state.source.span(),
-            value: ByteString::default(),
-        });
+    /// Tokenize the input, recording errors instead of stopping at the first one.
+    ///
+    /// Invalid input is replaced by `TokenKind::Error` tokens, and the errors are
+    /// returned in the order in which they appear in the input.
+    pub fn tokenize_with_errors<B: ?Sized + AsRef<[u8]>>(
+        &self,
+        input: &B,
+    ) -> (Vec<Token>, Vec<SyntaxError>) {
+        let mut tokens = self.tokens(input);
+        let collected = tokens.by_ref().collect();
 
-        Ok(tokens)
+        (collected, tokens.into_errors())
     }
 
-    fn skip_whitespace(&self, state: &mut State) {
-        while let Some(true) = state.source.current().map(|u: &u8| u.is_ascii_whitespace()) {
-            state.source.next();
+    /// Tokenize the input lazily, producing the tokens as they are iterated over rather
+    /// than all at once, and ending with a `TokenKind::Eof` token.
+    ///
+    /// Errors are recorded as in `tokenize_with_errors()`, and are available from the
+    /// iterator once it is exhausted.
+    pub fn tokens<'a, B: ?Sized + AsRef<[u8]>>(&self, input: &'a B) -> Tokens<'a> {
+        Tokens {
+            tokens: self.borrowed_tokens(input),
         }
     }
 
-    fn read_and_skip_whitespace(&self, state: &mut State) -> Vec<u8> {
-        let mut buffer = Vec::new();
-        while let Some(true) = state.source.current().map(|u: &u8| u.is_ascii_whitespace()) {
-            buffer.push(*state.source.current().unwrap());
-            state.source.next();
+    /// Tokenize the input lazily, as `tokens()` does, without copying the values of the
+    /// tokens out of the input where possible.
+    ///
+    /// Only the lexer borrows: the parser reads owned tokens, and the AST owns its text,
+    /// so `tokens()` and `tokenize()` copy the values of these tokens.
+    pub fn borrowed_tokens<'a, B: ?Sized + AsRef<[u8]>>(&self, input: &'a B) -> BorrowedTokens<'a> {
+        BorrowedTokens {
+            lexer: self.clone(),
+            state: State::new(Source::new(input.as_ref())),
+            pending: VecDeque::new(),
+            buffer: Vec::new(),
+            finished: false,
         }
-        buffer
     }
 
-    fn initial(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
-        let inline_span = state.source.span();
-        let mut buffer = Vec::new();
-        while let Some(char) = state.source.current() {
-            if state.source.at_case_insensitive(b"<?php", 5) {
-                let tag_span = state.source.span();
-
-                let tag = state.source.read_and_skip(5);
-                state.replace(StackFrame::Scripting);
-
-                if !buffer.is_empty() {
-                    tokens.push(Token {
-                        kind: TokenKind::InlineHtml,
-                        span: inline_span,
-                        value: buffer.into(),
-                    });
-                }
+    // Tokenize the next construct of the input, returning whether there is anything left
+    // to tokenize.
+    fn step<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<BorrowedToken<'a>>,
+    ) -> SyntaxResult<bool> {
+        if state.source.eof() {
+            return Ok(false);
+        }
 
-                tokens.push(Token {
-                    kind: TokenKind::OpenTag(OpenTagKind::Full),
-                    span: tag_span,
-                    value: tag.into(),
+        match state.frame()? {
+            // The "Initial" state is used to parse inline HTML. It is essentially a catch-all
+            // state that will build up a single token buffer until it encounters an open tag
+            // of some description.
+            StackFrame::Initial => self.initial(state, tokens)?,
+            // The scripting state is entered when an open tag is encountered in the source code.
+            // This tells the lexer to start analysing characters at PHP tokens instead of inline HTML.
+            StackFrame::Scripting | StackFrame::Interpolation(_) => {
+                self.skip_whitespace(state);
+
+                // If we have consumed whitespace and then reached the end of the file, we should stop.
+                if state.source.eof() {
+                    return Ok(false);
+                }
+
+                tokens.push(self.scripting(state)?);
+            }
+            // The "Halted" state is entered when the `__halt_compiler` token is encountered.
+            // In this state, all the text that follows is no longer parsed as PHP as is collected
+            // into a single "InlineHtml" token (kind of cheating, oh well).
+            StackFrame::Halted => {
+                tokens.push(BorrowedToken {
+                    kind: TokenKind::InlineHtml,
+                    span: state.source.span(),
+                    value: state.source.read_remaining().into(),
                 });
+                return Ok(false);
+            }
+            // The double quote state is entered when inside a double-quoted string that
+            // contains variables.
+            StackFrame::DoubleQuote(start) => {
+                let start = *start;
 
-                return Ok(());
-            } else if state.source.at_case_insensitive(b"<?=", 3) {
-                let tag_span = state.source.span();
+                self.double_quote(state, tokens, start)?
+            }
+            // The shell exec state is entered when inside of a execution string (`).
+            StackFrame::ShellExec(start) => {
+                let start = *start;
 
-                state.source.skip(3);
-                state.replace(StackFrame::Scripting);
+                self.shell_exec(state, tokens, start)?
+            }
+            // The doc string state is entered when tokenizing heredocs and nowdocs.
+            StackFrame::DocString(kind, label, _, _, start) => {
+                let label = label.clone();
+                let start = *start;
 
-                if !buffer.is_empty() {
-                    tokens.push(Token {
-                        kind: TokenKind::InlineHtml,
-                        span: inline_span,
-                        value: buffer.into(),
-                    });
+                match kind {
+                    DocStringKind::Heredoc => self.heredoc(state, tokens, label, start)?,
+                    DocStringKind::Nowdoc => self.nowdoc(state, tokens, label, start)?,
                 }
+            }
+            // LookingForProperty is entered inside double quotes,
+            // backticks, or a heredoc, expecting a variable name.
+            // If one isn't found, it switches to scripting.
+            StackFrame::LookingForVarname(start) => {
+                let start = *start;
 
-                tokens.push(Token {
-                    kind: TokenKind::OpenTag(OpenTagKind::Echo),
-                    span: tag_span,
-                    value: b"<?=".into(),
-                });
+                if let Some(token) = self.looking_for_varname(state, start)? {
+                    tokens.push(token);
+                }
+            }
+            // LookingForProperty is entered inside double quotes,
+            // backticks, or a heredoc, expecting an arrow followed by a
+            // property name.
+            StackFrame::LookingForProperty => {
+                tokens.push(self.looking_for_property(state)?);
+            }
+            StackFrame::VarOffset => {
+                if state.source.eof() {
+                    return Ok(false);
+                }
 
-                return Ok(());
-            } else if state.source.at_case_insensitive(b"<?", 2) {
-                let tag_span = state.source.span();
+                tokens.push(self.var_offset(state)?);
+            }
+        }
 
-                state.source.skip(2);
-                state.replace(StackFrame::Scripting);
+        Ok(true)
+    }
 
-                if !buffer.is_empty() {
-                    tokens.push(Token {
-                        kind: TokenKind::InlineHtml,
-                        span: inline_span,
-                        value: buffer.into(),
-                    });
+    // Strings that are left open at the end of the file are reported here, as the lexer
+    // stops as soon as it reaches the end of the file, whatever the state it is in.
+    fn check_unterminated(&self, state: &mut State) {
+        let end = state.source.span();
+
+        let mut interpolation = None;
+        for frame in state.stack.iter().rev() {
+            let error = match (frame, interpolation) {
+                (StackFrame::Interpolation(start) | StackFrame::LookingForVarname(start), None) => {
+                    interpolation = Some(*start);
+
+                    continue;
+                }
+                (
+                    StackFrame::DoubleQuote(_)
+                    | StackFrame::ShellExec(_)
+                    | StackFrame::DocString(..),
+                    Some(start),
+                ) => SyntaxError::UnterminatedInterpolation(start, end),
+                (StackFrame::DoubleQuote(start), None) => {
+                    SyntaxError::UnterminatedString(b'"', *start, end)
+                }
+                (StackFrame::ShellExec(start), None) => {
+                    SyntaxError::UnterminatedString(b'`', *start, end)
+                }
+                (StackFrame::DocString(_, label, _, _, start), None) => {
+                    SyntaxError::UnterminatedDocString(label.clone(), *start, end)
                 }
+                _ => continue,
+            };
 
-                tokens.push(Token {
-                    kind: TokenKind::OpenTag(OpenTagKind::Short),
-                    span: tag_span,
-                    value: b"<?".into(),
-                });
+            state.record(error);
 
-                return Ok(());
-            }
+            return;
+        }
+    }
 
+    fn skip_whitespace(&self, state: &mut State) {
+        while let Some(true) = state.source.current().map(|u: &u8| u.is_ascii_whitespace()) {
             state.source.next();
-            buffer.push(*char);
         }
+    }
 
-        tokens.push(Token {
+    fn initial<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<BorrowedToken<'a>>,
+    ) -> SyntaxResult<()> {
+        let inline_span = state.source.span();
+        while state.source.current().is_some() {
+            let (kind, length) = if state.source.at_case_insensitive(b"<?php", 5) {
+                (OpenTagKind::Full, 5)
+            } else if state.source.at_case_insensitive(b"<?=", 3) {
+                (OpenTagKind::Echo, 3)
+            } else if state.source.at_case_insensitive(b"<?", 2) {
+                (OpenTagKind::Short, 2)
+            } else {
+                state.source.next();
+
+                continue;
+            };
+
+            let html = state.source.since(inline_span.position);
+            if !html.is_empty() {
+                tokens.push(BorrowedToken {
+                    kind: TokenKind::InlineHtml,
+                    span: inline_span,
+                    value: html.into(),
+                });
+            }
+
+            let tag_span = state.source.span();
+            let tag = state.source.read_and_skip(length);
+            state.replace(StackFrame::Scripting);
+
+            tokens.push(BorrowedToken {
+                kind: TokenKind::OpenTag(kind),
+                span: tag_span,
+                value: tag.into(),
+            });
+
+            return Ok(());
+        }
+
+        tokens.push(BorrowedToken {
             kind: TokenKind::InlineHtml,
             span: inline_span,
-            value: buffer.into(),
+            value: state.source.since(inline_span.position).into(),
         });
 
         Ok(())
     }
 
-    fn scripting(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn scripting<'a>(&self, state: &mut State<'a>) -> SyntaxResult<BorrowedToken<'a>> {
         let span = state.source.span();
-        let (kind, value): (TokenKind, ByteString) = match state.source.read(3) {
+        let (kind, value): (TokenKind, Cow<'a, [u8]>) = match state.source.read(3) {
             [b'!', b'=', b'='] => {
                 state.source.skip(3);
 
@@ -232,7 +294,7 @@ impl Lexer {
             }
             [b'`', ..] => {
                 state.source.next();
-                state.replace(StackFrame::ShellExec);
+                state.replace(StackFrame::ShellExec(span));
                 (TokenKind::Backtick, b"`".into())
             }
             [b'@', ..] => {
@@ -294,19 +356,19 @@ impl Lexer {
             // Single quoted string.
             [b'\'', ..] => {
                 state.source.skip(1);
-                self.tokenize_single_quote_string(state)?
+                self.tokenize_single_quote_string(state, span)?
             }
             [b'b' | b'B', b'\'', ..] => {
                 state.source.skip(2);
-                self.tokenize_single_quote_string(state)?
+                self.tokenize_single_quote_string(state, span)?
             }
             [b'"', ..] => {
                 state.source.skip(1);
-                self.tokenize_double_quote_string(state)?
+                self.tokenize_double_quote_string(state, span)?
             }
             [b'b' | b'B', b'"', ..] => {
                 state.source.skip(2);
-                self.tokenize_double_quote_string(state)?
+                self.tokenize_double_quote_string(state, span)?
             }
             [b'$', ident_start!(), ..] => self.tokenize_variable(state),
             [b'$', ..] => {
@@ -327,25 +389,22 @@ impl Lexer {
                 state.source.next();
 
                 match self.scripting(state)? {
-                    Token {
+                    BorrowedToken {
                         kind: TokenKind::Identifier | TokenKind::QualifiedIdentifier,
-                        value,
                         ..
-                    } => {
-                        let mut bytes = value;
-                        bytes.insert(0, b'\\');
-
-                        (TokenKind::FullyQualifiedIdentifier, bytes)
-                    }
-                    Token {
+                    } => (
+                        TokenKind::FullyQualifiedIdentifier,
+                        state.source.since(span.position).into(),
+                    ),
+                    BorrowedToken {
                         kind: TokenKind::True,
                         ..
                     } => (TokenKind::FullyQualifiedIdentifier, b"\\true".into()),
-                    Token {
+                    BorrowedToken {
                         kind: TokenKind::False,
                         ..
                     } => (TokenKind::FullyQualifiedIdentifier, b"\\false".into()),
-                    Token {
+                    BorrowedToken {
                         kind: TokenKind::Null,
                         ..
                     } => (TokenKind::FullyQualifiedIdentifier, b"\\null".into()),
@@ -358,25 +417,29 @@ impl Lexer {
             }
             [b'/', b'*', ..] => {
                 state.source.next();
-                let mut buffer = vec![b'/'];
 
                 loop {
                     match state.source.read(2) {
                         [b'*', b'/'] => {
                             state.source.skip(2);
-                            buffer.extend_from_slice(b"*/");
                             break;
                         }
-                        &[t, ..] => {
+                        [_, ..] => {
                             state.source.next();
-                            buffer.push(t);
                         }
                         _ => {
+                            // PHP still treats the rest of the file as a comment.
+                            state.record(SyntaxError::UnterminatedComment(
+                                span,
+                                state.source.span(),
+                            ));
+
                             break;
                         }
                     }
                 }
 
+                let buffer = state.source.since(span.position);
                 if buffer.starts_with(b"/**") {
                     (TokenKind::DocumentComment, buffer.into())
                 } else {
@@ -388,25 +451,22 @@ impl Lexer {
                 (TokenKind::Attribute, b"#[".into())
             }
             [ch @ b'/', b'/', ..] | [ch @ b'#', ..] => {
-                let mut buffer = if *ch == b'/' {
+                if *ch == b'/' {
                     state.source.skip(2);
-                    b"//".to_vec()
                 } else {
                     state.source.next();
-                    b"#".to_vec()
-                };
+                }
 
                 while let Some(c) = state.source.current() {
-                    if *c == b'\n' {
-                        state.source.next();
+                    if *c == b'\n' || state.source.read(2) == [b'?', b'>'] {
                         break;
                     }
 
-                    if state.source.read(2) == [b'?', b'>'] {
-                        break;
-                    }
+                    state.source.next();
+                }
 
-                    buffer.push(*c);
+                let buffer = state.source.since(span.position);
+                if let Some(b'\n') = state.source.current() {
                     state.source.next();
                 }
 
@@ -443,72 +503,76 @@ impl Lexer {
             }
             [b'<', b'<', b'<'] => {
                 state.source.skip(3);
-                let mut buffer = b"<<<".to_vec();
-                buffer.extend(self.read_and_skip_whitespace(state));
+                self.skip_whitespace(state);
 
                 let doc_string_kind = match state.source.read(1) {
                     [b'\''] => {
-                        buffer.push(b'\'');
                         state.source.next();
                         DocStringKind::Nowdoc
                     }
                     [b'"'] => {
-                        buffer.push(b'"');
                         state.source.next();
                         DocStringKind::Heredoc
                     }
                     [_, ..] => DocStringKind::Heredoc,
                     [] => {
-                        return Err(SyntaxError::UnexpectedEndOfFile(state.source.span()));
+                        state.record(SyntaxError::UnexpectedEndOfFile(state.source.span()));
+
+                        return Ok(BorrowedToken {
+                            kind: TokenKind::Error,
+                            span,
+                            value: state.source.since(span.position).into(),
+                        });
                     }
                 };
 
                 let label: ByteString = match self.peek_identifier(state) {
                     Some(_) => self.consume_identifier(state).into(),
                     None => {
-                        return match state.source.current() {
-                            Some(c) => {
-                                Err(SyntaxError::UnexpectedCharacter(*c, state.source.span()))
-                            }
-                            None => Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
-                        }
+                        self.unexpected_character(state);
+
+                        return Ok(BorrowedToken {
+                            kind: TokenKind::Error,
+                            span,
+                            value: state.source.since(span.position).into(),
+                        });
                     }
                 };
 
-                buffer.extend_from_slice(&label);
-
                 if doc_string_kind == DocStringKind::Nowdoc {
-                    match state.source.current() {
-                        Some(b'\'') => {
-                            buffer.push(b'\'');
-                            state.source.next();
-                        }
-                        _ => {
-                            // TODO(azjezz) this is most likely a bug, what if current is none?
-                            return Err(SyntaxError::UnexpectedCharacter(
-                                *state.source.current().unwrap(),
-                                state.source.span(),
-                            ));
-                        }
-                    };
+                    if let Some(b'\'') = state.source.current() {
+                        state.source.next();
+                    } else {
+                        self.unexpected_character(state);
+
+                        return Ok(BorrowedToken {
+                            kind: TokenKind::Error,
+                            span,
+                            value: state.source.since(span.position).into(),
+                        });
+                    }
                 } else if let Some(b'"') = state.source.current() {
-                    buffer.push(b'"');
                     state.source.next();
                 }
 
                 if !matches!(state.source.current(), Some(b'\n')) {
-                    return Err(SyntaxError::UnexpectedCharacter(
-                        *state.source.current().unwrap(),
-                        state.source.span(),
-                    ));
+                    self.unexpected_character(state);
+
+                    return Ok(BorrowedToken {
+                        kind: TokenKind::Error,
+                        span,
+                        value: state.source.since(span.position).into(),
+                    });
                 }
 
+                let buffer = state.source.since(span.position);
                 state.source.next();
                 state.replace(StackFrame::DocString(
-                    doc_string_kind.clone(),
-                    label.clone(),
+                    doc_string_kind,
+                    label,
                     DocStringIndentationKind::None,
                     0,
+                    span,
                 ));
 
                 (TokenKind::StartDocString(doc_string_kind), buffer.into())
@@ -557,133 +621,192 @@ impl Lexer {
             }
             [b'(', ..] => {
                 state.source.next();
-                let mut buffer = b"(".to_vec();
-
-                // Inlined so we can add whitespace to the buffer.
-                while let Some(true) = state.source.current().map(|u: &u8| u.is_ascii_whitespace())
-                {
-                    buffer.push(*state.source.current().unwrap());
-                    state.source.next();
-                }
+                self.skip_whitespace(state);
 
                 if state.source.at_case_insensitive(b"int", 3) {
                     if state.source.at_case_insensitive(b"integer", 7)
                         && state.source.peek_ignoring_whitespace(7, 1) == [b')']
                     {
-                        buffer.extend(state.source.read_and_skip(7));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::IntegerCast, buffer.into())
+                        state.source.skip(7);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::IntegerCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else if state.source.peek_ignoring_whitespace(3, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(3));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
+                        state.source.skip(3);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
 
-                        (TokenKind::IntCast, buffer.into())
+                        (TokenKind::IntCast, state.source.since(span.position).into())
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"bool", 4) {
                     if state.source.at_case_insensitive(b"boolean", 7)
                         && state.source.peek_ignoring_whitespace(7, 1) == [b')']
                     {
-                        buffer.extend(state.source.read_and_skip(7));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::BooleanCast, buffer.into())
+                        state.source.skip(7);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::BooleanCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else if state.source.peek_ignoring_whitespace(4, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(4));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::BoolCast, buffer.into())
+                        state.source.skip(4);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::BoolCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"float", 5) {
                     if state.source.peek_ignoring_whitespace(5, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(5));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::FloatCast, buffer.into())
+                        state.source.skip(5);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::FloatCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"double", 6) {
                     if state.source.peek_ignoring_whitespace(6, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(6));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::DoubleCast, buffer.into())
+                        state.source.skip(6);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::DoubleCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"real", 4) {
                     if state.source.peek_ignoring_whitespace(4, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(4));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::RealCast, buffer.into())
+                        state.source.skip(4);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::RealCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"string", 6) {
                     if state.source.peek_ignoring_whitespace(6, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(6));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::StringCast, buffer.into())
+                        state.source.skip(6);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::StringCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"binary", 6) {
                     if state.source.peek_ignoring_whitespace(6, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(6));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::BinaryCast, buffer.into())
+                        state.source.skip(6);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::BinaryCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"array", 5) {
                     if state.source.peek_ignoring_whitespace(5, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(5));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::ArrayCast, buffer.into())
+                        state.source.skip(5);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::ArrayCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"object", 6) {
                     if state.source.peek_ignoring_whitespace(6, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(6));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::ObjectCast, buffer.into())
+                        state.source.skip(6);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::ObjectCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else if state.source.at_case_insensitive(b"unset", 5) {
                     if state.source.peek_ignoring_whitespace(5, 1) == [b')'] {
-                        buffer.extend(state.source.read_and_skip(5));
-                        buffer.extend(self.read_and_skip_whitespace(state));
-                        buffer.extend(state.source.read_and_skip(1));
-
-                        (TokenKind::UnsetCast, buffer.into())
+                        state.source.skip(5);
+                        self.skip_whitespace(state);
+                        state.source.skip(1);
+
+                        (
+                            TokenKind::UnsetCast,
+                            state.source.since(span.position).into(),
+                        )
                     } else {
-                        (TokenKind::LeftParen, buffer.into())
+                        (
+                            TokenKind::LeftParen,
+                            state.source.since(span.position).into(),
+                        )
                     }
                 } else {
-                    (TokenKind::LeftParen, buffer.into())
+                    (
+                        TokenKind::LeftParen,
+                        state.source.since(span.position).into(),
+                    )
                 }
             }
             [b')', ..] => {
@@ -782,15 +905,13 @@ impl Lexer {
                 state.source.next();
                 (TokenKind::BitwiseNot, b"~".into())
             }
-            [b @ ident_start!(), ..] => {
+            [ident_start!(), ..] => {
                 state.source.next();
                 let mut qualified = false;
                 let mut last_was_slash = false;
 
-                let mut buffer = vec![*b];
                 while let Some(next @ ident!() | next @ b'\\') = state.source.current() {
                     if matches!(next, ident!()) {
-                        buffer.push(*next);
                         state.source.next();
                         last_was_slash = false;
                         continue;
@@ -799,7 +920,6 @@ impl Lexer {
                     if *next == b'\\' && !last_was_slash {
                         qualified = true;
                         last_was_slash = true;
-                        buffer.push(*next);
                         state.source.next();
                         continue;
                     }
@@ -807,10 +927,11 @@ impl Lexer {
                     break;
                 }
 
+                let buffer = state.source.since(span.position);
                 if qualified {
                     (TokenKind::QualifiedIdentifier, buffer.into())
                 } else {
-                    let kind = identifier_to_keyword(&buffer).unwrap_or(TokenKind::Identifier);
+                    let kind = identifier_to_keyword(buffer).unwrap_or(TokenKind::Identifier);
 
                     if kind == TokenKind::HaltCompiler {
                         match state.source.read(3) {
@@ -818,47 +939,56 @@ impl Lexer {
                                 state.source.skip(3);
                                 state.replace(StackFrame::Halted);
                             }
-                            _ => return Err(SyntaxError::InvalidHaltCompiler(state.source.span())),
+                            _ => {
+                                state.record(SyntaxError::InvalidHaltCompiler(state.source.span()))
+                            }
                         }
                     }
 
                     (kind, buffer.into())
                 }
             }
-            [b, ..] => unimplemented!(
-                "<scripting> char: {}, line: {}, col: {}",
-                *b as char,
-                state.source.span().line,
-                state.source.span().column
-            ),
+            &[b, ..] => {
+                state.record(SyntaxError::UnexpectedCharacter(b, span));
+                state.source.next();
+
+                (TokenKind::Error, state.source.since(span.position).into())
+            }
             // We should never reach this point since we have the empty checks surrounding
             // the call to this function, but it's better to be safe than sorry.
             [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
         };
 
-        Ok(Token { kind, span, value })
+        Ok(BorrowedToken { kind, span, value })
     }
 
-    fn double_quote(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
+    fn double_quote<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<BorrowedToken<'a>>,
+        start: Span,
+    ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer = Vec::new();
         let (kind, value) = loop {
             match state.source.read(3) {
                 [b'$', b'{', ..] => {
+                    let interpolation = state.source.span();
                     state.source.skip(2);
-                    state.enter(StackFrame::LookingForVarname);
+                    state.enter(StackFrame::LookingForVarname(interpolation));
                     break (TokenKind::DollarLeftBrace, b"${".into());
                 }
                 [b'{', b'$', ..] => {
                     // Intentionally only consume the left brace.
+                    let interpolation = state.source.span();
                     state.source.next();
-                    state.enter(StackFrame::Scripting);
+                    state.enter(StackFrame::Interpolation(interpolation));
                     break (TokenKind::LeftBrace, b"{".into());
                 }
                 [b'"', ..] => {
                     state.source.next();
                     state.replace(StackFrame::Scripting);
-                    break (TokenKind::DoubleQuote, b'"'.into());
+                    break (TokenKind::DoubleQuote, b"\"".into());
                 }
                 &[b'\\', b @ (b'"' | b'\\' | b'$'), ..] => {
                     state.source.skip(2);
@@ -903,6 +1033,7 @@ impl Lexer {
                     buffer.push(b);
                 }
                 &[b'\\', b'u', b'{'] => {
+                    let escape = state.source.span();
                     state.source.skip(3);
 
                     let mut code_point = String::new();
@@ -913,26 +1044,26 @@ impl Lexer {
                         code_point.push(*b as char);
                     }
 
-                    if code_point.is_empty() || state.source.current() != Some(&b'}') {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                    let closed = state.source.current() == Some(&b'}');
+                    if closed {
+                        state.source.next();
                     }
-                    state.source.next();
 
-                    let c = if let Ok(c) = u32::from_str_radix(&code_point, 16) {
-                        c
-                    } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
-                    };
+                    let c = u32::from_str_radix(&code_point, 16)
+                        .ok()
+                        .filter(|_| closed)
+                        .and_then(char::from_u32);
 
-                    if let Some(c) = char::from_u32(c) {
+                    if let Some(c) = c {
                         let mut tmp = [0; 4];
                         let bytes = c.encode_utf8(&mut tmp);
                         buffer.extend(bytes.as_bytes());
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        state.record(SyntaxError::InvalidUnicodeEscape(escape));
                     }
                 }
                 &[b'\\', b @ b'0'..=b'7', ..] => {
+                    let escape = state.source.span();
                     state.source.skip(2);
 
                     let mut octal = String::from(b as char);
@@ -948,12 +1079,11 @@ impl Lexer {
                     if let Ok(b) = u8::from_str_radix(&octal, 8) {
                         buffer.push(b);
                     } else {
-                        return Err(SyntaxError::InvalidOctalEscape(state.source.span()));
+                        state.record(SyntaxError::InvalidOctalEscape(escape));
                     }
                 }
                 [b'$', ident_start!(), ..] => {
-                    let mut var = state.source.read_and_skip(1).to_vec();
-                    var.extend(self.consume_identifier(state));
+                    let (_, var) = self.tokenize_variable(state);
 
                     match state.source.read(4) {
                         [b'[', ..] => state.enter(StackFrame::VarOffset),
@@ -963,42 +1093,60 @@ impl Lexer {
                         _ => {}
                     }
 
-                    break (TokenKind::Variable, var.into());
+                    break (TokenKind::Variable, var);
                 }
                 &[b, ..] => {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    state.record(SyntaxError::UnterminatedString(
+                        b'"',
+                        start,
+                        state.source.span(),
+                    ));
+                    state.replace(StackFrame::Scripting);
+
+                    break (TokenKind::Error, Cow::default());
+                }
             }
         };
 
         if !buffer.is_empty() {
-            tokens.push(Token {
+            tokens.push(BorrowedToken {
                 kind: TokenKind::StringPart,
                 span,
                 value: buffer.into(),
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        // the token ending the string part starts where the part ends.
+        let span = state.source.span_before(value.len());
+        tokens.push(BorrowedToken { kind, span, value });
         Ok(())
     }
 
-    fn shell_exec(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
+    fn shell_exec<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<BorrowedToken<'a>>,
+        start: Span,
+    ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer = Vec::new();
         let (kind, value) = loop {
             match state.source.read(2) {
                 [b'$', b'{'] => {
+                    let interpolation = state.source.span();
                     state.source.skip(2);
-                    state.enter(StackFrame::LookingForVarname);
+                    state.enter(StackFrame::LookingForVarname(interpolation));
                     break (TokenKind::DollarLeftBrace, b"${".into());
                 }
                 [b'{', b'$'] => {
                     // Intentionally only consume the left brace.
+                    let interpolation = state.source.span();
                     state.source.next();
-                    state.enter(StackFrame::Scripting);
+                    state.enter(StackFrame::Interpolation(interpolation));
                     break (TokenKind::LeftBrace, b"{".into());
                 }
                 [b'`', ..] => {
@@ -1007,8 +1155,7 @@ impl Lexer {
                     break (TokenKind::Backtick, b"`".into());
                 }
                 [b'$', ident_start!()] => {
-                    let mut var = state.source.read_and_skip(1).to_vec();
-                    var.extend(self.consume_identifier(state));
+                    let (_, var) = self.tokenize_variable(state);
 
                     match state.source.read(4) {
                         [b'[', ..] => state.enter(StackFrame::VarOffset),
@@ -1018,34 +1165,46 @@ impl Lexer {
                         _ => {}
                     }
 
-                    break (TokenKind::Variable, var.into());
+                    break (TokenKind::Variable, var);
                 }
                 &[b, ..] => {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    state.record(SyntaxError::UnterminatedString(
+                        b'`',
+                        start,
+                        state.source.span(),
+                    ));
+                    state.replace(StackFrame::Scripting);
+
+                    break (TokenKind::Error, Cow::default());
+                }
             }
         };
 
         if !buffer.is_empty() {
-            tokens.push(Token {
+            tokens.push(BorrowedToken {
                 kind: TokenKind::StringPart,
                 span,
                 value: buffer.into(),
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        // the token ending the string part starts where the part ends.
+        let span = state.source.span_before(value.len());
+        tokens.push(BorrowedToken { kind, span, value });
 
         Ok(())
     }
 
-    fn heredoc(
+    fn heredoc<'a>(
         &self,
-        state: &mut State,
-        tokens: &mut Vec<Token>,
+        state: &mut State<'a>,
+        tokens: &mut Vec<BorrowedToken<'a>>,
         label: ByteString,
+        start: Span,
     ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer: Vec<u8> = Vec::new();
@@ -1053,14 +1212,16 @@ impl Lexer {
         let (kind, value) = loop {
             match state.source.read(3) {
                 [b'$', b'{', ..] => {
+                    let interpolation = state.source.span();
                     state.source.skip(2);
-                    state.enter(StackFrame::LookingForVarname);
+                    state.enter(StackFrame::LookingForVarname(interpolation));
                     break (TokenKind::DollarLeftBrace, b"${".into());
                 }
                 [b'{', b'$', ..] => {
                     // Intentionally only consume the left brace.
+                    let interpolation = state.source.span();
                     state.source.next();
-                    state.enter(StackFrame::Scripting);
+                    state.enter(StackFrame::Interpolation(interpolation));
                     break (TokenKind::LeftBrace, b"{".into());
                 }
                 &[b'\\', b @ (b'"' | b'\\' | b'$'), ..] => {
@@ -1106,6 +1267,7 @@ impl Lexer {
                     buffer.push(b);
                 }
                 &[b'\\', b'u', b'{'] => {
+                    let escape = state.source.span();
                     state.source.skip(3);
 
                     let mut code_point = String::new();
@@ -1116,26 +1278,26 @@ impl Lexer {
                         code_point.push(*b as char);
                     }
 
-                    if code_point.is_empty() || state.source.current() != Some(&b'}') {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                    let closed = state.source.current() == Some(&b'}');
+                    if closed {
+                        state.source.next();
                     }
-                    state.source.next();
 
-                    let c = if let Ok(c) = u32::from_str_radix(&code_point, 16) {
-                        c
-                    } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
-                    };
+                    let c = u32::from_str_radix(&code_point, 16)
+                        .ok()
+                        .filter(|_| closed)
+                        .and_then(char::from_u32);
 
-                    if let Some(c) = char::from_u32(c) {
+                    if let Some(c) = c {
                         let mut tmp = [0; 4];
                         let bytes = c.encode_utf8(&mut tmp);
                         buffer.extend(bytes.as_bytes());
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        state.record(SyntaxError::InvalidUnicodeEscape(escape));
                     }
                 }
                 &[b'\\', b @ b'0'..=b'7', ..] => {
+                    let escape = state.source.span();
                     state.source.skip(2);
 
                     let mut octal = String::from(b as char);
@@ -1151,12 +1313,11 @@ impl Lexer {
                     if let Ok(b) = u8::from_str_radix(&octal, 8) {
                         buffer.push(b);
                     } else {
-                        return Err(SyntaxError::InvalidOctalEscape(state.source.span()));
+                        state.record(SyntaxError::InvalidOctalEscape(escape));
                     }
                 }
                 [b'$', ident_start!(), ..] => {
-                    let mut var = state.source.read_and_skip(1).to_vec();
-                    var.extend(self.consume_identifier(state));
+                    let (_, var) = self.tokenize_variable(state);
 
                     match state.source.read(4) {
                         [b'[', ..] => state.enter(StackFrame::VarOffset),
@@ -1166,7 +1327,7 @@ impl Lexer {
                         _ => {}
                     }
 
-                    break (TokenKind::Variable, var.into());
+                    break (TokenKind::Variable, var);
                 }
                 // If we find a new-line, we can start to check if we can see the EndHeredoc token.
                 [b'\n', ..] => {
@@ -1175,11 +1336,11 @@ impl Lexer {
 
                     // Check if we can see the closing label right here.
                     if state.source.at(&label, label.len()) {
-                        state.source.skip(label.len());
+                        let label = state.source.read_and_skip(label.len());
                         state.replace(StackFrame::Scripting);
                         break (
                             TokenKind::EndDocString(DocStringIndentationKind::None, 0),
-                            label,
+                            label.into(),
                         );
                     }
 
@@ -1221,17 +1382,17 @@ impl Lexer {
                         if whitespace_kind != DocStringIndentationKind::None
                             && !extra_whitespace_buffer.is_empty()
                         {
-                            return Err(SyntaxError::InvalidDocIndentation(state.source.span()));
+                            state.record(SyntaxError::InvalidDocIndentation(state.source.span()));
                         }
 
                         // If we get here, only 1 type of indentation was found. We can move
                         // the process along by reading over the label and breaking out
                         // with the EndHeredoc token, storing the kind and amount of whitespace.
-                        state.source.skip(label.len());
+                        let label = state.source.read_and_skip(label.len());
                         state.replace(StackFrame::Scripting);
                         break (
                             TokenKind::EndDocString(whitespace_kind, whitespace_amount),
-                            label,
+                            label.into(),
                         );
                     } else {
                         // We didn't find the label. The buffer still needs to know about
@@ -1251,7 +1412,16 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    state.record(SyntaxError::UnterminatedDocString(
+                        label,
+                        start,
+                        state.source.span(),
+                    ));
+                    state.replace(StackFrame::Scripting);
+
+                    break (TokenKind::Error, Cow::default());
+                }
             }
         };
 
@@ -1261,23 +1431,26 @@ impl Lexer {
         }
 
         if !buffer.is_empty() {
-            tokens.push(Token {
+            tokens.push(BorrowedToken {
                 kind: TokenKind::StringPart,
                 span,
                 value: buffer.into(),
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        // the token ending the string part starts where the part ends.
+        let span = state.source.span_before(value.len());
+        tokens.push(BorrowedToken { kind, span, value });
 
         Ok(())
     }
 
-    fn nowdoc(
+    fn nowdoc<'a>(
         &self,
-        state: &mut State,
-        tokens: &mut Vec<Token>,
+        state: &mut State<'a>,
+        tokens: &mut Vec<BorrowedToken<'a>>,
         label: ByteString,
+        start: Span,
     ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer: Vec<u8> = Vec::new();
@@ -1291,11 +1464,11 @@ impl Lexer {
 
                     // Check if we can see the closing label right here.
                     if state.source.at(&label, label.len()) {
-                        state.source.skip(label.len());
+                        let label = state.source.read_and_skip(label.len());
                         state.replace(StackFrame::Scripting);
                         break (
                             TokenKind::EndDocString(DocStringIndentationKind::None, 0),
-                            label,
+                            label.into(),
                         );
                     }
 
@@ -1337,17 +1510,17 @@ impl Lexer {
                         if whitespace_kind != DocStringIndentationKind::None
                             && !extra_whitespace_buffer.is_empty()
                         {
-                            return Err(SyntaxError::InvalidDocIndentation(state.source.span()));
+                            state.record(SyntaxError::InvalidDocIndentation(state.source.span()));
                         }
 
                         // If we get here, only 1 type of indentation was found. We can move
                         // the process along by reading over the label and breaking out
                         // with the EndHeredoc token, storing the kind and amount of whitespace.
-                        state.source.skip(label.len());
+                        let label = state.source.read_and_skip(label.len());
                         state.replace(StackFrame::Scripting);
                         break (
                             TokenKind::EndDocString(whitespace_kind, whitespace_amount),
-                            label,
+                            label.into(),
                         );
                     } else {
                         // We didn't find the label. The buffer still needs to know about
@@ -1367,7 +1540,16 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    state.record(SyntaxError::UnterminatedDocString(
+                        label,
+                        start,
+                        state.source.span(),
+                    ));
+                    state.replace(StackFrame::Scripting);
+
+                    break (TokenKind::Error, Cow::default());
+                }
             }
         };
 
@@ -1377,19 +1559,25 @@ impl Lexer {
         }
 
         if !buffer.is_empty() {
-            tokens.push(Token {
+            tokens.push(BorrowedToken {
                 kind: TokenKind::StringPart,
                 span,
                 value: buffer.into(),
             })
         }
 
-        tokens.push(Token { kind, span, value });
+        // the token ending the string part starts where the part ends.
+        let span = state.source.span_before(value.len());
+        tokens.push(BorrowedToken { kind, span, value });
 
         Ok(())
     }
 
-    fn looking_for_varname(&self, state: &mut State) -> SyntaxResult<Option<Token>> {
+    fn looking_for_varname<'a>(
+        &self,
+        state: &mut State<'a>,
+        start: Span,
+    ) -> SyntaxResult<Option<BorrowedToken<'a>>> {
         let identifier = self.peek_identifier(state);
 
         if let Some(ident) = identifier {
@@ -1397,8 +1585,8 @@ impl Lexer {
                 let ident = ident.to_vec();
                 let span = state.source.span();
                 state.source.skip(ident.len());
-                state.replace(StackFrame::Scripting);
-                return Ok(Some(Token {
+                state.replace(StackFrame::Interpolation(start));
+                return Ok(Some(BorrowedToken {
                     kind: TokenKind::Identifier,
                     span,
                     value: ident.into(),
@@ -1406,12 +1594,12 @@ impl Lexer {
             }
         }
 
-        state.replace(StackFrame::Scripting);
+        state.replace(StackFrame::Interpolation(start));
 
         Ok(None)
     }
 
-    fn looking_for_property(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn looking_for_property<'a>(&self, state: &mut State<'a>) -> SyntaxResult<BorrowedToken<'a>> {
         let span = state.source.span();
         let (kind, value) = match state.source.read(3) {
             [b'?', b'-', b'>'] => {
@@ -1431,10 +1619,10 @@ impl Lexer {
             _ => unreachable!(),
         };
 
-        Ok(Token { kind, span, value })
+        Ok(BorrowedToken { kind, span, value })
     }
 
-    fn var_offset(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn var_offset<'a>(&self, state: &mut State<'a>) -> SyntaxResult<BorrowedToken<'a>> {
         let span = state.source.span();
         let (kind, value) = match state.source.read(2) {
             [b'$', ident_start!()] => self.tokenize_variable(state),
@@ -1461,43 +1649,71 @@ impl Lexer {
                 let label = self.consume_identifier(state);
                 (TokenKind::Identifier, label.into())
             }
-            &[b, ..] => return Err(SyntaxError::UnrecognisedToken(b, state.source.span())),
+            &[b, ..] => {
+                // Leave the offset, and let the rest of the string be tokenized as usual.
+                state.record(SyntaxError::UnrecognisedToken(b, span));
+                state.source.next();
+                state.exit();
+
+                (TokenKind::Error, state.source.since(span.position).into())
+            }
             [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
         };
-        Ok(Token { kind, span, value })
+        Ok(BorrowedToken { kind, span, value })
     }
 
-    fn tokenize_single_quote_string(
+    fn tokenize_single_quote_string<'a>(
         &self,
-        state: &mut State,
-    ) -> SyntaxResult<(TokenKind, ByteString)> {
-        let mut buffer = vec![];
-
-        loop {
+        state: &mut State<'a>,
+        start: Span,
+    ) -> SyntaxResult<(TokenKind, Cow<'a, [u8]>)> {
+        let content = state.source.span().position;
+        // the content is only copied once an escape sequence is found in it.
+        let mut buffer: Option<Vec<u8>> = None;
+
+        let value = loop {
             match state.source.read(2) {
                 [b'\'', ..] => {
+                    let value = match buffer {
+                        Some(buffer) => buffer.into(),
+                        None => state.source.since(content).into(),
+                    };
+
                     state.source.next();
-                    break;
+                    break value;
                 }
                 &[b'\\', b @ b'\'' | b @ b'\\'] => {
+                    buffer
+                        .get_or_insert_with(|| state.source.since(content).to_vec())
+                        .push(b);
                     state.source.skip(2);
-                    buffer.push(b);
                 }
                 &[b, ..] => {
+                    if let Some(buffer) = &mut buffer {
+                        buffer.push(b);
+                    }
                     state.source.next();
-                    buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    state.record(SyntaxError::UnterminatedString(
+                        b'\'',
+                        start,
+                        state.source.span(),
+                    ));
+
+                    return Ok((TokenKind::Error, state.source.since(start.position).into()));
+                }
             }
-        }
+        };
 
-        Ok((TokenKind::LiteralSingleQuotedString, buffer.into()))
+        Ok((TokenKind::LiteralSingleQuotedString, value))
     }
 
-    fn tokenize_double_quote_string(
+    fn tokenize_double_quote_string<'a>(
         &self,
-        state: &mut State,
-    ) -> SyntaxResult<(TokenKind, ByteString)> {
+        state: &mut State<'a>,
+        start: Span,
+    ) -> SyntaxResult<(TokenKind, Cow<'a, [u8]>)> {
         let mut buffer = vec![];
 
         let constant = loop {
@@ -1549,6 +1765,7 @@ impl Lexer {
                     buffer.push(b);
                 }
                 &[b'\\', b'u', b'{'] => {
+                    let escape = state.source.span();
                     state.source.skip(3);
 
                     let mut code_point = String::new();
@@ -1559,26 +1776,26 @@ impl Lexer {
                         code_point.push(*b as char);
                     }
 
-                    if code_point.is_empty() || state.source.current() != Some(&b'}') {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                    let closed = state.source.current() == Some(&b'}');
+                    if closed {
+                        state.source.next();
                     }
-                    state.source.next();
 
-                    let c = if let Ok(c) = u32::from_str_radix(&code_point, 16) {
-                        c
-                    } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
-                    };
+                    let c = u32::from_str_radix(&code_point, 16)
+                        .ok()
+                        .filter(|_| closed)
+                        .and_then(char::from_u32);
 
-                    if let Some(c) = char::from_u32(c) {
+                    if let Some(c) = c {
                         let mut tmp = [0; 4];
                         let bytes = c.encode_utf8(&mut tmp);
                         buffer.extend(bytes.as_bytes());
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        state.record(SyntaxError::InvalidUnicodeEscape(escape));
                     }
                 }
                 &[b'\\', b @ b'0'..=b'7', ..] => {
+                    let escape = state.source.span();
                     state.source.skip(2);
 
                     let mut octal = String::from(b as char);
@@ -1595,7 +1812,7 @@ impl Lexer {
                     if let Ok(b) = u8::from_str_radix(&octal, 8) {
                         buffer.push(b);
                     } else {
-                        return Err(SyntaxError::InvalidOctalEscape(state.source.span()));
+                        state.record(SyntaxError::InvalidOctalEscape(escape));
                     }
                 }
                 [b'$', ident_start!(), ..] | [b'{', b'$', ..] | [b'$', b'{', ..] => {
@@ -1605,19 +1822,37 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    state.record(SyntaxError::UnterminatedString(
+                        b'"',
+                        start,
+                        state.source.span(),
+                    ));
+
+                    return Ok((TokenKind::Error, state.source.since(start.position).into()));
+                }
             }
         };
 
         Ok(if constant {
             (TokenKind::LiteralDoubleQuotedString, buffer.into())
         } else {
-            state.replace(StackFrame::DoubleQuote);
+            state.replace(StackFrame::DoubleQuote(start));
             (TokenKind::StringPart, buffer.into())
         })
     }
 
-    fn peek_identifier<'a>(&'a self, state: &'a State) -> Option<&'a [u8]> {
+    // Record an unexpected character, or an unexpected end of file, at the current position.
+    fn unexpected_character(&self, state: &mut State) {
+        let error = match state.source.current() {
+            Some(c) => SyntaxError::UnexpectedCharacter(*c, state.source.span()),
+            None => SyntaxError::UnexpectedEndOfFile(state.source.span()),
+        };
+
+        state.record(error);
+    }
+
+    fn peek_identifier<'a>(&self, state: &State<'a>) -> Option<&'a [u8]> {
         let mut size = 0;
 
         if let [ident_start!()] = state.source.read(1) {
@@ -1632,20 +1867,24 @@ impl Lexer {
         }
     }
 
-    fn consume_identifier(&self, state: &mut State) -> Vec<u8> {
-        let ident = self.peek_identifier(state).unwrap().to_vec();
+    fn consume_identifier<'a>(&self, state: &mut State<'a>) -> &'a [u8] {
+        let ident = self.peek_identifier(state).unwrap();
         state.source.skip(ident.len());
 
         ident
     }
 
-    fn tokenize_variable(&self, state: &mut State) -> (TokenKind, ByteString) {
-        let mut var = state.source.read_and_skip(1).to_vec();
-        var.extend(self.consume_identifier(state));
-        (TokenKind::Variable, var.into())
+    fn tokenize_variable<'a>(&self, state: &mut State<'a>) -> (TokenKind, Cow<'a, [u8]>) {
+        let start = state.source.span().position;
+        state.source.next();
+        self.consume_identifier(state);
+        (TokenKind::Variable, state.source.since(start).into())
     }
 
-    fn tokenize_number(&self, state: &mut State) -> SyntaxResult<(TokenKind, ByteString)> {
+    fn tokenize_number<'a>(
+        &self,
+        state: &mut State<'a>,
+    ) -> SyntaxResult<(TokenKind, Cow<'a, [u8]>)> {
         let mut buffer = Vec::new();
 
         let (base, kind) = match state.source.read(2) {
@@ -1675,7 +1914,7 @@ impl Lexer {
         if kind != NumberKind::Float {
             self.read_digits(state, &mut buffer, base);
             if kind == NumberKind::Int {
-                return parse_int(&buffer);
+                return parse_int(buffer);
             }
         }
 
@@ -1686,7 +1925,7 @@ impl Lexer {
         );
 
         if !is_float {
-            return parse_int(&buffer);
+            return parse_int(buffer);
         }
 
         if let Some(b'.') = state.source.current() {
@@ -1753,7 +1992,7 @@ impl Lexer {
 
 // Parses an integer literal in the given base and converts errors to SyntaxError.
 // It returns a float token instead on overflow.
-fn parse_int(buffer: &[u8]) -> SyntaxResult<(TokenKind, ByteString)> {
+fn parse_int<'a>(buffer: Vec<u8>) -> SyntaxResult<(TokenKind, Cow<'a, [u8]>)> {
     Ok((TokenKind::LiteralInteger, buffer.into()))
 }
 
@@ -1849,6 +2088,100 @@ fn identifier_to_keyword(ident: &[u8]) -> Option<TokenKind> {
     })
 }
 
+/// The tokens of an input, produced lazily by `Lexer::tokens()`.
+///
+/// Only the tokens of the construct being tokenized are held in memory, along with the
+/// stack of states the lexer is in.
+#[derive(Debug)]
+pub struct Tokens<'a> {
+    tokens: BorrowedTokens<'a>,
+}
+
+impl<'a> Tokens<'a> {
+    /// The errors found so far, which are all of the errors of the input, in the order in
+    /// which they appear in it, once the iterator is exhausted.
+    pub fn errors(&self) -> &[SyntaxError] {
+        self.tokens.errors()
+    }
+
+    pub fn into_errors(self) -> Vec<SyntaxError> {
+        self.tokens.into_errors()
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token;
+
+    // the tokens are read by the borrowing lexer, and their values copied.
+    fn next(&mut self) -> Option<Token> {
+        self.tokens.next().map(BorrowedToken::into_owned)
+    }
+}
+
+/// The tokens of an input, borrowing their values from it, produced lazily by
+/// `Lexer::borrowed_tokens()`.
+#[derive(Debug)]
+pub struct BorrowedTokens<'a> {
+    lexer: Lexer,
+    state: State<'a>,
+    // the tokens produced, but not yet returned.
+    pending: VecDeque<BorrowedToken<'a>>,
+    // the tokens produced by the last step of the lexer.
+    buffer: Vec<BorrowedToken<'a>>,
+    finished: bool,
+}
+
+impl<'a> BorrowedTokens<'a> {
+    /// The errors found so far, as in `Tokens::errors()`.
+    pub fn errors(&self) -> &[SyntaxError] {
+        &self.state.errors
+    }
+
+    pub fn into_errors(self) -> Vec<SyntaxError> {
+        self.state.errors
+    }
+
+    fn finish(&mut self) {
+        self.lexer.check_unterminated(&mut self.state);
+
+        self.buffer.push(BorrowedToken {
+            kind: TokenKind::Eof,
+            span: self.state.source.span(),
+            value: Cow::default(),
+        });
+
+        self.state.errors.sort_by_key(|error| error.span().position);
+        self.finished = true;
+    }
+}
+
+impl<'a> Iterator for BorrowedTokens<'a> {
+    type Item = BorrowedToken<'a>;
+
+    fn next(&mut self) -> Option<BorrowedToken<'a>> {
+        loop {
+            if let Some(token) = self.pending.pop_front() {
+                return Some(token);
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            match self.lexer.step(&mut self.state, &mut self.buffer) {
+                Ok(true) => {}
+                Ok(false) => self.finish(),
+                Err(error) => {
+                    self.state.record(error);
+                    self.finish();
+                }
+            }
+
+            self.pending.extend(self.buffer.drain(..));
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 enum NumberKind {
     Int,
@@ -1856,3 +2189,115 @@ enum NumberKind {
     IntOrFloat,
     OctalOrFloat,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_with_errors_recovers() {
+        let (tokens, errors) = Lexer::new().tokenize_with_errors("<?php $a = \x01; $b = 'foo");
+
+        let kinds = tokens
+            .iter()
+            .map(|token| token.kind)
+            .collect::<Vec<TokenKind>>();
+
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::OpenTag(OpenTagKind::Full),
+                TokenKind::Variable,
+                TokenKind::Equals,
+                TokenKind::Error,
+                TokenKind::SemiColon,
+                TokenKind::Variable,
+                TokenKind::Equals,
+                TokenKind::Error,
+                TokenKind::Eof,
+            ]
+        );
+
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                SyntaxError::UnexpectedCharacter(b'\x01', _),
+                SyntaxError::UnterminatedString(b'\'', start, _),
+            ] if start.position == 19
+        ));
+    }
+
+    #[test]
+    fn test_tokenize_fails_with_the_first_error() {
+        assert!(matches!(
+            Lexer::new().tokenize("<?php /* foo"),
+            Err(SyntaxError::UnterminatedComment(start, _)) if start.position == 6
+        ));
+    }
+
+    #[test]
+    fn test_tokens_are_produced_lazily() {
+        let code = "<?php echo 1; /* foo";
+        let mut tokens = Lexer::new().tokens(code);
+
+        assert_eq!(
+            tokens.next().unwrap().kind,
+            TokenKind::OpenTag(OpenTagKind::Full)
+        );
+        assert_eq!(tokens.next().unwrap().kind, TokenKind::Echo);
+        assert!(tokens.errors().is_empty());
+
+        let rest = tokens.by_ref().map(|token| token.kind).collect::<Vec<_>>();
+        assert_eq!(
+            rest,
+            vec![
+                TokenKind::LiteralInteger,
+                TokenKind::SemiColon,
+                TokenKind::MultiLineComment,
+                TokenKind::Eof,
+            ]
+        );
+        assert!(tokens.next().is_none());
+
+        let (all, errors) = Lexer::new().tokenize_with_errors(code);
+        assert_eq!(all.len(), 6);
+        assert_eq!(errors, tokens.into_errors());
+    }
+
+    #[test]
+    fn test_borrowed_tokens_only_copy_changed_values() {
+        let code = "<?php $a = 'foo' . 'it\\'s' . 1_000; // bar";
+
+        let tokens = Lexer::new().borrowed_tokens(code).collect::<Vec<_>>();
+        let values = tokens
+            .iter()
+            .map(|token| match &token.value {
+                Cow::Borrowed(value) => (true, *value),
+                Cow::Owned(value) => (false, value.as_slice()),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            values,
+            vec![
+                (true, &b"<?php"[..]),
+                (true, b"$a"),
+                (true, b"="),
+                (true, b"foo"),
+                (true, b"."),
+                (false, b"it's"),
+                (true, b"."),
+                (false, b"1000"),
+                (true, b";"),
+                (true, b"// bar"),
+                (false, b""),
+            ]
+        );
+
+        let owned = tokens
+            .into_iter()
+            .map(BorrowedToken::into_owned)
+            .collect::<Vec<_>>();
+        assert_eq!(owned, Lexer::new().tokenize(code).unwrap());
+    }
+}
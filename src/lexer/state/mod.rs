@@ -7,31 +7,38 @@ use crate::lexer::state::source::Source;
 use crate::lexer::token::DocStringIndentationAmount;
 use crate::lexer::token::DocStringIndentationKind;
 use crate::lexer::token::DocStringKind;
+use crate::lexer::token::Span;
 
 pub mod source;
 
+// The spans stored in the frames are where the string, or the interpolation,
+// started; they are used to report unterminated strings and interpolations.
 #[derive(Debug)]
 pub enum StackFrame {
     Initial,
     Scripting,
     Halted,
-    DoubleQuote,
-    ShellExec,
+    DoubleQuote(Span),
+    ShellExec(Span),
     DocString(
         DocStringKind,
         ByteString,
         DocStringIndentationKind,
         DocStringIndentationAmount,
+        Span,
     ),
-    LookingForVarname,
+    LookingForVarname(Span),
     LookingForProperty,
     VarOffset,
+    // Scripting inside of a string, entered using `{$` or `${`.
+    Interpolation(Span),
 }
 
 #[derive(Debug)]
 pub struct State<'a> {
     pub source: Source<'a>,
     pub stack: VecDeque<StackFrame>,
+    pub errors: Vec<SyntaxError>,
 }
 
 impl<'a> State<'a> {
@@ -39,9 +46,14 @@ impl<'a> State<'a> {
         Self {
             source,
             stack: VecDeque::from([StackFrame::Initial]),
+            errors: vec![],
         }
     }
 
+    pub fn record(&mut self, error: SyntaxError) {
+        self.errors.push(error);
+    }
+
     pub fn frame(&self) -> SyntaxResult<&StackFrame> {
         self.stack
             .back()
@@ -78,6 +78,23 @@ impl<'a> Source<'a> {
         })..]
     }
 
+    /// The input between the given position and the current one.
+    pub fn since(&self, position: usize) -> &'a [u8] {
+        let until = self.span.position.min(self.length);
+
+        &self.input[position.min(until)..until]
+    }
+
+    /// The span of the given number of bytes preceding the current position, which
+    /// must all be on the current line.
+    pub fn span_before(&self, length: usize) -> Span {
+        Span::new(
+            self.span.line,
+            self.span.column - length,
+            self.span.position - length,
+        )
+    }
+
     pub fn at(&self, search: &[u8], len: usize) -> bool {
         self.read(len) == search
     }
@@ -1,3 +1,6 @@
+use std::cell::OnceCell;
+use std::cell::RefCell;
+
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 
@@ -10,22 +13,29 @@ use crate::parser::ast::comments::CommentGroup;
 /// # Examples
 ///
 /// ```rust
+/// use php_parser_rs::lexer::token::Span;
 /// use php_parser_rs::lexer::token::Token;
 /// use php_parser_rs::lexer::token::TokenKind;
 /// use php_parser_rs::lexer::stream::TokenStream;
 ///
+/// let token = |kind, value: &str, line, column| Token {
+///     kind,
+///     span: Span::new(line, column, 0),
+///     value: value.into(),
+/// };
+///
 /// let tokens = vec![
-///     Token { kind: TokenKind::SingleLineComment("// some class".into()), span: (1, 1) },
-///     Token { kind: TokenKind::Readonly, span: (2, 1) },
-///     Token { kind: TokenKind::Class, span: (2, 10) },
-///     Token { kind: TokenKind::Enum, span: (2, 16) },
-///     Token { kind: TokenKind::LeftBrace, span: (2, 21) },
-///     Token { kind: TokenKind::SingleLineComment("// empty body!".into()), span: (3, 1) },
-///     Token { kind: TokenKind::RightBrace, span: (4, 1) },
-///     Token { kind: TokenKind::Eof, span: (0, 0) },
+///     token(TokenKind::SingleLineComment, "// some class", 1, 1),
+///     token(TokenKind::Readonly, "readonly", 2, 1),
+///     token(TokenKind::Class, "class", 2, 10),
+///     token(TokenKind::Enum, "enum", 2, 16),
+///     token(TokenKind::LeftBrace, "{", 2, 21),
+///     token(TokenKind::SingleLineComment, "// empty body!", 3, 1),
+///     token(TokenKind::RightBrace, "}", 4, 1),
+///     token(TokenKind::Eof, "", 0, 0),
 /// ];
 ///
-/// let mut stream = TokenStream::new(tokens);
+/// let mut stream = TokenStream::new(&tokens);
 ///
 /// assert!(matches!(stream.current().kind, TokenKind::Readonly));
 /// assert!(matches!(stream.peek().kind, TokenKind::Class));
@@ -50,10 +60,9 @@ use crate::parser::ast::comments::CommentGroup;
 /// assert!(matches!(stream.current().kind, TokenKind::Eof));
 /// assert!(stream.is_eof());
 ///
-/// assert_eq!(stream.comments(), vec![
-///     Token { kind: TokenKind::SingleLineComment("// some class".into()), span: (1, 1) },
-///     Token { kind: TokenKind::SingleLineComment("// empty body!".into()), span: (3, 1) },
-/// ]);
+/// let comments = stream.comments().comments;
+/// assert_eq!(comments[0].content, b"// some class");
+/// assert_eq!(comments[1].content, b"// empty body!");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TokenStream<'a> {
@@ -65,7 +74,7 @@ pub struct TokenStream<'a> {
 
 /// Token stream.
 impl<'a> TokenStream<'a> {
-    pub fn new(tokens: &'a [Token]) -> TokenStream {
+    pub fn new(tokens: &'a [Token]) -> TokenStream<'a> {
         let length = tokens.len();
 
         let mut stream = TokenStream {
@@ -251,3 +260,303 @@ impl<'a> From<&'a Vec<Token>> for TokenStream<'a> {
         Self::new(tokens.as_slice())
     }
 }
+
+/// A token stream reading its tokens from an iterator, such as the one returned by
+/// `Lexer::tokens()`, as they are needed.
+///
+/// Only the current token and the tokens peeked at past it are held in memory, and
+/// tokens are moved out of the stream by [`LazyTokenStream::take`] rather than cloned.
+///
+/// # Examples
+///
+/// ```rust
+/// use php_parser_rs::lexer::Lexer;
+/// use php_parser_rs::lexer::token::TokenKind;
+/// use php_parser_rs::lexer::stream::LazyTokenStream;
+///
+/// let mut stream = LazyTokenStream::from_tokens(Lexer::new().tokens("<?php echo $a;"));
+///
+/// assert!(matches!(stream.current().kind, TokenKind::OpenTag(_)));
+/// assert!(matches!(stream.lookahead(2).kind, TokenKind::SemiColon));
+///
+/// stream.next();
+///
+/// assert_eq!(stream.take().value, b"echo");
+/// assert!(matches!(stream.current().kind, TokenKind::Variable));
+/// ```
+pub struct LazyTokenStream<'a> {
+    tokens: RefCell<Box<dyn Iterator<Item = Token> + 'a>>,
+    // the current token, linked to the tokens read after it.
+    current: Buffered,
+    comments: Vec<Token>,
+}
+
+struct Buffered {
+    token: Token,
+    next: OnceCell<Box<Buffered>>,
+}
+
+impl<'a> LazyTokenStream<'a> {
+    /// Create a stream reading the given tokens, which should end with the end of the file.
+    pub fn from_tokens<I>(tokens: I) -> LazyTokenStream<'a>
+    where
+        I: IntoIterator<Item = Token>,
+        I::IntoIter: 'a,
+    {
+        let mut tokens: Box<dyn Iterator<Item = Token> + 'a> = Box::new(tokens.into_iter());
+        // streams that don't end with the end of the file are given one.
+        let token = tokens.next().unwrap_or_default();
+
+        let mut stream = LazyTokenStream {
+            tokens: RefCell::new(tokens),
+            current: Buffered::new(token),
+            comments: vec![],
+        };
+
+        stream.collect_comments();
+
+        stream
+    }
+
+    /// Move cursor to next token.
+    ///
+    /// Comments are collected.
+    pub fn next(&mut self) {
+        self.advance();
+        self.collect_comments();
+    }
+
+    /// Move past the current token, returning it.
+    ///
+    /// Comments are collected.
+    pub fn take(&mut self) -> Token {
+        let token = self.advance();
+        self.collect_comments();
+
+        token
+    }
+
+    /// Get current token.
+    pub fn current(&self) -> &Token {
+        &self.current.token
+    }
+
+    /// Peek next token.
+    ///
+    /// All comments are skipped.
+    pub fn peek(&self) -> &Token {
+        self.peek_nth(1)
+    }
+
+    /// Peek nth+1 token.
+    ///
+    /// All comments are skipped.
+    pub fn lookahead(&self, n: usize) -> &Token {
+        self.peek_nth(n + 1)
+    }
+
+    /// Peek nth token, reading it if needed.
+    ///
+    /// All comments are skipped.
+    fn peek_nth(&self, n: usize) -> &Token {
+        let mut buffered = &self.current;
+        let mut target = 0;
+        while target < n {
+            buffered = self.following(buffered);
+
+            if buffered.token.kind == TokenKind::Eof {
+                break;
+            }
+
+            if !is_comment(&buffered.token.kind) {
+                target += 1;
+            }
+        }
+
+        &buffered.token
+    }
+
+    /// Check if current token is EOF.
+    pub fn is_eof(&self) -> bool {
+        self.current.token.kind == TokenKind::Eof
+    }
+
+    /// Get all comments.
+    pub fn comments(&mut self) -> CommentGroup {
+        CommentGroup {
+            comments: std::mem::take(&mut self.comments)
+                .into_iter()
+                .map(|token| Comment {
+                    span: token.span,
+                    format: match token.kind {
+                        TokenKind::SingleLineComment => CommentFormat::SingleLine,
+                        TokenKind::MultiLineComment => CommentFormat::MultiLine,
+                        TokenKind::HashMarkComment => CommentFormat::HashMark,
+                        TokenKind::DocumentComment => CommentFormat::Document,
+                        _ => unreachable!(),
+                    },
+                    content: token.value,
+                })
+                .collect(),
+        }
+    }
+
+    // the token read after the given one, the end of the file being followed by itself.
+    fn following<'s>(&self, buffered: &'s Buffered) -> &'s Buffered {
+        if buffered.token.kind == TokenKind::Eof {
+            return buffered;
+        }
+
+        buffered
+            .next
+            .get_or_init(|| Box::new(Buffered::new(self.read())))
+    }
+
+    fn read(&self) -> Token {
+        self.tokens.borrow_mut().next().unwrap_or_default()
+    }
+
+    // move to the next token, returning the current one; the end of the file is never
+    // moved past.
+    fn advance(&mut self) -> Token {
+        if self.is_eof() {
+            return self.current.token.clone();
+        }
+
+        let next = match self.current.next.take() {
+            Some(next) => *next,
+            None => Buffered::new(self.read()),
+        };
+
+        std::mem::replace(&mut self.current, next).token
+    }
+
+    fn collect_comments(&mut self) {
+        while is_comment(&self.current.token.kind) {
+            let comment = self.advance();
+
+            self.comments.push(comment);
+        }
+    }
+}
+
+impl Buffered {
+    fn new(token: Token) -> Self {
+        Self {
+            token,
+            next: OnceCell::new(),
+        }
+    }
+}
+
+fn is_comment(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::SingleLineComment
+            | TokenKind::MultiLineComment
+            | TokenKind::HashMarkComment
+            | TokenKind::DocumentComment
+    )
+}
+
+impl<'a> std::fmt::Debug for LazyTokenStream<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyTokenStream")
+            .field("current", &self.current.token)
+            .field("comments", &self.comments)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+    use crate::lexer::Lexer;
+
+    const CODE: &str =
+        "<?php // a\n/** b */ class A { # c\n public function a() { return [1, /* d */ 2]; } }";
+
+    #[test]
+    fn test_lazy_stream_reads_the_same_tokens() {
+        let tokens = Lexer::new().tokenize(CODE).unwrap();
+        let mut stream = TokenStream::new(&tokens);
+        let mut lazy = LazyTokenStream::from_tokens(Lexer::new().tokens(CODE));
+
+        loop {
+            assert_eq!(lazy.current(), stream.current());
+            for n in 0..5 {
+                assert_eq!(lazy.lookahead(n), stream.lookahead(n));
+            }
+            assert_eq!(lazy.comments(), stream.comments());
+
+            if stream.is_eof() {
+                assert!(lazy.is_eof());
+                break;
+            }
+
+            assert_eq!(&lazy.take(), stream.current());
+            stream.next();
+        }
+
+        // the end of the file is never moved past.
+        lazy.next();
+        assert!(lazy.is_eof());
+        assert_eq!(lazy.take().kind, TokenKind::Eof);
+    }
+
+    #[test]
+    fn test_lazy_stream_without_end_of_file() {
+        let mut tokens = Lexer::new().tokenize("<?php echo 1;").unwrap();
+        tokens.pop();
+
+        let mut stream = LazyTokenStream::from_tokens(tokens);
+        assert_eq!(stream.lookahead(5).kind, TokenKind::Eof);
+        for _ in 0..4 {
+            stream.next();
+        }
+        assert!(stream.is_eof());
+
+        assert!(LazyTokenStream::from_tokens(vec![]).is_eof());
+    }
+
+    #[test]
+    fn test_construct_stream_matches_construct() {
+        // unoptimized builds need more than the default test stack for long chains.
+        std::thread::Builder::new()
+            .stack_size(16 * 1024 * 1024)
+            .spawn(move || {
+                let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+                for entry in fs::read_dir(fixtures).unwrap() {
+                    let fixture = entry.unwrap().path();
+                    let code = fs::read(fixture.join("code.php")).unwrap();
+                    let Ok(tokens) = Lexer::new().tokenize(&code) else {
+                        continue;
+                    };
+
+                    let lazy = crate::construct_stream(LazyTokenStream::from_tokens(
+                        Lexer::new().tokens(&code),
+                    ));
+                    let expected = crate::construct(&tokens);
+                    assert_eq!(lazy.is_ok(), expected.is_ok(), "{}", fixture.display());
+                    match (lazy, expected) {
+                        (Ok(lazy), Ok(expected)) => {
+                            assert_eq!(lazy, expected, "{}", fixture.display())
+                        }
+                        (Err(lazy), Err(expected)) => assert_eq!(
+                            format!("{:?}", lazy.errors),
+                            format!("{:?}", expected.errors),
+                            "{}",
+                            fixture.display()
+                        ),
+                        _ => unreachable!(),
+                    }
+                }
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+}
@@ -2,6 +2,7 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use std::borrow::Cow;
 use std::fmt::Display;
 
 use crate::lexer::byte_string::ByteString;
@@ -24,7 +25,7 @@ impl Span {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
 
 pub enum OpenTagKind {
     Full,  // `<?php`
@@ -32,7 +33,7 @@ pub enum OpenTagKind {
     Echo,  // `<?=`
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
 
 pub enum DocStringKind {
     Heredoc,
@@ -41,7 +42,7 @@ pub enum DocStringKind {
 
 pub type DocStringIndentationAmount = usize;
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
 
 pub enum DocStringIndentationKind {
     Space,
@@ -70,7 +71,7 @@ impl From<DocStringIndentationKind> for u8 {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
 
 pub enum TokenKind {
     Die,
@@ -168,6 +169,8 @@ pub enum TokenKind {
     Enum,
     Eof,
     Equals,
+    // Invalid input, skipped by the lexer after recording an error.
+    Error,
     Extends,
     False,
     Final,
@@ -287,6 +290,37 @@ impl Display for Token {
     }
 }
 
+/// A token whose value borrows from the input it was read from, as produced by
+/// `Lexer::borrowed_tokens()`.
+///
+/// The value is only owned when it differs from the input, such as the content of a
+/// string containing escape sequences, or a number containing separators.
+///
+/// Borrowed tokens are not parsed: the parser and the AST only use owned values.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BorrowedToken<'a> {
+    pub kind: TokenKind,
+    pub span: Span,
+    pub value: Cow<'a, [u8]>,
+}
+
+impl<'a> BorrowedToken<'a> {
+    /// Convert the token into a `Token`, copying its value if it is borrowed.
+    pub fn into_owned(self) -> Token {
+        Token {
+            kind: self.kind,
+            span: self.span,
+            value: ByteString::new(self.value.into_owned()),
+        }
+    }
+}
+
+impl<'a> From<BorrowedToken<'a>> for Token {
+    fn from(token: BorrowedToken<'a>) -> Self {
+        token.into_owned()
+    }
+}
+
 impl Display for TokenKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -370,6 +404,7 @@ impl Display for TokenKind {
             Self::Enum => "enum",
             Self::Eof => "[end of file]",
             Self::Equals => "=",
+            Self::Error => "[invalid token]",
             Self::Extends => "extends",
             Self::False => "false",
             Self::Final => "final",
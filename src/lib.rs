@@ -1,9 +1,20 @@
+pub mod analysis;
+pub mod baseline;
+pub mod batch;
 pub mod downcast;
+pub mod export;
+pub mod fixer;
 pub mod lexer;
+pub mod line_index;
+pub mod linter;
+pub mod lookup;
 pub mod node;
 pub mod parser;
 pub mod printer;
+pub mod reporter;
+pub mod source;
 pub mod traverser;
 
+pub use lexer::stream::LazyTokenStream;
 pub use lexer::stream::TokenStream;
-pub use parser::{construct, parse};
+pub use parser::{construct, construct_stream, construct_stream_with, parse, parse_with};
@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+
+/// The unit columns are counted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnUnit {
+    /// Bytes, as `Span::column` is.
+    Byte,
+    /// UTF-16 code units, as the Language Server Protocol uses by default.
+    Utf16,
+    /// Unicode scalar values, as Rust's `char`s are.
+    Char,
+}
+
+/// A 1-based line and column, like the ones of a `Span`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl LineColumn {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// Converts byte offsets into lines and columns, and back, in any [`ColumnUnit`].
+///
+/// Lines end with `\n`, as they do for the lexer, so a `\r` before it is the last
+/// character of its line. Bytes that aren't valid UTF-8 count as one character each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    // the offset of the first byte of every line.
+    lines: Vec<usize>,
+    // the number of characters before every line.
+    chars: Vec<usize>,
+    // the characters encoded with more than one byte, by line, in source order.
+    multibyte: BTreeMap<usize, Vec<MultibyteChar>>,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MultibyteChar {
+    // the 0-based byte column of the first byte of the character.
+    column: usize,
+    bytes: usize,
+    utf16: usize,
+}
+
+impl MultibyteChar {
+    fn len(&self, unit: ColumnUnit) -> usize {
+        match unit {
+            ColumnUnit::Byte => self.bytes,
+            ColumnUnit::Utf16 => self.utf16,
+            ColumnUnit::Char => 1,
+        }
+    }
+}
+
+impl LineIndex {
+    pub fn new<B: ?Sized + AsRef<[u8]>>(source: &B) -> Self {
+        let source = source.as_ref();
+
+        let mut index = Self {
+            lines: vec![0],
+            chars: vec![0],
+            multibyte: BTreeMap::new(),
+            len: source.len(),
+        };
+
+        let mut offset = 0;
+        let mut chars = 0;
+        for chunk in source.utf8_chunks() {
+            for (position, c) in chunk.valid().char_indices() {
+                let position = offset + position;
+                chars += 1;
+
+                if c == '\n' {
+                    index.lines.push(position + 1);
+                    index.chars.push(chars);
+                } else if c.len_utf8() > 1 {
+                    let line = index.lines.len() - 1;
+
+                    index
+                        .multibyte
+                        .entry(line)
+                        .or_default()
+                        .push(MultibyteChar {
+                            column: position - index.lines[line],
+                            bytes: c.len_utf8(),
+                            utf16: c.len_utf16(),
+                        });
+                }
+            }
+
+            offset += chunk.valid().len() + chunk.invalid().len();
+            chars += chunk.invalid().len();
+        }
+
+        index
+    }
+
+    /// The number of lines, which is one more than the number of `\n`s.
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// The line and column of the given byte offset.
+    ///
+    /// Offsets past the end of the source are treated as the end of the source, and
+    /// offsets inside of a character as the start of that character.
+    pub fn position(&self, offset: usize, unit: ColumnUnit) -> LineColumn {
+        let offset = offset.min(self.len);
+        let line = self.lines.partition_point(|start| *start <= offset) - 1;
+
+        LineColumn {
+            line: line + 1,
+            column: self.column(line, offset - self.lines[line], unit) + 1,
+        }
+    }
+
+    /// The byte offset of the given line and column.
+    ///
+    /// Columns inside of a character, such as between the two UTF-16 code units of an
+    /// emoji, are treated as the start of that character. Returns `None` if there is no
+    /// such line, or if the column is past the end of the line.
+    pub fn offset(&self, position: LineColumn, unit: ColumnUnit) -> Option<usize> {
+        let line = position.line.checked_sub(1)?;
+        let start = *self.lines.get(line)?;
+        let end = self.lines.get(line + 1).map_or(self.len, |next| next - 1);
+
+        let mut column = position.column.checked_sub(1)?;
+        for c in self.multibyte_chars(line) {
+            if c.column >= column {
+                break;
+            }
+
+            if c.column + c.len(unit) > column {
+                column = c.column;
+                break;
+            }
+
+            column += c.bytes - c.len(unit);
+        }
+
+        (start + column <= end).then_some(start + column)
+    }
+
+    /// The number of characters before the given byte offset.
+    pub fn char_offset(&self, offset: usize) -> usize {
+        let offset = offset.min(self.len);
+        let line = self.lines.partition_point(|start| *start <= offset) - 1;
+
+        self.chars[line] + self.column(line, offset - self.lines[line], ColumnUnit::Char)
+    }
+
+    // convert a 0-based byte column of the given 0-based line into the given unit.
+    fn column(&self, line: usize, byte_column: usize, unit: ColumnUnit) -> usize {
+        let mut column = byte_column;
+        for c in self.multibyte_chars(line) {
+            if c.column >= byte_column {
+                break;
+            }
+
+            if c.column + c.bytes > byte_column {
+                column -= byte_column - c.column;
+                break;
+            }
+
+            column -= c.bytes - c.len(unit);
+        }
+
+        column
+    }
+
+    fn multibyte_chars(&self, line: usize) -> &[MultibyteChar] {
+        self.multibyte
+            .get(&line)
+            .map_or(&[], |chars| chars.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let index = LineIndex::new("<?php\r\n$a = 'é😀';\n$b");
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.position(0, ColumnUnit::Byte), LineColumn::new(1, 1));
+        assert_eq!(index.position(5, ColumnUnit::Byte), LineColumn::new(1, 6));
+        assert_eq!(index.position(7, ColumnUnit::Byte), LineColumn::new(2, 1));
+
+        // the `'` after the emoji.
+        assert_eq!(index.position(19, ColumnUnit::Byte), LineColumn::new(2, 13));
+        assert_eq!(
+            index.position(19, ColumnUnit::Utf16),
+            LineColumn::new(2, 10)
+        );
+        assert_eq!(index.position(19, ColumnUnit::Char), LineColumn::new(2, 9));
+
+        // the second byte of the `é`.
+        assert_eq!(index.position(14, ColumnUnit::Char), LineColumn::new(2, 7));
+
+        assert_eq!(index.position(22, ColumnUnit::Char), LineColumn::new(3, 1));
+        assert_eq!(index.position(100, ColumnUnit::Char), LineColumn::new(3, 3));
+    }
+
+    #[test]
+    fn test_offset() {
+        let index = LineIndex::new("<?php\r\n$a = 'é😀';\n$b");
+
+        for offset in [0, 5, 6, 7, 13, 15, 19, 21, 22, 24] {
+            for unit in [ColumnUnit::Byte, ColumnUnit::Utf16, ColumnUnit::Char] {
+                assert_eq!(
+                    index.offset(index.position(offset, unit), unit),
+                    Some(offset),
+                    "{} in {:?}",
+                    offset,
+                    unit
+                );
+            }
+        }
+
+        // between the two UTF-16 code units of the emoji.
+        assert_eq!(
+            index.offset(LineColumn::new(2, 9), ColumnUnit::Utf16),
+            Some(15)
+        );
+
+        assert_eq!(index.offset(LineColumn::new(1, 8), ColumnUnit::Byte), None);
+        assert_eq!(index.offset(LineColumn::new(4, 1), ColumnUnit::Byte), None);
+        assert_eq!(index.offset(LineColumn::new(0, 1), ColumnUnit::Byte), None);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let index = LineIndex::new(b"<?php '\xE9';\n\xE9");
+
+        assert_eq!(index.position(8, ColumnUnit::Char), LineColumn::new(1, 9));
+        assert_eq!(index.position(12, ColumnUnit::Utf16), LineColumn::new(2, 2));
+        assert_eq!(index.char_offset(12), 12);
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::parser::error::ParseErrorSeverity;
+
+/// The name of the configuration file, which is looked up in the current directory
+/// and its ancestors.
+pub const CONFIG_FILE: &str = "php-parser.toml";
+
+/// The contents of a `php-parser.toml` file.
+///
+/// ```toml
+/// [lint.rules]
+/// no-eval = "error"
+/// no-goto = "off"
+///
+/// [lint.rules.too-many-parameters]
+/// severity = "notice"
+/// max = 8
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub lint: LintConfig,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleConfig>,
+}
+
+/// The configuration of a single rule, given either as a level (`rule = "off"`),
+/// or as a table of options, with an optional `severity`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "RawRuleConfig")]
+pub struct RuleConfig {
+    pub level: Option<RuleLevel>,
+    pub options: toml::Table,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Notice,
+    Warning,
+    Error,
+}
+
+impl RuleLevel {
+    /// The severity of the findings, or `None` if the rule is turned off.
+    pub fn severity(&self) -> Option<ParseErrorSeverity> {
+        match self {
+            RuleLevel::Off => None,
+            RuleLevel::Notice => Some(ParseErrorSeverity::Notice),
+            RuleLevel::Warning => Some(ParseErrorSeverity::Warning),
+            RuleLevel::Error => Some(ParseErrorSeverity::Error),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawRuleConfig {
+    Level(RuleLevel),
+    Table {
+        severity: Option<RuleLevel>,
+        #[serde(flatten)]
+        options: toml::Table,
+    },
+}
+
+impl From<RawRuleConfig> for RuleConfig {
+    fn from(raw: RawRuleConfig) -> Self {
+        match raw {
+            RawRuleConfig::Level(level) => RuleConfig {
+                level: Some(level),
+                options: toml::Table::new(),
+            },
+            RawRuleConfig::Table { severity, options } => RuleConfig {
+                level: severity,
+                options,
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, std::io::Error),
+    Invalid(String),
+    UnknownRule(String),
+    InvalidOption { rule: String, message: String },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, error) => {
+                write!(f, "failed to read `{}`: {}", path.display(), error)
+            }
+            ConfigError::Invalid(message) => write!(f, "invalid configuration: {}", message),
+            ConfigError::UnknownRule(id) => write!(f, "unknown lint rule `{}`", id),
+            ConfigError::InvalidOption { rule, message } => {
+                write!(
+                    f,
+                    "invalid configuration for lint rule `{}`: {}",
+                    rule, message
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|error| ConfigError::Invalid(error.message().to_string()))
+    }
+}
+
+impl Config {
+    /// Load the configuration from the given file.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| ConfigError::Io(path.to_path_buf(), error))?;
+
+        contents.parse().map_err(|error| match error {
+            ConfigError::Invalid(message) => {
+                ConfigError::Invalid(format!("{}: {}", path.display(), message))
+            }
+            error => error,
+        })
+    }
+
+    /// Find the closest `php-parser.toml` file, starting from `directory`.
+    pub fn find(directory: &Path) -> Option<PathBuf> {
+        directory
+            .ancestors()
+            .map(|directory| directory.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config: Config = r#"
+            [lint.rules]
+            no-eval = "error"
+
+            [lint.rules.too-many-parameters]
+            max = 8
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            config.lint.rules["no-eval"],
+            RuleConfig {
+                level: Some(RuleLevel::Error),
+                options: toml::Table::new(),
+            }
+        );
+        assert_eq!(config.lint.rules["too-many-parameters"].level, None);
+        assert_eq!(
+            config.lint.rules["too-many-parameters"].options["max"].as_integer(),
+            Some(8)
+        );
+
+        assert!("[lint.rules]\nno-eval = \"loud\""
+            .parse::<Config>()
+            .is_err());
+        assert!("[linter]".parse::<Config>().is_err());
+        assert_eq!("".parse::<Config>().unwrap(), Config::default());
+    }
+}
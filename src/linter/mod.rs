@@ -0,0 +1,519 @@
+use std::any::Any;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use crate::analysis::scope::function_scope;
+use crate::analysis::scope::Scope;
+use crate::node::Node;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::comments::CommentGroup;
+use crate::parser::ast::Program;
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseErrorSeverity;
+use crate::traverser::Visitor;
+
+use self::config::ConfigError;
+use self::config::LintConfig;
+use self::config::RuleLevel;
+
+pub mod config;
+pub mod rules;
+
+/// The directive used in comments to suppress the findings of one or more rules.
+pub const IGNORE_DIRECTIVE: &str = "@lint-ignore";
+
+/// A lint rule, inspecting nodes of the given types and reporting findings about them.
+pub trait Rule: Send + Sync {
+    /// The identifier of the rule, used in the configuration file, in `@lint-ignore`
+    /// comments, and as the id of its findings.
+    fn id(&self) -> &'static str;
+
+    /// A short, single line description of what the rule reports.
+    fn description(&self) -> &'static str;
+
+    /// The severity of the findings, unless configured otherwise.
+    fn default_severity(&self) -> ParseErrorSeverity {
+        ParseErrorSeverity::Warning
+    }
+
+    /// The types of the nodes passed to [`Rule::check`].
+    fn node_types(&self) -> Vec<TypeId>;
+
+    /// Configure the rule using the options of its table in the configuration file,
+    /// excluding `severity`.
+    fn configure(&mut self, options: &toml::Table) -> Result<(), String> {
+        match options.keys().next() {
+            Some(key) => Err(format!("unknown option `{}`", key)),
+            None => Ok(()),
+        }
+    }
+
+    /// Inspect `node`, which is one of the types returned by [`Rule::node_types`],
+    /// pushing any finding to `errors`.
+    ///
+    /// `context` holds what is shared by the rules checking the program, such as
+    /// the scopes of its functions.
+    fn check(&self, node: &dyn Node, context: &Context, errors: &mut Vec<ParseError>);
+}
+
+// a node, identified by its type and its address, as a struct and its first field
+// share the same address.
+type NodeKey = (TypeId, *const ());
+
+/// What the rules checking a program share, so that it is computed only once.
+#[derive(Default)]
+pub struct Context {
+    // the scopes of the nodes given to `function_scope`.
+    scopes: RefCell<HashMap<NodeKey, Option<Rc<Scope>>>>,
+}
+
+impl Context {
+    /// The scope of a function, method, closure or arrow function, as built by
+    /// [`function_scope`], or `None` if `node` is none of them.
+    ///
+    /// The scope is only built the first time it is asked for.
+    pub fn function_scope(&self, node: &dyn Node) -> Option<Rc<Scope>> {
+        let key: NodeKey = (Any::type_id(node), node as *const dyn Node as *const ());
+
+        self.scopes
+            .borrow_mut()
+            .entry(key)
+            .or_insert_with(|| function_scope(node).map(Rc::new))
+            .clone()
+    }
+}
+
+struct EnabledRule {
+    rule: Box<dyn Rule>,
+    severity: ParseErrorSeverity,
+}
+
+/// Runs a set of rules over a program.
+pub struct Linter {
+    rules: Vec<EnabledRule>,
+    dispatch: HashMap<TypeId, Vec<usize>>,
+}
+
+impl Default for Linter {
+    /// A linter running every built-in rule, with its default severity.
+    fn default() -> Self {
+        Self::new(rules::all())
+    }
+}
+
+impl Linter {
+    /// Create a linter running each of the given rules with its default severity.
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Self {
+        let mut linter = Self {
+            rules: vec![],
+            dispatch: HashMap::new(),
+        };
+
+        for rule in rules {
+            let severity = rule.default_severity();
+
+            linter.enable(rule, severity);
+        }
+
+        linter
+    }
+
+    /// Create a linter from the given rules, configured using `config`.
+    ///
+    /// Rules that are turned `off` are not run, and configuring a rule that
+    /// is not part of `rules` is an error.
+    pub fn configured(
+        mut rules: Vec<Box<dyn Rule>>,
+        config: &LintConfig,
+    ) -> Result<Self, ConfigError> {
+        if let Some(id) = config
+            .rules
+            .keys()
+            .find(|id| !rules.iter().any(|rule| rule.id() == id.as_str()))
+        {
+            return Err(ConfigError::UnknownRule(id.clone()));
+        }
+
+        let mut linter = Self::new(vec![]);
+
+        for mut rule in rules.drain(..) {
+            let severity = match config.rules.get(rule.id()) {
+                None => Some(rule.default_severity()),
+                Some(rule_config) => {
+                    rule.configure(&rule_config.options).map_err(|message| {
+                        ConfigError::InvalidOption {
+                            rule: rule.id().to_string(),
+                            message,
+                        }
+                    })?;
+
+                    match rule_config.level {
+                        Some(RuleLevel::Off) => None,
+                        Some(level) => level.severity(),
+                        None => Some(rule.default_severity()),
+                    }
+                }
+            };
+
+            if let Some(severity) = severity {
+                linter.enable(rule, severity);
+            }
+        }
+
+        Ok(linter)
+    }
+
+    fn enable(&mut self, rule: Box<dyn Rule>, severity: ParseErrorSeverity) {
+        let index = self.rules.len();
+
+        for node_type in rule.node_types() {
+            self.dispatch.entry(node_type).or_default().push(index);
+        }
+
+        self.rules.push(EnabledRule { rule, severity });
+    }
+
+    /// The enabled rules, along with their severity.
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, ParseErrorSeverity)> {
+        self.rules
+            .iter()
+            .map(|enabled| (enabled.rule.as_ref(), enabled.severity))
+    }
+
+    /// Run the enabled rules over `program`, returning their findings sorted by position.
+    ///
+    /// Findings are suppressed by the `@lint-ignore` comments of the nodes they are
+    /// found in, and by the ones on the line before them, wherever the parser attached
+    /// those comments.
+    ///
+    /// The program is only borrowed mutably because that is what `Visitor` requires;
+    /// it is not modified.
+    pub fn lint(&self, program: &mut Program) -> Vec<ParseError> {
+        let mut linting = Linting {
+            linter: self,
+            context: Context::default(),
+            ignored: vec![],
+            ignored_lines: HashMap::new(),
+            errors: vec![],
+        };
+
+        let Ok(()) = linting.visit_node(program);
+
+        let Linting {
+            ignored_lines,
+            mut errors,
+            ..
+        } = linting;
+
+        errors.retain(|error| {
+            !ignored_lines
+                .get(&error.span.line)
+                .is_some_and(|ids| ignores(ids, &error.id))
+        });
+
+        errors.sort_by_key(|error| error.span.position);
+        errors
+    }
+}
+
+struct Linting<'a> {
+    linter: &'a Linter,
+    context: Context,
+    // the rules ignored by the `@lint-ignore` comments of the nodes being visited.
+    ignored: Vec<Vec<String>>,
+    // the rules ignored on each line by the `@lint-ignore` comments on the line before.
+    //
+    // comments are attached to the next node that can have some, which is often not
+    // the statement following them, so the findings are only filtered once every
+    // comment has been seen.
+    ignored_lines: HashMap<usize, Vec<String>>,
+    errors: Vec<ParseError>,
+}
+
+impl Linting<'_> {
+    fn is_ignored(&self, id: &str) -> bool {
+        self.ignored.iter().any(|ids| ignores(ids, id))
+    }
+}
+
+impl Visitor<Infallible> for Linting<'_> {
+    fn visit_node(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        for comment in node
+            .comments()
+            .map(|comments| comments.iter())
+            .unwrap_or_default()
+        {
+            let Some(ids) = directive(comment) else {
+                continue;
+            };
+
+            // the directive applies to the line following the end of the comment.
+            let lines = comment
+                .content
+                .iter()
+                .filter(|byte| **byte == b'\n')
+                .count();
+            match self.ignored_lines.entry(comment.span.line + lines + 1) {
+                Entry::Occupied(mut ignored) => merge(ignored.get_mut(), ids),
+                Entry::Vacant(entry) => {
+                    entry.insert(ids);
+                }
+            }
+        }
+
+        let ignored = node.comments().and_then(ignored_rules);
+        let has_ignored = ignored.is_some();
+        if let Some(ignored) = ignored {
+            self.ignored.push(ignored);
+        }
+
+        self.visit(node)?;
+
+        for child in node.children() {
+            self.visit_node(child)?;
+        }
+
+        if has_ignored {
+            self.ignored.pop();
+        }
+
+        Ok(())
+    }
+
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        let node: &dyn Node = node;
+        let Some(indices) = self.linter.dispatch.get(&Any::type_id(node)) else {
+            return Ok(());
+        };
+
+        for index in indices {
+            let enabled = &self.linter.rules[*index];
+            if self.is_ignored(enabled.rule.id()) {
+                continue;
+            }
+
+            let mut errors = vec![];
+            enabled.rule.check(node, &self.context, &mut errors);
+
+            self.errors.extend(
+                errors
+                    .into_iter()
+                    .map(|error| error.severity(enabled.severity)),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Collect the rules ignored by the `@lint-ignore` directives in `comments`.
+///
+/// Returns `None` if there are no directives, and an empty list if a directive
+/// doesn't name any rule, in which case every rule is ignored.
+fn ignored_rules(comments: &CommentGroup) -> Option<Vec<String>> {
+    let mut ignored: Option<Vec<String>> = None;
+
+    for ids in comments.iter().filter_map(directive) {
+        match &mut ignored {
+            Some(previous) => merge(previous, ids),
+            None => ignored = Some(ids),
+        }
+    }
+
+    ignored
+}
+
+// the rules ignored by the `@lint-ignore` directives of a single comment.
+fn directive(comment: &Comment) -> Option<Vec<String>> {
+    let mut ignored: Option<Vec<String>> = None;
+
+    for line in comment.content.to_string().lines() {
+        let Some((_, rest)) = line.split_once(IGNORE_DIRECTIVE) else {
+            continue;
+        };
+
+        let rest = rest.split("*/").next().unwrap_or_default();
+        let ids = rest
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|id| !id.is_empty())
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>();
+
+        match &mut ignored {
+            Some(previous) => merge(previous, ids),
+            None => ignored = Some(ids),
+        }
+    }
+
+    ignored
+}
+
+// add the rules of another directive to the ignored ones, an empty list meaning every rule.
+fn merge(ignored: &mut Vec<String>, ids: Vec<String>) {
+    match ids.is_empty() {
+        // an earlier directive already ignores every rule.
+        _ if ignored.is_empty() => {}
+        true => ignored.clear(),
+        false => ignored.extend(ids),
+    }
+}
+
+fn ignores(ignored: &[String], id: &str) -> bool {
+    ignored.is_empty() || ignored.iter().any(|ignored| ignored == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ast::Statement;
+
+    fn lint(linter: &Linter, code: &str) -> Vec<(String, ParseErrorSeverity)> {
+        let mut program = crate::parse(code).unwrap();
+
+        linter
+            .lint(&mut program)
+            .into_iter()
+            .map(|error| (error.id, error.severity))
+            .collect()
+    }
+
+    fn config(source: &str) -> LintConfig {
+        source.parse::<config::Config>().unwrap().lint
+    }
+
+    #[test]
+    fn test_shared_scopes() {
+        let program = crate::parse("<?php function a() {} function b() {}").unwrap();
+        let functions = program
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Function(function) => Some(function.as_ref()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let context = Context::default();
+
+        let a = context.function_scope(functions[0]).unwrap();
+        assert!(Rc::ptr_eq(
+            &a,
+            &context.function_scope(functions[0]).unwrap()
+        ));
+        assert_eq!(a.name.as_deref(), Some("a"));
+        assert_eq!(
+            context
+                .function_scope(functions[1])
+                .unwrap()
+                .name
+                .as_deref(),
+            Some("b")
+        );
+    }
+
+    #[test]
+    fn test_default_rules() {
+        assert_eq!(
+            lint(&Linter::default(), "<?php eval('1');"),
+            vec![("no-eval".to_string(), ParseErrorSeverity::Warning)]
+        );
+    }
+
+    #[test]
+    fn test_configured_rules() {
+        let linter = Linter::configured(
+            rules::all(),
+            &config(
+                r#"
+                [lint.rules]
+                no-eval = "error"
+                no-goto = "off"
+
+                [lint.rules.too-many-parameters]
+                severity = "notice"
+                max = 1
+                "#,
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            lint(&linter, "<?php eval('1'); a: goto a; function a($a, $b) {}"),
+            vec![
+                ("no-eval".to_string(), ParseErrorSeverity::Error),
+                (
+                    "too-many-parameters".to_string(),
+                    ParseErrorSeverity::Notice
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        assert!(matches!(
+            Linter::configured(rules::all(), &config("[lint.rules]\nfoo = \"off\"")),
+            Err(ConfigError::UnknownRule(id)) if id == "foo"
+        ));
+
+        assert!(matches!(
+            Linter::configured(
+                rules::all(),
+                &config("[lint.rules.no-eval]\nmax = 1")
+            ),
+            Err(ConfigError::InvalidOption { rule, .. }) if rule == "no-eval"
+        ));
+    }
+
+    #[test]
+    fn test_ignore_comments() {
+        let linter = Linter::default();
+
+        assert!(lint(
+            &linter,
+            "<?php\n// @lint-ignore no-eval\nfunction a() { eval('1'); }"
+        )
+        .is_empty());
+        assert!(lint(
+            &linter,
+            "<?php\n/** @lint-ignore */\nfunction a() { eval('1'); goto a; a: }"
+        )
+        .is_empty());
+        assert_eq!(
+            lint(
+                &linter,
+                "<?php\n// @lint-ignore no-goto\nfunction a() { eval('1'); }"
+            ),
+            vec![("no-eval".to_string(), ParseErrorSeverity::Warning)]
+        );
+    }
+
+    #[test]
+    fn test_ignore_statement_comments() {
+        let linter = Linter::default();
+
+        assert!(lint(&linter, "<?php\n// @lint-ignore no-eval\neval('1');").is_empty());
+        assert!(lint(
+            &linter,
+            "<?php\nclass A {\n    public function a() {\n        $a = 1;\n        /* @lint-ignore\n         */\n        eval('1');\n    }\n}"
+        )
+        .is_empty());
+        // the directive only applies to the line following it.
+        assert_eq!(
+            lint(
+                &linter,
+                "<?php\n// @lint-ignore no-eval\n$a = 1;\neval('1');"
+            ),
+            vec![("no-eval".to_string(), ParseErrorSeverity::Warning)]
+        );
+        assert_eq!(
+            lint(
+                &linter,
+                "<?php\n// @lint-ignore no-goto\neval('1'); goto a; a:"
+            ),
+            vec![("no-eval".to_string(), ParseErrorSeverity::Warning)]
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use std::any::TypeId;
+
+use crate::downcast::downcast;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::error::ParseError;
+
+/// Reports `catch` blocks without any statement, which silently swallow exceptions.
+pub struct EmptyCatch;
+
+impl Rule for EmptyCatch {
+    fn id(&self) -> &'static str {
+        "empty-catch"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow empty `catch` blocks"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![TypeId::of::<CatchBlock>()]
+    }
+
+    fn check(&self, node: &dyn Node, _context: &Context, errors: &mut Vec<ParseError>) {
+        let Some(catch) = downcast::<CatchBlock>(node) else {
+            return;
+        };
+
+        if catch.body.is_empty() {
+            errors.push(
+                ParseError::new(self.id(), "empty `catch` block", catch.start)
+                    .error("this block is empty", catch.start.position, 5)
+                    .note("handle the exception, or let it propagate"),
+            );
+        }
+    }
+}
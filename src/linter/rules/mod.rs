@@ -0,0 +1,111 @@
+use crate::linter::Rule;
+
+pub mod empty_catch;
+pub mod no_error_suppression;
+pub mod no_eval;
+pub mod no_goto;
+pub mod too_many_parameters;
+pub mod undefined_variable;
+pub mod unreachable_code;
+pub mod unused_closure_use;
+pub mod unused_parameter;
+pub mod unused_variable;
+
+/// Every built-in rule, with its default configuration.
+pub fn all() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(empty_catch::EmptyCatch),
+        Box::new(no_error_suppression::NoErrorSuppression),
+        Box::new(no_eval::NoEval),
+        Box::new(no_goto::NoGoto),
+        Box::new(too_many_parameters::TooManyParameters::default()),
+        Box::new(undefined_variable::UndefinedVariable),
+        Box::new(unreachable_code::UnreachableCode),
+        Box::new(unused_closure_use::UnusedClosureUse),
+        Box::new(unused_parameter::UnusedParameter::default()),
+        Box::new(unused_variable::UnusedVariable),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::Linter;
+
+    fn ids(code: &str) -> Vec<String> {
+        let mut program = crate::parse(code).unwrap();
+
+        Linter::default()
+            .lint(&mut program)
+            .into_iter()
+            .map(|error| error.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_rule_ids_are_unique() {
+        let mut ids = all().iter().map(|rule| rule.id()).collect::<Vec<&str>>();
+        ids.sort_unstable();
+        ids.dedup();
+
+        assert_eq!(ids.len(), all().len());
+    }
+
+    #[test]
+    fn test_rules() {
+        assert_eq!(
+            ids("<?php try { a(); } catch (E $e) {}"),
+            vec!["empty-catch"]
+        );
+        assert!(ids("<?php try { a(); } catch (E $e) { b(); }").is_empty());
+
+        assert_eq!(ids("<?php $a = @file('a');"), vec!["no-error-suppression"]);
+        assert_eq!(ids("<?php eval('1');"), vec!["no-eval"]);
+        assert_eq!(ids("<?php a: goto a;"), vec!["no-goto"]);
+
+        assert_eq!(
+            ids("<?php function a($a, $b, $c, $d, $e, $f) {}"),
+            vec!["too-many-parameters"]
+        );
+        assert_eq!(
+            ids("<?php class A { function __construct($a, $b, $c, $d, $e, $f) {} }"),
+            vec!["too-many-parameters"]
+        );
+        assert!(ids("<?php $a = fn($a, $b, $c, $d, $e) => 1;").is_empty());
+
+        assert_eq!(
+            ids("<?php function a() { return; b(); }"),
+            vec!["unreachable-code"]
+        );
+        assert!(ids("<?php return; a();").is_empty());
+
+        assert_eq!(
+            ids("<?php function a($a) { if ($a) { $b = 1; } return $b + $c; }"),
+            vec!["undefined-variable", "undefined-variable"]
+        );
+        assert!(ids("<?php function a() { extract($_GET); return $b; } echo $c;").is_empty());
+
+        assert_eq!(
+            ids("<?php function a() { $b = 1; $c = 2; return $c; }"),
+            vec!["unused-variable"]
+        );
+        assert!(ids("<?php function a($a) { foreach ($a as $k => $v) { echo $k; } }").is_empty());
+        assert!(ids("<?php function a() { $x = 1; $y = &$x; $y = 2; return $x; }").is_empty());
+
+        assert_eq!(
+            ids("<?php $f = function () use ($a, &$b, &$c) { $b = 1; };"),
+            vec!["unused-closure-use", "unused-closure-use"]
+        );
+
+        assert_eq!(
+            ids("<?php function a($a, $_b, &$c) { $c = 1; }"),
+            vec!["unused-parameter"]
+        );
+        assert!(ids("<?php
+            class A extends B { function a($a) { return 1; } }
+            class C { function __call($a, $b) { return 1; } function d($a) {} }
+            class D { function __construct(private $a, $b) { f($b); } }
+            $f = function ($a) { return 1; };")
+        .is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+use std::any::TypeId;
+
+use crate::downcast::downcast;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::ErrorSuppressExpression;
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseErrorEdit;
+
+/// Reports uses of the `@` operator, which hides errors instead of handling them.
+pub struct NoErrorSuppression;
+
+impl Rule for NoErrorSuppression {
+    fn id(&self) -> &'static str {
+        "no-error-suppression"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow the `@` error suppression operator"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![TypeId::of::<ErrorSuppressExpression>()]
+    }
+
+    fn check(&self, node: &dyn Node, _context: &Context, errors: &mut Vec<ParseError>) {
+        let Some(expression) = downcast::<ErrorSuppressExpression>(node) else {
+            return;
+        };
+
+        errors.push(
+            ParseError::new(self.id(), "errors should not be suppressed", expression.at)
+                .error("try removing this `@`", expression.at.position, 1)
+                .suggestion(
+                    "remove the `@`",
+                    vec![ParseErrorEdit::remove(expression.at.position, 1)],
+                ),
+        );
+    }
+}
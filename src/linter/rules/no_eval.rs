@@ -0,0 +1,37 @@
+use std::any::TypeId;
+
+use crate::downcast::downcast;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::EvalExpression;
+use crate::parser::error::ParseError;
+
+/// Reports uses of `eval`, which executes arbitrary code.
+pub struct NoEval;
+
+impl Rule for NoEval {
+    fn id(&self) -> &'static str {
+        "no-eval"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow `eval`"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![TypeId::of::<EvalExpression>()]
+    }
+
+    fn check(&self, node: &dyn Node, _context: &Context, errors: &mut Vec<ParseError>) {
+        let Some(expression) = downcast::<EvalExpression>(node) else {
+            return;
+        };
+
+        errors.push(
+            ParseError::new(self.id(), "`eval` should not be used", expression.eval)
+                .error("this evaluates arbitrary code", expression.eval.position, 4)
+                .note("`eval` is hard to reason about, and unsafe when used with user input"),
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::any::TypeId;
+
+use crate::downcast::downcast;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::goto::GotoStatement;
+use crate::parser::error::ParseError;
+
+/// Reports `goto` statements, which make the control flow hard to follow.
+pub struct NoGoto;
+
+impl Rule for NoGoto {
+    fn id(&self) -> &'static str {
+        "no-goto"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow `goto`"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![TypeId::of::<GotoStatement>()]
+    }
+
+    fn check(&self, node: &dyn Node, _context: &Context, errors: &mut Vec<ParseError>) {
+        let Some(statement) = downcast::<GotoStatement>(node) else {
+            return;
+        };
+
+        errors.push(
+            ParseError::new(self.id(), "`goto` should not be used", statement.keyword)
+                .error(
+                    "try using a loop or a function instead",
+                    statement.keyword.position,
+                    4,
+                )
+                .highlight(statement.label.span.position, statement.label.value.len()),
+        );
+    }
+}
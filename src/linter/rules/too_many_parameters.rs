@@ -0,0 +1,111 @@
+use std::any::TypeId;
+
+use crate::downcast::downcast;
+use crate::lexer::token::Span;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::functions::ConstructorParameterList;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseErrorSeverity;
+
+/// Reports functions, methods, and closures declaring more than `max` parameters.
+pub struct TooManyParameters {
+    pub max: usize,
+}
+
+impl Default for TooManyParameters {
+    fn default() -> Self {
+        Self { max: 5 }
+    }
+}
+
+impl TooManyParameters {
+    fn check_parameters(
+        &self,
+        left_parenthesis: Span,
+        parameters: Vec<&SimpleVariable>,
+        errors: &mut Vec<ParseError>,
+    ) {
+        let Some(first_excess) = parameters.get(self.max) else {
+            return;
+        };
+
+        errors.push(
+            ParseError::new(
+                self.id(),
+                format!(
+                    "too many parameters, found {} but at most {} are allowed",
+                    parameters.len(),
+                    self.max
+                ),
+                left_parenthesis,
+            )
+            .highlight(left_parenthesis.position, 1)
+            .error(
+                format!("parameter #{} exceeds the limit", self.max + 1),
+                first_excess.span.position,
+                first_excess.name.len(),
+            )
+            .note("consider grouping related parameters into an object"),
+        );
+    }
+}
+
+impl Rule for TooManyParameters {
+    fn id(&self) -> &'static str {
+        "too-many-parameters"
+    }
+
+    fn description(&self) -> &'static str {
+        "limit the number of parameters of a function (option: `max`, defaults to 5)"
+    }
+
+    fn default_severity(&self) -> ParseErrorSeverity {
+        ParseErrorSeverity::Notice
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![
+            TypeId::of::<FunctionParameterList>(),
+            TypeId::of::<ConstructorParameterList>(),
+        ]
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> Result<(), String> {
+        for (key, value) in options {
+            match key.as_str() {
+                "max" => {
+                    self.max = value
+                        .as_integer()
+                        .and_then(|max| usize::try_from(max).ok())
+                        .ok_or_else(|| "`max` must be a positive integer".to_string())?;
+                }
+                _ => return Err(format!("unknown option `{}`", key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check(&self, node: &dyn Node, _context: &Context, errors: &mut Vec<ParseError>) {
+        if let Some(list) = downcast::<FunctionParameterList>(node) {
+            self.check_parameters(
+                list.left_parenthesis,
+                list.iter().map(|parameter| &parameter.name).collect(),
+                errors,
+            );
+        } else if let Some(list) = downcast::<ConstructorParameterList>(node) {
+            self.check_parameters(
+                list.left_parenthesis,
+                list.parameters
+                    .iter()
+                    .map(|parameter| &parameter.name)
+                    .collect(),
+                errors,
+            );
+        }
+    }
+}
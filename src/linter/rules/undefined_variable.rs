@@ -0,0 +1,88 @@
+use std::any::TypeId;
+
+use crate::analysis::scope::is_predefined;
+use crate::analysis::scope::AccessKind;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::error::ParseError;
+
+/// Reports variables of functions, methods and closures which are read before being
+/// assigned, on every path or only on some of them.
+///
+/// Functions accessing their variables dynamically, through `compact()`, `extract()`,
+/// `$$name`, ..., are skipped, as is the code outside of functions, whose variables
+/// may be defined by an including file.
+pub struct UndefinedVariable;
+
+impl Rule for UndefinedVariable {
+    fn id(&self) -> &'static str {
+        "undefined-variable"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow reading variables that may not be defined"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![
+            TypeId::of::<FunctionStatement>(),
+            TypeId::of::<ConcreteMethod>(),
+            TypeId::of::<ConcreteConstructor>(),
+            TypeId::of::<ClosureExpression>(),
+        ]
+    }
+
+    fn check(&self, node: &dyn Node, context: &Context, errors: &mut Vec<ParseError>) {
+        let Some(scope) = context.function_scope(node) else {
+            return;
+        };
+
+        if scope.dynamic {
+            return;
+        }
+
+        for (name, symbol) in &scope.variables {
+            if is_predefined(name) {
+                continue;
+            }
+
+            // only the first read is reported, the following ones being a consequence.
+            let Some(access) = symbol.accesses.iter().find(|access| {
+                matches!(access.kind, AccessKind::Read | AccessKind::Update) && !access.defined
+            }) else {
+                continue;
+            };
+
+            let error = if symbol.definitions().next().is_none() {
+                ParseError::new(
+                    self.id(),
+                    format!("undefined variable `{}`", name),
+                    access.span,
+                )
+                .error(
+                    format!("`{}` is never defined", name),
+                    access.span.position,
+                    name.len(),
+                )
+            } else {
+                ParseError::new(
+                    self.id(),
+                    format!("variable `{}` might not be defined", name),
+                    access.span,
+                )
+                .error(
+                    format!("`{}` is not defined on every path leading here", name),
+                    access.span.position,
+                    name.len(),
+                )
+            };
+
+            errors.push(error);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::any::TypeId;
+
+use crate::analysis::cfg::ControlFlowGraph;
+use crate::analysis::unreachable::unreachable;
+use crate::downcast::downcast;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::functions::FunctionBody;
+use crate::parser::ast::functions::MethodBody;
+use crate::parser::error::ParseError;
+
+/// Reports the code of functions and methods that can never run, such as
+/// statements following a `return`.
+pub struct UnreachableCode;
+
+impl Rule for UnreachableCode {
+    fn id(&self) -> &'static str {
+        "unreachable-code"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow code that can never run"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![TypeId::of::<FunctionBody>(), TypeId::of::<MethodBody>()]
+    }
+
+    fn check(&self, node: &dyn Node, _context: &Context, errors: &mut Vec<ParseError>) {
+        let cfg = if let Some(body) = downcast::<FunctionBody>(node) {
+            ControlFlowGraph::from_function(body)
+        } else if let Some(body) = downcast::<MethodBody>(node) {
+            ControlFlowGraph::from_method(body)
+        } else {
+            return;
+        };
+
+        for code in unreachable(&cfg) {
+            errors.push(
+                ParseError::new(self.id(), "unreachable code", code.start).error(
+                    "this code can never run",
+                    code.start.position,
+                    code.length(),
+                ),
+            );
+        }
+    }
+}
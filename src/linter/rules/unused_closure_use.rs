@@ -0,0 +1,71 @@
+use std::any::TypeId;
+
+use crate::analysis::scope::AccessKind;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::error::ParseError;
+
+/// Reports variables imported by the `use` clause of a closure which the closure
+/// never uses.
+///
+/// Variables imported by reference are used as soon as the closure assigns them.
+pub struct UnusedClosureUse;
+
+impl Rule for UnusedClosureUse {
+    fn id(&self) -> &'static str {
+        "unused-closure-use"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow importing variables a closure doesn't use"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![TypeId::of::<ClosureExpression>()]
+    }
+
+    fn check(&self, node: &dyn Node, context: &Context, errors: &mut Vec<ParseError>) {
+        let Some(scope) = context.function_scope(node) else {
+            return;
+        };
+
+        if scope.dynamic {
+            return;
+        }
+
+        for (name, symbol) in &scope.variables {
+            let Some((capture, by_reference)) =
+                symbol.accesses.iter().find_map(|access| match access.kind {
+                    AccessKind::Capture { by_reference } => Some((access, by_reference)),
+                    _ => None,
+                })
+            else {
+                continue;
+            };
+
+            let used = match by_reference {
+                true => symbol.accesses.len() > 1,
+                false => symbol.uses().next().is_some(),
+            };
+
+            if used {
+                continue;
+            }
+
+            errors.push(
+                ParseError::new(
+                    self.id(),
+                    format!("unused variable `{}` in closure `use`", name),
+                    capture.span,
+                )
+                .error(
+                    format!("`{}` is imported, but never used", name),
+                    capture.span.position,
+                    name.len(),
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use std::any::TypeId;
+
+use crate::analysis::scope::AccessKind;
+use crate::downcast::downcast;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::AnonymousClassMember;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::Statement;
+use crate::parser::error::ParseError;
+
+/// Reports parameters of functions and methods which are never used.
+///
+/// Functions with an empty body are usually stubs, or no-op implementations meant
+/// to be overridden, and are skipped, as are magic methods and closures, whose
+/// signatures are imposed by their caller. Parameters promoted to properties are
+/// always used.
+pub struct UnusedParameter {
+    /// Skip the parameters whose name starts with `$_`.
+    pub ignore_underscored: bool,
+    /// Skip the methods whose signature may be imposed by a parent class or an interface:
+    /// the methods of classes and enums which extend or implement another type, the
+    /// methods of traits, and the methods marked `#[Override]`.
+    pub ignore_inherited: bool,
+}
+
+impl Default for UnusedParameter {
+    fn default() -> Self {
+        Self {
+            ignore_underscored: true,
+            ignore_inherited: true,
+        }
+    }
+}
+
+impl UnusedParameter {
+    fn check_method(
+        &self,
+        method: &ConcreteMethod,
+        inherited: bool,
+        context: &Context,
+        errors: &mut Vec<ParseError>,
+    ) {
+        let name = method.name.value.to_string();
+        if name.starts_with("__") || is_empty(&method.body.statements) {
+            return;
+        }
+
+        if self.ignore_inherited && (inherited || overrides(&method.attributes)) {
+            return;
+        }
+
+        self.check_function(method, &[], context, errors);
+    }
+
+    fn check_constructor(
+        &self,
+        constructor: &ConcreteConstructor,
+        context: &Context,
+        errors: &mut Vec<ParseError>,
+    ) {
+        if is_empty(&constructor.body.statements) {
+            return;
+        }
+
+        let promoted = constructor
+            .parameters
+            .parameters
+            .iter()
+            .filter(|parameter| !parameter.modifiers.is_empty())
+            .map(|parameter| parameter.name.name.to_string())
+            .collect::<Vec<String>>();
+
+        self.check_function(constructor, &promoted, context, errors);
+    }
+
+    fn check_function(
+        &self,
+        node: &dyn Node,
+        skipped: &[String],
+        context: &Context,
+        errors: &mut Vec<ParseError>,
+    ) {
+        let Some(scope) = context.function_scope(node) else {
+            return;
+        };
+
+        // `func_get_args()` uses every parameter.
+        if scope.dynamic {
+            return;
+        }
+
+        for (name, symbol) in &scope.variables {
+            let Some((parameter, by_reference)) =
+                symbol.accesses.iter().find_map(|access| match access.kind {
+                    AccessKind::Parameter { by_reference } => Some((access, by_reference)),
+                    _ => None,
+                })
+            else {
+                continue;
+            };
+
+            if skipped.contains(name) || (self.ignore_underscored && name.starts_with("$_")) {
+                continue;
+            }
+
+            // assigning a parameter taken by reference is a use.
+            let used = match by_reference {
+                true => symbol.accesses.len() > 1,
+                false => symbol.uses().next().is_some(),
+            };
+
+            if used {
+                continue;
+            }
+
+            let mut error = ParseError::new(
+                self.id(),
+                format!("unused parameter `{}`", name),
+                parameter.span,
+            )
+            .error(
+                format!("`{}` is never used", name),
+                parameter.span.position,
+                name.len(),
+            );
+
+            if self.ignore_underscored {
+                error = error.note(format!(
+                    "if this is intentional, rename it to `$_{}`",
+                    name.trim_start_matches('$')
+                ));
+            }
+
+            errors.push(error);
+        }
+    }
+}
+
+impl Rule for UnusedParameter {
+    fn id(&self) -> &'static str {
+        "unused-parameter"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow function parameters that are never used (options: `ignore-underscored`, `ignore-inherited`, both default to true)"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![
+            TypeId::of::<FunctionStatement>(),
+            TypeId::of::<ClassStatement>(),
+            TypeId::of::<AnonymousClassExpression>(),
+            TypeId::of::<TraitStatement>(),
+            TypeId::of::<UnitEnumStatement>(),
+            TypeId::of::<BackedEnumStatement>(),
+        ]
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> Result<(), String> {
+        for (key, value) in options {
+            let option = match key.as_str() {
+                "ignore-underscored" => &mut self.ignore_underscored,
+                "ignore-inherited" => &mut self.ignore_inherited,
+                _ => return Err(format!("unknown option `{}`", key)),
+            };
+
+            *option = value
+                .as_bool()
+                .ok_or_else(|| format!("`{}` must be a boolean", key))?;
+        }
+
+        Ok(())
+    }
+
+    fn check(&self, node: &dyn Node, context: &Context, errors: &mut Vec<ParseError>) {
+        if let Some(function) = downcast::<FunctionStatement>(node) {
+            if !is_empty(&function.body.statements) {
+                self.check_function(function, &[], context, errors);
+            }
+        } else if let Some(class) = downcast::<ClassStatement>(node) {
+            let inherited = class.extends.is_some() || class.implements.is_some();
+
+            for member in &class.body.members {
+                match member {
+                    ClassMember::ConcreteMethod(method) => {
+                        self.check_method(method, inherited, context, errors)
+                    }
+                    ClassMember::ConcreteConstructor(constructor) => {
+                        self.check_constructor(constructor, context, errors)
+                    }
+                    _ => {}
+                }
+            }
+        } else if let Some(class) = downcast::<AnonymousClassExpression>(node) {
+            let inherited = class.extends.is_some() || class.implements.is_some();
+
+            for member in &class.body.members {
+                match member {
+                    AnonymousClassMember::ConcreteMethod(method) => {
+                        self.check_method(method, inherited, context, errors)
+                    }
+                    AnonymousClassMember::ConcreteConstructor(constructor) => {
+                        self.check_constructor(constructor, context, errors)
+                    }
+                    _ => {}
+                }
+            }
+        } else if let Some(r#trait) = downcast::<TraitStatement>(node) {
+            // the methods of a trait may implement an interface of the classes using it.
+            for member in &r#trait.body.members {
+                match member {
+                    TraitMember::ConcreteMethod(method) => {
+                        self.check_method(method, true, context, errors)
+                    }
+                    TraitMember::ConcreteConstructor(constructor) => {
+                        self.check_constructor(constructor, context, errors)
+                    }
+                    _ => {}
+                }
+            }
+        } else if let Some(r#enum) = downcast::<UnitEnumStatement>(node) {
+            for member in &r#enum.body.members {
+                if let UnitEnumMember::Method(method) = member {
+                    self.check_method(method, !r#enum.implements.is_empty(), context, errors);
+                }
+            }
+        } else if let Some(r#enum) = downcast::<BackedEnumStatement>(node) {
+            for member in &r#enum.body.members {
+                if let BackedEnumMember::Method(method) = member {
+                    self.check_method(method, !r#enum.implements.is_empty(), context, errors);
+                }
+            }
+        }
+    }
+}
+
+fn is_empty(statements: &[Statement]) -> bool {
+    statements
+        .iter()
+        .all(|statement| matches!(statement, Statement::Comment(_) | Statement::Noop(_)))
+}
+
+// whether the method is marked `#[Override]`.
+fn overrides(attributes: &[AttributeGroup]) -> bool {
+    attributes
+        .iter()
+        .flat_map(|group| group.iter())
+        .any(|attribute| {
+            attribute
+                .name
+                .value
+                .to_string()
+                .trim_start_matches('\\')
+                .eq_ignore_ascii_case("Override")
+        })
+}
@@ -0,0 +1,88 @@
+use std::any::TypeId;
+
+use crate::analysis::scope::is_predefined;
+use crate::analysis::scope::AccessKind;
+use crate::linter::Context;
+use crate::linter::Rule;
+use crate::node::Node;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::error::ParseError;
+
+/// Reports variables of functions, methods and closures which are assigned, but
+/// never read.
+///
+/// Parameters and closure captures have rules of their own. `global` and `static`
+/// variables outlive the function, and the variables of `foreach` and `catch` can't
+/// always be omitted, so they are never reported.
+pub struct UnusedVariable;
+
+impl Rule for UnusedVariable {
+    fn id(&self) -> &'static str {
+        "unused-variable"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow variables that are assigned but never read"
+    }
+
+    fn node_types(&self) -> Vec<TypeId> {
+        vec![
+            TypeId::of::<FunctionStatement>(),
+            TypeId::of::<ConcreteMethod>(),
+            TypeId::of::<ConcreteConstructor>(),
+            TypeId::of::<ClosureExpression>(),
+        ]
+    }
+
+    fn check(&self, node: &dyn Node, context: &Context, errors: &mut Vec<ParseError>) {
+        let Some(scope) = context.function_scope(node) else {
+            return;
+        };
+
+        if scope.dynamic {
+            return;
+        }
+
+        for (name, symbol) in &scope.variables {
+            if is_predefined(name) || symbol.uses().next().is_some() {
+                continue;
+            }
+
+            let exempt = symbol.accesses.iter().any(|access| {
+                matches!(
+                    access.kind,
+                    AccessKind::Parameter { .. }
+                        | AccessKind::Capture { .. }
+                        | AccessKind::Global
+                        | AccessKind::Static
+                        | AccessKind::Foreach
+                        | AccessKind::Catch
+                )
+            });
+
+            if exempt {
+                continue;
+            }
+
+            let Some(definition) = symbol.definitions().next() else {
+                continue;
+            };
+
+            errors.push(
+                ParseError::new(
+                    self.id(),
+                    format!("variable `{}` is assigned but never read", name),
+                    definition.span,
+                )
+                .error(
+                    format!("`{}` is assigned here", name),
+                    definition.span.position,
+                    name.len(),
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::downcast::downcast;
+use crate::node::Node;
+use crate::parser::ast::Program;
+
+/// Finds the nodes of a program covering a position of its source, such as the one of
+/// the cursor in an editor.
+pub trait Lookup {
+    /// The nodes covering the byte at the given offset, from the outermost to the
+    /// innermost one.
+    fn path_at(&self, offset: usize) -> NodePath<'_> {
+        self.path_covering(offset, offset + 1)
+    }
+
+    /// The nodes covering the bytes from `start` to `end`, excluded, from the outermost
+    /// to the innermost one.
+    ///
+    /// When a node ends where the next one starts, an empty range between them is
+    /// covered by the first one.
+    fn path_covering(&self, start: usize, end: usize) -> NodePath<'_>;
+}
+
+impl Lookup for Program {
+    fn path_covering(&self, start: usize, end: usize) -> NodePath<'_> {
+        let mut nodes = vec![];
+        let mut children = self.children_ref();
+        // nodes without a position, such as empty lists, can't cover anything.
+        while let Some(child) = children.into_iter().find(|child| {
+            matches!(
+                (child.start(), child.end()),
+                (Some(first), Some(last)) if first.position <= start && end <= last
+            )
+        }) {
+            nodes.push(child);
+            children = child.children_ref();
+        }
+
+        NodePath { nodes }
+    }
+}
+
+/// The nodes covering a range of the source, from the outermost to the innermost one.
+pub struct NodePath<'a> {
+    pub nodes: Vec<&'a dyn Node>,
+}
+
+impl<'a> NodePath<'a> {
+    /// The innermost node of the path, if any node covers the range.
+    pub fn innermost(&self) -> Option<&'a dyn Node> {
+        self.nodes.last().copied()
+    }
+
+    /// The innermost `T` of the path, such as the innermost `Expression` when selecting
+    /// the expression enclosing the cursor.
+    pub fn innermost_of<T: Node>(&self) -> Option<&'a T> {
+        self.nodes
+            .iter()
+            .rev()
+            .find_map(|node| downcast::<T>(*node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ast::functions::ConcreteMethod;
+    use crate::parser::ast::variables::Variable;
+    use crate::parser::ast::Expression;
+    use crate::parser::ast::Statement;
+
+    const CODE: &[u8] = b"<?php $a = foo($b + 1);\nif ($c) { echo \"x{$d}\"; }";
+
+    // the positions of the given node.
+    fn range(node: &dyn Node) -> (usize, usize) {
+        (node.start().unwrap().position, node.end().unwrap())
+    }
+
+    #[test]
+    fn test_path_at() {
+        let program = crate::parse(CODE).unwrap();
+
+        // the `b` of `$b`.
+        let path = program.path_at(16);
+
+        assert!(downcast::<Statement>(path.nodes[0]).is_some());
+        assert_eq!(range(path.nodes[0]), (6, 23));
+
+        let innermost = path.innermost().unwrap();
+        assert_eq!(range(innermost), (15, 17));
+        assert!(matches!(
+            downcast::<Variable>(innermost),
+            Some(Variable::SimpleVariable(variable)) if variable.name == b"$b"
+        ));
+
+        // the variable within the interpolated string.
+        let path = program.path_at(43);
+        let variable = path.innermost_of::<Variable>().unwrap();
+        assert_eq!(range(variable), (42, 44));
+
+        assert!(program.path_at(CODE.len()).nodes.is_empty());
+    }
+
+    #[test]
+    fn test_path_covering() {
+        let program = crate::parse(CODE).unwrap();
+
+        // `$b + 1`, as selected in an editor.
+        let path = program.path_covering(15, 21);
+        let expression = path.innermost_of::<Expression>().unwrap();
+
+        assert_eq!(range(expression), (15, 21));
+        assert!(matches!(expression, Expression::ArithmeticOperation(_)));
+
+        // `($b + 1)` is only covered by the call.
+        let path = program.path_covering(14, 21);
+        let expression = path.innermost_of::<Expression>().unwrap();
+
+        assert_eq!(range(expression), (11, 22));
+        assert!(matches!(expression, Expression::FunctionCall(_)));
+    }
+
+    #[test]
+    fn test_attributes_and_modifiers() {
+        let code = b"<?php class A {\n    #[Pure] public static function a() {}\n}";
+        let program = crate::parse(code).unwrap();
+
+        // the attribute and the modifiers are part of the method.
+        let method = program
+            .path_at(21)
+            .innermost_of::<ConcreteMethod>()
+            .unwrap();
+        assert_eq!(range(method), (20, 57));
+        assert!(program
+            .path_at(30)
+            .innermost_of::<ConcreteMethod>()
+            .is_some());
+    }
+}
@@ -1,35 +1,279 @@
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
+use std::fmt::{Display, Formatter};
 use std::io::Result;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use php_parser_rs::analysis::cfg;
+use php_parser_rs::analysis::metrics::{self, Metrics};
+use php_parser_rs::baseline::Baseline;
+use php_parser_rs::batch;
+use php_parser_rs::export::{nikic, php_ast, tokens};
+use php_parser_rs::fixer;
+use php_parser_rs::linter::config::Config;
+use php_parser_rs::linter::{rules, Linter};
+use php_parser_rs::parser::codes;
+use php_parser_rs::parser::error::{ParseError, ParseErrorSeverity};
+use php_parser_rs::parser::options::Options;
+use php_parser_rs::reporter::{self, ReportFormat, ReportedFile};
+use php_parser_rs::source::SourceFile;
 
 #[derive(Parser, Default, Debug)]
-#[clap(version, about = "A PHP Parser")]
+#[clap(
+    version,
+    about = "A PHP Parser",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Arguments {
-    file: String,
+    #[clap(subcommand)]
+    command: Option<Command>,
+    #[clap(required = true)]
+    file: Option<String>,
     #[clap(short, long)]
     /// Don't print anything
     silent: bool,
     #[clap(short, long)]
     /// Print as json
     json: bool,
+    #[clap(long, conflicts_with = "json")]
+    /// Print the control flow graphs of the file in the DOT format of Graphviz
+    cfg: bool,
+    #[clap(long, conflicts_with_all = ["json", "cfg"])]
+    /// Print the tokens of the file, in the format of PHP's `token_get_all()`
+    tokens: bool,
+    #[clap(short, long, default_value_t = OutputFormat::Report(ReportFormat::Text))]
+    /// Format used to print the AST (nikic-json or php-ast-json), or to report errors
+    /// (text, json, sarif, checkstyle, junit, or github)
+    format: OutputFormat,
+    #[clap(long, value_name = "VERSION", default_value_t = php_ast::Version::default())]
+    /// Version of the AST printed by the php-ast-json format (70, 80, 85, 90, or 100)
+    ast_version: php_ast::Version,
+    #[clap(long, value_name = "CODE", exclusive = true)]
+    /// Print a detailed explanation of the given error code
+    explain: Option<String>,
+    #[clap(long)]
+    /// Apply safe fixes to the file
+    fix: bool,
+    #[clap(long, requires = "fix")]
+    /// Apply fixes that might change the meaning of the code too
+    unsafe_fixes: bool,
+}
+
+/// The format of the output when parsing a single file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// Report the errors, if any, in the given format.
+    Report(ReportFormat),
+    /// Print the AST in the JSON format of nikic/PHP-Parser.
+    NikicJson,
+    /// Print the AST in the JSON format of the php-ast extension.
+    PhpAstJson,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Report(ReportFormat::Text)
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Report(format) => write!(f, "{}", format),
+            OutputFormat::NikicJson => write!(f, "nikic-json"),
+            OutputFormat::PhpAstJson => write!(f, "php-ast-json"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("nikic-json") {
+            return Ok(OutputFormat::NikicJson);
+        }
+
+        if s.eq_ignore_ascii_case("php-ast-json") {
+            return Ok(OutputFormat::PhpAstJson);
+        }
+
+        s.parse().map(OutputFormat::Report).map_err(|_| {
+            format!(
+                "unknown format `{}`, expecting one of: nikic-json, php-ast-json, {}",
+                s,
+                ReportFormat::ALL.map(|format| format.as_str()).join(", ")
+            )
+        })
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Parse every PHP file in the given files and directories, and report any errors
+    Check(CheckArguments),
+    /// Run the lint rules over every PHP file in the given files and directories
+    Lint(LintArguments),
+    /// Measure the complexity and size of the functions, methods and classes of every
+    /// PHP file in the given files and directories
+    Metrics(MetricsArguments),
+}
+
+#[derive(Args, Debug)]
+struct InputArguments {
+    /// Files or directories to check
+    #[clap(default_value = ".")]
+    paths: Vec<PathBuf>,
+    #[clap(short, long)]
+    /// Only check files matching the given glob (defaults to `**/*.php`)
+    include: Vec<String>,
+    #[clap(short, long)]
+    /// Skip files and directories matching the given glob
+    exclude: Vec<String>,
+    #[clap(short, long)]
+    /// Number of threads to use (defaults to the number of available cores)
+    threads: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+struct FileArguments {
+    #[clap(flatten)]
+    input: InputArguments,
+    #[clap(long)]
+    /// Don't use colors in the reports
+    no_color: bool,
+    #[clap(short, long)]
+    /// Only print the summary
+    quiet: bool,
+    #[clap(short, long, default_value_t = ReportFormat::Text)]
+    /// Format used to report errors (text, json, sarif, checkstyle, junit, or github)
+    format: ReportFormat,
+    #[clap(long, value_name = "FILE")]
+    /// Don't report the errors recorded in the given baseline file
+    baseline: Option<PathBuf>,
+    #[clap(long, requires = "baseline")]
+    /// Record the current errors in the baseline file instead of reporting them
+    generate_baseline: bool,
+    #[clap(long, requires = "baseline", conflicts_with = "generate_baseline")]
+    /// Report the baseline entries which no longer occur, and fail if there are any
+    report_stale_baseline: bool,
+}
+
+#[derive(Args, Debug)]
+struct CheckArguments {
+    #[clap(flatten)]
+    files: FileArguments,
+    #[clap(long)]
+    /// Apply safe fixes to the checked files
+    fix: bool,
+    #[clap(long, requires = "fix")]
+    /// Apply fixes that might change the meaning of the code too
+    unsafe_fixes: bool,
+}
+
+#[derive(Args, Debug)]
+struct LintArguments {
+    #[clap(flatten)]
+    files: FileArguments,
+    #[clap(short, long)]
+    /// Configuration file to use (defaults to the closest `php-parser.toml`)
+    config: Option<PathBuf>,
+    #[clap(long)]
+    /// List the available rules, along with their configured severity
+    list_rules: bool,
+}
+
+#[derive(Args, Debug)]
+struct MetricsArguments {
+    #[clap(flatten)]
+    input: InputArguments,
+    #[clap(short, long)]
+    /// Print as json
+    json: bool,
+}
+
+enum CheckResult {
+    Success,
+    Failure(Reported),
+    Unreadable(std::io::Error),
+    Unwritable(std::io::Error),
+}
+
+struct CheckedFile {
+    result: CheckResult,
+    fixed: usize,
 }
 
 fn main() -> Result<()> {
     let args = Arguments::parse();
 
-    let file = args.file;
-    let contents = std::fs::read_to_string(&file)?;
+    match args.command {
+        Some(Command::Check(args)) => return check(args),
+        Some(Command::Lint(args)) => return lint(args),
+        Some(Command::Metrics(args)) => return measure(args),
+        None => {}
+    }
+
+    if let Some(code) = args.explain {
+        return explain(&code);
+    }
+
+    let file = args.file.unwrap_or_default();
+    let mut source = SourceFile::read(&file)?;
+
+    if args.fix {
+        let fixed = fixer::fix(&source.contents, args.unsafe_fixes, &options());
+        if fixed.applied > 0 {
+            std::fs::write(&file, source.file_bytes(&fixed.source))?;
+            eprintln!("applied {} fix(es) to `{}`", fixed.applied, file);
+
+            source.contents = fixed.source;
+        }
+    }
     let silent = args.silent;
     let print_json = args.json;
 
-    match php_parser_rs::parse(&contents) {
-        Ok(ast) => {
+    // tokens are printed even if the file can't be parsed.
+    if args.tokens {
+        let tokens = tokens::token_get_all(&source.contents)
+            .iter()
+            .map(|token| serde_json::to_string(token).unwrap_or_default())
+            .collect::<Vec<_>>();
+        println!("[\n    {}\n]", tokens.join(",\n    "));
+
+        return Ok(());
+    }
+
+    match php_parser_rs::parse_with(&source.contents, &options()) {
+        Ok(mut ast) => {
             // if --silent is passed, don't print anything
             if silent {
                 return Ok(());
             }
 
-            // if --json is passed, print as json
-            if print_json {
+            // if --cfg is passed, print the control flow graphs
+            if args.cfg {
+                print!("{}", cfg::to_dot(&mut ast));
+            } else if args.format == OutputFormat::NikicJson {
+                match serde_json::to_string_pretty(&nikic::to_json(&ast, &source.contents)) {
+                    Ok(json) => println!("{}", json),
+                    Err(error) => {
+                        eprintln!("Failed to convert ast to json: {}", error);
+
+                        std::process::exit(1);
+                    }
+                }
+            } else if args.format == OutputFormat::PhpAstJson {
+                match serde_json::to_string_pretty(&php_ast::to_json(&ast, args.ast_version)) {
+                    Ok(json) => println!("{}", json),
+                    Err(error) => {
+                        eprintln!("Failed to convert ast to json: {}", error);
+
+                        std::process::exit(1);
+                    }
+                }
+            } else if print_json {
                 match serde_json::to_string_pretty(&ast) {
                     Ok(json) => println!("{}", json),
                     Err(error) => {
@@ -44,11 +288,707 @@ fn main() -> Result<()> {
             }
         }
         Err(error) => {
-            println!("{}", error.report(&contents, Some(&file), true, false)?);
+            let reported = reported(&source, error.errors);
+            let files = [ReportedFile {
+                path: &file,
+                source: &reported.text,
+                errors: &reported.errors,
+                file_offset: reported.file_offset,
+                failure: None,
+            }];
+
+            let format = match args.format {
+                OutputFormat::Report(format) => format,
+                OutputFormat::NikicJson | OutputFormat::PhpAstJson => ReportFormat::Text,
+            };
+
+            println!("{}", reporter::report(format, &files, true, false)?);
+
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+fn explain(code: &str) -> Result<()> {
+    match codes::lookup(code) {
+        Some(entry) => {
+            println!(
+                "{}: {}\n\n{}",
+                entry.code,
+                entry.summary,
+                entry.explanation.trim_end()
+            );
+        }
+        None => {
+            eprintln!("error code `{}` is not a valid error code", code);
+
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+fn check(args: CheckArguments) -> Result<()> {
+    let FoundFiles { files, unreadable } = find_files(&args.files.input);
+    let threads = args.files.input.threads;
+    let total = files.len() + unreadable.len();
+
+    let fix = args.fix.then_some(args.unsafe_fixes);
+    let results = batch::process(&files, threads, |file| match SourceFile::read(file) {
+        Ok(source) => check_file(file, source, fix),
+        Err(error) => CheckedFile {
+            result: CheckResult::Unreadable(error),
+            fixed: 0,
+        },
+    });
+
+    let mut fixed = 0;
+    let mut reported = vec![];
+    let mut failures = vec![];
+    let unreadable = unreadable.into_iter().map(|(path, error)| {
+        let checked = CheckedFile {
+            result: CheckResult::Unreadable(error),
+            fixed: 0,
+        };
+
+        (path, checked)
+    });
+
+    for (file, checked) in files.into_iter().zip(results).chain(unreadable) {
+        let name = file.to_string_lossy().to_string();
+
+        if checked.fixed > 0 {
+            fixed += 1;
+
+            eprintln!("applied {} fix(es) to `{}`", checked.fixed, name);
+        }
+
+        match checked.result {
+            CheckResult::Success => reported.push((name, Reported::default())),
+            CheckResult::Failure(errors) => reported.push((name, errors)),
+            CheckResult::Unreadable(error) => {
+                let failure = format!("failed to read `{}`: {}", name, error);
+
+                eprintln!("{}", failure);
+                failures.push((name, failure));
+            }
+            CheckResult::Unwritable(error) => {
+                let failure = format!("failed to write the fixes to `{}`: {}", name, error);
+
+                eprintln!("{}", failure);
+                failures.push((name, failure));
+            }
+        }
+    }
+
+    let Some((baselined, stale)) = apply_baseline(&args.files, &mut reported)? else {
+        return Ok(());
+    };
+
+    let failed = failures.len()
+        + reported
+            .iter()
+            .filter(|(_, reported)| !reported.errors.is_empty())
+            .count();
+
+    let mut summary = format!(
+        "checked {} file(s): {} passed, {} failed",
+        total,
+        total - failed,
+        failed
+    );
+
+    if args.fix {
+        summary.push_str(&format!(", {} fixed", fixed));
+    }
+
+    summary.push_str(&baselined);
+
+    print_report(&args.files, &reported, &failures, &summary)?;
+
+    if failed > 0 || stale {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn lint(args: LintArguments) -> Result<()> {
+    let config = match args
+        .config
+        .clone()
+        .or_else(|| Config::find(&std::env::current_dir().ok()?))
+    {
+        Some(path) => Config::load(&path),
+        None => Ok(Config::default()),
+    };
+
+    let linter = match config.and_then(|config| Linter::configured(rules::all(), &config.lint)) {
+        Ok(linter) => linter,
+        Err(error) => {
+            eprintln!("{}", error);
+
+            std::process::exit(1);
+        }
+    };
+
+    if args.list_rules {
+        for rule in rules::all() {
+            let severity = linter
+                .rules()
+                .find(|(enabled, _)| enabled.id() == rule.id())
+                .map(|(_, severity)| severity.as_str())
+                .unwrap_or("off");
+
+            println!("{:<24} {:<8} {}", rule.id(), severity, rule.description());
+        }
+
+        return Ok(());
+    }
+
+    let FoundFiles { files, unreadable } = find_files(&args.files.input);
+    let threads = args.files.input.threads;
+    let total = files.len() + unreadable.len();
+
+    let results = batch::process(&files, threads, |file| -> Result<_> {
+        let source = SourceFile::read(file)?;
+        let errors = match php_parser_rs::parse_with(&source.contents, &options()) {
+            Ok(mut program) => linter.lint(&mut program),
+            Err(error) => error.errors,
+        };
+
+        Ok(reported(&source, errors))
+    });
+
+    let mut reported = vec![];
+    let mut failures = vec![];
+    let unreadable = unreadable
+        .into_iter()
+        .map(|(path, error)| (path, Err(error)));
+
+    for (file, result) in files.into_iter().zip(results).chain(unreadable) {
+        let name = file.to_string_lossy().to_string();
+
+        match result {
+            Ok(errors) => reported.push((name, errors)),
+            Err(error) => {
+                let failure = format!("failed to read `{}`: {}", name, error);
+
+                eprintln!("{}", failure);
+                failures.push((name, failure));
+            }
+        }
+    }
+
+    let Some((baselined, stale)) = apply_baseline(&args.files, &mut reported)? else {
+        return Ok(());
+    };
+
+    let count = |severity: ParseErrorSeverity| {
+        reported
+            .iter()
+            .flat_map(|(_, reported)| &reported.errors)
+            .filter(|error| error.severity == severity)
+            .count()
+    };
+
+    let errors = count(ParseErrorSeverity::Error);
+    let mut summary = format!(
+        "linted {} file(s): {} error(s), {} warning(s), {} notice(s)",
+        total,
+        errors,
+        count(ParseErrorSeverity::Warning),
+        count(ParseErrorSeverity::Notice),
+    );
+
+    summary.push_str(&baselined);
+
+    print_report(&args.files, &reported, &failures, &summary)?;
+
+    if !failures.is_empty() || errors > 0 || stale {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn measure(args: MetricsArguments) -> Result<()> {
+    let FoundFiles { files, unreadable } = find_files(&args.input);
+    let threads = args.input.threads;
+
+    let results = batch::process(&files, threads, |file| -> Result<Option<Metrics>> {
+        let source = SourceFile::read(file)?;
+
+        Ok(php_parser_rs::parse(&source.contents)
+            .ok()
+            .map(|mut program| metrics::metrics(&mut program)))
+    });
+
+    let mut failed = false;
+    let mut measured = vec![];
+    let unreadable = unreadable
+        .into_iter()
+        .map(|(path, error)| (path, Err(error)));
+
+    for (file, result) in files.into_iter().zip(results).chain(unreadable) {
+        let name = file.to_string_lossy().to_string();
+
+        match result {
+            Ok(Some(metrics)) => measured.push((name, metrics)),
+            Ok(None) => {
+                failed = true;
+
+                eprintln!("failed to parse `{}`, use `check` to see the errors", name);
+            }
+            Err(error) => {
+                failed = true;
+
+                eprintln!("failed to read `{}`: {}", name, error);
+            }
+        }
+    }
+
+    if args.json {
+        let files = measured
+            .iter()
+            .map(|(path, metrics)| {
+                serde_json::json!({
+                    "path": path,
+                    "functions": metrics.functions,
+                    "classes": metrics.classes,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        println!("{}", serde_json::json!({ "files": files }));
+    } else {
+        for (path, metrics) in &measured {
+            if metrics.functions.is_empty() && metrics.classes.is_empty() {
+                continue;
+            }
+
+            println!("{}\n{}", path, metrics.to_table());
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Apply the baseline options to the reported errors.
+///
+/// When generating the baseline, the errors are written to it, and `None` is returned as
+/// there is nothing left to report. Otherwise, the baselined errors are removed from
+/// `reported`, and the summary of the baseline is returned, along with whether stale
+/// entries were found.
+fn apply_baseline(
+    args: &FileArguments,
+    reported: &mut [(String, Reported)],
+) -> Result<Option<(String, bool)>> {
+    let Some(path) = &args.baseline else {
+        return Ok(Some((String::new(), false)));
+    };
+
+    if args.generate_baseline {
+        let mut baseline = Baseline::for_file(path)?;
+        for (file, reported) in reported.iter() {
+            for error in &reported.errors {
+                baseline.add(file, &reported.text, error);
+            }
+        }
+
+        baseline.save(path)?;
+        eprintln!(
+            "recorded {} error(s) in the baseline `{}`",
+            baseline.len(),
+            path.display()
+        );
+
+        return Ok(None);
+    }
+
+    let mut baseline = match Baseline::load(path) {
+        Ok(baseline) => baseline,
+        Err(error) => {
+            eprintln!(
+                "failed to read the baseline `{}`: {}",
+                path.display(),
+                error
+            );
 
             std::process::exit(1);
         }
+    };
+
+    let total = baseline.len();
+    for (file, reported) in reported.iter_mut() {
+        reported.errors =
+            baseline.filter(file, &reported.text, std::mem::take(&mut reported.errors));
+    }
+
+    let mut summary = format!(", {} baselined", total - baseline.len());
+
+    if !args.report_stale_baseline {
+        return Ok(Some((summary, false)));
+    }
+
+    for entry in baseline.entries() {
+        eprintln!(
+            "stale baseline entry: {} in `{}` no longer occurs ({} time(s))",
+            entry.id, entry.file, entry.count
+        );
+    }
+
+    summary.push_str(&format!(", {} stale", baseline.len()));
+
+    Ok(Some((summary, !baseline.is_empty())))
+}
+
+/// Print the reported errors, and the files that couldn't be processed, along with the
+/// reason why, which are reported by the machine-readable formats only.
+fn print_report(
+    args: &FileArguments,
+    reported: &[(String, Reported)],
+    failures: &[(String, String)],
+    summary: &str,
+) -> Result<()> {
+    if !args.quiet {
+        let failures = failures.iter().map(|(path, failure)| ReportedFile {
+            path,
+            source: "",
+            errors: &[],
+            file_offset: None,
+            failure: Some(failure),
+        });
+
+        let reported = reported
+            .iter()
+            .map(|(path, reported)| ReportedFile {
+                path,
+                source: &reported.text,
+                errors: &reported.errors,
+                file_offset: reported.file_offset,
+                failure: None,
+            })
+            .chain(failures)
+            .collect::<Vec<ReportedFile>>();
+
+        let output = reporter::report(args.format, &reported, !args.no_color, false)?;
+        if !output.is_empty() {
+            println!("{}", output);
+        }
+    }
+
+    // keep the standard output parseable when using a machine-readable format.
+    if args.format == ReportFormat::Text {
+        println!("{}", summary);
+    } else {
+        eprintln!("{}", summary);
     }
 
     Ok(())
 }
+
+/// The files to process, found by walking the given paths.
+struct FoundFiles {
+    files: Vec<PathBuf>,
+    /// The directories, or their entries, that couldn't be read while walking them.
+    unreadable: Vec<(PathBuf, std::io::Error)>,
+}
+
+fn find_files(args: &InputArguments) -> FoundFiles {
+    let include = if args.include.is_empty() {
+        vec!["**/*.php".to_string()]
+    } else {
+        args.include.clone()
+    };
+
+    let mut found = FoundFiles {
+        files: vec![],
+        unreadable: vec![],
+    };
+
+    for path in &args.paths {
+        if path.is_dir() {
+            collect_files(path, path, &include, &args.exclude, &mut found);
+        } else {
+            // Files given explicitly are always checked.
+            found.files.push(path.clone());
+        }
+    }
+
+    found
+}
+
+/// Parse the given file, fixing it in place when `fix` is set, in which case
+/// `fix` tells whether unsafe fixes should be applied too.
+fn check_file(file: &Path, mut source: SourceFile, fix: Option<bool>) -> CheckedFile {
+    let errors = match php_parser_rs::parse_with(&source.contents, &options()) {
+        Ok(_) => {
+            return CheckedFile {
+                result: CheckResult::Success,
+                fixed: 0,
+            }
+        }
+        Err(error) => error.errors,
+    };
+
+    let Some(unsafe_fixes) = fix else {
+        return CheckedFile {
+            result: CheckResult::Failure(reported(&source, errors)),
+            fixed: 0,
+        };
+    };
+
+    let fixed = fixer::fix(&source.contents, unsafe_fixes, &options());
+    if fixed.applied > 0 {
+        if let Err(error) = std::fs::write(file, source.file_bytes(&fixed.source)) {
+            return CheckedFile {
+                result: CheckResult::Unwritable(error),
+                fixed: 0,
+            };
+        }
+    }
+
+    let result = if fixed.errors.is_empty() {
+        CheckResult::Success
+    } else {
+        source.contents = fixed.source;
+
+        CheckResult::Failure(reported(&source, fixed.errors))
+    };
+
+    CheckedFile {
+        result,
+        fixed: fixed.applied,
+    }
+}
+
+/// The options files are parsed with, which report the errors PHP reports at compile
+/// time too.
+fn options() -> Options {
+    Options {
+        validate: true,
+        ..Options::default()
+    }
+}
+
+/// The decoded text of a file, along with its errors, positioned in the text.
+#[derive(Default)]
+struct Reported {
+    text: String,
+    /// See [`SourceFile::file_offset`].
+    file_offset: Option<usize>,
+    errors: Vec<ParseError>,
+}
+
+fn reported(source: &SourceFile, errors: Vec<ParseError>) -> Reported {
+    let errors = errors
+        .into_iter()
+        .map(|error| source.text_error(error))
+        .collect();
+
+    Reported {
+        text: source.text().into_owned(),
+        file_offset: source.file_offset(),
+        errors,
+    }
+}
+
+fn collect_files(
+    root: &Path,
+    directory: &Path,
+    include: &[String],
+    exclude: &[String],
+    found: &mut FoundFiles,
+) {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(error) => {
+            found.unreadable.push((directory.to_path_buf(), error));
+
+            return;
+        }
+    };
+
+    let mut paths = vec![];
+    for entry in entries {
+        match entry.and_then(|entry| Ok((entry.path(), entry.file_type()?))) {
+            Ok(path) => paths.push(path),
+            // the name of the entry might not be known, so its directory is reported.
+            Err(error) => found.unreadable.push((directory.to_path_buf(), error)),
+        }
+    }
+
+    paths.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    for (entry, file_type) in paths {
+        let relative = entry
+            .strip_prefix(root)
+            .unwrap_or(&entry)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        if exclude
+            .iter()
+            .any(|pattern| glob_matches(pattern, &relative))
+        {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            collect_files(root, &entry, include, exclude, found);
+        } else if file_type.is_symlink() && entry.is_dir() {
+            // symbolic links to directories aren't followed, as they can form cycles.
+            continue;
+        } else if include
+            .iter()
+            .any(|pattern| glob_matches(pattern, &relative))
+        {
+            found.files.push(entry);
+        }
+    }
+}
+
+/// Match a `/`-separated path against a glob pattern.
+///
+/// `*` matches any sequence of characters within a path segment, `?` matches
+/// a single character, and `**` matches any number of path segments.
+///
+/// Patterns without a `/` are matched against the last path segment only, so that
+/// `vendor` excludes `vendor` directories at any depth and `*.php` matches PHP files anywhere.
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+
+    if !pattern.contains('/') {
+        return path
+            .rsplit('/')
+            .next()
+            .map(|name| segment_matches(pattern.as_bytes(), name.as_bytes()))
+            .unwrap_or(false);
+    }
+
+    let pattern = pattern.split('/').collect::<Vec<&str>>();
+    let path = path.split('/').collect::<Vec<&str>>();
+
+    segments_match(&pattern, &path)
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    wildcard_matches(
+        pattern,
+        path,
+        |pattern| *pattern == "**",
+        |pattern, segment| segment_matches(pattern.as_bytes(), segment.as_bytes()),
+    )
+}
+
+fn segment_matches(pattern: &[u8], name: &[u8]) -> bool {
+    wildcard_matches(
+        pattern,
+        name,
+        |pattern| *pattern == b'*',
+        |pattern, byte| *pattern == b'?' || pattern == byte,
+    )
+}
+
+// match the items against the pattern, where a star matches any number of items,
+// using two cursors rather than backtracking, so that it stays linear for each star.
+fn wildcard_matches<P, T>(
+    pattern: &[P],
+    items: &[T],
+    is_star: impl Fn(&P) -> bool,
+    matches: impl Fn(&P, &T) -> bool,
+) -> bool {
+    let (mut p, mut i) = (0, 0);
+    // the position after the last star, and of the first item it doesn't match yet.
+    let mut star = None;
+
+    while i < items.len() {
+        if p < pattern.len() && is_star(&pattern[p]) {
+            p += 1;
+            star = Some((p, i));
+        } else if p < pattern.len() && matches(&pattern[p], &items[i]) {
+            p += 1;
+            i += 1;
+        } else if let Some((after, matched)) = star {
+            // let the last star match one more item, and retry the rest of the pattern.
+            p = after;
+            i = matched + 1;
+            star = Some((after, i));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(is_star)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("**/*.php", "index.php"));
+        assert!(glob_matches("**/*.php", "src/Foo/Bar.php"));
+        assert!(!glob_matches("**/*.php", "src/Foo/Bar.phpt"));
+        assert!(glob_matches("*.php", "src/Foo/Bar.php"));
+        assert!(glob_matches("vendor", "lib/vendor"));
+        assert!(!glob_matches("vendor", "vendors"));
+        assert!(glob_matches("tests/**/fixtures", "tests/Unit/Foo/fixtures"));
+        assert!(!glob_matches("tests/*/fixtures", "tests/Unit/Foo/fixtures"));
+        assert!(glob_matches("src/?oo.php", "src/Foo.php"));
+        assert!(glob_matches("./src/", "src"));
+        assert!(glob_matches("**", "src/Foo.php"));
+        assert!(glob_matches("src/**", "src/Foo/Bar.php"));
+        assert!(glob_matches("**/Foo/**/*.php", "src/Foo/a/Foo/b/Bar.php"));
+        assert!(!glob_matches("**/Foo/**/*.php", "src/Foo"));
+        assert!(glob_matches("*a*b", "xaab"));
+        assert!(!glob_matches("*a*b", "xaba"));
+    }
+
+    #[test]
+    fn test_glob_matches_many_stars() {
+        // backtracking on every star would take exponential time.
+        let name = "a".repeat(100);
+        assert!(!glob_matches(&format!("{}b", "a*".repeat(50)), &name));
+
+        let path = ["a"; 100].join("/");
+        assert!(!glob_matches(&format!("{}b", "a/**/".repeat(50)), &path));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_collect_files_skips_directory_links() {
+        let root = std::env::temp_dir().join(format!("php-parser-rs-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.php"), "<?php").unwrap();
+        std::fs::write(root.join("src/b.txt"), "").unwrap();
+        // a link to its own directory, which would be walked forever if followed.
+        std::os::unix::fs::symlink(root.join("src"), root.join("src/loop")).unwrap();
+        std::os::unix::fs::symlink(root.join("src/a.php"), root.join("src/c.php")).unwrap();
+
+        let mut found = FoundFiles {
+            files: vec![],
+            unreadable: vec![],
+        };
+        collect_files(&root, &root, &["*.php".to_string()], &[], &mut found);
+        collect_files(&root, &root.join("missing"), &[], &[], &mut found);
+
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            found.files,
+            vec![root.join("src/a.php"), root.join("src/c.php")]
+        );
+        assert_eq!(found.unreadable.len(), 1);
+        assert_eq!(found.unreadable[0].0, root.join("missing"));
+    }
+}
@@ -1,7 +1,147 @@
 use std::any::Any;
 
+use crate::lexer::token::Span;
+use crate::parser::ast::comments::CommentGroup;
+
 pub trait Node: Any {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![]
     }
+
+    /// The same children as `children`, borrowed immutably.
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![]
+    }
+
+    /// The comments attached to this node, if it can have any.
+    fn comments(&self) -> Option<&CommentGroup> {
+        None
+    }
+
+    /// The span of the first token of this node, attributes included but not comments.
+    ///
+    /// Defaults to the earliest start of the children of this node, for nodes that
+    /// begin with one of them.
+    fn start(&self) -> Option<Span> {
+        self.children_ref()
+            .into_iter()
+            .filter_map(|child| child.start())
+            .min_by_key(|span| span.position)
+    }
+
+    /// The position following the last byte of this node.
+    ///
+    /// Defaults to the latest end of the children of this node, for nodes that end
+    /// with one of them.
+    fn end(&self) -> Option<usize> {
+        self.children_ref()
+            .into_iter()
+            .filter_map(|child| child.end())
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::fs;
+    use std::path::Path;
+
+    use super::Node;
+    use crate::downcast::downcast;
+    use crate::export::token_end;
+    use crate::lexer::Lexer;
+    use crate::parser::ast::literals::LiteralFloat;
+    use crate::parser::ast::literals::LiteralInteger;
+    use crate::parser::ast::literals::LiteralString;
+
+    struct Boundaries {
+        starts: HashSet<usize>,
+        ends: HashSet<usize>,
+    }
+
+    // whether the end of the given node is the one of a literal, whose value is decoded
+    // so that its end is approximate.
+    fn ends_with_literal(node: &dyn Node) -> bool {
+        if downcast::<LiteralString>(node).is_some()
+            || downcast::<LiteralInteger>(node).is_some()
+            || downcast::<LiteralFloat>(node).is_some()
+        {
+            return true;
+        }
+
+        let end = node.end();
+        node.children_ref()
+            .into_iter()
+            .find(|child| end.is_some() && child.end() == end)
+            .is_some_and(ends_with_literal)
+    }
+
+    fn check(node: &dyn Node, boundaries: &Boundaries, errors: &mut Vec<String>) {
+        let start = node.start().map(|span| span.position);
+        let end = node.end().filter(|_| !ends_with_literal(node));
+
+        if let Some(start) = start.filter(|start| !boundaries.starts.contains(start)) {
+            errors.push(format!("a node starts at {start}, within a token"));
+        }
+        if let Some(end) = end.filter(|end| !boundaries.ends.contains(end)) {
+            errors.push(format!("a node ends at {end}, within a token"));
+        }
+
+        for child in node.children_ref() {
+            let child_end = child.end().filter(|_| !ends_with_literal(child));
+            let within = start
+                .zip(child.start())
+                .is_none_or(|(start, child)| start <= child.position)
+                && end.zip(child_end).is_none_or(|(end, child)| child <= end);
+            if !within {
+                errors.push(format!(
+                    "a node at {start:?}..{end:?} doesn't cover its child at {:?}..{child_end:?}",
+                    child.start().map(|span| span.position),
+                ));
+            }
+
+            check(child, boundaries, errors);
+        }
+    }
+
+    #[test]
+    fn test_spans_of_fixtures() {
+        // unoptimized builds need more than the default test stack for long chains.
+        std::thread::Builder::new()
+            .stack_size(16 * 1024 * 1024)
+            .spawn(move || {
+                let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+                let mut errors = vec![];
+                for entry in fs::read_dir(fixtures).unwrap() {
+                    let fixture = entry.unwrap().path();
+                    if !fixture.join("ast.txt").exists() {
+                        continue;
+                    }
+
+                    let code = fs::read(fixture.join("code.php")).unwrap();
+                    let program = crate::parse(&code).unwrap();
+                    let tokens = Lexer::new().tokenize(&code).unwrap();
+                    let boundaries = Boundaries {
+                        starts: tokens.iter().map(|token| token.span.position).collect(),
+                        ends: (0..tokens.len())
+                            .map(|index| token_end(&code, &tokens, index))
+                            .collect(),
+                    };
+
+                    let mut found = vec![];
+                    check(&program, &boundaries, &mut found);
+                    errors.extend(
+                        found
+                            .into_iter()
+                            .map(|error| format!("{}: {error}", fixture.display())),
+                    );
+                }
+
+                assert!(errors.is_empty(), "{}", errors.join("\n"));
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
 }
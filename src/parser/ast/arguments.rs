@@ -18,9 +18,21 @@ pub struct PositionalArgument {
 }
 
 impl Node for PositionalArgument {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.value]
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.ellipsis.or_else(|| self.value.start())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -33,9 +45,17 @@ pub struct NamedArgument {
 }
 
 impl Node for NamedArgument {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.value]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -52,6 +72,13 @@ impl Node for Argument {
             Argument::Named(argument) => vec![argument],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Argument::Positional(argument) => vec![argument],
+            Argument::Named(argument) => vec![argument],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -79,12 +106,28 @@ impl IntoIterator for ArgumentList {
 }
 
 impl Node for ArgumentList {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.arguments
             .iter_mut()
             .map(|a| a as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.arguments.iter().map(|a| a as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_parenthesis)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -97,9 +140,25 @@ pub struct SingleArgument {
 }
 
 impl Node for SingleArgument {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.argument]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.argument]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_parenthesis)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
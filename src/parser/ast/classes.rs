@@ -49,6 +49,21 @@ impl Node for ClassBody {
             .map(|member| member as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members
+            .iter()
+            .map(|member| member as &dyn Node)
+            .collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -76,6 +91,31 @@ impl Node for ClassStatement {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(extends) = &self.extends {
+            children.push(extends);
+        }
+        if let Some(implements) = &self.implements {
+            children.push(implements);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| {
+                self.modifiers
+                    .modifiers
+                    .first()
+                    .map(|modifier| modifier.span())
+            })
+            .or(Some(self.class))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -108,6 +148,21 @@ impl Node for AnonymousClassBody {
             .map(|member| member as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members
+            .iter()
+            .map(|member| member as &dyn Node)
+            .collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -132,6 +187,25 @@ impl Node for AnonymousClassExpression {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(extends) = &self.extends {
+            children.push(extends);
+        }
+        if let Some(implements) = &self.implements {
+            children.push(implements);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.class))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -145,6 +219,14 @@ impl Node for ClassExtends {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.parent]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.parent]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.extends)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -173,6 +255,14 @@ impl Node for ClassImplements {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.interfaces.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.interfaces.children_ref()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.implements)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -201,6 +291,19 @@ impl Node for ClassMember {
             ClassMember::ConcreteConstructor(method) => vec![method],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ClassMember::Constant(constant) => vec![constant],
+            ClassMember::TraitUsage(usage) => vec![usage],
+            ClassMember::Property(property) => vec![property],
+            ClassMember::VariableProperty(property) => vec![property],
+            ClassMember::AbstractMethod(method) => vec![method],
+            ClassMember::AbstractConstructor(method) => vec![method],
+            ClassMember::ConcreteMethod(method) => vec![method],
+            ClassMember::ConcreteConstructor(method) => vec![method],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -225,4 +328,15 @@ impl Node for AnonymousClassMember {
             AnonymousClassMember::ConcreteConstructor(method) => vec![method],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            AnonymousClassMember::Constant(constant) => vec![constant],
+            AnonymousClassMember::TraitUsage(usage) => vec![usage],
+            AnonymousClassMember::Property(property) => vec![property],
+            AnonymousClassMember::VariableProperty(property) => vec![property],
+            AnonymousClassMember::ConcreteMethod(method) => vec![method],
+            AnonymousClassMember::ConcreteConstructor(method) => vec![method],
+        }
+    }
 }
@@ -25,7 +25,15 @@ pub struct Comment {
     pub content: ByteString,
 }
 
-impl Node for Comment {}
+impl Node for Comment {
+    fn start(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.span.position + self.content.len())
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
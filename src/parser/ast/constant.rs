@@ -24,6 +24,10 @@ impl Node for ConstantEntry {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.value]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -51,12 +55,28 @@ impl IntoIterator for ConstantStatement {
 }
 
 impl Node for ConstantStatement {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.entries
             .iter_mut()
             .map(|e| e as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.entries.iter().map(|e| e as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#const)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -87,10 +107,35 @@ impl IntoIterator for ClassishConstant {
 }
 
 impl Node for ClassishConstant {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.entries
             .iter_mut()
             .map(|e| e as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.entries.iter().map(|e| e as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| {
+                self.modifiers
+                    .modifiers
+                    .first()
+                    .map(|modifier| modifier.span())
+            })
+            .or(Some(self.r#const))
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
@@ -22,6 +22,14 @@ impl Node for IfStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.condition, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.condition, &self.body]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#if)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -82,6 +90,64 @@ impl Node for IfStatementBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            IfStatementBody::Statement {
+                statement,
+                elseifs,
+                r#else,
+            } => {
+                let mut children: Vec<&dyn Node> = vec![statement.as_ref()];
+                children.extend(
+                    elseifs
+                        .iter()
+                        .map(|elseif| elseif as &dyn Node)
+                        .collect::<Vec<&dyn Node>>(),
+                );
+                if let Some(r#else) = r#else {
+                    children.push(r#else as &dyn Node);
+                }
+                children
+            }
+            IfStatementBody::Block {
+                statements,
+                elseifs,
+                r#else,
+                ..
+            } => {
+                let mut children: Vec<&dyn Node> = vec![];
+                children.extend(statements.iter().map(|statement| statement as &dyn Node));
+                children.extend(elseifs.iter().map(|elseif| elseif as &dyn Node));
+                if let Some(r#else) = r#else {
+                    children.push(r#else as &dyn Node);
+                }
+                children
+            }
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            IfStatementBody::Statement { statement, .. } => statement.start(),
+            IfStatementBody::Block { colon, .. } => Some(*colon),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            IfStatementBody::Statement {
+                statement,
+                elseifs,
+                r#else,
+            } => match (r#else, elseifs.last()) {
+                (Some(r#else), _) => r#else.end(),
+                (None, Some(elseif)) => elseif.end(),
+                (None, None) => statement.end(),
+            },
+            IfStatementBody::Block { ending, .. } => Some(ending.end()),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -98,6 +164,14 @@ impl Node for IfStatementElseIf {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.condition, self.statement.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.condition, self.statement.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.elseif)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -111,6 +185,14 @@ impl Node for IfStatementElse {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.statement.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.statement.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#else)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -134,6 +216,27 @@ impl Node for IfStatementElseIfBlock {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.condition];
+        children.extend(
+            self.statements
+                .iter()
+                .map(|statement| statement as &dyn Node),
+        );
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.elseif)
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.statements
+            .last()
+            .and_then(|statement| statement.end())
+            .or(Some(self.colon.position + 1))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -151,4 +254,22 @@ impl Node for IfStatementElseBlock {
             .map(|statement| statement as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements
+            .iter()
+            .map(|statement| statement as &dyn Node)
+            .collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#else)
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.statements
+            .last()
+            .and_then(|statement| statement.end())
+            .or(Some(self.colon.position + 1))
+    }
 }
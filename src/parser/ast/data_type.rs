@@ -150,4 +150,25 @@ impl Node for Type {
             _ => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Type::Nullable(_, t) => vec![t.as_ref() as &dyn Node],
+            Type::Union(ts) => ts.iter().map(|x| x as &dyn Node).collect(),
+            Type::Intersection(ts) => ts.iter().map(|x| x as &dyn Node).collect(),
+            _ => vec![],
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.first_span())
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            Type::Nullable(_, inner) => inner.end(),
+            Type::Union(inner) | Type::Intersection(inner) => inner.last()?.end(),
+            _ => Some(self.first_span().position + self.to_string().len()),
+        }
+    }
 }
@@ -21,6 +21,10 @@ impl Node for DeclareEntry {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.key, &mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.key, &self.value]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -38,6 +42,18 @@ impl Node for DeclareEntryGroup {
             .map(|e| e as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.entries.iter().map(|e| e as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_parenthesis)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -79,6 +95,37 @@ impl Node for DeclareBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            DeclareBody::Noop { .. } => vec![],
+            DeclareBody::Braced { statements, .. } => {
+                statements.iter().map(|s| s as &dyn Node).collect()
+            }
+            DeclareBody::Expression { expression, .. } => vec![expression],
+            DeclareBody::Block { statements, .. } => {
+                statements.iter().map(|s| s as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            DeclareBody::Noop { semicolon } => Some(*semicolon),
+            DeclareBody::Braced { left_brace, .. } => Some(*left_brace),
+            DeclareBody::Expression { expression, .. } => expression.start(),
+            DeclareBody::Block { colon, .. } => Some(*colon),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            DeclareBody::Noop { semicolon } => Some(semicolon.position + 1),
+            DeclareBody::Braced { right_brace, .. } => Some(right_brace.position + 1),
+            DeclareBody::Expression { semicolon, .. } => Some(semicolon.position + 1),
+            DeclareBody::Block { end, .. } => Some(end.1.position + 1),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -93,4 +140,12 @@ impl Node for DeclareStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.entries, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.entries, &self.body]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.declare)
+    }
 }
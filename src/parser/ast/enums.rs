@@ -25,6 +25,21 @@ impl Node for UnitEnumCase {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name]
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.start))
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -45,6 +60,15 @@ impl Node for UnitEnumMember {
             UnitEnumMember::TraitUsage(trait_usage) => vec![trait_usage],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            UnitEnumMember::Case(case) => vec![case],
+            UnitEnumMember::Method(method) => vec![method],
+            UnitEnumMember::Constant(constant) => vec![constant],
+            UnitEnumMember::TraitUsage(trait_usage) => vec![trait_usage],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -62,6 +86,18 @@ impl Node for UnitEnumBody {
             .map(|m| m as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members.iter().map(|m| m as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -83,6 +119,22 @@ impl Node for UnitEnumStatement {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        for implement in &self.implements {
+            children.push(implement);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.r#enum))
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
@@ -93,7 +145,18 @@ pub enum BackedEnumType {
 }
 
 impl Node for BackedEnumType {
-    //
+    fn start(&self) -> Option<Span> {
+        match self {
+            BackedEnumType::String(colon, _) | BackedEnumType::Int(colon, _) => Some(*colon),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            BackedEnumType::String(_, span) => Some(span.position + "string".len()),
+            BackedEnumType::Int(_, span) => Some(span.position + "int".len()),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -111,6 +174,21 @@ impl Node for BackedEnumCase {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.value]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.value]
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.case))
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -131,6 +209,15 @@ impl Node for BackedEnumMember {
             BackedEnumMember::TraitUsage(trait_usage) => vec![trait_usage],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            BackedEnumMember::Case(case) => vec![case],
+            BackedEnumMember::Method(method) => vec![method],
+            BackedEnumMember::Constant(constant) => vec![constant],
+            BackedEnumMember::TraitUsage(trait_usage) => vec![trait_usage],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -148,6 +235,18 @@ impl Node for BackedEnumBody {
             .map(|m| m as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members.iter().map(|m| m as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -170,4 +269,20 @@ impl Node for BackedEnumStatement {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name, &self.backed_type];
+        for implement in &self.implements {
+            children.push(implement);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.r#enum))
+    }
 }
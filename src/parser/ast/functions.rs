@@ -28,6 +28,14 @@ impl Node for ReturnType {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.data_type]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.data_type]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.colon)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -43,6 +51,10 @@ pub struct FunctionParameter {
 }
 
 impl Node for FunctionParameter {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.name];
         if let Some(data_type) = &mut self.data_type {
@@ -53,6 +65,27 @@ impl Node for FunctionParameter {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(data_type) = &self.data_type {
+            children.push(data_type);
+        }
+        if let Some(default) = &self.default {
+            children.push(default);
+        }
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| self.data_type.as_ref()?.start())
+            .or(self.ampersand)
+            .or(self.ellipsis)
+            .or_else(|| self.name.start())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -80,9 +113,25 @@ impl IntoIterator for FunctionParameterList {
 }
 
 impl Node for FunctionParameterList {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.parameters.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parameters.children_ref()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_parenthesis)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -95,12 +144,28 @@ pub struct FunctionBody {
 }
 
 impl Node for FunctionBody {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.statements
             .iter_mut()
             .map(|x| x as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|x| x as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -117,6 +182,10 @@ pub struct FunctionStatement {
 }
 
 impl Node for FunctionStatement {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> =
             vec![&mut self.name, &mut self.parameters, &mut self.body];
@@ -125,6 +194,21 @@ impl Node for FunctionStatement {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name, &self.parameters, &self.body];
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.function))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -136,9 +220,21 @@ pub struct ClosureUseVariable {
 }
 
 impl Node for ClosureUseVariable {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.variable]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.variable]
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.ampersand.or_else(|| self.variable.start())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -152,9 +248,25 @@ pub struct ClosureUse {
 }
 
 impl Node for ClosureUse {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.variables.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.variables.children_ref()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#use)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -172,6 +284,10 @@ pub struct ClosureExpression {
 }
 
 impl Node for ClosureExpression {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.parameters];
         if let Some(uses) = &mut self.uses {
@@ -183,6 +299,26 @@ impl Node for ClosureExpression {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.parameters];
+        if let Some(uses) = &self.uses {
+            children.push(uses);
+        }
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(self.r#static)
+            .or(Some(self.function))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -200,6 +336,10 @@ pub struct ArrowFunctionExpression {
 }
 
 impl Node for ArrowFunctionExpression {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.parameters];
         if let Some(return_type) = &mut self.return_type {
@@ -208,6 +348,23 @@ impl Node for ArrowFunctionExpression {
         children.push(self.body.as_mut());
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.parameters];
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children.push(self.body.as_ref());
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(self.r#static)
+            .or(Some(self.r#fn))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -225,6 +382,10 @@ pub struct ConstructorParameter {
 }
 
 impl Node for ConstructorParameter {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.name];
         if let Some(data_type) = &mut self.data_type {
@@ -235,6 +396,33 @@ impl Node for ConstructorParameter {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(data_type) = &self.data_type {
+            children.push(data_type);
+        }
+        if let Some(default) = &self.default {
+            children.push(default);
+        }
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| {
+                self.modifiers
+                    .modifiers
+                    .first()
+                    .map(|modifier| modifier.span())
+            })
+            .or_else(|| self.data_type.as_ref()?.start())
+            .or(self.ampersand)
+            .or(self.ellipsis)
+            .or_else(|| self.name.start())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -247,9 +435,25 @@ pub struct ConstructorParameterList {
 }
 
 impl Node for ConstructorParameterList {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.parameters.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parameters.children_ref()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_parenthesis)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -269,9 +473,34 @@ pub struct AbstractConstructor {
 }
 
 impl Node for AbstractConstructor {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.parameters]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.parameters]
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| {
+                self.modifiers
+                    .modifiers
+                    .first()
+                    .map(|modifier| modifier.span())
+            })
+            .or(Some(self.function))
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -291,9 +520,30 @@ pub struct ConcreteConstructor {
 }
 
 impl Node for ConcreteConstructor {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.parameters, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.parameters, &self.body]
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| {
+                self.modifiers
+                    .modifiers
+                    .first()
+                    .map(|modifier| modifier.span())
+            })
+            .or(Some(self.function))
+    }
 }
 
 impl ConcreteConstructor {
@@ -330,6 +580,10 @@ pub struct AbstractMethod {
 }
 
 impl Node for AbstractMethod {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.name, &mut self.parameters];
         if let Some(return_type) = &mut self.return_type {
@@ -337,6 +591,31 @@ impl Node for AbstractMethod {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name, &self.parameters];
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| {
+                self.modifiers
+                    .modifiers
+                    .first()
+                    .map(|modifier| modifier.span())
+            })
+            .or(Some(self.function))
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -355,6 +634,10 @@ pub struct ConcreteMethod {
 }
 
 impl Node for ConcreteMethod {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         let mut children: Vec<&mut dyn Node> = vec![&mut self.name, &mut self.parameters];
         if let Some(return_type) = &mut self.return_type {
@@ -363,6 +646,28 @@ impl Node for ConcreteMethod {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name, &self.parameters];
+        if let Some(return_type) = &self.return_type {
+            children.push(return_type);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| {
+                self.modifiers
+                    .modifiers
+                    .first()
+                    .map(|modifier| modifier.span())
+            })
+            .or(Some(self.function))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -375,10 +680,26 @@ pub struct MethodBody {
 }
 
 impl Node for MethodBody {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.statements
             .iter_mut()
             .map(|s| s as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|s| s as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
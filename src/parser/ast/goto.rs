@@ -16,9 +16,21 @@ pub struct LabelStatement {
 }
 
 impl Node for LabelStatement {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.label]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.label]
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.colon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -31,7 +43,23 @@ pub struct GotoStatement {
 }
 
 impl Node for GotoStatement {
+    fn comments(&self) -> Option<&CommentGroup> {
+        Some(&self.comments)
+    }
+
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.label]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.label]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.keyword)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
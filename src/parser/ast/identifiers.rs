@@ -23,6 +23,27 @@ impl Node for Identifier {
             Identifier::DynamicIdentifier(identifier) => identifier.children(),
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Identifier::SimpleIdentifier(identifier) => identifier.children_ref(),
+            Identifier::DynamicIdentifier(identifier) => identifier.children_ref(),
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            Identifier::SimpleIdentifier(identifier) => identifier.start(),
+            Identifier::DynamicIdentifier(identifier) => identifier.start(),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            Identifier::SimpleIdentifier(identifier) => identifier.end(),
+            Identifier::DynamicIdentifier(identifier) => identifier.end(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -33,7 +54,13 @@ pub struct SimpleIdentifier {
 }
 
 impl Node for SimpleIdentifier {
-    //
+    fn start(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.span.position + self.value.len())
+    }
 }
 
 impl Display for SimpleIdentifier {
@@ -54,4 +81,16 @@ impl Node for DynamicIdentifier {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.expr.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.expr.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
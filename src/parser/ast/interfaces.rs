@@ -27,6 +27,14 @@ impl Node for InterfaceMember {
             InterfaceMember::Method(method) => vec![method],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            InterfaceMember::Constant(constant) => vec![constant],
+            InterfaceMember::Constructor(constructor) => vec![constructor],
+            InterfaceMember::Method(method) => vec![method],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -40,6 +48,14 @@ impl Node for InterfaceExtends {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.parents.children()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parents.children_ref()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.extends)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -57,6 +73,21 @@ impl Node for InterfaceBody {
             .map(|member| member as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members
+            .iter()
+            .map(|member| member as &dyn Node)
+            .collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -77,4 +108,19 @@ impl Node for InterfaceStatement {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(extends) = &self.extends {
+            children.push(extends);
+        }
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.interface))
+    }
 }
@@ -22,6 +22,14 @@ impl Node for Literal {
             Literal::Float(literal) => vec![literal],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Literal::String(literal) => vec![literal],
+            Literal::Integer(literal) => vec![literal],
+            Literal::Float(literal) => vec![literal],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -38,8 +46,16 @@ pub enum LiteralStringKind {
     DoubleQuoted,
 }
 
+// the value of literals is decoded, so their ends are only exact when they don't contain
+// escape sequences or digit separators.
 impl Node for LiteralString {
-    //
+    fn start(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.span.position + self.value.len() + 2)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -50,7 +66,13 @@ pub struct LiteralInteger {
 }
 
 impl Node for LiteralInteger {
-    //
+    fn start(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.span.position + self.value.len())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -61,5 +83,11 @@ pub struct LiteralFloat {
 }
 
 impl Node for LiteralFloat {
-    //
+    fn start(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.span.position + self.value.len())
+    }
 }
@@ -24,6 +24,14 @@ impl Node for ForeachStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.iterator, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.iterator, &self.body]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.foreach)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -63,6 +71,22 @@ impl Node for ForeachStatementIterator {
             } => vec![expression, key, value],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ForeachStatementIterator::Value {
+                expression, value, ..
+            } => {
+                vec![expression, value]
+            }
+            ForeachStatementIterator::KeyAndValue {
+                expression,
+                key,
+                value,
+                ..
+            } => vec![expression, key, value],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -88,6 +112,29 @@ impl Node for ForeachStatementBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ForeachStatementBody::Statement { statement } => vec![statement.as_ref()],
+            ForeachStatementBody::Block { statements, .. } => {
+                statements.iter().map(|s| s as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            ForeachStatementBody::Statement { statement } => statement.start(),
+            ForeachStatementBody::Block { colon, .. } => Some(*colon),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            ForeachStatementBody::Statement { statement } => statement.end(),
+            ForeachStatementBody::Block { ending, .. } => Some(ending.end()),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -104,6 +151,14 @@ impl Node for ForStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.iterator, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.iterator, &self.body]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#for)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -129,6 +184,26 @@ impl Node for ForStatementIterator {
         children.extend(self.r#loop.inner.iter_mut().map(|x| x as &mut dyn Node));
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children = vec![];
+        children.extend(self.initializations.inner.iter().map(|x| x as &dyn Node));
+        children.extend(self.conditions.inner.iter().map(|x| x as &dyn Node));
+        children.extend(self.r#loop.inner.iter().map(|x| x as &dyn Node));
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.initializations
+            .start()
+            .or(Some(self.initializations_semicolon))
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.r#loop
+            .end()
+            .or(Some(self.conditions_semicolon.position + 1))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -154,6 +229,29 @@ impl Node for ForStatementBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ForStatementBody::Statement { statement } => vec![statement.as_ref()],
+            ForStatementBody::Block { statements, .. } => {
+                statements.iter().map(|x| x as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            ForStatementBody::Statement { statement } => statement.start(),
+            ForStatementBody::Block { colon, .. } => Some(*colon),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            ForStatementBody::Statement { statement } => statement.end(),
+            ForStatementBody::Block { ending, .. } => Some(ending.end()),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -172,6 +270,18 @@ impl Node for DoWhileStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.body.as_mut(), &mut self.condition]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.body.as_ref(), &self.condition]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#do)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -188,6 +298,14 @@ impl Node for WhileStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.condition, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.condition, &self.body]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#while)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -213,6 +331,29 @@ impl Node for WhileStatementBody {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            WhileStatementBody::Statement { statement } => vec![statement.as_ref()],
+            WhileStatementBody::Block { statements, .. } => {
+                statements.iter().map(|s| s as &dyn Node).collect()
+            }
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            WhileStatementBody::Statement { statement } => statement.start(),
+            WhileStatementBody::Block { colon, .. } => Some(*colon),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            WhileStatementBody::Statement { statement } => statement.end(),
+            WhileStatementBody::Block { ending, .. } => Some(ending.end()),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -226,6 +367,29 @@ pub enum Level {
     },
 }
 
+impl Level {
+    /// The number of loops the level refers to, or `None` if the literal is out of range.
+    pub fn value(&self) -> Option<usize> {
+        match self {
+            Level::Literal(literal) => {
+                let value = literal.value.to_string().replace('_', "");
+                if let Some(hex) = value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+                    usize::from_str_radix(hex, 16).ok()
+                } else if let Some(binary) = value.strip_prefix("0b").or(value.strip_prefix("0B")) {
+                    usize::from_str_radix(binary, 2).ok()
+                } else if let Some(octal) = value.strip_prefix("0o").or(value.strip_prefix("0O")) {
+                    usize::from_str_radix(octal, 8).ok()
+                } else if value.len() > 1 && value.starts_with('0') {
+                    usize::from_str_radix(&value[1..], 8).ok()
+                } else {
+                    value.parse().ok()
+                }
+            }
+            Level::Parenthesized { level, .. } => level.value(),
+        }
+    }
+}
+
 impl Node for Level {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         match self {
@@ -233,6 +397,31 @@ impl Node for Level {
             Level::Parenthesized { level, .. } => level.children(),
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Level::Literal(literal) => vec![literal],
+            Level::Parenthesized { level, .. } => level.children_ref(),
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            Level::Literal(literal) => literal.start(),
+            Level::Parenthesized {
+                left_parenthesis, ..
+            } => Some(*left_parenthesis),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            Level::Literal(literal) => literal.end(),
+            Level::Parenthesized {
+                right_parenthesis, ..
+            } => Some(right_parenthesis.position + 1),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -250,6 +439,21 @@ impl Node for BreakStatement {
             None => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match &self.level {
+            Some(level) => vec![level],
+            None => vec![],
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#break)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.ending.end())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -267,4 +471,19 @@ impl Node for ContinueStatement {
             None => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match &self.level {
+            Some(level) => vec![level],
+            None => vec![],
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#continue)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.ending.end())
+    }
 }
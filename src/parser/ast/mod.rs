@@ -72,10 +72,20 @@ impl Node for Block {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.iter_mut().map(|s| s as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.iter().map(|s| s as &dyn Node).collect()
+    }
 }
 
 pub type Program = Block;
 
+// Every statement and expression takes the size of its largest variant, so the large
+// ones are boxed, which keeps the tree of a large file several times smaller.
+#[cfg(target_pointer_width = "64")]
+const _: () =
+    assert!(std::mem::size_of::<Statement>() <= 200 && std::mem::size_of::<Expression>() <= 144);
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub enum UseKind {
@@ -99,6 +109,14 @@ impl Node for StaticVar {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.var];
+        if let Some(default) = &self.default {
+            children.push(default);
+        }
+        children
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -108,24 +126,59 @@ pub enum Ending {
     CloseTag(Span),
 }
 
+impl Ending {
+    /// The position following the `;` or the `?>`, excluding the line break that
+    /// might follow a closing tag.
+    pub fn end(&self) -> usize {
+        match self {
+            Ending::Semicolon(span) => span.position + 1,
+            Ending::CloseTag(span) => span.position + 2,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub struct HaltCompilerStatement {
+    pub halt_compiler: Span, // `__halt_compiler`
     pub content: Option<ByteString>,
 }
 
-impl Node for HaltCompilerStatement {}
+impl Node for HaltCompilerStatement {
+    fn start(&self) -> Option<Span> {
+        Some(self.halt_compiler)
+    }
+
+    fn end(&self) -> Option<usize> {
+        // `__halt_compiler();`, the content following it being data rather than code.
+        Some(self.halt_compiler.position + "__halt_compiler();".len())
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub struct StaticStatement {
+    pub r#static: Span,
     pub vars: Vec<StaticVar>,
+    pub semicolon: Span,
 }
 
 impl Node for StaticStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.vars.iter_mut().map(|v| v as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.vars.iter().map(|v| v as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#static)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -136,6 +189,7 @@ pub struct SwitchStatement {
     pub condition: Expression,
     pub right_parenthesis: Span,
     pub cases: Vec<Case>,
+    pub end: SwitchStatementEnd,
 }
 
 impl Node for SwitchStatement {
@@ -144,6 +198,35 @@ impl Node for SwitchStatement {
         children.extend(self.cases.iter_mut().map(|c| c as &mut dyn Node));
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.condition];
+        children.extend(self.cases.iter().map(|c| c as &dyn Node));
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.switch)
+    }
+
+    fn end(&self) -> Option<usize> {
+        match &self.end {
+            SwitchStatementEnd::RightBrace { right_brace } => Some(right_brace.position + 1),
+            SwitchStatementEnd::EndSwitch { ending, .. } => Some(ending.end()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum SwitchStatementEnd {
+    RightBrace {
+        right_brace: Span, // `}`
+    },
+    EndSwitch {
+        endswitch: Span, // `endswitch`
+        ending: Ending,  // `;` or `?>`
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -158,6 +241,18 @@ impl Node for EchoStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.values.iter_mut().map(|v| v as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.values.iter().map(|v| v as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.echo)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.ending.end())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -176,27 +271,59 @@ impl Node for ReturnStatement {
             vec![]
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        if let Some(value) = &self.value {
+            vec![value]
+        } else {
+            vec![]
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#return)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.ending.end())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub struct UseStatement {
+    pub r#use: Span,
     pub kind: UseKind,
     pub uses: Vec<Use>,
+    pub semicolon: Span,
 }
 
 impl Node for UseStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.uses.iter_mut().map(|u| u as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.uses.iter().map(|u| u as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#use)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "type")]
 pub struct GroupUseStatement {
+    pub r#use: Span,
     pub prefix: SimpleIdentifier,
     pub kind: UseKind,
     pub uses: Vec<Use>,
+    pub semicolon: Span,
 }
 
 impl Node for GroupUseStatement {
@@ -205,6 +332,20 @@ impl Node for GroupUseStatement {
         children.extend(self.uses.iter_mut().map(|u| u as &mut dyn Node));
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.prefix];
+        children.extend(self.uses.iter().map(|u| u as &dyn Node));
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#use)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -219,19 +360,19 @@ pub enum Statement {
     Goto(GotoStatement),
     HaltCompiler(HaltCompilerStatement),
     Static(StaticStatement),
-    DoWhile(DoWhileStatement),
-    While(WhileStatement),
-    For(ForStatement),
-    Foreach(ForeachStatement),
+    DoWhile(Box<DoWhileStatement>),
+    While(Box<WhileStatement>),
+    For(Box<ForStatement>),
+    Foreach(Box<ForeachStatement>),
     Break(BreakStatement),
     Continue(ContinueStatement),
     Constant(ConstantStatement),
-    Function(FunctionStatement),
-    Class(ClassStatement),
+    Function(Box<FunctionStatement>),
+    Class(Box<ClassStatement>),
     Trait(TraitStatement),
-    Interface(InterfaceStatement),
-    If(IfStatement),
-    Switch(SwitchStatement),
+    Interface(Box<InterfaceStatement>),
+    If(Box<IfStatement>),
+    Switch(Box<SwitchStatement>),
     Echo(EchoStatement),
     Expression(ExpressionStatement),
     Return(ReturnStatement),
@@ -241,16 +382,17 @@ pub enum Statement {
     Comment(Comment),
     Try(TryStatement),
     UnitEnum(UnitEnumStatement),
-    BackedEnum(BackedEnumStatement),
+    BackedEnum(Box<BackedEnumStatement>),
     Block(BlockStatement),
     Global(GlobalStatement),
-    Declare(DeclareStatement),
+    Declare(Box<DeclareStatement>),
     Noop(Span),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct InlineHtmlStatement {
+    pub span: Span,
     pub html: ByteString,
 }
 
@@ -285,19 +427,19 @@ impl Node for Statement {
             Statement::Goto(statement) => vec![statement],
             Statement::HaltCompiler(statement) => vec![statement],
             Statement::Static(statement) => vec![statement],
-            Statement::DoWhile(statement) => vec![statement],
-            Statement::While(statement) => vec![statement],
-            Statement::For(statement) => vec![statement],
-            Statement::Foreach(statement) => vec![statement],
+            Statement::DoWhile(statement) => vec![statement.as_mut()],
+            Statement::While(statement) => vec![statement.as_mut()],
+            Statement::For(statement) => vec![statement.as_mut()],
+            Statement::Foreach(statement) => vec![statement.as_mut()],
             Statement::Break(statement) => vec![statement],
             Statement::Continue(statement) => vec![statement],
             Statement::Constant(statement) => vec![statement],
-            Statement::Function(statement) => vec![statement],
-            Statement::Class(statement) => vec![statement],
+            Statement::Function(statement) => vec![statement.as_mut()],
+            Statement::Class(statement) => vec![statement.as_mut()],
             Statement::Trait(statement) => vec![statement],
-            Statement::Interface(statement) => vec![statement],
-            Statement::If(statement) => vec![statement],
-            Statement::Switch(statement) => vec![statement],
+            Statement::Interface(statement) => vec![statement.as_mut()],
+            Statement::If(statement) => vec![statement.as_mut()],
+            Statement::Switch(statement) => vec![statement.as_mut()],
             Statement::Echo(statement) => vec![statement],
             Statement::Expression(statement) => vec![statement],
             Statement::Return(statement) => vec![statement],
@@ -307,13 +449,79 @@ impl Node for Statement {
             Statement::Comment(statement) => vec![statement],
             Statement::Try(statement) => vec![statement],
             Statement::UnitEnum(statement) => vec![statement],
-            Statement::BackedEnum(statement) => vec![statement],
+            Statement::BackedEnum(statement) => vec![statement.as_mut()],
             Statement::Block(statement) => vec![statement],
             Statement::Global(statement) => vec![statement],
-            Statement::Declare(statement) => vec![statement],
+            Statement::Declare(statement) => vec![statement.as_mut()],
             _ => vec![],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Statement::Label(statement) => vec![statement],
+            Statement::Goto(statement) => vec![statement],
+            Statement::HaltCompiler(statement) => vec![statement],
+            Statement::Static(statement) => vec![statement],
+            Statement::DoWhile(statement) => vec![statement.as_ref()],
+            Statement::While(statement) => vec![statement.as_ref()],
+            Statement::For(statement) => vec![statement.as_ref()],
+            Statement::Foreach(statement) => vec![statement.as_ref()],
+            Statement::Break(statement) => vec![statement],
+            Statement::Continue(statement) => vec![statement],
+            Statement::Constant(statement) => vec![statement],
+            Statement::Function(statement) => vec![statement.as_ref()],
+            Statement::Class(statement) => vec![statement.as_ref()],
+            Statement::Trait(statement) => vec![statement],
+            Statement::Interface(statement) => vec![statement.as_ref()],
+            Statement::If(statement) => vec![statement.as_ref()],
+            Statement::Switch(statement) => vec![statement.as_ref()],
+            Statement::Echo(statement) => vec![statement],
+            Statement::Expression(statement) => vec![statement],
+            Statement::Return(statement) => vec![statement],
+            Statement::Namespace(statement) => vec![statement],
+            Statement::Use(statement) => vec![statement],
+            Statement::GroupUse(statement) => vec![statement],
+            Statement::Comment(statement) => vec![statement],
+            Statement::Try(statement) => vec![statement],
+            Statement::UnitEnum(statement) => vec![statement],
+            Statement::BackedEnum(statement) => vec![statement.as_ref()],
+            Statement::Block(statement) => vec![statement],
+            Statement::Global(statement) => vec![statement],
+            Statement::Declare(statement) => vec![statement.as_ref()],
+            _ => vec![],
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            Statement::FullOpeningTag(FullOpeningTagStatement { span })
+            | Statement::ShortOpeningTag(ShortOpeningTagStatement { span })
+            | Statement::EchoOpeningTag(EchoOpeningTagStatement { span })
+            | Statement::ClosingTag(ClosingTagStatement { span })
+            | Statement::InlineHtml(InlineHtmlStatement { span, .. })
+            | Statement::Noop(span) => Some(*span),
+            _ => self.children_ref().first()?.start(),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            Statement::FullOpeningTag(FullOpeningTagStatement { span }) => {
+                Some(span.position + "<?php".len())
+            }
+            Statement::ShortOpeningTag(ShortOpeningTagStatement { span })
+            | Statement::ClosingTag(ClosingTagStatement { span }) => Some(span.position + 2),
+            Statement::EchoOpeningTag(EchoOpeningTagStatement { span }) => {
+                Some(span.position + "<?=".len())
+            }
+            Statement::InlineHtml(InlineHtmlStatement { span, html }) => {
+                Some(span.position + html.len())
+            }
+            Statement::Noop(span) => Some(span.position + 1),
+            _ => self.children_ref().first()?.end(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -327,6 +535,14 @@ impl Node for ExpressionStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.expression]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.expression]
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.ending.end())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -334,6 +550,7 @@ impl Node for ExpressionStatement {
 pub struct GlobalStatement {
     pub global: Span,
     pub variables: Vec<Variable>,
+    pub semicolon: Span,
 }
 
 impl Node for GlobalStatement {
@@ -343,6 +560,18 @@ impl Node for GlobalStatement {
             .map(|v| v as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.variables.iter().map(|v| v as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.global)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.semicolon.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -360,6 +589,18 @@ impl Node for BlockStatement {
             .map(|s| s as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|s| s as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 // See https://www.php.net/manual/en/language.types.type-juggling.php#language.types.typecasting for more info.
@@ -392,14 +633,16 @@ impl From<TokenKind> for CastKind {
 
 impl From<&TokenKind> for CastKind {
     fn from(kind: &TokenKind) -> Self {
-        kind.clone().into()
+        (*kind).into()
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct Case {
+    pub keyword: Span, // `case` or `default`
     pub condition: Option<Expression>,
+    pub colon: Span, // `:` or `;`
     pub body: Block,
 }
 
@@ -417,6 +660,31 @@ impl Node for Case {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(condition) = &self.condition {
+            children.push(condition);
+        }
+        children.extend(
+            self.body
+                .iter()
+                .map(|statement| statement as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.keyword)
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.body
+            .last()
+            .and_then(|statement| statement.end())
+            .or(Some(self.colon.position + 1))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -435,6 +703,14 @@ impl Node for Use {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.name];
+        if let Some(alias) = &self.alias {
+            children.push(alias);
+        }
+        children
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -556,6 +832,10 @@ impl Node for FunctionCallExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.arguments]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -569,6 +849,14 @@ impl Node for FunctionClosureCreationExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref()]
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.placeholder.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -590,6 +878,10 @@ impl Node for MethodCallExpression {
             &mut self.arguments,
         ]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.method.as_ref(), &self.arguments]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -607,6 +899,14 @@ impl Node for MethodClosureCreationExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), self.method.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.method.as_ref()]
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.placeholder.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -628,6 +928,10 @@ impl Node for NullsafeMethodCallExpression {
             &mut self.arguments,
         ]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.method.as_ref(), &self.arguments]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -645,6 +949,10 @@ impl Node for StaticMethodCallExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.method, &mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.method, &self.arguments]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -662,6 +970,10 @@ impl Node for StaticVariableMethodCallExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.method, &mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.method, &self.arguments]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -679,6 +991,14 @@ impl Node for StaticMethodClosureCreationExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.method]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.method]
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.placeholder.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -696,6 +1016,14 @@ impl Node for StaticVariableMethodClosureCreationExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.method]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.method]
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.placeholder.right_parenthesis.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -711,6 +1039,10 @@ impl Node for PropertyFetchExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), self.property.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.property.as_ref()]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -726,6 +1058,10 @@ impl Node for NullsafePropertyFetchExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), self.property.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), self.property.as_ref()]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -741,6 +1077,10 @@ impl Node for StaticPropertyFetchExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.property]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.property]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -756,6 +1096,10 @@ impl Node for ConstantFetchExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut(), &mut self.constant]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref(), &self.constant]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -771,6 +1115,18 @@ impl Node for ShortArrayExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.items]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.items]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -788,6 +1144,18 @@ impl Node for ArrayExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.items]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.items]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.array)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -805,6 +1173,18 @@ impl Node for ListExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.items.iter_mut().map(|i| i as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.items.iter().map(|i| i as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.list)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -824,11 +1204,25 @@ impl Node for NewExpression {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![self.target.as_ref()];
+        if let Some(arguments) = &self.arguments {
+            children.push(arguments);
+        }
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.new)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct InterpolatedStringExpression {
+    pub start: Span, // `"`
     pub parts: Vec<StringPart>,
+    pub end: Span, // `"`
 }
 
 impl Node for InterpolatedStringExpression {
@@ -838,12 +1232,26 @@ impl Node for InterpolatedStringExpression {
             .map(|part| part as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parts.iter().map(|part| part as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct HeredocExpression {
+    pub start: Span, // `<<<EOT`
     pub label: ByteString,
     pub parts: Vec<StringPart>,
+    pub end: Span, // `EOT`, without its indentation
 }
 
 impl Node for HeredocExpression {
@@ -853,19 +1261,52 @@ impl Node for HeredocExpression {
             .map(|part| part as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parts.iter().map(|part| part as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + closing_label_length(&self.label))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct NowdocExpression {
+    pub start: Span, // `<<<'EOT'`
     pub label: ByteString,
     pub value: ByteString,
+    pub end: Span, // `EOT`, without its indentation
 }
 
-impl Node for NowdocExpression {}
+impl Node for NowdocExpression {
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + closing_label_length(&self.label))
+    }
+}
+
+// the length of the label closing a heredoc or a nowdoc, which the opening one surrounds
+// with `<<<` and optional quotes.
+fn closing_label_length(label: &[u8]) -> usize {
+    label
+        .iter()
+        .filter(|byte| byte.is_ascii_alphanumeric() || **byte == b'_' || **byte >= 0x80)
+        .count()
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ShellExecExpression {
+    pub start: Span, // opening backtick
     pub parts: Vec<StringPart>,
+    pub end: Span, // closing backtick
 }
 
 impl Node for ShellExecExpression {
@@ -875,14 +1316,38 @@ impl Node for ShellExecExpression {
             .map(|part| part as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.parts.iter().map(|part| part as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct BoolExpression {
+    pub span: Span,
     pub value: bool,
 }
 
-impl Node for BoolExpression {}
+impl Node for BoolExpression {
+    fn start(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self.value {
+            true => Some(self.span.position + "true".len()),
+            false => Some(self.span.position + "false".len()),
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ArrayIndexExpression {
@@ -894,12 +1359,24 @@ pub struct ArrayIndexExpression {
 
 impl Node for ArrayIndexExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
-        let mut children: Vec<&mut dyn Node> = vec![];
+        let mut children: Vec<&mut dyn Node> = vec![self.array.as_mut()];
         if let Some(index) = &mut self.index {
             children.push(index.as_mut());
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![self.array.as_ref()];
+        if let Some(index) = &self.index {
+            children.push(index.as_ref());
+        }
+        children
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_bracket.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -915,6 +1392,10 @@ impl Node for ShortTernaryExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.condition.as_mut(), self.r#else.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.condition.as_ref(), self.r#else.as_ref()]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -938,6 +1419,14 @@ impl Node for TernaryExpression {
             self.r#else.as_mut(),
         ]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![
+            self.condition.as_ref(),
+            self.then.as_ref(),
+            self.r#else.as_ref(),
+        ]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -951,10 +1440,15 @@ impl Node for CoalesceExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.lhs.as_mut(), self.rhs.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct CloneExpression {
+    pub clone: Span,
     pub target: Box<Expression>,
 }
 
@@ -962,6 +1456,14 @@ impl Node for CloneExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.target.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.target.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.clone)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -990,10 +1492,33 @@ impl Node for MatchExpression {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![self.condition.as_ref()];
+        if let Some(default) = &self.default {
+            children.push(default.as_ref());
+        }
+        children.extend(
+            self.arms
+                .iter()
+                .map(|arm| arm as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.keyword)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ThrowExpression {
+    pub throw: Span,
     pub value: Box<Expression>,
 }
 
@@ -1001,10 +1526,19 @@ impl Node for ThrowExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.value.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.value.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.throw)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct YieldExpression {
+    pub r#yield: Span,
     pub key: Option<Box<Expression>>,
     pub value: Option<Box<Expression>>,
 }
@@ -1020,10 +1554,34 @@ impl Node for YieldExpression {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(key) = &self.key {
+            children.push(key.as_ref());
+        }
+        if let Some(value) = &self.value {
+            children.push(value.as_ref());
+        }
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#yield)
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.value
+            .as_ref()
+            .and_then(|value| value.end())
+            .or(Some(self.r#yield.position + "yield".len()))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct YieldFromExpression {
+    pub r#yield: Span, // `yield`
+    pub from: Span,    // `from`
     pub value: Box<Expression>,
 }
 
@@ -1031,6 +1589,14 @@ impl Node for YieldFromExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.value.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.value.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#yield)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1044,6 +1610,14 @@ impl Node for CastExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.value.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.value.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.cast)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1102,13 +1676,13 @@ pub enum Expression {
     // `$foo?->bar(1, 2, 3)`
     NullsafeMethodCall(NullsafeMethodCallExpression),
     // `Foo::bar(1, 2, 3)`
-    StaticMethodCall(StaticMethodCallExpression),
+    StaticMethodCall(Box<StaticMethodCallExpression>),
     // `Foo::$bar(1, 2, 3)`
-    StaticVariableMethodCall(StaticVariableMethodCallExpression),
+    StaticVariableMethodCall(Box<StaticVariableMethodCallExpression>),
     // `Foo::bar(...)`
-    StaticMethodClosureCreation(StaticMethodClosureCreationExpression),
+    StaticMethodClosureCreation(Box<StaticMethodClosureCreationExpression>),
     // `Foo::$bar(...)`
-    StaticVariableMethodClosureCreation(StaticVariableMethodClosureCreationExpression),
+    StaticVariableMethodClosureCreation(Box<StaticVariableMethodClosureCreationExpression>),
     // `foo()->bar`
     PropertyFetch(PropertyFetchExpression),
     // `foo()?->bar`
@@ -1118,11 +1692,11 @@ pub enum Expression {
     // `foo()::bar` or `foo()::{$name}`
     ConstantFetch(ConstantFetchExpression),
     // `static`
-    Static,
+    Static(Span),
     // `self`
-    Self_,
+    Self_(Span),
     // `parent`
-    Parent,
+    Parent(Span),
     // `[1, 2, 3]`
     ShortArray(ShortArrayExpression),
     // `array(1, 2, 3)`
@@ -1130,9 +1704,9 @@ pub enum Expression {
     // list($a, $b)
     List(ListExpression),
     // `function() {}`
-    Closure(ClosureExpression),
+    Closure(Box<ClosureExpression>),
     // `fn() => $foo`
-    ArrowFunction(ArrowFunctionExpression),
+    ArrowFunction(Box<ArrowFunctionExpression>),
     // `new Foo(1, 2, 3)`
     New(NewExpression),
     // `"foo $bar foo"`
@@ -1144,13 +1718,13 @@ pub enum Expression {
     // ``foo``
     ShellExec(ShellExecExpression),
     // `new class { ... }`
-    AnonymousClass(AnonymousClassExpression),
+    AnonymousClass(Box<AnonymousClassExpression>),
     // `true`, `false`
     Bool(BoolExpression),
     // `$foo[0]`
     ArrayIndex(ArrayIndexExpression),
     // `null`
-    Null,
+    Null(Span),
     // `__DIR__`, etc
     MagicConstant(MagicConstantExpression),
     // `foo() ?: bar()`
@@ -1162,7 +1736,7 @@ pub enum Expression {
     // `clone $foo`
     Clone(CloneExpression),
     // `match ($foo) { ... }`
-    Match(MatchExpression),
+    Match(Box<MatchExpression>),
     // `throw new Exception`
     Throw(ThrowExpression),
     // `yield $foo`
@@ -1179,12 +1753,28 @@ impl Node for EvalExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.argument.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.argument.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.eval)
+    }
 }
 
 impl Node for EmptyExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.argument.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.argument.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.empty)
+    }
 }
 
 impl Node for DieExpression {
@@ -1195,6 +1785,25 @@ impl Node for DieExpression {
             vec![]
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        if let Some(argument) = &self.argument {
+            vec![argument.as_ref()]
+        } else {
+            vec![]
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.die)
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.argument
+            .as_ref()
+            .and_then(|argument| argument.end())
+            .or(Some(self.die.position + "die".len()))
+    }
 }
 
 impl Node for ExitExpression {
@@ -1205,18 +1814,53 @@ impl Node for ExitExpression {
             vec![]
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        if let Some(argument) = &self.argument {
+            vec![argument.as_ref()]
+        } else {
+            vec![]
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.exit)
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.argument
+            .as_ref()
+            .and_then(|argument| argument.end())
+            .or(Some(self.exit.position + "exit".len()))
+    }
 }
 
 impl Node for IssetExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.arguments]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.isset)
+    }
 }
 
 impl Node for UnsetExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.arguments]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.arguments]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.unset)
+    }
 }
 
 impl Node for PrintExpression {
@@ -1229,60 +1873,142 @@ impl Node for PrintExpression {
             vec![]
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        if let Some(argument) = &self.argument {
+            vec![argument.as_ref()]
+        } else if let Some(value) = &self.value {
+            vec![value.as_ref()]
+        } else {
+            vec![]
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.print)
+    }
 }
 
 impl Node for ConcatExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.left.as_mut(), self.right.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
 }
 
 impl Node for InstanceofExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.left.as_mut(), self.right.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
 }
 
 impl Node for ReferenceExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.right.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.right.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.ampersand)
+    }
 }
 
 impl Node for ParenthesizedExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.expr.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.expr.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 impl Node for ErrorSuppressExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.expr.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.expr.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.at)
+    }
 }
 
 impl Node for IncludeExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.path.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.path.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.include)
+    }
 }
 
 impl Node for IncludeOnceExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.path.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.path.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.include_once)
+    }
 }
 
 impl Node for RequireExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.path.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.path.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.require)
+    }
 }
 
 impl Node for RequireOnceExpression {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.path.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.path.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.require_once)
+    }
 }
 
 impl Node for Expression {
@@ -1317,37 +2043,111 @@ impl Node for Expression {
             Expression::MethodCall(expression) => vec![expression],
             Expression::MethodClosureCreation(expression) => vec![expression],
             Expression::NullsafeMethodCall(expression) => vec![expression],
-            Expression::StaticMethodCall(expression) => vec![expression],
-            Expression::StaticVariableMethodCall(expression) => vec![expression],
-            Expression::StaticMethodClosureCreation(expression) => vec![expression],
-            Expression::StaticVariableMethodClosureCreation(expression) => vec![expression],
+            Expression::StaticMethodCall(expression) => vec![expression.as_mut()],
+            Expression::StaticVariableMethodCall(expression) => vec![expression.as_mut()],
+            Expression::StaticMethodClosureCreation(expression) => vec![expression.as_mut()],
+            Expression::StaticVariableMethodClosureCreation(expression) => {
+                vec![expression.as_mut()]
+            }
+            Expression::PropertyFetch(expression) => vec![expression],
+            Expression::NullsafePropertyFetch(expression) => vec![expression],
+            Expression::StaticPropertyFetch(expression) => vec![expression],
+            Expression::ConstantFetch(expression) => vec![expression],
+            Expression::Static(_) => vec![],
+            Expression::Self_(_) => vec![],
+            Expression::Parent(_) => vec![],
+            Expression::ShortArray(expression) => vec![expression],
+            Expression::Array(expression) => vec![expression],
+            Expression::List(expression) => vec![expression],
+            Expression::Closure(expression) => vec![expression.as_mut()],
+            Expression::ArrowFunction(expression) => vec![expression.as_mut()],
+            Expression::New(expression) => vec![expression],
+            Expression::InterpolatedString(expression) => vec![expression],
+            Expression::Heredoc(expression) => vec![expression],
+            Expression::Nowdoc(expression) => vec![expression],
+            Expression::ShellExec(expression) => vec![expression],
+            Expression::AnonymousClass(expression) => vec![expression.as_mut()],
+            Expression::Bool(_) => vec![],
+            Expression::ArrayIndex(expression) => vec![expression],
+            Expression::Null(_) => vec![],
+            Expression::MagicConstant(constant) => vec![constant],
+            Expression::ShortTernary(expression) => vec![expression],
+            Expression::Ternary(expression) => vec![expression],
+            Expression::Coalesce(expression) => vec![expression],
+            Expression::Clone(expression) => vec![expression],
+            Expression::Match(expression) => vec![expression.as_mut()],
+            Expression::Throw(expression) => vec![expression],
+            Expression::Yield(expression) => vec![expression],
+            Expression::YieldFrom(expression) => vec![expression],
+            Expression::Cast(expression) => vec![expression],
+            Expression::Noop => vec![],
+        }
+    }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Expression::Eval(expression) => vec![expression],
+            Expression::Empty(expression) => vec![expression],
+            Expression::Die(expression) => vec![expression],
+            Expression::Exit(expression) => vec![expression],
+            Expression::Isset(expression) => vec![expression],
+            Expression::Unset(expression) => vec![expression],
+            Expression::Print(expression) => vec![expression],
+            Expression::Literal(literal) => vec![literal],
+            Expression::ArithmeticOperation(operation) => vec![operation],
+            Expression::AssignmentOperation(operation) => vec![operation],
+            Expression::BitwiseOperation(operation) => vec![operation],
+            Expression::ComparisonOperation(operation) => vec![operation],
+            Expression::LogicalOperation(operation) => vec![operation],
+            Expression::Concat(expression) => vec![expression],
+            Expression::Instanceof(expression) => vec![expression],
+            Expression::Reference(expression) => vec![expression],
+            Expression::Parenthesized(expression) => vec![expression],
+            Expression::ErrorSuppress(expression) => vec![expression],
+            Expression::Identifier(identifier) => vec![identifier],
+            Expression::Variable(variable) => vec![variable],
+            Expression::Include(expression) => vec![expression],
+            Expression::IncludeOnce(expression) => vec![expression],
+            Expression::Require(expression) => vec![expression],
+            Expression::RequireOnce(expression) => vec![expression],
+            Expression::FunctionCall(expression) => vec![expression],
+            Expression::FunctionClosureCreation(expression) => vec![expression],
+            Expression::MethodCall(expression) => vec![expression],
+            Expression::MethodClosureCreation(expression) => vec![expression],
+            Expression::NullsafeMethodCall(expression) => vec![expression],
+            Expression::StaticMethodCall(expression) => vec![expression.as_ref()],
+            Expression::StaticVariableMethodCall(expression) => vec![expression.as_ref()],
+            Expression::StaticMethodClosureCreation(expression) => vec![expression.as_ref()],
+            Expression::StaticVariableMethodClosureCreation(expression) => {
+                vec![expression.as_ref()]
+            }
             Expression::PropertyFetch(expression) => vec![expression],
             Expression::NullsafePropertyFetch(expression) => vec![expression],
             Expression::StaticPropertyFetch(expression) => vec![expression],
             Expression::ConstantFetch(expression) => vec![expression],
-            Expression::Static => vec![],
-            Expression::Self_ => vec![],
-            Expression::Parent => vec![],
+            Expression::Static(_) => vec![],
+            Expression::Self_(_) => vec![],
+            Expression::Parent(_) => vec![],
             Expression::ShortArray(expression) => vec![expression],
             Expression::Array(expression) => vec![expression],
             Expression::List(expression) => vec![expression],
-            Expression::Closure(expression) => vec![expression],
-            Expression::ArrowFunction(expression) => vec![expression],
+            Expression::Closure(expression) => vec![expression.as_ref()],
+            Expression::ArrowFunction(expression) => vec![expression.as_ref()],
             Expression::New(expression) => vec![expression],
             Expression::InterpolatedString(expression) => vec![expression],
             Expression::Heredoc(expression) => vec![expression],
             Expression::Nowdoc(expression) => vec![expression],
             Expression::ShellExec(expression) => vec![expression],
-            Expression::AnonymousClass(expression) => vec![expression],
+            Expression::AnonymousClass(expression) => vec![expression.as_ref()],
             Expression::Bool(_) => vec![],
             Expression::ArrayIndex(expression) => vec![expression],
-            Expression::Null => vec![],
+            Expression::Null(_) => vec![],
             Expression::MagicConstant(constant) => vec![constant],
             Expression::ShortTernary(expression) => vec![expression],
             Expression::Ternary(expression) => vec![expression],
             Expression::Coalesce(expression) => vec![expression],
             Expression::Clone(expression) => vec![expression],
-            Expression::Match(expression) => vec![expression],
+            Expression::Match(expression) => vec![expression.as_ref()],
             Expression::Throw(expression) => vec![expression],
             Expression::Yield(expression) => vec![expression],
             Expression::YieldFrom(expression) => vec![expression],
@@ -1355,6 +2155,28 @@ impl Node for Expression {
             Expression::Noop => vec![],
         }
     }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            Expression::Static(span)
+            | Expression::Self_(span)
+            | Expression::Parent(span)
+            | Expression::Null(span) => Some(*span),
+            Expression::Bool(expression) => expression.start(),
+            _ => self.children_ref().first()?.start(),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            Expression::Static(span) => Some(span.position + "static".len()),
+            Expression::Self_(span) => Some(span.position + "self".len()),
+            Expression::Parent(span) => Some(span.position + "parent".len()),
+            Expression::Null(span) => Some(span.position + "null".len()),
+            Expression::Bool(expression) => expression.end(),
+            _ => self.children_ref().first()?.end(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1369,6 +2191,14 @@ impl Node for DefaultMatchArm {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.body]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.keyword)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1389,6 +2219,16 @@ impl Node for MatchArm {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = self
+            .conditions
+            .iter()
+            .map(|condition| condition as &dyn Node)
+            .collect();
+        children.push(&self.body);
+        children
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1406,7 +2246,35 @@ pub enum MagicConstantExpression {
 }
 
 impl Node for MagicConstantExpression {
-    //
+    fn start(&self) -> Option<Span> {
+        match self {
+            MagicConstantExpression::Directory(span)
+            | MagicConstantExpression::File(span)
+            | MagicConstantExpression::Line(span)
+            | MagicConstantExpression::Class(span)
+            | MagicConstantExpression::Function(span)
+            | MagicConstantExpression::Method(span)
+            | MagicConstantExpression::Namespace(span)
+            | MagicConstantExpression::Trait(span)
+            | MagicConstantExpression::CompilerHaltOffset(span) => Some(*span),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        let (span, constant) = match self {
+            MagicConstantExpression::Directory(span) => (span, "__DIR__"),
+            MagicConstantExpression::File(span) => (span, "__FILE__"),
+            MagicConstantExpression::Line(span) => (span, "__LINE__"),
+            MagicConstantExpression::Class(span) => (span, "__CLASS__"),
+            MagicConstantExpression::Function(span) => (span, "__FUNCTION__"),
+            MagicConstantExpression::Method(span) => (span, "__METHOD__"),
+            MagicConstantExpression::Namespace(span) => (span, "__NAMESPACE__"),
+            MagicConstantExpression::Trait(span) => (span, "__TRAIT__"),
+            MagicConstantExpression::CompilerHaltOffset(span) => (span, "__COMPILER_HALT_OFFSET__"),
+        };
+
+        Some(span.position + constant.len())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1436,6 +2304,10 @@ impl Node for ExpressionStringPart {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.expression.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.expression.as_ref()]
+    }
 }
 
 impl Node for StringPart {
@@ -1445,6 +2317,13 @@ impl Node for StringPart {
             StringPart::Expression(part) => vec![part],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            StringPart::Literal(part) => vec![part],
+            StringPart::Expression(part) => vec![part],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1498,6 +2377,37 @@ impl Node for ArrayItem {
             } => vec![key, value],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ArrayItem::Skipped => vec![],
+            ArrayItem::Value { value } => vec![value],
+            ArrayItem::ReferencedValue {
+                ampersand: _,
+                value,
+            } => vec![value],
+            ArrayItem::SpreadValue { ellipsis: _, value } => vec![value],
+            ArrayItem::KeyValue {
+                key,
+                double_arrow: _,
+                value,
+            } => vec![key, value],
+            ArrayItem::ReferencedKeyValue {
+                key,
+                double_arrow: _,
+                ampersand: _,
+                value,
+            } => vec![key, value],
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            ArrayItem::ReferencedValue { ampersand, .. } => Some(*ampersand),
+            ArrayItem::SpreadValue { ellipsis, .. } => Some(*ellipsis),
+            _ => self.children_ref().first()?.start(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1526,4 +2436,16 @@ impl Node for ListEntry {
             } => vec![key, value],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ListEntry::Skipped => vec![],
+            ListEntry::Value { value } => vec![value],
+            ListEntry::KeyValue {
+                key,
+                double_arrow: _,
+                value,
+            } => vec![key, value],
+        }
+    }
 }
@@ -237,6 +237,16 @@ pub enum ClassModifier {
     Readonly(Span),
 }
 
+impl ClassModifier {
+    pub fn span(&self) -> Span {
+        match self {
+            ClassModifier::Final(span) => *span,
+            ClassModifier::Abstract(span) => *span,
+            ClassModifier::Readonly(span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[repr(transparent)]
 pub struct ClassModifierGroup {
@@ -276,6 +286,17 @@ pub enum ConstantModifier {
     Private(Span),
 }
 
+impl ConstantModifier {
+    pub fn span(&self) -> Span {
+        match self {
+            ConstantModifier::Final(span) => *span,
+            ConstantModifier::Public(span) => *span,
+            ConstantModifier::Protected(span) => *span,
+            ConstantModifier::Private(span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[repr(transparent)]
 pub struct ConstantModifierGroup {
@@ -27,6 +27,28 @@ impl Node for UnbracedNamespace {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children = vec![&self.name as &dyn Node];
+        children.extend(
+            self.statements
+                .iter()
+                .map(|s| s as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.statements
+            .last()
+            .and_then(|statement| statement.end())
+            .or(Some(self.end.position + 1))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -46,6 +68,19 @@ impl Node for BracedNamespace {
         children.push(&mut self.body);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(name) = &self.name {
+            children.push(name);
+        }
+        children.push(&self.body);
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.namespace)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -63,6 +98,18 @@ impl Node for BracedNamespaceBody {
             .map(|s| s as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.statements.iter().map(|s| s as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -79,4 +126,11 @@ impl Node for NamespaceStatement {
             NamespaceStatement::Braced(namespace) => vec![namespace],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            NamespaceStatement::Unbraced(namespace) => vec![namespace],
+            NamespaceStatement::Braced(namespace) => vec![namespace],
+        }
+    }
 }
@@ -94,6 +94,61 @@ impl Node for ArithmeticOperationExpression {
             ArithmeticOperationExpression::PostDecrement { left, .. } => vec![left.as_mut()],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ArithmeticOperationExpression::Addition { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Subtraction { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Multiplication { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Division { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Modulo { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Exponentiation { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ArithmeticOperationExpression::Negative { right, .. } => vec![right.as_ref()],
+            ArithmeticOperationExpression::Positive { right, .. } => vec![right.as_ref()],
+            ArithmeticOperationExpression::PreIncrement { right, .. } => vec![right.as_ref()],
+            ArithmeticOperationExpression::PostIncrement { left, .. } => vec![left.as_ref()],
+            ArithmeticOperationExpression::PreDecrement { right, .. } => vec![right.as_ref()],
+            ArithmeticOperationExpression::PostDecrement { left, .. } => vec![left.as_ref()],
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            ArithmeticOperationExpression::Negative { minus: span, .. }
+            | ArithmeticOperationExpression::Positive { plus: span, .. }
+            | ArithmeticOperationExpression::PreIncrement {
+                increment: span, ..
+            }
+            | ArithmeticOperationExpression::PreDecrement {
+                decrement: span, ..
+            } => Some(*span),
+            _ => self.children_ref().first()?.start(),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            ArithmeticOperationExpression::PostIncrement {
+                increment: span, ..
+            }
+            | ArithmeticOperationExpression::PostDecrement {
+                decrement: span, ..
+            } => Some(span.position + 2),
+            _ => self.children_ref().last()?.end(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -287,6 +342,53 @@ impl Node for AssignmentOperationExpression {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            AssignmentOperationExpression::Assign { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Addition { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Subtraction { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Multiplication { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Division { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Modulo { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Exponentiation { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Concat { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::BitwiseAnd { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::BitwiseOr { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::BitwiseXor { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::LeftShift { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::RightShift { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            AssignmentOperationExpression::Coalesce { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -344,6 +446,34 @@ impl Node for BitwiseOperationExpression {
             BitwiseOperationExpression::Not { right, .. } => vec![right.as_mut()],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            BitwiseOperationExpression::And { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::Or { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::Xor { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::LeftShift { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::RightShift { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            BitwiseOperationExpression::Not { right, .. } => vec![right.as_ref()],
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            BitwiseOperationExpression::Not { not, .. } => Some(*not),
+            _ => self.children_ref().first()?.start(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -436,6 +566,41 @@ impl Node for ComparisonOperationExpression {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            ComparisonOperationExpression::Equal { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::Identical { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::NotEqual { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::AngledNotEqual { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::NotIdentical { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::LessThan { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::GreaterThan { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::LessThanOrEqual { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::GreaterThanOrEqual { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            ComparisonOperationExpression::Spaceship { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -493,4 +658,32 @@ impl Node for LogicalOperationExpression {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            LogicalOperationExpression::And { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            LogicalOperationExpression::Or { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            LogicalOperationExpression::Not { right, .. } => vec![right.as_ref()],
+            LogicalOperationExpression::LogicalAnd { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            LogicalOperationExpression::LogicalOr { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+            LogicalOperationExpression::LogicalXor { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            LogicalOperationExpression::Not { bang, .. } => Some(*bang),
+            _ => self.children_ref().first()?.start(),
+        }
+    }
 }
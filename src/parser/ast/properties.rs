@@ -35,12 +35,45 @@ impl Node for Property {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(r#type) = &self.r#type {
+            children.push(r#type);
+        }
+        children.extend(
+            self.entries
+                .iter()
+                .map(|e| e as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or_else(|| {
+                self.modifiers
+                    .modifiers
+                    .first()
+                    .map(|modifier| modifier.span())
+            })
+            .or_else(|| self.r#type.as_ref()?.start())
+            .or_else(|| self.entries.first()?.start())
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct VariableProperty {
     pub attributes: Vec<AttributeGroup>,
+    pub var: Span,
     pub r#type: Option<Type>,
     pub entries: Vec<PropertyEntry>,
     pub end: Span,
@@ -60,6 +93,31 @@ impl Node for VariableProperty {
         );
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![];
+        if let Some(r#type) = &self.r#type {
+            children.push(r#type);
+        }
+        children.extend(
+            self.entries
+                .iter()
+                .map(|e| e as &dyn Node)
+                .collect::<Vec<&dyn Node>>(),
+        );
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.var))
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -84,6 +142,15 @@ impl Node for PropertyEntry {
             } => vec![variable, value],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            PropertyEntry::Uninitialized { variable } => vec![variable],
+            PropertyEntry::Initialized {
+                variable, value, ..
+            } => vec![variable, value],
+        }
+    }
 }
 
 impl PropertyEntry {
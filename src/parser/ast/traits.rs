@@ -41,6 +41,19 @@ impl Node for TraitMember {
             TraitMember::ConcreteConstructor(constructor) => vec![constructor],
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            TraitMember::Constant(constant) => vec![constant],
+            TraitMember::TraitUsage(usage) => vec![usage],
+            TraitMember::Property(property) => vec![property],
+            TraitMember::VariableProperty(property) => vec![property],
+            TraitMember::AbstractMethod(method) => vec![method],
+            TraitMember::AbstractConstructor(constructor) => vec![constructor],
+            TraitMember::ConcreteMethod(method) => vec![method],
+            TraitMember::ConcreteConstructor(constructor) => vec![constructor],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -58,6 +71,21 @@ impl Node for TraitBody {
             .map(|member| member as &mut dyn Node)
             .collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.members
+            .iter()
+            .map(|member| member as &dyn Node)
+            .collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.left_brace)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.right_brace.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -73,6 +101,17 @@ impl Node for TraitStatement {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.name, &mut self.body]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.name, &self.body]
+    }
+
+    fn start(&self) -> Option<Span> {
+        self.attributes
+            .first()
+            .map(|group| group.start)
+            .or(Some(self.r#trait))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -81,12 +120,25 @@ pub struct TraitUsage {
     pub r#use: Span,
     pub traits: Vec<SimpleIdentifier>,
     pub adaptations: Vec<TraitUsageAdaptation>,
+    pub end: Span, // `;` or `}`
 }
 
 impl Node for TraitUsage {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.traits.iter_mut().map(|t| t as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.traits.iter().map(|t| t as &dyn Node).collect()
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.r#use)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -25,13 +25,22 @@ impl Node for CatchType {
             }
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            CatchType::Identifier { identifier } => vec![identifier],
+            CatchType::Union { identifiers } => {
+                identifiers.iter().map(|i| i as &dyn Node).collect()
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct TryStatement {
-    pub start: Span,
-    pub end: Span,
+    pub start: Span, // `try`
+    pub end: Span,   // the last `}`
     pub body: Block,
     pub catches: Vec<CatchBlock>,
     pub finally: Option<FinallyBlock>,
@@ -48,13 +57,32 @@ impl Node for TryStatement {
         }
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children: Vec<&dyn Node> = vec![&self.body];
+        for catch in &self.catches {
+            children.push(catch);
+        }
+        if let Some(finally) = &self.finally {
+            children.push(finally);
+        }
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct CatchBlock {
-    pub start: Span,
-    pub end: Span,
+    pub start: Span, // `catch`
+    pub end: Span,   // `}`
     pub types: CatchType,
     pub var: Option<SimpleVariable>,
     pub body: Block,
@@ -69,13 +97,30 @@ impl Node for CatchBlock {
         children.push(&mut self.body as &mut dyn Node);
         children
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        let mut children = vec![&self.types as &dyn Node];
+        if let Some(var) = &self.var {
+            children.push(var as &dyn Node);
+        }
+        children.push(&self.body as &dyn Node);
+        children
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 
 pub struct FinallyBlock {
-    pub start: Span,
-    pub end: Span,
+    pub start: Span, // `finally`
+    pub end: Span,   // `}`
     pub body: Block,
 }
 
@@ -83,4 +128,16 @@ impl Node for FinallyBlock {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![&mut self.body as &mut dyn Node]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![&self.body as &dyn Node]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
@@ -29,6 +29,16 @@ impl<T: Node> Node for CommaSeparated<T> {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         self.inner.iter_mut().map(|x| x as &mut dyn Node).collect()
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        self.inner.iter().map(|x| x as &dyn Node).collect()
+    }
+
+    fn end(&self) -> Option<usize> {
+        let comma = self.commas.last().map(|comma| comma.position + 1);
+
+        self.inner.last().and_then(|last| last.end()).max(comma)
+    }
 }
 
 impl<T> IntoIterator for CommaSeparated<T> {
@@ -25,6 +25,30 @@ impl Node for Variable {
             Variable::BracedVariableVariable(variable) => variable.children(),
         }
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        match self {
+            Variable::SimpleVariable(variable) => variable.children_ref(),
+            Variable::VariableVariable(variable) => variable.children_ref(),
+            Variable::BracedVariableVariable(variable) => variable.children_ref(),
+        }
+    }
+
+    fn start(&self) -> Option<Span> {
+        match self {
+            Variable::SimpleVariable(variable) => variable.start(),
+            Variable::VariableVariable(variable) => variable.start(),
+            Variable::BracedVariableVariable(variable) => variable.start(),
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        match self {
+            Variable::SimpleVariable(variable) => variable.end(),
+            Variable::VariableVariable(variable) => variable.end(),
+            Variable::BracedVariableVariable(variable) => variable.end(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -35,7 +59,13 @@ pub struct SimpleVariable {
 }
 
 impl Node for SimpleVariable {
-    //
+    fn start(&self) -> Option<Span> {
+        Some(self.span)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.span.position + self.name.len())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -49,6 +79,14 @@ impl Node for VariableVariable {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.variable.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.variable.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.span)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -63,6 +101,18 @@ impl Node for BracedVariableVariable {
     fn children(&mut self) -> Vec<&mut dyn Node> {
         vec![self.variable.as_mut()]
     }
+
+    fn children_ref(&self) -> Vec<&dyn Node> {
+        vec![self.variable.as_ref()]
+    }
+
+    fn start(&self) -> Option<Span> {
+        Some(self.start)
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.end.position + 1)
+    }
 }
 
 impl Display for SimpleVariable {
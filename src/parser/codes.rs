@@ -0,0 +1,2120 @@
+//! A catalog of the stable error codes used by [`ParseError::id`](crate::parser::error::ParseError).
+//!
+//! Codes are never reused: once a code has been assigned to an error, it keeps
+//! referring to that error, even if the error is later removed.
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ErrorCode {
+    /// The code, as found in `ParseError::id` (e.g. `E012`).
+    pub code: &'static str,
+    /// The constructor in `parser::error`, or the `SyntaxError` variant, producing this error.
+    pub source: &'static str,
+    /// A one-line summary of the error.
+    pub summary: &'static str,
+    /// A long-form explanation of the error, including examples.
+    pub explanation: &'static str,
+}
+
+/// Find the explanation for the given code.
+///
+/// The lookup is case-insensitive, and the leading zeros can be omitted (e.g. `e12`).
+pub fn lookup(code: &str) -> Option<&'static ErrorCode> {
+    let number = code
+        .strip_prefix('E')
+        .or_else(|| code.strip_prefix('e'))
+        .and_then(|number| number.parse::<usize>().ok())?;
+
+    CODES
+        .iter()
+        .find(|entry| entry.code[1..].parse::<usize>().ok() == Some(number))
+}
+
+pub const CODES: &[ErrorCode] = &[
+    ErrorCode {
+        code: "E001",
+        source: "SyntaxError::UnexpectedEndOfFile",
+        summary: "unexpected end of file",
+        explanation: r#"The end of the file was reached in the middle of a token, for example
+right after the `<<<` that starts a heredoc or a nowdoc.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = <<<```
+
+Complete the token:
+
+```php
+<?php
+
+$a = <<<EOT
+foo
+EOT;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E002",
+        source: "unexpected_token",
+        summary: "unexpected end of file",
+        explanation: r#"The end of the file was reached while the parser was still expecting
+more code, usually because a block or a statement was left unfinished.
+
+Erroneous code example:
+
+```php
+<?php
+
+function foo() {
+    return 1;
+```
+
+Complete the unfinished code:
+
+```php
+<?php
+
+function foo() {
+    return 1;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E003",
+        source: "unexpected_token",
+        summary: "unexpected token",
+        explanation: r#"A token was found in a position where it is not allowed.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = 1 +;
+```
+
+Remove the token, or complete the surrounding code:
+
+```php
+<?php
+
+$a = 1 + 2;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E005",
+        source: "unexpected_token",
+        summary: "unexpected token, expecting something else",
+        explanation: r#"A token was found where the parser was expecting one of a known set of
+tokens. The error lists the tokens that would have been accepted.
+
+Erroneous code example:
+
+```php
+<?php
+
+class {
+}
+```
+
+Here, the parser expects the name of the class after the `class` keyword:
+
+```php
+<?php
+
+class Foo {
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E006",
+        source: "unexpected_identifier",
+        summary: "unexpected identifier",
+        explanation: r#"An identifier was found where only a specific set of identifiers is
+allowed, such as the backing type of an enum.
+
+Erroneous code example:
+
+```php
+<?php
+
+enum Suit: float {
+    case Hearts = 1.0;
+}
+```
+
+Use one of the identifiers listed in the error message instead:
+
+```php
+<?php
+
+enum Suit: int {
+    case Hearts = 1;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E007",
+        source: "multiple_modifiers",
+        summary: "a modifier was declared more than once",
+        explanation: r#"The same modifier was used more than once on a declaration.
+
+Erroneous code example:
+
+```php
+<?php
+
+final final class Foo {}
+```
+
+Remove the duplicated modifier:
+
+```php
+<?php
+
+final class Foo {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E008",
+        source: "multiple_visibility_modifiers",
+        summary: "more than one visibility modifier",
+        explanation: r#"A class member can only have a single visibility modifier (`public`,
+`protected`, or `private`).
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    public private $bar;
+}
+```
+
+Keep only one visibility modifier:
+
+```php
+<?php
+
+class Foo {
+    private $bar;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E009",
+        source: "standalone_type_used_as_nullable",
+        summary: "standalone type used as nullable",
+        explanation: r#"The `never`, `void`, and `mixed` types cannot be made nullable: `mixed`
+already includes `null`, while `void` and `never` do not return a value at all.
+
+Erroneous code example:
+
+```php
+<?php
+
+function foo(): ?mixed {}
+```
+
+Remove the `?`:
+
+```php
+<?php
+
+function foo(): mixed {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E010",
+        source: "standalone_type_used_in_union",
+        summary: "standalone type used in a union",
+        explanation: r#"The `never`, `void`, and `mixed` types, as well as nullable types, can
+only be used on their own, and cannot be part of a union type.
+
+Erroneous code example:
+
+```php
+<?php
+
+function foo(): void|int {}
+```
+
+Use a different type, or a nullable type without the `?` syntax:
+
+```php
+<?php
+
+function foo(): null|int {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E011",
+        source: "standalone_type_used_in_intersection",
+        summary: "standalone type used in an intersection",
+        explanation: r#"The `never`, `void`, and `mixed` types, as well as nullable types, can
+only be used on their own, and cannot be part of an intersection type.
+
+Erroneous code example:
+
+```php
+<?php
+
+function foo(Countable&mixed $bar) {}
+```
+
+Only class and interface types can be used in an intersection:
+
+```php
+<?php
+
+function foo(Countable&Traversable $bar) {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E012",
+        source: "try_without_catch_or_finally",
+        summary: "`try` without `catch` or `finally`",
+        explanation: r#"A `try` block must be followed by at least one `catch` block, a `finally`
+block, or both.
+
+Erroneous code example:
+
+```php
+<?php
+
+try {
+    foo();
+}
+```
+
+Add a `catch` or a `finally` block:
+
+```php
+<?php
+
+try {
+    foo();
+} catch (Exception $e) {
+    report($e);
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E013",
+        source: "variadic_promoted_property",
+        summary: "promoted property cannot be variadic",
+        explanation: r#"Constructor property promotion cannot be used with variadic parameters.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    public function __construct(public string ...$bars) {}
+}
+```
+
+Declare the property explicitly instead:
+
+```php
+<?php
+
+class Foo {
+    public array $bars;
+
+    public function __construct(string ...$bars) {
+        $this->bars = $bars;
+    }
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E014",
+        source: "missing_type_for_readonly_property",
+        summary: "readonly property without a type",
+        explanation: r#"Readonly properties must declare a type.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    public readonly $bar;
+}
+```
+
+Add a type to the property, `mixed` can be used if any value is allowed:
+
+```php
+<?php
+
+class Foo {
+    public readonly mixed $bar;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E015",
+        source: "abstract_method_on_a_non_abstract_class",
+        summary: "abstract method in a non-abstract class",
+        explanation: r#"Only abstract classes can declare abstract methods.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    abstract public function bar(): void;
+}
+```
+
+Either make the class abstract, or give the method a body:
+
+```php
+<?php
+
+abstract class Foo {
+    abstract public function bar(): void;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E016",
+        source: "constructor_in_enum",
+        summary: "constructor in an enum",
+        explanation: r#"Enums cannot declare a constructor, as enum cases are created by the
+engine.
+
+Erroneous code example:
+
+```php
+<?php
+
+enum Suit {
+    case Hearts;
+
+    public function __construct() {}
+}
+```
+
+Remove the constructor, and use a static method if a factory is needed.
+"#,
+    },
+    ErrorCode {
+        code: "E017",
+        source: "magic_method_in_enum",
+        summary: "magic method in an enum",
+        explanation: r#"Enums cannot declare magic methods other than `__call`, `__callStatic`,
+and `__invoke`.
+
+Erroneous code example:
+
+```php
+<?php
+
+enum Suit {
+    case Hearts;
+
+    public function __toString(): string {
+        return 'Hearts';
+    }
+}
+```
+
+Use a regular method instead:
+
+```php
+<?php
+
+enum Suit {
+    case Hearts;
+
+    public function label(): string {
+        return 'Hearts';
+    }
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E018",
+        source: "missing_case_value_for_backed_enum",
+        summary: "backed enum case without a value",
+        explanation: r#"Every case of a backed enum must declare a value of the backing type.
+
+Erroneous code example:
+
+```php
+<?php
+
+enum Suit: string {
+    case Hearts;
+}
+```
+
+Add a value to the case:
+
+```php
+<?php
+
+enum Suit: string {
+    case Hearts = 'H';
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E019",
+        source: "case_value_for_unit_enum",
+        summary: "unit enum case with a value",
+        explanation: r#"Cases of a unit (non-backed) enum cannot have a value.
+
+Erroneous code example:
+
+```php
+<?php
+
+enum Suit {
+    case Hearts = 'H';
+}
+```
+
+Either remove the value, or declare a backing type on the enum:
+
+```php
+<?php
+
+enum Suit: string {
+    case Hearts = 'H';
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E020",
+        source: "modifier_cannot_be_used_for_constant",
+        summary: "invalid class constant modifier",
+        explanation: r#"Only the `public`, `protected`, `private`, and `final` modifiers can be
+used on class constants.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    static const BAR = 1;
+}
+```
+
+Remove the modifier:
+
+```php
+<?php
+
+class Foo {
+    const BAR = 1;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E021",
+        source: "modifier_cannot_be_used_for_interface_constant",
+        summary: "invalid interface constant modifier",
+        explanation: r#"Only the `public` and `final` modifiers can be used on interface
+constants, as interface constants are always visible to implementations.
+
+Erroneous code example:
+
+```php
+<?php
+
+interface Foo {
+    private const BAR = 1;
+}
+```
+
+Remove the modifier:
+
+```php
+<?php
+
+interface Foo {
+    const BAR = 1;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E022",
+        source: "modifier_cannot_be_used_for_promoted_property",
+        summary: "invalid promoted property modifier",
+        explanation: r#"Only the `public`, `protected`, `private`, and `readonly` modifiers can be
+used on promoted constructor properties.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    public function __construct(static string $bar) {}
+}
+```
+
+Remove the modifier:
+
+```php
+<?php
+
+class Foo {
+    public function __construct(public string $bar) {}
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E023",
+        source: "modifier_cannot_be_used_for_property",
+        summary: "invalid property modifier",
+        explanation: r#"Only the `public`, `protected`, `private`, `static`, and `readonly`
+modifiers can be used on properties.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    final public $bar;
+}
+```
+
+Remove the modifier:
+
+```php
+<?php
+
+class Foo {
+    public $bar;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E024",
+        source: "modifier_cannot_be_used_for_class",
+        summary: "invalid class modifier",
+        explanation: r#"Only the `final`, `abstract`, and `readonly` modifiers can be used on
+classes.
+
+Erroneous code example:
+
+```php
+<?php
+
+final public class Foo {}
+```
+
+Remove the modifier, classes are always visible:
+
+```php
+<?php
+
+final class Foo {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E025",
+        source: "modifier_cannot_be_used_for_class_method",
+        summary: "invalid class method modifier",
+        explanation: r#"Only the `public`, `protected`, `private`, `final`, `static`, and
+`abstract` modifiers can be used on class methods.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    readonly public function bar() {}
+}
+```
+
+Remove the modifier:
+
+```php
+<?php
+
+class Foo {
+    public function bar() {}
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E026",
+        source: "modifier_cannot_be_used_for_enum_method",
+        summary: "invalid enum method modifier",
+        explanation: r#"Only the `public`, `protected`, `private`, `final`, and `static`
+modifiers can be used on enum methods. Enums cannot be extended, so methods
+cannot be `abstract`.
+
+Erroneous code example:
+
+```php
+<?php
+
+enum Suit {
+    abstract public function label(): string;
+}
+```
+
+Remove the modifier and implement the method:
+
+```php
+<?php
+
+enum Suit {
+    public function label(): string {
+        return 'suit';
+    }
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E027",
+        source: "modifier_cannot_be_used_for_interface_method",
+        summary: "invalid interface method modifier",
+        explanation: r#"Only the `public` and `static` modifiers can be used on interface
+methods.
+
+Erroneous code example:
+
+```php
+<?php
+
+interface Foo {
+    protected function bar();
+}
+```
+
+Interface methods are always public:
+
+```php
+<?php
+
+interface Foo {
+    public function bar();
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E028",
+        source: "final_and_abstract_modifiers_combined_for_class",
+        summary: "class is both `final` and `abstract`",
+        explanation: r#"A `final` class cannot be extended, while an `abstract` class must be
+extended to be used, so a class cannot be both.
+
+Erroneous code example:
+
+```php
+<?php
+
+final abstract class Foo {}
+```
+
+Remove one of the modifiers:
+
+```php
+<?php
+
+abstract class Foo {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E029",
+        source: "final_and_abstract_modifiers_combined_for_class_member",
+        summary: "class member is both `final` and `abstract`",
+        explanation: r#"A `final` method cannot be overridden, while an `abstract` method must be
+overridden, so a method cannot be both.
+
+Erroneous code example:
+
+```php
+<?php
+
+abstract class Foo {
+    final abstract public function bar();
+}
+```
+
+Remove one of the modifiers:
+
+```php
+<?php
+
+abstract class Foo {
+    abstract public function bar();
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E030",
+        source: "final_and_private_modifiers_combined_for_constant",
+        summary: "constant is both `final` and `private`",
+        explanation: r#"Private constants are not visible to child classes, so they cannot be
+overridden, and declaring them `final` is not allowed.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    final private const BAR = 1;
+}
+```
+
+Remove one of the modifiers:
+
+```php
+<?php
+
+class Foo {
+    private const BAR = 1;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E031",
+        source: "reached_unpredictable_state",
+        summary: "reached an unpredictable state",
+        explanation: r#"The parser reached a state that should not be possible. This is a bug in
+the parser rather than in the code being parsed.
+
+Please report it, including the code that triggered the error, at
+https://github.com/php-rust-tools/php-parser/issues.
+"#,
+    },
+    ErrorCode {
+        code: "E032",
+        source: "static_property_cannot_be_readonly",
+        summary: "readonly property cannot be static",
+        explanation: r#"Readonly properties are initialized once per object, so they cannot be
+static.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    public static readonly int $bar;
+}
+```
+
+Remove one of the modifiers:
+
+```php
+<?php
+
+class Foo {
+    public readonly int $bar;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E033",
+        source: "readonly_property_has_default_value",
+        summary: "readonly property with a default value",
+        explanation: r#"Readonly properties cannot have a default value, as they can only be
+initialized once, from within the class scope.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    public readonly int $bar = 1;
+}
+```
+
+Initialize the property in the constructor instead, or use a constant:
+
+```php
+<?php
+
+class Foo {
+    public readonly int $bar;
+
+    public function __construct() {
+        $this->bar = 1;
+    }
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E034",
+        source: "unbraced_namespace_declarations_in_braced_context",
+        summary: "unbraced namespace after a braced namespace",
+        explanation: r#"A file that declares a braced namespace (`namespace Foo { ... }`) cannot
+also declare unbraced namespaces (`namespace Foo;`).
+
+Erroneous code example:
+
+```php
+<?php
+
+namespace Foo {
+}
+
+namespace Bar;
+```
+
+Use the same syntax for every namespace in the file:
+
+```php
+<?php
+
+namespace Foo {
+}
+
+namespace Bar {
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E035",
+        source: "braced_namespace_declarations_in_unbraced_context",
+        summary: "braced namespace after an unbraced namespace",
+        explanation: r#"A file that declares an unbraced namespace (`namespace Foo;`) cannot also
+declare braced namespaces (`namespace Foo { ... }`).
+
+Erroneous code example:
+
+```php
+<?php
+
+namespace Foo;
+
+namespace Bar {
+}
+```
+
+Use the same syntax for every namespace in the file:
+
+```php
+<?php
+
+namespace Foo;
+
+namespace Bar;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E036",
+        source: "nested_namespace_declarations",
+        summary: "nested namespace declaration",
+        explanation: r#"Namespaces cannot be nested.
+
+Erroneous code example:
+
+```php
+<?php
+
+namespace Foo {
+    namespace Bar {
+    }
+}
+```
+
+Close the first namespace before declaring the next one:
+
+```php
+<?php
+
+namespace Foo {
+}
+
+namespace Foo\Bar {
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E037",
+        source: "forbidden_type_used_in_property",
+        summary: "property with a forbidden type",
+        explanation: r#"The `void`, `never`, and `callable` types cannot be used as property
+types.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo {
+    public callable $bar;
+}
+```
+
+Use `Closure` to store callbacks:
+
+```php
+<?php
+
+class Foo {
+    public Closure $bar;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E038",
+        source: "match_expression_has_multiple_default_arms",
+        summary: "`match` with more than one default arm",
+        explanation: r#"A `match` expression can only have a single `default` arm.
+
+Erroneous code example:
+
+```php
+<?php
+
+$label = match ($value) {
+    1 => 'one',
+    default => 'many',
+    default => 'unknown',
+};
+```
+
+Remove the extra arm:
+
+```php
+<?php
+
+$label = match ($value) {
+    1 => 'one',
+    default => 'many',
+};
+```
+"#,
+    },
+    ErrorCode {
+        code: "E039",
+        source: "missing_item_definition_after_attributes",
+        summary: "attributes without an item definition",
+        explanation: r#"Attributes must be followed by the declaration they apply to. Inside of
+an expression, only closures and arrow functions can have attributes.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = #[Foo] 1;
+```
+
+Remove the attributes, or apply them to a closure:
+
+```php
+<?php
+
+$a = #[Foo] fn() => 1;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E040",
+        source: "nested_disjunctive_normal_form_types",
+        summary: "nested disjunctive normal form types",
+        explanation: r#"Disjunctive normal form (DNF) types must be a union of intersections,
+and the intersections cannot be nested.
+
+Erroneous code example:
+
+```php
+<?php
+
+function foo((A&(B|C))|D $bar) {}
+```
+
+Flatten the type into a union of intersections:
+
+```php
+<?php
+
+function foo((A&B)|(A&C)|D $bar) {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E041",
+        source: "illegal_spread_operator_usage",
+        summary: "illegal use of the spread operator",
+        explanation: r#"The spread operator (`...`) cannot be used when destructuring an array.
+
+Erroneous code example:
+
+```php
+<?php
+
+list(...$a) = $b;
+```
+
+Remove the spread operator:
+
+```php
+<?php
+
+list($a) = $b;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E042",
+        source: "cannot_assign_reference_to_non_referencable_value",
+        summary: "reference to a non-referencable value",
+        explanation: r#"A reference (`&`) can only be taken of a variable, a property, or an
+array element, not of an arbitrary expression.
+
+Erroneous code example:
+
+```php
+<?php
+
+list(&$a) = [1];
+```
+
+Assign by value instead:
+
+```php
+<?php
+
+list($a) = [1];
+```
+"#,
+    },
+    ErrorCode {
+        code: "E043",
+        source: "mixing_keyed_and_unkeyed_list_entries",
+        summary: "keyed and un-keyed list entries mixed",
+        explanation: r#"A `list()` can either use keys for all of its entries, or for none of
+them.
+
+Erroneous code example:
+
+```php
+<?php
+
+list($a, 'b' => $b) = $array;
+```
+
+Use keys for every entry:
+
+```php
+<?php
+
+list(0 => $a, 'b' => $b) = $array;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E044",
+        source: "cannot_use_positional_argument_after_named_argument",
+        summary: "positional argument after a named argument",
+        explanation: r#"Once a named argument has been passed, every following argument must be
+named too.
+
+Erroneous code example:
+
+```php
+<?php
+
+foo(bar: 1, 2);
+```
+
+Name the following arguments, or pass them before the named ones:
+
+```php
+<?php
+
+foo(2, bar: 1);
+```
+"#,
+    },
+    ErrorCode {
+        code: "E045",
+        source: "cannot_use_reserved_keyword_as_a_type_name",
+        summary: "reserved keyword used as a type name",
+        explanation: r#"Reserved keywords, as well as type names such as `int` or `string`,
+cannot be used as the name of a class, interface, trait, or enum.
+
+Erroneous code example:
+
+```php
+<?php
+
+class interface {}
+```
+
+Choose a different name:
+
+```php
+<?php
+
+class Text {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E046",
+        source: "cannot_use_reserved_keyword_as_a_goto_label",
+        summary: "reserved keyword used as a goto label",
+        explanation: r#"Reserved keywords cannot be used as `goto` labels.
+
+Erroneous code example:
+
+```php
+<?php
+
+goto list;
+```
+
+Choose a different label:
+
+```php
+<?php
+
+goto end;
+
+end:
+```
+"#,
+    },
+    ErrorCode {
+        code: "E047",
+        source: "cannot_use_reserved_keyword_as_a_constant_name",
+        summary: "reserved keyword used as a constant name",
+        explanation: r#"Reserved keywords cannot be used as the name of a global constant.
+
+Erroneous code example:
+
+```php
+<?php
+
+const class = 1;
+```
+
+Choose a different name:
+
+```php
+<?php
+
+const CLASS_NAME = 1;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E048",
+        source: "cannot_use_type_in_context",
+        summary: "type cannot be used in this context",
+        explanation: r#"The `self`, `static`, and `parent` types only have a meaning inside of a
+class, and cannot be used as a name.
+
+Erroneous code example:
+
+```php
+<?php
+
+class Foo extends parent {}
+```
+
+Use the name of the class instead:
+
+```php
+<?php
+
+class Foo extends Bar {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E049",
+        source: "only_positional_arguments_are_accepted",
+        summary: "named argument where only positional arguments are accepted",
+        explanation: r#"Language constructs that look like function calls, such as `exit()`,
+`die()`, `eval()`, and `empty()`, do not accept named arguments.
+
+Erroneous code example:
+
+```php
+<?php
+
+exit(status: 1);
+```
+
+Pass the argument positionally:
+
+```php
+<?php
+
+exit(1);
+```
+"#,
+    },
+    ErrorCode {
+        code: "E050",
+        source: "only_one_argument_is_accepted",
+        summary: "more than one argument passed to a single-argument construct",
+        explanation: r#"Language constructs such as `exit()`, `die()`, `eval()`, and `empty()`
+accept a single argument.
+
+Erroneous code example:
+
+```php
+<?php
+
+empty($a, $b);
+```
+
+Pass a single argument:
+
+```php
+<?php
+
+empty($a) && empty($b);
+```
+"#,
+    },
+    ErrorCode {
+        code: "E051",
+        source: "argument_is_required",
+        summary: "missing required argument",
+        explanation: r#"Language constructs such as `eval()` and `empty()` require an argument.
+
+Erroneous code example:
+
+```php
+<?php
+
+empty();
+```
+
+Pass an argument:
+
+```php
+<?php
+
+empty($a);
+```
+"#,
+    },
+    ErrorCode {
+        code: "E052",
+        source: "SyntaxError::UnexpectedError",
+        summary: "unexpected error",
+        explanation: r#"The lexer failed for a reason that it could not describe more
+precisely. This error is not currently produced, and the code is reserved.
+
+Please report it, including the code that triggered the error, at
+https://github.com/php-rust-tools/php-parser/issues.
+"#,
+    },
+    ErrorCode {
+        code: "E053",
+        source: "SyntaxError::UnexpectedCharacter",
+        summary: "unexpected character",
+        explanation: r#"A character was found where it is not allowed, for example after the
+label of a heredoc or a nowdoc, which must be followed by a new line.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = <<<EOT foo
+EOT;
+```
+
+Start the body of the heredoc on the next line:
+
+```php
+<?php
+
+$a = <<<EOT
+foo
+EOT;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E054",
+        source: "SyntaxError::InvalidHaltCompiler",
+        summary: "invalid `__halt_compiler` call",
+        explanation: r#"`__halt_compiler` must be immediately followed by `();`.
+
+Erroneous code example:
+
+```php
+<?php
+
+__halt_compiler;
+```
+
+Call it with empty parentheses:
+
+```php
+<?php
+
+__halt_compiler();
+```
+"#,
+    },
+    ErrorCode {
+        code: "E055",
+        source: "SyntaxError::InvalidOctalEscape",
+        summary: "invalid octal escape sequence",
+        explanation: r#"An octal escape sequence (`\0` to `\777`) in a double-quoted string or a
+heredoc represents a value that does not fit in a single byte.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = "\400";
+```
+
+Octal escape sequences must be at most `\377`:
+
+```php
+<?php
+
+$a = "\377";
+```
+"#,
+    },
+    ErrorCode {
+        code: "E056",
+        source: "SyntaxError::InvalidOctalLiteral",
+        summary: "invalid octal literal",
+        explanation: r#"An octal integer literal contains digits that are not valid in base 8.
+This error is not currently produced, invalid octal literals are reported as
+unexpected tokens (`E005`) instead.
+
+For example:
+
+```php
+<?php
+
+$a = 0o19;
+```
+
+Only the digits `0` to `7` can be used:
+
+```php
+<?php
+
+$a = 0o17;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E057",
+        source: "SyntaxError::InvalidUnicodeEscape",
+        summary: "invalid unicode escape sequence",
+        explanation: r#"A unicode escape sequence (`\u{...}`) in a double-quoted string or a
+heredoc is not closed, is empty, or is not a valid code point.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = "\u{1F600";
+```
+
+Close the escape sequence, and make sure it contains a valid hexadecimal code point:
+
+```php
+<?php
+
+$a = "\u{1F600}";
+```
+"#,
+    },
+    ErrorCode {
+        code: "E058",
+        source: "SyntaxError::UnpredictableState",
+        summary: "reached an unpredictable state",
+        explanation: r#"The lexer reached a state that should not be possible. This is a bug in
+the lexer rather than in the code being parsed.
+
+Please report it, including the code that triggered the error, at
+https://github.com/php-rust-tools/php-parser/issues.
+"#,
+    },
+    ErrorCode {
+        code: "E059",
+        source: "SyntaxError::InvalidDocIndentation",
+        summary: "heredoc mixes tabs and spaces",
+        explanation: r#"The closing marker of a heredoc or nowdoc is indented with both tabs and
+spaces.
+
+For example (`→` is a tab):
+
+```php
+<?php
+
+$a = <<<EOT
+    foo
+ →  EOT;
+```
+
+Indent the closing marker using either tabs or spaces, but not both.
+"#,
+    },
+    ErrorCode {
+        code: "E060",
+        source: "SyntaxError::InvalidDocBodyIndentationLevel",
+        summary: "heredoc body is less indented than its closing marker",
+        explanation: r#"The indentation of the closing marker of a heredoc or nowdoc is removed
+from every line of its body, so every line must be indented at least as much as
+the closing marker.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = <<<EOT
+  foo
+    EOT;
+```
+
+Indent the body, or dedent the closing marker:
+
+```php
+<?php
+
+$a = <<<EOT
+    foo
+    EOT;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E061",
+        source: "SyntaxError::UnrecognisedToken",
+        summary: "unrecognised token in string offset",
+        explanation: r#"An array offset inside of an interpolated string (`"$foo[...]"`) can
+only be an integer, an unquoted identifier, or a variable.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = "$foo[@]";
+```
+
+Use braces to interpolate more complex expressions:
+
+```php
+<?php
+
+$a = "{$foo['@']}";
+```
+"#,
+    },
+    ErrorCode {
+        code: "E062",
+        source: "SyntaxError::UnterminatedString",
+        summary: "unterminated string",
+        explanation: r#"A single-quoted string, double-quoted string, or shell command was opened,
+but the end of the file was reached before its closing delimiter.
+
+Erroneous code example:
+
+```php
+<?php
+
+$name = 'Taylor;
+```
+
+Close the string:
+
+```php
+<?php
+
+$name = 'Taylor';
+```
+"#,
+    },
+    ErrorCode {
+        code: "E063",
+        source: "SyntaxError::UnterminatedDocString",
+        summary: "unterminated heredoc",
+        explanation: r#"A heredoc or a nowdoc was opened, but the end of the file was reached
+before its closing marker.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = <<<EOT
+foo
+```
+
+Add the closing marker on its own line:
+
+```php
+<?php
+
+$a = <<<EOT
+foo
+EOT;
+```
+"#,
+    },
+    ErrorCode {
+        code: "E064",
+        source: "SyntaxError::UnterminatedComment",
+        summary: "unterminated comment",
+        explanation: r#"A block comment (`/* ... */`) or a doc comment (`/** ... */`) was opened,
+but the end of the file was reached before the closing `*/`.
+
+Erroneous code example:
+
+```php
+<?php
+
+/* foo
+```
+
+Close the comment:
+
+```php
+<?php
+
+/* foo */
+```
+"#,
+    },
+    ErrorCode {
+        code: "E065",
+        source: "SyntaxError::UnterminatedInterpolation",
+        summary: "unterminated string interpolation",
+        explanation: r#"An expression was interpolated into a string using `{$` or `${`, but the
+end of the file was reached before the closing `}`.
+
+Erroneous code example:
+
+```php
+<?php
+
+$a = "{$foo
+```
+
+Close the interpolation and the string:
+
+```php
+<?php
+
+$a = "{$foo}";
+```
+"#,
+    },
+    ErrorCode {
+        code: "E066",
+        source: "duplicate_parameter",
+        summary: "duplicate parameter",
+        explanation: r#"A function, method, or closure declares two parameters with the same name.
+
+Erroneous code example:
+
+```php
+<?php
+
+function greet($name, $greeting, $name) {}
+```
+
+Give each parameter a unique name:
+
+```php
+<?php
+
+function greet($name, $greeting, $title) {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E067",
+        source: "this_used_as_parameter",
+        summary: "`$this` used as a parameter",
+        explanation: r#"`$this` is reserved for the current object, so it cannot be used as the name
+of a parameter.
+
+Erroneous code example:
+
+```php
+<?php
+
+function greet($this) {}
+```
+
+Use a different name for the parameter:
+
+```php
+<?php
+
+function greet($user) {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E068",
+        source: "cannot_reassign_this",
+        summary: "`$this` re-assigned",
+        explanation: r#"`$this` always refers to the current object, and cannot be assigned to,
+incremented or decremented, destructured into, referenced, or used as the target
+of a `foreach` or a `catch`.
+
+Erroneous code example:
+
+```php
+<?php
+
+foreach ($users as $this) {}
+```
+
+Use a different variable:
+
+```php
+<?php
+
+foreach ($users as $user) {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E069",
+        source: "loop_control_outside_of_loop",
+        summary: "`break` or `continue` outside of a loop",
+        explanation: r#"`break` and `continue` can only be used inside of a loop (`for`, `foreach`,
+`while`, or `do`-`while`), or a `switch`. Functions and closures start a new
+context, so a loop outside of a function does not count.
+
+Erroneous code example:
+
+```php
+<?php
+
+if ($done) {
+    break;
+}
+```
+
+Use `return` or `exit` to stop execution outside of a loop:
+
+```php
+<?php
+
+if ($done) {
+    return;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E070",
+        source: "loop_control_level_too_deep",
+        summary: "`break` or `continue` level too deep",
+        explanation: r#"The level given to `break` or `continue` is greater than the number of
+loops and `switch`es that enclose it.
+
+Erroneous code example:
+
+```php
+<?php
+
+while (true) {
+    break 2;
+}
+```
+
+Use a level that matches the number of enclosing loops:
+
+```php
+<?php
+
+while (true) {
+    break 1;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E071",
+        source: "loop_control_level_not_positive",
+        summary: "`break` or `continue` level is not positive",
+        explanation: r#"The level given to `break` or `continue` must be a positive integer.
+
+Erroneous code example:
+
+```php
+<?php
+
+while (true) {
+    break 0;
+}
+```
+
+Use a level of at least `1`, or omit it:
+
+```php
+<?php
+
+while (true) {
+    break;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E072",
+        source: "duplicate_class_member",
+        summary: "duplicate class member",
+        explanation: r#"A class, interface, trait, or enum declares the same member twice. Methods
+are compared case-insensitively, and enum cases share their names with
+constants.
+
+Erroneous code example:
+
+```php
+<?php
+
+enum Status {
+    case Active;
+    case Active;
+}
+```
+
+Remove or rename the duplicate member:
+
+```php
+<?php
+
+enum Status {
+    case Active;
+    case Inactive;
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E073",
+        source: "void_function_returns_value",
+        summary: "`void` function returns a value",
+        explanation: r#"A function with a `void` return type can use `return;` to stop early, but it
+cannot return a value.
+
+Erroneous code example:
+
+```php
+<?php
+
+function log(string $message): void {
+    return print($message);
+}
+```
+
+Remove the returned value, or change the return type:
+
+```php
+<?php
+
+function log(string $message): void {
+    print($message);
+}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E074",
+        source: "variadic_parameter_is_not_last",
+        summary: "variadic parameter is not last",
+        explanation: r#"A variadic parameter collects all remaining arguments, so it must be the
+last parameter.
+
+Erroneous code example:
+
+```php
+<?php
+
+function sum(int ...$numbers, int $initial) {}
+```
+
+Move the variadic parameter to the end:
+
+```php
+<?php
+
+function sum(int $initial, int ...$numbers) {}
+```
+"#,
+    },
+    ErrorCode {
+        code: "E075",
+        source: "nesting_too_deep",
+        summary: "statements and expressions are nested too deeply",
+        explanation: r#"Traversing the tree of a program recurses for every nested statement and
+expression, so code nested deeper than the maximum depth is rejected rather than
+risking a stack overflow. The maximum is 128 levels by default, and can be
+changed with `Options::max_depth`.
+
+This usually happens with generated code, such as thousands of nested arrays:
+
+```php
+<?php
+
+$tree = [[[[[[[[/* ... */]]]]]]]];
+```
+
+Split the code into smaller pieces, for example by building the nested parts in
+a loop or by moving them into functions.
+"#,
+    },
+    ErrorCode {
+        code: "E076",
+        source: "input_too_large",
+        summary: "the input is too large",
+        explanation: r#"The input is larger than `Options::max_input_size` allows, so it is rejected
+before being read.
+
+There is no limit by default; the limit is meant for parsing untrusted code,
+such as uploaded files.
+"#,
+    },
+    ErrorCode {
+        code: "E077",
+        source: "too_many_tokens",
+        summary: "the input has too many tokens",
+        explanation: r#"The input has more tokens, comments included, than `Options::max_tokens`
+allows, so the parse stops at the first token past the limit.
+
+There is no limit by default; the limit is meant for parsing untrusted code,
+such as uploaded files.
+"#,
+    },
+    ErrorCode {
+        code: "E078",
+        source: "too_many_nodes",
+        summary: "the input has too many statements and expressions",
+        explanation: r#"The input has more statements, expressions, and operations than
+`Options::max_nodes` allows, so the parse stops at the first one past the limit.
+
+There is no limit by default; the limit is meant for parsing untrusted code,
+such as uploaded files.
+"#,
+    },
+    ErrorCode {
+        code: "E079",
+        source: "parse_cancelled",
+        summary: "the parse was cancelled",
+        explanation: r#"The `Options::cancellation` flag was set while the input was being parsed,
+so the parse stopped at the next token, statement, or expression.
+"#,
+    },
+    ErrorCode {
+        code: "E080",
+        source: "chain_too_long",
+        summary: "too many operations are chained",
+        explanation: r#"A chain of operations, such as concatenations or method calls, is parsed
+without recursing, but every operation wraps the ones before it, so traversing
+the resulting tree recurses once per operation. Chains longer than the maximum
+length are rejected rather than risking a stack overflow. The operations of the
+enclosing expressions count too. The maximum is 1024 operations by default, and
+can be changed with `Options::max_chain_length`.
+
+This usually happens with generated code, such as a very long chain of
+concatenations:
+
+```php
+<?php
+
+$query = 'SELECT ' . $a . ', ' . $b . ', ' /* ... */ . $z;
+```
+
+Split the code into smaller pieces, for example by building the string in a loop
+or with a function:
+
+```php
+<?php
+
+$query = 'SELECT ' . implode(', ', [$a, $b, /* ... */ $z]);
+```
+"#,
+    },
+    ErrorCode {
+        code: "E081",
+        source: "this_used_as_variable",
+        summary: "`$this` used as a global, static, or lexical variable",
+        explanation: r#"`$this` always refers to the current object, so it cannot be imported with
+`global`, declared `static`, or captured by a closure's `use`. Closures declared
+inside of a method already have access to `$this`.
+
+Erroneous code example:
+
+```php
+<?php
+
+$callback = function () use ($this) {};
+```
+
+Use `$this` directly:
+
+```php
+<?php
+
+$callback = function () { return $this; };
+```
+"#,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::error::SyntaxError;
+    use crate::lexer::token::Span;
+    use crate::parser::options::Options;
+
+    #[test]
+    fn test_codes_are_unique_and_sorted() {
+        for pair in CODES.windows(2) {
+            assert!(
+                pair[0].code < pair[1].code,
+                "{} is out of order",
+                pair[1].code
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_parser_error_is_documented() {
+        let source = include_str!("error.rs");
+
+        for (index, _) in source.match_indices("\"E0") {
+            let code = &source[index + 1..index + 5];
+
+            assert!(lookup(code).is_some(), "{} is not documented", code);
+        }
+    }
+
+    #[test]
+    fn test_every_syntax_error_is_documented() {
+        let span = Span::new(1, 1, 0);
+        let errors = [
+            SyntaxError::UnexpectedEndOfFile(span),
+            SyntaxError::UnexpectedError(span),
+            SyntaxError::UnexpectedCharacter(b'a', span),
+            SyntaxError::InvalidHaltCompiler(span),
+            SyntaxError::InvalidOctalEscape(span),
+            SyntaxError::InvalidOctalLiteral(span),
+            SyntaxError::InvalidUnicodeEscape(span),
+            SyntaxError::UnpredictableState(span),
+            SyntaxError::InvalidDocIndentation(span),
+            SyntaxError::InvalidDocBodyIndentationLevel(1, span),
+            SyntaxError::UnrecognisedToken(b'a', span),
+            SyntaxError::UnterminatedString(b'"', span, span),
+            SyntaxError::UnterminatedDocString(b"EOT".into(), span, span),
+            SyntaxError::UnterminatedComment(span, span),
+            SyntaxError::UnterminatedInterpolation(span, span),
+        ];
+
+        for error in errors {
+            let code = lookup(error.id()).unwrap();
+
+            assert!(code.source.starts_with("SyntaxError::"));
+        }
+    }
+
+    #[test]
+    fn test_erroneous_code_examples() {
+        for entry in CODES {
+            let example = match entry.explanation.split_once("Erroneous code example:") {
+                Some((_, rest)) => rest,
+                None => continue,
+            };
+
+            let code = example
+                .split("```php\n")
+                .nth(1)
+                .and_then(|code| code.split("```").next())
+                .unwrap();
+
+            let options = Options {
+                validate: true,
+                ..Options::default()
+            };
+            let errors = crate::parse_with(code, &options)
+                .err()
+                .map(|stack| stack.errors);
+
+            assert!(
+                errors
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|error| error.id == entry.code),
+                "the example of {} does not produce {}",
+                entry.code,
+                entry.code
+            );
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(
+            lookup("E012").unwrap().source,
+            "try_without_catch_or_finally"
+        );
+        assert_eq!(lookup("e12").unwrap().code, "E012");
+        assert!(lookup("E004").is_none());
+        assert!(lookup("foo").is_none());
+    }
+}
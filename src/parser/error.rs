@@ -7,6 +7,7 @@ use serde::Serialize;
 
 use crate::lexer::error::SyntaxError;
 use crate::lexer::token::{Span, Token, TokenKind};
+use crate::line_index::LineIndex;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::data_type::Type;
 use crate::parser::ast::modifiers::PromotedPropertyModifier;
@@ -33,6 +34,73 @@ pub struct ParseErrorAnnotation {
     pub length: usize,
 }
 
+/// A single text edit, replacing `length` bytes starting at `position` with `replacement`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ParseErrorEdit {
+    pub position: usize,
+    pub length: usize,
+    pub replacement: String,
+}
+
+impl ParseErrorEdit {
+    pub fn remove(position: usize, length: usize) -> Self {
+        Self::replace(position, length, "")
+    }
+
+    pub fn insert<T: ToString>(position: usize, text: T) -> Self {
+        Self::replace(position, 0, text)
+    }
+
+    pub fn replace<T: ToString>(position: usize, length: usize, text: T) -> Self {
+        Self {
+            position,
+            length,
+            replacement: text.to_string(),
+        }
+    }
+}
+
+/// A fix for an error, made of one or more non-overlapping edits.
+///
+/// Safe fixes never change the meaning of the code, and can be applied without
+/// review; other fixes are suggestions, which might not be what the user intended.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ParseErrorFix {
+    pub description: String,
+    pub safe: bool,
+    pub edits: Vec<ParseErrorEdit>,
+}
+
+/// How serious an error is.
+///
+/// Parse errors are always errors, while lint rules can report warnings and notices too.
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default, Deserialize, Serialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseErrorSeverity {
+    Notice,
+    Warning,
+    #[default]
+    Error,
+}
+
+impl ParseErrorSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParseErrorSeverity::Notice => "notice",
+            ParseErrorSeverity::Warning => "warning",
+            ParseErrorSeverity::Error => "error",
+        }
+    }
+}
+
+impl Display for ParseErrorSeverity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ParseError {
     pub id: String,
@@ -40,6 +108,10 @@ pub struct ParseError {
     pub span: Span,
     pub annotations: Vec<ParseErrorAnnotation>,
     pub note: Option<String>,
+    #[serde(default)]
+    pub fixes: Vec<ParseErrorFix>,
+    #[serde(default)]
+    pub severity: ParseErrorSeverity,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -56,10 +128,11 @@ impl ParseErrorStack {
         colored: bool,
         ascii: bool,
     ) -> std::io::Result<String> {
+        let index = LineIndex::new(source);
         let mut reports = Vec::new();
 
         for error in &self.errors {
-            reports.push(error.report(source, origin, colored, ascii)?);
+            reports.push(error.report_with_index(source, &index, origin, colored, ascii)?);
         }
 
         Ok(reports.join("\n"))
@@ -74,9 +147,17 @@ impl ParseError {
             span,
             annotations: Vec::new(),
             note: None,
+            fixes: Vec::new(),
+            severity: ParseErrorSeverity::Error,
         }
     }
 
+    pub fn severity(mut self, severity: ParseErrorSeverity) -> Self {
+        self.severity = severity;
+
+        self
+    }
+
     pub fn highlight(mut self, position: usize, length: usize) -> Self {
         self.annotations.push(ParseErrorAnnotation {
             r#type: ParseErrorAnnotationType::Hint,
@@ -105,16 +186,58 @@ impl ParseError {
         self
     }
 
+    /// Attach a fix that is safe to apply automatically.
+    pub fn fix<T: ToString>(mut self, description: T, edits: Vec<ParseErrorEdit>) -> Self {
+        self.fixes.push(ParseErrorFix {
+            description: description.to_string(),
+            safe: true,
+            edits,
+        });
+
+        self
+    }
+
+    /// Attach a fix that might change the meaning of the code.
+    pub fn suggestion<T: ToString>(mut self, description: T, edits: Vec<ParseErrorEdit>) -> Self {
+        self.fixes.push(ParseErrorFix {
+            description: description.to_string(),
+            safe: false,
+            edits,
+        });
+
+        self
+    }
+
     pub fn report<'a>(
         &self,
         source: &'a str,
         origin: Option<&'a str>,
         colored: bool,
         ascii: bool,
+    ) -> std::io::Result<String> {
+        self.report_with_index(source, &LineIndex::new(source), origin, colored, ascii)
+    }
+
+    /// Report the error, as `report` does, using the given index of the source, so that
+    /// the errors of a file can share a single one.
+    pub fn report_with_index<'a>(
+        &self,
+        source: &'a str,
+        index: &LineIndex,
+        origin: Option<&'a str>,
+        colored: bool,
+        ascii: bool,
     ) -> std::io::Result<String> {
         let origin = origin.unwrap_or("input");
 
-        let mut report = Report::build(ReportKind::Error, origin, self.span.position)
+        let kind = match self.severity {
+            ParseErrorSeverity::Notice => ReportKind::Advice,
+            ParseErrorSeverity::Warning => ReportKind::Warning,
+            ParseErrorSeverity::Error => ReportKind::Error,
+        };
+
+        // the report counts characters, rather than bytes.
+        let mut report = Report::build(kind, origin, index.char_offset(self.span.position))
             .with_code(&self.id)
             .with_message(&self.message)
             .with_config(
@@ -130,7 +253,8 @@ impl ParseError {
         for (order, annotation) in self.annotations.iter().enumerate() {
             let mut label = Label::new((
                 origin,
-                annotation.position..annotation.position + annotation.length,
+                index.char_offset(annotation.position)
+                    ..index.char_offset(annotation.position + annotation.length),
             ))
             .with_order(order.try_into().unwrap());
 
@@ -152,6 +276,18 @@ impl ParseError {
             report = report.with_note(note);
         }
 
+        // a report only has a single help message, so the fixes share it.
+        if !self.fixes.is_empty() {
+            let help = self
+                .fixes
+                .iter()
+                .map(|fix| fix.description.as_str())
+                .collect::<Vec<&str>>()
+                .join(", or ");
+
+            report = report.with_help(help);
+        }
+
         let code = (origin, Source::from(source));
 
         let mut bytes = Vec::new();
@@ -252,7 +388,12 @@ pub fn unexpected_identifier(expected: Vec<String>, found: String, span: Span) -
     )
 }
 
-pub fn multiple_modifiers(modifier: String, first: Span, second: Span) -> ParseError {
+pub fn multiple_modifiers(
+    modifier: String,
+    first: Span,
+    second: Span,
+    removal: ParseErrorEdit,
+) -> ParseError {
     ParseError::new(
         "E007",
         format!("multiple `{}` modifiers are not allowed", modifier),
@@ -260,9 +401,17 @@ pub fn multiple_modifiers(modifier: String, first: Span, second: Span) -> ParseE
     )
     .highlight(first.position, modifier.len())
     .error("try removing this", second.position, modifier.len())
+    .fix(
+        format!("remove the duplicate `{}` modifier", modifier),
+        vec![removal],
+    )
 }
 
-pub fn multiple_visibility_modifiers(first: (String, Span), second: (String, Span)) -> ParseError {
+pub fn multiple_visibility_modifiers(
+    first: (String, Span),
+    second: (String, Span),
+    removal: ParseErrorEdit,
+) -> ParseError {
     ParseError::new(
         "E008",
         "multiple visibility modifiers are not allowed",
@@ -270,6 +419,7 @@ pub fn multiple_visibility_modifiers(first: (String, Span), second: (String, Spa
     )
     .highlight(first.1.position, first.0.len())
     .error("try removing this", second.1.position, second.0.len())
+    .suggestion(format!("remove the `{}` modifier", second.0), vec![removal])
 }
 
 pub fn standalone_type_used_as_nullable(ty: &Type, span: Span) -> ParseError {
@@ -284,6 +434,10 @@ pub fn standalone_type_used_as_nullable(ty: &Type, span: Span) -> ParseError {
     .error("try removing this", span.position, 1)
     .highlight(type_span.position, type_string.len())
     .note("`never`, `void`, and `mixed` cannot be nullable")
+    .fix(
+        "remove the `?`",
+        vec![ParseErrorEdit::remove(span.position, 1)],
+    )
 }
 
 pub fn standalone_type_used_in_union(ty: &Type, span: Span) -> ParseError {
@@ -338,6 +492,13 @@ pub fn try_without_catch_or_finally(try_span: Span, last_right_brace: Span) -> P
         try_span.position,
         last_right_brace.position - try_span.position + 1,
     )
+    .suggestion(
+        "add an empty `finally` block",
+        vec![ParseErrorEdit::insert(
+            last_right_brace.position + 1,
+            " finally {}",
+        )],
+    )
 }
 
 pub fn variadic_promoted_property(
@@ -628,6 +789,7 @@ pub fn modifier_cannot_be_used_for_interface_method(
 pub fn final_and_abstract_modifiers_combined_for_class(
     final_span: Span,
     abstract_span: Span,
+    removal: ParseErrorEdit,
 ) -> ParseError {
     ParseError::new(
         "E028",
@@ -640,11 +802,13 @@ pub fn final_and_abstract_modifiers_combined_for_class(
         abstract_span.position,
         "abstract".len(),
     )
+    .suggestion("remove the `abstract` modifier", vec![removal])
 }
 
 pub fn final_and_abstract_modifiers_combined_for_class_member(
     final_span: Span,
     abstract_span: Span,
+    removal: ParseErrorEdit,
 ) -> ParseError {
     ParseError::new(
         "E029",
@@ -657,11 +821,13 @@ pub fn final_and_abstract_modifiers_combined_for_class_member(
         abstract_span.position,
         "abstract".len(),
     )
+    .suggestion("remove the `abstract` modifier", vec![removal])
 }
 
 pub fn final_and_private_modifiers_combined_for_constant(
     final_span: Span,
     private_span: Span,
+    removal: ParseErrorEdit,
 ) -> ParseError {
     ParseError::new(
         "E030",
@@ -671,6 +837,7 @@ pub fn final_and_private_modifiers_combined_for_constant(
     .highlight(private_span.position, "private".len())
     .error("try removing this", final_span.position, "final".len())
     .note("private constants cannot be final as they are not visible to other classes")
+    .fix("remove the `final` modifier", vec![removal])
 }
 
 pub fn reached_unpredictable_state(span: Span) -> ParseError {
@@ -717,6 +884,7 @@ pub fn readonly_property_has_default_value(
     property: &SimpleVariable,
     readonly_span: Span,
     equals_span: Span,
+    removal: ParseErrorEdit,
 ) -> ParseError {
     let error = ParseError::new(
         "E033",
@@ -731,7 +899,8 @@ pub fn readonly_property_has_default_value(
     )
     .highlight(property.span.position, property.name.len())
     .highlight(readonly_span.position, "readonly".len())
-    .error("try removing this `=`", equals_span.position, 1);
+    .error("try removing this `=`", equals_span.position, 1)
+    .suggestion("remove the default value", vec![removal]);
 
     // If the class is anonymous, we don't have a span to highlight
     if let Some(class) = class {
@@ -760,7 +929,7 @@ pub fn braced_namespace_declarations_in_unbraced_context(span: Span) -> ParseErr
 }
 
 pub fn nested_namespace_declarations(span: Span) -> ParseError {
-    ParseError::new("E035", "cannot nest namespace declarations", span).error(
+    ParseError::new("E036", "cannot nest namespace declarations", span).error(
         "try closing previous namespace with `}` before declaring a new one",
         span.position,
         1,
@@ -850,6 +1019,8 @@ pub fn missing_item_definition_after_attributes(
         span: current.span,
         annotations,
         note: None,
+        fixes: vec![],
+        severity: ParseErrorSeverity::Error,
     }
 }
 
@@ -972,14 +1143,328 @@ pub fn argument_is_required(span: Span, current_span: Span) -> ParseError {
     )
 }
 
+pub fn duplicate_parameter(parameter: &SimpleVariable, previous: &SimpleVariable) -> ParseError {
+    ParseError::new(
+        "E066",
+        format!("redefinition of parameter `{}`", parameter.name),
+        parameter.span,
+    )
+    .error(
+        "try renaming this parameter",
+        parameter.span.position,
+        parameter.name.len(),
+    )
+    .highlight(previous.span.position, previous.name.len())
+}
+
+pub fn this_used_as_parameter(parameter: &SimpleVariable) -> ParseError {
+    ParseError::new("E067", "cannot use `$this` as a parameter", parameter.span).error(
+        "try renaming this parameter",
+        parameter.span.position,
+        parameter.name.len(),
+    )
+}
+
+pub fn cannot_reassign_this(variable: &SimpleVariable) -> ParseError {
+    ParseError::new("E068", "cannot re-assign `$this`", variable.span)
+        .error(
+            "try using a different variable",
+            variable.span.position,
+            variable.name.len(),
+        )
+        .note("`$this` always refers to the current object")
+}
+
+pub fn this_used_as_variable(kind: &str, variable: &SimpleVariable) -> ParseError {
+    ParseError::new(
+        "E081",
+        format!("cannot use `$this` as a {} variable", kind),
+        variable.span,
+    )
+    .error(
+        "try using a different variable",
+        variable.span.position,
+        variable.name.len(),
+    )
+    .note("`$this` always refers to the current object")
+}
+
+pub fn loop_control_outside_of_loop(keyword: &str, span: Span) -> ParseError {
+    ParseError::new(
+        "E069",
+        format!("cannot use `{}` outside of a loop or `switch`", keyword),
+        span,
+    )
+    .error(
+        format!("try removing this `{}`", keyword),
+        span.position,
+        keyword.len(),
+    )
+}
+
+pub fn loop_control_level_too_deep(
+    keyword: &str,
+    span: Span,
+    level: usize,
+    depth: usize,
+    level_span: Span,
+    level_length: usize,
+) -> ParseError {
+    ParseError::new(
+        "E070",
+        format!("cannot `{}` {} levels", keyword, level),
+        span,
+    )
+    .highlight(span.position, keyword.len())
+    .error(
+        if depth == 1 {
+            "there is only 1 enclosing loop or `switch`".to_string()
+        } else {
+            format!("there are only {} enclosing loops or `switch`es", depth)
+        },
+        level_span.position,
+        level_length,
+    )
+}
+
+pub fn loop_control_level_not_positive(
+    keyword: &str,
+    span: Span,
+    level_span: Span,
+    level_length: usize,
+) -> ParseError {
+    ParseError::new(
+        "E071",
+        format!("`{}` only accepts positive integers", keyword),
+        span,
+    )
+    .highlight(span.position, keyword.len())
+    .error("try using `1` here", level_span.position, level_length)
+}
+
+pub fn duplicate_class_member(
+    kind: &str,
+    class: &str,
+    member: &str,
+    span: Span,
+    previous: Span,
+) -> ParseError {
+    ParseError::new(
+        "E072",
+        format!("cannot redeclare {} `{}::{}`", kind, class, member),
+        span,
+    )
+    .error(
+        format!("try removing this {}", kind),
+        span.position,
+        member.len(),
+    )
+    .highlight(previous.position, member.len())
+}
+
+pub fn void_function_returns_value(r#return: Span, end: Span, void: Span) -> ParseError {
+    let value = r#return.position + "return".len();
+
+    ParseError::new(
+        "E073",
+        "a `void` function must not return a value",
+        r#return,
+    )
+    .error(
+        "try removing the returned value",
+        r#return.position,
+        end.position - r#return.position,
+    )
+    .highlight(void.position, "void".len())
+    .suggestion(
+        "remove the returned value",
+        vec![ParseErrorEdit::remove(value, end.position - value)],
+    )
+}
+
+pub fn variadic_parameter_is_not_last(ellipsis: Span, next: &SimpleVariable) -> ParseError {
+    ParseError::new("E074", "only the last parameter can be variadic", ellipsis)
+        .error(
+            "try removing this variadic declaration",
+            ellipsis.position,
+            3,
+        )
+        .highlight(next.span.position, next.name.len())
+}
+
+pub fn nesting_too_deep(max_depth: usize, current: &Token) -> ParseError {
+    ParseError::new(
+        "E075",
+        format!(
+            "cannot nest statements and expressions more than {} levels deep",
+            max_depth
+        ),
+        current.span,
+    )
+    .error(
+        "the maximum depth is exceeded here",
+        current.span.position,
+        current.value.len().max(1),
+    )
+    .note("try splitting the code into smaller functions")
+}
+
+pub fn input_too_large(size: usize, max_input_size: usize) -> ParseError {
+    ParseError::new(
+        "E076",
+        format!(
+            "cannot parse {} bytes, at most {} bytes are allowed",
+            size, max_input_size
+        ),
+        Span::new(1, 1, 0),
+    )
+}
+
+pub fn too_many_tokens(max_tokens: usize, token: &Token) -> ParseError {
+    ParseError::new(
+        "E077",
+        format!("cannot parse more than {} tokens", max_tokens),
+        token.span,
+    )
+    .error(
+        "the maximum number of tokens is exceeded here",
+        token.span.position,
+        token.value.len().max(1),
+    )
+}
+
+pub fn too_many_nodes(max_nodes: usize, current: &Token) -> ParseError {
+    ParseError::new(
+        "E078",
+        format!(
+            "cannot parse more than {} statements and expressions",
+            max_nodes
+        ),
+        current.span,
+    )
+    .error(
+        "the maximum number of statements and expressions is exceeded here",
+        current.span.position,
+        current.value.len().max(1),
+    )
+}
+
+pub fn parse_cancelled(span: Span) -> ParseError {
+    ParseError::new("E079", "the parse was cancelled", span)
+}
+
+pub fn chain_too_long(max_chain_length: usize, current: &Token) -> ParseError {
+    ParseError::new(
+        "E080",
+        format!("cannot chain more than {} operations", max_chain_length),
+        current.span,
+    )
+    .error(
+        "the maximum length is exceeded here",
+        current.span.position,
+        current.value.len().max(1),
+    )
+    .note("try splitting the expression into smaller ones")
+}
+
 impl From<SyntaxError> for ParseError {
     fn from(e: SyntaxError) -> Self {
-        Self {
-            id: "E001".to_string(),
-            message: format!("syntax error, {}", e),
-            annotations: vec![],
-            span: e.span(),
-            note: None,
+        let id = e.id();
+
+        match e {
+            SyntaxError::UnexpectedEndOfFile(span) => {
+                ParseError::new(id, "unexpected end of file", span)
+            }
+            SyntaxError::UnexpectedError(span) => ParseError::new(id, "unexpected error", span),
+            SyntaxError::UnexpectedCharacter(character, span) => ParseError::new(
+                id,
+                format!("unexpected character `{}`", character.escape_ascii()),
+                span,
+            )
+            .error("unexpected character", span.position, 1),
+            SyntaxError::InvalidHaltCompiler(span) => ParseError::new(
+                id,
+                "`__halt_compiler` must be followed by `();`",
+                span,
+            )
+            .error("expected `();` here", span.position, 1),
+            SyntaxError::InvalidOctalEscape(span) => {
+                ParseError::new(id, "invalid octal escape sequence", span)
+                    .error("this escape sequence", span.position, 2)
+                    .note("octal escape sequences must be at most `\\377`")
+            }
+            SyntaxError::InvalidOctalLiteral(span) => {
+                ParseError::new(id, "invalid octal literal", span)
+            }
+            SyntaxError::InvalidUnicodeEscape(span) => ParseError::new(
+                id,
+                "invalid unicode escape sequence",
+                span,
+            )
+            .error("this escape sequence", span.position, 2)
+            .note("unicode escape sequences must contain a valid code point, e.g. `\\u{1F600}`"),
+            SyntaxError::UnpredictableState(span) => ParseError::new(
+                id,
+                "reached unpredictable state",
+                span,
+            )
+            .error("please report this as a bug", span.position, 1),
+            SyntaxError::InvalidDocIndentation(span) => {
+                ParseError::new(id, "invalid heredoc indentation", span).error(
+                    "the closing marker is indented with both tabs and spaces",
+                    span.position,
+                    1,
+                )
+            }
+            SyntaxError::InvalidDocBodyIndentationLevel(expected, span) => {
+                ParseError::new(id, "invalid heredoc body indentation", span).error(
+                    format!("expected an indentation level of at least {}", expected),
+                    span.position,
+                    1,
+                )
+            }
+            SyntaxError::UnrecognisedToken(token, span) => ParseError::new(
+                id,
+                format!(
+                    "unrecognised token `{}` in string offset",
+                    token.escape_ascii()
+                ),
+                span,
+            )
+            .error("unrecognised token", span.position, 1)
+            .note("use braces to interpolate more complex expressions, e.g. `{$foo['bar']}`"),
+            SyntaxError::UnterminatedString(delimiter, start, _) => {
+                let kind = if delimiter == b'`' {
+                    "shell command"
+                } else {
+                    "string"
+                };
+
+                let note = if delimiter == b'`' {
+                    "add a closing backtick".to_string()
+                } else {
+                    format!("add a closing `{}`", delimiter as char)
+                };
+
+                ParseError::new(id, format!("unterminated {}", kind), start)
+                    .error(format!("this {} is never closed", kind), start.position, 1)
+                    .note(note)
+            }
+            SyntaxError::UnterminatedDocString(label, start, _) => {
+                ParseError::new(id, "unterminated heredoc", start)
+                    .error("this heredoc is never closed", start.position, 3)
+                    .note(format!("add the closing marker `{}`", label))
+            }
+            SyntaxError::UnterminatedComment(start, _) => {
+                ParseError::new(id, "unterminated comment", start)
+                    .error("this comment is never closed", start.position, 2)
+                    .note("add a closing `*/`")
+            }
+            SyntaxError::UnterminatedInterpolation(start, _) => {
+                ParseError::new(id, "unterminated string interpolation", start)
+                    .error("this interpolation is never closed", start.position, 1)
+                    .note("add a closing `}`")
+            }
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::expected_token_err;
 use crate::lexer::token::DocStringKind;
+use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::arguments::ArgumentPlaceholder;
 use crate::parser::ast::identifiers::DynamicIdentifier;
@@ -75,30 +76,37 @@ fn clone_or_new_precedence(state: &mut State) -> ParseResult<Expression> {
 }
 
 fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expression> {
+    state.nested(|state| parse_for_precedence(state, precedence))
+}
+
+fn parse_for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expression> {
     let mut left = left(state, &precedence)?;
 
     loop {
         let current = state.stream.current();
         let span = current.span;
-        let kind = &current.kind;
+        let kind = current.kind;
 
-        if matches!(current.kind, TokenKind::SemiColon | TokenKind::Eof) {
+        if matches!(kind, TokenKind::SemiColon | TokenKind::Eof) {
             break;
         }
 
-        if is_postfix(kind) {
-            let lpred = Precedence::postfix(kind);
+        if is_postfix(&kind) {
+            let lpred = Precedence::postfix(&kind);
 
             if lpred < precedence {
                 break;
             }
 
-            left = postfix(state, left, kind)?;
+            // a chain of operations is parsed without recursing, so it doesn't go any
+            // deeper, but each of them still wraps the previous ones in the tree.
+            state.chain()?;
+            left = postfix(state, left, &kind)?;
             continue;
         }
 
-        if is_infix(kind) {
-            let rpred = Precedence::infix(kind);
+        if is_infix(&kind) {
+            let rpred = Precedence::infix(&kind);
 
             if rpred < precedence {
                 break;
@@ -112,16 +120,21 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                 return Err(error::unexpected_token(vec![], current));
             }
 
+            state.chain()?;
             state.stream.next();
 
-            let op = state.stream.current();
+            let &Token {
+                kind: op,
+                span: op_span,
+                ..
+            } = state.stream.current();
 
             left = match kind {
                 TokenKind::Question => {
                     // this happens due to a comment, or whitespaces between the ? and the :
                     // we consider `foo() ? : bar()` a ternary expression, with `then` being a noop
                     // however, this must behave like a short ternary at runtime.
-                    if op.kind == TokenKind::Colon {
+                    if op == TokenKind::Colon {
                         state.stream.next();
 
                         let r#else = create(state)?;
@@ -130,7 +143,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                             condition: Box::new(left),
                             question: span,
                             then: Box::new(Expression::Noop),
-                            colon: op.span,
+                            colon: op_span,
                             r#else: Box::new(r#else),
                         })
                     } else {
@@ -155,7 +168,7 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                         r#else: Box::new(r#else),
                     })
                 }
-                TokenKind::Equals if op.kind == TokenKind::Ampersand => {
+                TokenKind::Equals if op == TokenKind::Ampersand => {
                     state.stream.next();
 
                     // FIXME: You should only be allowed to assign a referencable variable,
@@ -166,40 +179,40 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                         left: Box::new(left),
                         equals: span,
                         right: Box::new(Expression::Reference(ReferenceExpression {
-                            ampersand: op.span,
+                            ampersand: op_span,
                             right,
                         })),
                     })
                 }
-                TokenKind::Instanceof if op.kind == TokenKind::Self_ => {
+                TokenKind::Instanceof if op == TokenKind::Self_ => {
                     state.stream.next();
 
                     Expression::Instanceof(InstanceofExpression {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Self_),
+                        right: Box::new(Expression::Self_(op_span)),
                     })
                 }
-                TokenKind::Instanceof if op.kind == TokenKind::Parent => {
+                TokenKind::Instanceof if op == TokenKind::Parent => {
                     state.stream.next();
 
                     Expression::Instanceof(InstanceofExpression {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Parent),
+                        right: Box::new(Expression::Parent(op_span)),
                     })
                 }
-                TokenKind::Instanceof if op.kind == TokenKind::Static => {
+                TokenKind::Instanceof if op == TokenKind::Static => {
                     state.stream.next();
 
                     Expression::Instanceof(InstanceofExpression {
                         left: Box::new(left),
                         instanceof: span,
-                        right: Box::new(Expression::Static),
+                        right: Box::new(Expression::Static(op_span)),
                     })
                 }
-                TokenKind::Instanceof if op.kind == TokenKind::Enum => {
-                    let enum_span = op.span;
+                TokenKind::Instanceof if op == TokenKind::Enum => {
+                    let enum_span = op_span;
                     state.stream.next();
 
                     Expression::Instanceof(InstanceofExpression {
@@ -213,8 +226,8 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                         ))),
                     })
                 }
-                TokenKind::Instanceof if op.kind == TokenKind::From => {
-                    let from_span = op.span;
+                TokenKind::Instanceof if op == TokenKind::From => {
+                    let from_span = op_span;
                     state.stream.next();
 
                     Expression::Instanceof(InstanceofExpression {
@@ -731,49 +744,54 @@ expressions! {
 
     #[before(r#yield), current(TokenKind::Throw)]
     throw({
+        let throw = state.stream.current().span;
         state.stream.next();
 
         Ok(Expression::Throw(ThrowExpression {
+            throw,
             value: Box::new(for_precedence(state, Precedence::Lowest)?)
         }))
     })
 
     #[before(clone), current(TokenKind::Yield)]
     r#yield({
+        let r#yield = state.stream.current().span;
         state.stream.next();
         if state.stream.current().kind == TokenKind::SemiColon || state.stream.current().kind == TokenKind::RightParen {
             Ok(Expression::Yield(YieldExpression {
+                r#yield,
                 key: None,
                 value: None,
             }))
         } else {
-            let mut from = false;
+            let mut from = None;
 
             if state.stream.current().kind == TokenKind::From {
+                from = Some(state.stream.current().span);
                 state.stream.next();
-                from = true;
             }
 
             let mut key = None;
             let mut value = Box::new(for_precedence(
                 state,
-                if from {
+                if from.is_some() {
                     Precedence::YieldFrom
                 } else {
                     Precedence::Yield
                 },
             )?);
 
-            if state.stream.current().kind == TokenKind::DoubleArrow && !from {
+            if state.stream.current().kind == TokenKind::DoubleArrow && from.is_none() {
                 state.stream.next();
                 key = Some(value.clone());
                 value = Box::new(for_precedence(state, Precedence::Yield)?);
             }
 
-            if from {
-                Ok(Expression::YieldFrom(YieldFromExpression { value }))
+            if let Some(from) = from {
+                Ok(Expression::YieldFrom(YieldFromExpression { r#yield, from, value }))
             } else {
                 Ok(Expression::Yield(YieldExpression {
+                    r#yield,
                     key,
                     value: Some(value),
                 }))
@@ -783,39 +801,44 @@ expressions! {
 
     #[before(r#true), current(TokenKind::Clone)]
     clone({
+        let clone = state.stream.current().span;
         state.stream.next();
 
         let target = for_precedence(state, Precedence::CloneOrNew)?;
 
         Ok(Expression::Clone(CloneExpression {
+            clone,
             target: Box::new(target),
         }))
     })
 
     #[before(r#false), current(TokenKind::True)]
     r#true({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        Ok(Expression::Bool(BoolExpression { value: true }))
+        Ok(Expression::Bool(BoolExpression { span, value: true }))
     })
 
     #[before(null), current(TokenKind::False)]
     r#false({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        Ok(Expression::Bool(BoolExpression { value: false }))
+        Ok(Expression::Bool(BoolExpression { span, value: false }))
     })
 
     #[before(literal_integer), current(TokenKind::Null)]
     null({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        Ok(Expression::Null)
+        Ok(Expression::Null(span))
     })
 
     #[before(literal_float), current(TokenKind::LiteralInteger)]
     literal_integer({
-        let current = state.stream.current();
+        let current = state.stream.current().clone();
 
         if let TokenKind::LiteralInteger = &current.kind {
             state.stream.next();
@@ -823,7 +846,7 @@ expressions! {
             Ok(Expression::Literal(Literal::Integer(
                 LiteralInteger {
                     span: current.span,
-                    value: current.value.clone()
+                    value: current.value
                 }
             )))
         } else {
@@ -833,7 +856,7 @@ expressions! {
 
     #[before(literal_string), current(TokenKind::LiteralFloat)]
     literal_float({
-        let current = state.stream.current();
+        let current = state.stream.current().clone();
 
         if let TokenKind::LiteralFloat = &current.kind {
             state.stream.next();
@@ -841,7 +864,7 @@ expressions! {
             Ok(Expression::Literal(
                 Literal::Float(LiteralFloat {
                     span: current.span,
-                    value: current.value.clone()
+                    value: current.value
                 })
             ))
         } else {
@@ -851,7 +874,7 @@ expressions! {
 
     #[before(string_part), current(TokenKind::LiteralSingleQuotedString | TokenKind::LiteralDoubleQuotedString)]
     literal_string({
-        let current = state.stream.current();
+        let current = state.stream.current().clone();
 
         if let TokenKind::LiteralSingleQuotedString = &current.kind {
             state.stream.next();
@@ -859,7 +882,7 @@ expressions! {
             Ok(Expression::Literal(
                 Literal::String(LiteralString {
                     span: current.span,
-                    value: current.value.clone(),
+                    value: current.value,
                     kind: LiteralStringKind::SingleQuoted,
                 })
             ))
@@ -869,7 +892,7 @@ expressions! {
             Ok(Expression::Literal(
                 Literal::String(LiteralString {
                     span: current.span,
-                    value: current.value.clone(),
+                    value: current.value,
                     kind: LiteralStringKind::DoubleQuoted,
                 })
             ))
@@ -905,23 +928,26 @@ expressions! {
 
     #[before(self_identifier), current(TokenKind::Static)]
     static_postfix({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        postfix(state, Expression::Static, &TokenKind::DoubleColon)
+        postfix(state, Expression::Static(span), &TokenKind::DoubleColon)
     })
 
     #[before(parent_identifier), current(TokenKind::Self_)]
     self_identifier({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        Ok(Expression::Self_)
+        Ok(Expression::Self_(span))
     })
 
     #[before(left_parenthesis), current(TokenKind::Parent)]
     parent_identifier({
+        let span = state.stream.current().span;
         state.stream.next();
 
-        Ok(Expression::Parent)
+        Ok(Expression::Parent(span))
     })
 
     #[before(r#match), current(TokenKind::LeftParen)]
@@ -963,19 +989,22 @@ expressions! {
 
         let target = match state.stream.current().kind {
             TokenKind::Self_ => {
+                let span = state.stream.current().span;
                 state.stream.next();
 
-                Expression::Self_
+                Expression::Self_(span)
             }
             TokenKind::Static => {
+                let span = state.stream.current().span;
                 state.stream.next();
 
-                Expression::Static
+                Expression::Static(span)
             }
             TokenKind::Parent => {
+                let span = state.stream.current().span;
                 state.stream.next();
 
-                Expression::Parent
+                Expression::Parent(span)
             }
             TokenKind::Enum => {
                 let span = state.stream.current().span;
@@ -1083,12 +1112,13 @@ expressions! {
     include({
         let current = state.stream.current();
         let span = current.span;
+        let kind = current.kind;
 
         state.stream.next();
 
         let path = Box::new(create(state)?);
 
-        Ok(match current.kind {
+        Ok(match kind {
             TokenKind::Include => Expression::Include(IncludeExpression { include: span, path }),
             TokenKind::IncludeOnce => Expression::IncludeOnce(IncludeOnceExpression { include_once: span, path }),
             TokenKind::Require => Expression::Require(RequireExpression { require: span, path }),
@@ -1107,7 +1137,7 @@ expressions! {
         let current = state.stream.current();
 
         let span = current.span;
-        let kind = current.kind.clone().into();
+        let kind = current.kind.into();
 
         state.stream.next();
 
@@ -1125,7 +1155,7 @@ expressions! {
         let current = state.stream.current();
 
         let span = current.span;
-        let op = current.kind.clone();
+        let op = current.kind;
 
         state.stream.next();
 
@@ -1249,6 +1279,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
             let span = utils::skip_double_colon(state)?;
 
             let current = state.stream.current();
+            let start = current.span;
 
             let property = match current.kind {
                 TokenKind::Variable | TokenKind::Dollar | TokenKind::DollarLeftBrace => {
@@ -1263,7 +1294,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                     state.stream.next();
 
                     Expression::Identifier(Identifier::DynamicIdentifier(DynamicIdentifier {
-                        start: current.span,
+                        start,
                         expr: Box::new(create(state)?),
                         end: utils::skip_right_brace(state)?,
                     }))
@@ -1272,7 +1303,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
                     state.stream.next();
 
                     Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
-                        span: current.span,
+                        span: start,
                         value: "class".into(),
                     }))
                 }
@@ -1300,24 +1331,24 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
 
                     match property {
                         Expression::Identifier(identifier) => {
-                            Expression::StaticMethodClosureCreation(
+                            Expression::StaticMethodClosureCreation(Box::new(
                                 StaticMethodClosureCreationExpression {
                                     target: lhs,
                                     double_colon: span,
                                     method: identifier,
                                     placeholder,
                                 },
-                            )
+                            ))
                         }
                         Expression::Variable(variable) => {
-                            Expression::StaticVariableMethodClosureCreation(
+                            Expression::StaticVariableMethodClosureCreation(Box::new(
                                 StaticVariableMethodClosureCreationExpression {
                                     target: lhs,
                                     double_colon: span,
                                     method: variable,
                                     placeholder,
                                 },
-                            )
+                            ))
                         }
                         _ => unreachable!(),
                     }
@@ -1326,20 +1357,20 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> ParseResult<Ex
 
                     match property {
                         Expression::Identifier(identifier) => {
-                            Expression::StaticMethodCall(StaticMethodCallExpression {
+                            Expression::StaticMethodCall(Box::new(StaticMethodCallExpression {
                                 target: lhs,
                                 double_colon: span,
                                 method: identifier,
                                 arguments,
-                            })
+                            }))
                         }
                         Expression::Variable(variable) => Expression::StaticVariableMethodCall(
-                            StaticVariableMethodCallExpression {
+                            Box::new(StaticVariableMethodCallExpression {
                                 target: lhs,
                                 double_colon: span,
                                 method: variable,
                                 arguments,
-                            },
+                            }),
                         ),
                         _ => unreachable!(),
                     }
@@ -16,14 +16,14 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
             let mut items = Vec::new();
             let mut has_at_least_one_key = false;
 
-            let mut current = state.stream.current();
+            let mut current = state.stream.current().clone();
             while current.kind != TokenKind::RightParen {
                 if current.kind == TokenKind::Comma {
                     state.stream.next();
 
                     items.push(ListEntry::Skipped);
 
-                    current = state.stream.current();
+                    current = state.stream.current().clone();
 
                     continue;
                 }
@@ -43,7 +43,7 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
                 }
 
                 let mut value = expressions::create(state)?;
-                current = state.stream.current();
+                current = state.stream.current().clone();
                 if current.kind == TokenKind::DoubleArrow {
                     if !has_at_least_one_key && !items.is_empty() {
                         state.record(error::mixing_keyed_and_unkeyed_list_entries(current.span));
@@ -53,7 +53,7 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
 
                     state.stream.next();
 
-                    current = state.stream.current();
+                    current = state.stream.current().clone();
                     if current.kind == TokenKind::Ellipsis {
                         state.stream.next();
 
@@ -69,7 +69,7 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
                     }
 
                     let mut key = expressions::create(state)?;
-                    current = state.stream.current();
+                    current = state.stream.current().clone();
 
                     std::mem::swap(&mut key, &mut value);
 
@@ -90,7 +90,7 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
 
                 if current.kind == TokenKind::Comma {
                     state.stream.next();
-                    current = state.stream.current();
+                    current = state.stream.current().clone();
                 } else {
                     break;
                 }
@@ -135,11 +135,11 @@ pub fn array_expression(state: &mut State) -> ParseResult<Expression> {
 }
 
 fn array_pair(state: &mut State) -> ParseResult<ArrayItem> {
-    let mut current = state.stream.current();
+    let mut current = state.stream.current().clone();
     let ellipsis = if current.kind == TokenKind::Ellipsis {
         state.stream.next();
         let span = current.span;
-        current = state.stream.current();
+        current = state.stream.current().clone();
 
         Some(span)
     } else {
@@ -170,13 +170,13 @@ fn array_pair(state: &mut State) -> ParseResult<ArrayItem> {
         return Ok(ArrayItem::ReferencedValue { ampersand, value });
     }
 
-    let mut current = state.stream.current();
+    let mut current = state.stream.current().clone();
     if current.kind == TokenKind::DoubleArrow {
         let double_arrow = current.span;
 
         state.stream.next();
 
-        current = state.stream.current();
+        current = state.stream.current().clone();
         if current.kind == TokenKind::Ellipsis {
             state.stream.next();
 
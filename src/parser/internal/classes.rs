@@ -80,7 +80,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         right_brace: utils::skip_right_brace(state)?,
     };
 
-    Ok(Statement::Class(ClassStatement {
+    Ok(Statement::Class(Box::new(ClassStatement {
         class,
         name,
         modifiers,
@@ -88,7 +88,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         implements,
         attributes,
         body,
-    }))
+    })))
 }
 
 pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Expression> {
@@ -109,7 +109,7 @@ pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Exp
         None
     };
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let extends = if current.kind == TokenKind::Extends {
         state.stream.next();
 
@@ -121,7 +121,7 @@ pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Exp
         None
     };
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let implements = if current.kind == TokenKind::Implements {
         state.stream.next();
 
@@ -152,13 +152,15 @@ pub fn parse_anonymous(state: &mut State, span: Option<Span>) -> ParseResult<Exp
     };
 
     Ok(Expression::New(NewExpression {
-        target: Box::new(Expression::AnonymousClass(AnonymousClassExpression {
-            class,
-            extends,
-            implements,
-            attributes,
-            body,
-        })),
+        target: Box::new(Expression::AnonymousClass(Box::new(
+            AnonymousClassExpression {
+                class,
+                extends,
+                implements,
+                attributes,
+                body,
+            },
+        ))),
         new,
         arguments,
     }))
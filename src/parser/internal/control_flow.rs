@@ -14,6 +14,7 @@ use crate::parser::ast::Expression;
 use crate::parser::ast::MatchArm;
 use crate::parser::ast::Statement;
 use crate::parser::ast::SwitchStatement;
+use crate::parser::ast::SwitchStatementEnd;
 use crate::parser::ast::{Block, MatchExpression};
 use crate::parser::error;
 use crate::parser::error::ParseResult;
@@ -35,7 +36,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
     let mut default: Option<Box<DefaultMatchArm>> = None;
     let mut arms = Vec::new();
     while state.stream.current().kind != TokenKind::RightBrace {
-        let current = state.stream.current();
+        let current = state.stream.current().clone();
         if current.kind == TokenKind::Default {
             if let Some(default_arm) = default {
                 return Err(error::match_expression_has_multiple_default_arms(
@@ -96,7 +97,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
 
     let right_brace = utils::skip_right_brace(state)?;
 
-    Ok(Expression::Match(MatchExpression {
+    Ok(Expression::Match(Box::new(MatchExpression {
         keyword,
         left_parenthesis,
         condition,
@@ -105,7 +106,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
         default,
         arms,
         right_brace,
-    }))
+    })))
 }
 
 pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
@@ -126,11 +127,11 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
     while state.stream.current().kind != end_token {
         match state.stream.current().kind {
             TokenKind::Case => {
-                state.stream.next();
+                let keyword = utils::skip(state, TokenKind::Case)?;
 
                 let condition = expressions::create(state)?;
 
-                utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
+                let colon = utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
 
                 let mut body = Block::new();
 
@@ -143,14 +144,16 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
                 }
 
                 cases.push(Case {
+                    keyword,
                     condition: Some(condition),
+                    colon,
                     body,
                 });
             }
             TokenKind::Default => {
-                state.stream.next();
+                let keyword = utils::skip(state, TokenKind::Default)?;
 
-                utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
+                let colon = utils::skip_any_of(state, &[TokenKind::Colon, TokenKind::SemiColon])?;
 
                 let mut body = Block::new();
 
@@ -162,7 +165,9 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
                 }
 
                 cases.push(Case {
+                    keyword,
                     condition: None,
+                    colon,
                     body,
                 });
             }
@@ -172,20 +177,25 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
         }
     }
 
-    if end_token == TokenKind::EndSwitch {
-        utils::skip(state, TokenKind::EndSwitch)?;
-        utils::skip_ending(state)?;
+    let end = if end_token == TokenKind::EndSwitch {
+        SwitchStatementEnd::EndSwitch {
+            endswitch: utils::skip(state, TokenKind::EndSwitch)?,
+            ending: utils::skip_ending(state)?,
+        }
     } else {
-        utils::skip_right_brace(state)?;
-    }
+        SwitchStatementEnd::RightBrace {
+            right_brace: utils::skip_right_brace(state)?,
+        }
+    };
 
-    Ok(Statement::Switch(SwitchStatement {
+    Ok(Statement::Switch(Box::new(SwitchStatement {
         switch,
         left_parenthesis,
         condition,
         right_parenthesis,
         cases,
-    }))
+        end,
+    })))
 }
 
 pub fn if_statement(state: &mut State) -> ParseResult<Statement> {
@@ -194,7 +204,7 @@ pub fn if_statement(state: &mut State) -> ParseResult<Statement> {
     let (left_parenthesis, condition, right_parenthesis) =
         utils::parenthesized(state, &expressions::create)?;
 
-    Ok(Statement::If(IfStatement {
+    Ok(Statement::If(Box::new(IfStatement {
         r#if,
         left_parenthesis,
         condition,
@@ -204,14 +214,14 @@ pub fn if_statement(state: &mut State) -> ParseResult<Statement> {
         } else {
             if_statement_statement_body(state)?
         },
-    }))
+    })))
 }
 
 fn if_statement_statement_body(state: &mut State) -> ParseResult<IfStatementBody> {
     let statement = parser::statement(state).map(Box::new)?;
 
     let mut elseifs: Vec<IfStatementElseIf> = vec![];
-    let mut current = state.stream.current();
+    let mut current = state.stream.current().clone();
     while current.kind == TokenKind::ElseIf {
         state.stream.next();
 
@@ -226,7 +236,7 @@ fn if_statement_statement_body(state: &mut State) -> ParseResult<IfStatementBody
             statement: parser::statement(state).map(Box::new)?,
         });
 
-        current = state.stream.current();
+        current = state.stream.current().clone();
     }
 
     let r#else = if current.kind == TokenKind::Else {
@@ -255,7 +265,7 @@ fn if_statement_block_body(state: &mut State) -> ParseResult<IfStatementBody> {
     )?;
 
     let mut elseifs: Vec<IfStatementElseIfBlock> = vec![];
-    let mut current = state.stream.current();
+    let mut current = state.stream.current().clone();
     while current.kind == TokenKind::ElseIf {
         state.stream.next();
 
@@ -274,7 +284,7 @@ fn if_statement_block_body(state: &mut State) -> ParseResult<IfStatementBody> {
             )?,
         });
 
-        current = state.stream.current();
+        current = state.stream.current().clone();
     }
 
     let r#else = if current.kind == TokenKind::Else {
@@ -192,7 +192,7 @@ fn simple_data_type(state: &mut State) -> ParseResult<Type> {
 }
 
 fn nullable(state: &mut State) -> ParseResult<Type> {
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
 
     state.stream.next();
 
@@ -76,14 +76,14 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
             right_brace: utils::skip_right_brace(state)?,
         };
 
-        Ok(Statement::BackedEnum(BackedEnumStatement {
+        Ok(Statement::BackedEnum(Box::new(BackedEnumStatement {
             r#enum: span,
             name,
             backed_type,
             attributes,
             implements,
             body,
-        }))
+        })))
     } else {
         let body = UnitEnumBody {
             left_brace: utils::skip_left_brace(state)?,
@@ -121,7 +121,7 @@ fn unit_member(
             .map(Some);
     }
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     if current.kind == TokenKind::Case {
         let attributes = state.get_attributes();
 
@@ -130,7 +130,7 @@ fn unit_member(
 
         let name = identifiers::identifier_maybe_reserved(state)?;
 
-        let current = state.stream.current();
+        let current = state.stream.current().clone();
         if current.kind == TokenKind::Equals {
             // parse the value, but don't do anything with it.
             let _ = utils::skip(state, TokenKind::Equals)?;
@@ -177,7 +177,7 @@ fn backed_member(
             .map(Some);
     }
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     if current.kind == TokenKind::Case {
         let attributes = state.get_attributes();
 
@@ -186,7 +186,7 @@ fn backed_member(
 
         let name = identifiers::identifier_maybe_reserved(state)?;
 
-        let current = state.stream.current();
+        let current = state.stream.current().clone();
         if current.kind == TokenKind::SemiColon {
             // parse the semicolon, but don't do anything with it.
             let _ = utils::skip_semicolon(state)?;
@@ -41,7 +41,7 @@ pub enum Method {
 pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
     let comments = state.stream.comments();
     let attributes = state.get_attributes();
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let r#static = if current.kind == TokenKind::Static {
         state.stream.next();
 
@@ -52,7 +52,7 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
 
     let function = utils::skip(state, TokenKind::Function)?;
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let ampersand = if current.kind == TokenKind::Ampersand {
         state.stream.next();
 
@@ -63,7 +63,7 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
 
     let parameters = parameters::function_parameter_list(state)?;
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let uses = if current.kind == TokenKind::Use {
         state.stream.next();
 
@@ -75,7 +75,7 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
                 state,
                 &|state| {
                     let use_comments = state.stream.comments();
-                    let current = state.stream.current();
+                    let current = state.stream.current().clone();
                     let use_ampersand = if current.kind == TokenKind::Ampersand {
                         state.stream.next();
 
@@ -116,7 +116,7 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
         right_brace: utils::skip_right_brace(state)?,
     };
 
-    Ok(Expression::Closure(ClosureExpression {
+    Ok(Expression::Closure(Box::new(ClosureExpression {
         comments,
         function,
         attributes,
@@ -126,12 +126,12 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
         body,
         r#static,
         ampersand,
-    }))
+    })))
 }
 
 pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
     let comments = state.stream.comments();
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let r#static = if current.kind == TokenKind::Static {
         state.stream.next();
 
@@ -142,7 +142,7 @@ pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
 
     let r#fn = utils::skip(state, TokenKind::Fn)?;
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let ampersand = if state.stream.current().kind == TokenKind::Ampersand {
         state.stream.next();
 
@@ -166,17 +166,19 @@ pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
 
     let body = Box::new(expressions::create(state)?);
 
-    Ok(Expression::ArrowFunction(ArrowFunctionExpression {
-        comments,
-        attributes,
-        r#static,
-        r#fn,
-        ampersand,
-        parameters,
-        return_type,
-        double_arrow,
-        body,
-    }))
+    Ok(Expression::ArrowFunction(Box::new(
+        ArrowFunctionExpression {
+            comments,
+            attributes,
+            r#static,
+            r#fn,
+            ampersand,
+            parameters,
+            return_type,
+            double_arrow,
+            body,
+        },
+    )))
 }
 
 pub fn function(state: &mut State) -> ParseResult<Statement> {
@@ -184,7 +186,7 @@ pub fn function(state: &mut State) -> ParseResult<Statement> {
 
     let function = utils::skip(state, TokenKind::Function)?;
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let ampersand = if current.kind == TokenKind::Ampersand {
         state.stream.next();
 
@@ -216,7 +218,7 @@ pub fn function(state: &mut State) -> ParseResult<Statement> {
         right_brace: utils::skip_right_brace(state)?,
     };
 
-    Ok(Statement::Function(FunctionStatement {
+    Ok(Statement::Function(Box::new(FunctionStatement {
         comments,
         function,
         name,
@@ -225,7 +227,7 @@ pub fn function(state: &mut State) -> ParseResult<Statement> {
         return_type,
         body,
         ampersand,
-    }))
+    })))
 }
 
 pub fn method(
@@ -238,7 +240,7 @@ pub fn method(
     let attributes = state.get_attributes();
     let function = utils::skip(state, TokenKind::Function)?;
 
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
     let ampersand = if current.kind == TokenKind::Ampersand {
         state.stream.next();
 
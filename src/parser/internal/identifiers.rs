@@ -24,8 +24,8 @@ pub fn identifier_of(state: &mut State, kinds: &[&str]) -> ParseResult<SimpleIde
 
 /// Expect an unqualified identifier such as Foo or Bar for a class, interface, trait, or an enum name.
 pub fn type_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
-    let current = state.stream.current();
-    match &current.kind {
+    let current = state.stream.current().clone();
+    match current.kind {
         TokenKind::Identifier => {
             let span = current.span;
 
@@ -33,7 +33,7 @@ pub fn type_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value,
             })
         }
         TokenKind::Enum | TokenKind::From => {
@@ -57,7 +57,7 @@ pub fn type_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier { span, value: name })
         }
-        t if is_reserved_identifier(t) => {
+        t if is_reserved_identifier(&t) => {
             state.record(error::cannot_use_reserved_keyword_as_a_type_name(
                 current.span,
                 current.to_string(),
@@ -72,15 +72,15 @@ pub fn type_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
         }
         _ => Err(error::unexpected_token(
             vec!["an identifier".to_owned()],
-            current,
+            &current,
         )),
     }
 }
 
 /// Expect an unqualified identifier such as foo or bar for a goto label name.
 pub fn label_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
-    let current = state.stream.current();
-    match &current.kind {
+    let current = state.stream.current().clone();
+    match current.kind {
         TokenKind::Identifier => {
             let span = current.span;
 
@@ -88,7 +88,7 @@ pub fn label_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value,
             })
         }
         TokenKind::Enum | TokenKind::From => {
@@ -113,7 +113,7 @@ pub fn label_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier { span, value: name })
         }
-        t if is_reserved_identifier(t) => {
+        t if is_reserved_identifier(&t) => {
             state.record(error::cannot_use_reserved_keyword_as_a_goto_label(
                 current.span,
                 current.to_string(),
@@ -128,15 +128,15 @@ pub fn label_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
         }
         _ => Err(error::unexpected_token(
             vec!["an identifier".to_owned()],
-            current,
+            &current,
         )),
     }
 }
 
 /// Expect an unqualified identifier such as FOO or BAR for a constant name.
 pub fn constant_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
-    let current = state.stream.current();
-    match &current.kind {
+    let current = state.stream.current().clone();
+    match current.kind {
         TokenKind::Identifier => {
             let span = current.span;
 
@@ -144,7 +144,7 @@ pub fn constant_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value,
             })
         }
         TokenKind::Enum | TokenKind::From | TokenKind::Self_ | TokenKind::Parent => {
@@ -155,7 +155,7 @@ pub fn constant_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier { span, value: name })
         }
-        t if is_reserved_identifier(t) => {
+        t if is_reserved_identifier(&t) => {
             state.record(error::cannot_use_reserved_keyword_as_a_constant_name(
                 current.span,
                 current.to_string(),
@@ -170,27 +170,27 @@ pub fn constant_identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
         }
         _ => Err(error::unexpected_token(
             vec!["an identifier".to_owned()],
-            current,
+            &current,
         )),
     }
 }
 
 /// Expect an unqualified identifier such as Foo or Bar.
 pub fn identifier(state: &mut State) -> ParseResult<SimpleIdentifier> {
-    let current = state.stream.current();
-    if let TokenKind::Identifier = &current.kind {
+    let current = state.stream.current().clone();
+    if let TokenKind::Identifier = current.kind {
         let span = current.span;
 
         state.stream.next();
 
         Ok(SimpleIdentifier {
             span,
-            value: current.value.clone(),
+            value: current.value,
         })
     } else {
         Err(error::unexpected_token(
             vec!["an identifier".to_owned()],
-            current,
+            &current,
         ))
     }
 }
@@ -211,23 +211,23 @@ pub fn name(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
 /// Expect an optional unqualified or qualified identifier such as Foo, Bar or Foo\Bar.
 pub fn optional_name(state: &mut State) -> Option<SimpleIdentifier> {
-    let current = state.stream.current();
+    let current = state.stream.current().clone();
 
-    match &current.kind {
+    match current.kind {
         TokenKind::Identifier | TokenKind::QualifiedIdentifier => {
             state.stream.next();
 
             Some(SimpleIdentifier {
                 span: current.span,
-                value: current.value.clone(),
+                value: current.value,
             })
         }
-        t if is_reserved_identifier(t) => {
+        t if is_reserved_identifier(&t) => {
             state.stream.next();
 
             Some(SimpleIdentifier {
                 span: current.span,
-                value: current.value.clone(),
+                value: current.value,
             })
         }
         _ => None,
@@ -236,8 +236,8 @@ pub fn optional_name(state: &mut State) -> Option<SimpleIdentifier> {
 
 /// Expect an unqualified, qualified or fully qualified identifier such as Foo, Foo\Bar or \Foo\Bar.
 pub fn full_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
-    let current = state.stream.current();
-    match &current.kind {
+    let current = state.stream.current().clone();
+    match current.kind {
         TokenKind::Identifier
         | TokenKind::QualifiedIdentifier
         | TokenKind::FullyQualifiedIdentifier => {
@@ -247,20 +247,20 @@ pub fn full_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value,
             })
         }
         _ => Err(error::unexpected_token(
             vec!["an identifier".to_owned()],
-            current,
+            &current,
         )),
     }
 }
 
 /// Expect an unqualified, qualified or fully qualified identifier such as Foo, Foo\Bar or \Foo\Bar.
 pub fn full_type_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
-    let current = state.stream.current();
-    match &current.kind {
+    let current = state.stream.current().clone();
+    match current.kind {
         TokenKind::Identifier
         | TokenKind::QualifiedIdentifier
         | TokenKind::FullyQualifiedIdentifier => {
@@ -270,7 +270,7 @@ pub fn full_type_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value,
             })
         }
         TokenKind::Enum | TokenKind::From => {
@@ -294,7 +294,7 @@ pub fn full_type_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
 
             Ok(SimpleIdentifier { span, value: name })
         }
-        t if is_reserved_identifier(t) => {
+        t if is_reserved_identifier(&t) => {
             state.record(error::cannot_use_reserved_keyword_as_a_type_name(
                 current.span,
                 current.to_string(),
@@ -309,15 +309,15 @@ pub fn full_type_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
         }
         _ => Err(error::unexpected_token(
             vec!["an identifier".to_owned()],
-            current,
+            &current,
         )),
     }
 }
 
 /// Expect an unqualified, qualified or fully qualified identifier such as Foo, Foo\Bar or \Foo\Bar.
 pub fn full_type_name_including_self(state: &mut State) -> ParseResult<SimpleIdentifier> {
-    let current = state.stream.current();
-    match &current.kind {
+    let current = state.stream.current().clone();
+    match current.kind {
         TokenKind::Identifier
         | TokenKind::QualifiedIdentifier
         | TokenKind::FullyQualifiedIdentifier => {
@@ -327,7 +327,7 @@ pub fn full_type_name_including_self(state: &mut State) -> ParseResult<SimpleIde
 
             Ok(SimpleIdentifier {
                 span,
-                value: current.value.clone(),
+                value: current.value,
             })
         }
         TokenKind::Enum
@@ -342,7 +342,7 @@ pub fn full_type_name_including_self(state: &mut State) -> ParseResult<SimpleIde
 
             Ok(SimpleIdentifier { span, value: name })
         }
-        t if is_reserved_identifier(t) => {
+        t if is_reserved_identifier(&t) => {
             state.record(error::cannot_use_reserved_keyword_as_a_type_name(
                 current.span,
                 current.to_string(),
@@ -357,7 +357,7 @@ pub fn full_type_name_including_self(state: &mut State) -> ParseResult<SimpleIde
         }
         _ => Err(error::unexpected_token(
             vec!["an identifier".to_owned()],
-            current,
+            &current,
         )),
     }
 }
@@ -55,13 +55,13 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         right_brace: utils::skip_right_brace(state)?,
     };
 
-    Ok(Statement::Interface(InterfaceStatement {
+    Ok(Statement::Interface(Box::new(InterfaceStatement {
         interface: span,
         name,
         attributes,
         extends,
         body,
-    }))
+    })))
 }
 
 fn member(state: &mut State, interface_name: &SimpleIdentifier) -> ParseResult<InterfaceMember> {
@@ -30,7 +30,7 @@ pub fn foreach_statement(state: &mut State) -> ParseResult<Statement> {
 
             let r#as = utils::skip(state, TokenKind::As)?;
 
-            let current = state.stream.current();
+            let current = state.stream.current().clone();
             let ampersand = if current.kind == TokenKind::Ampersand {
                 state.stream.next();
                 Some(current.span)
@@ -40,12 +40,12 @@ pub fn foreach_statement(state: &mut State) -> ParseResult<Statement> {
 
             let mut value = expressions::create(state)?;
 
-            let current = state.stream.current();
+            let current = state.stream.current().clone();
             if current.kind == TokenKind::DoubleArrow {
                 state.stream.next();
                 let arrow = current.span;
 
-                let current = state.stream.current();
+                let current = state.stream.current().clone();
                 let ampersand = if current.kind == TokenKind::Ampersand {
                     state.stream.next();
                     Some(current.span)
@@ -88,13 +88,13 @@ pub fn foreach_statement(state: &mut State) -> ParseResult<Statement> {
         }
     };
 
-    Ok(Statement::Foreach(ForeachStatement {
+    Ok(Statement::Foreach(Box::new(ForeachStatement {
         foreach,
         left_parenthesis,
         iterator,
         right_parenthesis,
         body,
-    }))
+    })))
 }
 
 pub fn for_statement(state: &mut State) -> ParseResult<Statement> {
@@ -140,13 +140,13 @@ pub fn for_statement(state: &mut State) -> ParseResult<Statement> {
         }
     };
 
-    Ok(Statement::For(ForStatement {
+    Ok(Statement::For(Box::new(ForStatement {
         r#for,
         left_parenthesis,
         iterator,
         right_parenthesis,
         body,
-    }))
+    })))
 }
 
 pub fn do_while_statement(state: &mut State) -> ParseResult<Statement> {
@@ -161,7 +161,7 @@ pub fn do_while_statement(state: &mut State) -> ParseResult<Statement> {
             utils::parenthesized(state, &expressions::create)
         })?;
 
-    Ok(Statement::DoWhile(DoWhileStatement {
+    Ok(Statement::DoWhile(Box::new(DoWhileStatement {
         r#do,
         body,
         r#while,
@@ -169,7 +169,7 @@ pub fn do_while_statement(state: &mut State) -> ParseResult<Statement> {
         condition,
         right_parenthesis,
         semicolon,
-    }))
+    })))
 }
 
 pub fn while_statement(state: &mut State) -> ParseResult<Statement> {
@@ -191,13 +191,13 @@ pub fn while_statement(state: &mut State) -> ParseResult<Statement> {
         }
     };
 
-    Ok(Statement::While(WhileStatement {
+    Ok(Statement::While(Box::new(WhileStatement {
         r#while,
         left_parenthesis,
         condition,
         right_parenthesis,
         body,
-    }))
+    })))
 }
 
 pub fn continue_statement(state: &mut State) -> ParseResult<Statement> {
@@ -233,14 +233,11 @@ fn loop_level(state: &mut State) -> ParseResult<Level> {
         kind: TokenKind::LiteralInteger,
         span,
         value,
-    } = state.stream.current()
+    } = state.stream.current().clone()
     {
         state.stream.next();
 
-        return Ok(Level::Literal(LiteralInteger {
-            value: value.clone(),
-            span: *span,
-        }));
+        return Ok(Level::Literal(LiteralInteger { value, span }));
     }
 
     let (left_parenthesis, level, right_parenthesis) =
@@ -11,6 +11,7 @@ use crate::parser::ast::modifiers::PromotedPropertyModifierGroup;
 use crate::parser::ast::modifiers::PropertyModifier;
 use crate::parser::ast::modifiers::PropertyModifierGroup;
 use crate::parser::error;
+use crate::parser::error::ParseErrorEdit;
 use crate::parser::error::ParseResult;
 use crate::parser::state::State;
 
@@ -21,24 +22,28 @@ pub fn class_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ClassModifierGr
 
     let modifiers = input
         .iter()
-        .map(|(span, token)| match token {
+        .enumerate()
+        .map(|(index, (span, token))| match token {
             TokenKind::Readonly => Ok(ClassModifier::Readonly(*span)),
             TokenKind::Final => {
                 final_span = Some(*span);
-                if let Some(abstract_span) = abstract_span {
+                if let Some((abstract_index, abstract_span)) = abstract_span {
                     Err(error::final_and_abstract_modifiers_combined_for_class(
                         *span,
                         abstract_span,
+                        removal(&input, abstract_index),
                     ))
                 } else {
                     Ok(ClassModifier::Final(*span))
                 }
             }
             TokenKind::Abstract => {
-                abstract_span = Some(*span);
+                abstract_span = Some((index, *span));
                 if let Some(final_span) = final_span {
                     Err(error::final_and_abstract_modifiers_combined_for_class(
-                        final_span, *span,
+                        final_span,
+                        *span,
+                        removal(&input, index),
                     ))
                 } else {
                     Ok(ClassModifier::Abstract(*span))
@@ -61,14 +66,16 @@ pub fn method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<MethodModifier
 
     let modifiers = input
         .iter()
-        .map(|(span, token)| match token {
+        .enumerate()
+        .map(|(index, (span, token))| match token {
             TokenKind::Final => {
                 final_span = Some(*span);
-                if let Some(abstract_span) = abstract_span {
+                if let Some((abstract_index, abstract_span)) = abstract_span {
                     Err(
                         error::final_and_abstract_modifiers_combined_for_class_member(
                             *span,
                             abstract_span,
+                            removal(&input, abstract_index),
                         ),
                     )
                 } else {
@@ -76,11 +83,13 @@ pub fn method_group(input: Vec<(Span, TokenKind)>) -> ParseResult<MethodModifier
                 }
             }
             TokenKind::Abstract => {
-                abstract_span = Some(*span);
+                abstract_span = Some((index, *span));
                 if let Some(final_span) = final_span {
                     Err(
                         error::final_and_abstract_modifiers_combined_for_class_member(
-                            final_span, *span,
+                            final_span,
+                            *span,
+                            removal(&input, index),
                         ),
                     )
                 } else {
@@ -185,25 +194,29 @@ pub fn constant_group(input: Vec<(Span, TokenKind)>) -> ParseResult<ConstantModi
 
     let modifiers = input
         .iter()
-        .map(|(span, token)| match token {
+        .enumerate()
+        .map(|(index, (span, token))| match token {
             TokenKind::Protected => Ok(ConstantModifier::Protected(*span)),
             TokenKind::Public => Ok(ConstantModifier::Public(*span)),
             TokenKind::Private => {
                 private_span = Some(*span);
-                if let Some(final_span) = final_span {
+                if let Some((final_index, final_span)) = final_span {
                     Err(error::final_and_private_modifiers_combined_for_constant(
-                        final_span, *span,
+                        final_span,
+                        *span,
+                        removal(&input, final_index),
                     ))
                 } else {
                     Ok(ConstantModifier::Final(*span))
                 }
             }
             TokenKind::Final => {
-                final_span = Some(*span);
+                final_span = Some((index, *span));
                 if let Some(private_span) = private_span {
                     Err(error::final_and_private_modifiers_combined_for_constant(
                         *span,
                         private_span,
+                        removal(&input, index),
                     ))
                 } else {
                     Ok(ConstantModifier::Final(*span))
@@ -255,11 +268,17 @@ pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind)>> {
     let mut current_span = current.span;
 
     while collectable_tokens.contains(&current_kind) {
-        if let Some((span, _)) = collected.iter().find(|(_, kind)| kind == &current_kind) {
+        collected.push((current_span, current_kind));
+
+        let index = collected.len() - 1;
+        let previous = &collected[..index];
+
+        if let Some((span, _)) = previous.iter().find(|(_, kind)| kind == &current_kind) {
             return Err(error::multiple_modifiers(
                 current_kind.to_string(),
                 *span,
                 current_span,
+                removal(&collected, index),
             ));
         }
 
@@ -268,21 +287,22 @@ pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind)>> {
             current_kind,
             TokenKind::Public | TokenKind::Protected | TokenKind::Private
         ) {
-            if let Some((span, visibility)) = collected.iter().find(|(_, kind)| {
+            if let Some((span, visibility)) = previous.iter().find(|(_, kind)| {
                 matches!(
                     kind,
                     TokenKind::Public | TokenKind::Protected | TokenKind::Private
                 )
             }) {
-                state.record(error::multiple_visibility_modifiers(
+                let error = error::multiple_visibility_modifiers(
                     (visibility.to_string(), *span),
                     (current_kind.to_string(), current_span),
-                ));
+                    removal(&collected, index),
+                );
+
+                state.record(error);
             }
         }
 
-        collected.push((current_span, current_kind));
-
         state.stream.next();
 
         current = state.stream.current().clone();
@@ -292,3 +312,20 @@ pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind)>> {
 
     Ok(collected)
 }
+
+/// An edit removing the modifier at `index`, along with the whitespace separating
+/// it from the modifier before it (or after it, if it is the first one).
+fn removal(modifiers: &[(Span, TokenKind)], index: usize) -> ParseErrorEdit {
+    let end = |(span, kind): &(Span, TokenKind)| span.position + kind.to_string().len();
+
+    let (span, _) = &modifiers[index];
+    if index > 0 {
+        let start = end(&modifiers[index - 1]);
+
+        ParseErrorEdit::remove(start, end(&modifiers[index]) - start)
+    } else if let Some((next, _)) = modifiers.get(index + 1) {
+        ParseErrorEdit::remove(span.position, next.position - span.position)
+    } else {
+        ParseErrorEdit::remove(span.position, end(&modifiers[index]) - span.position)
+    }
+}
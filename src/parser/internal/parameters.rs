@@ -28,10 +28,10 @@ pub fn function_parameter_list(state: &mut State) -> Result<FunctionParameterLis
 
             let ty = data_type::optional_data_type(state)?;
 
-            let mut current = state.stream.current();
+            let mut current = state.stream.current().clone();
             let ampersand = if current.kind == TokenKind::Ampersand {
                 state.stream.next();
-                current = state.stream.current();
+                current = state.stream.current().clone();
                 Some(current.span)
             } else {
                 None
@@ -93,11 +93,11 @@ pub fn constructor_parameter_list(
 
             let ty = data_type::optional_data_type(state)?;
 
-            let mut current = state.stream.current();
+            let mut current = state.stream.current().clone();
             let ampersand = if matches!(current.kind, TokenKind::Ampersand) {
                 state.stream.next();
 
-                current = state.stream.current();
+                current = state.stream.current().clone();
 
                 Some(current.span)
             } else {
@@ -229,7 +229,10 @@ pub fn single_argument(
 
     while !state.stream.is_eof() && state.stream.current().kind != TokenKind::RightParen {
         let span = state.stream.current().span;
-        let (named, argument) = argument(state).ok()?;
+        let (named, argument) = match argument(state) {
+            Ok(argument) => argument,
+            Err(error) => return Some(Err(error)),
+        };
         if only_positional && named {
             return Some(Err(error::only_positional_arguments_are_accepted(
                 span,
@@ -260,7 +263,10 @@ pub fn single_argument(
         )));
     }
 
-    let end = utils::skip_right_parenthesis(state).ok()?;
+    let end = match utils::skip_right_parenthesis(state) {
+        Ok(end) => end,
+        Err(error) => return Some(Err(error)),
+    };
 
     first_argument.as_ref()?;
 
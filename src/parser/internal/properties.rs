@@ -5,6 +5,7 @@ use crate::parser::ast::properties::Property;
 use crate::parser::ast::properties::PropertyEntry;
 use crate::parser::ast::properties::VariableProperty;
 use crate::parser::error;
+use crate::parser::error::ParseErrorEdit;
 use crate::parser::error::ParseResult;
 use crate::parser::expressions;
 use crate::parser::internal::data_type;
@@ -66,23 +67,28 @@ pub fn parse(
             }
         }
 
-        let current = state.stream.current();
+        let current = state.stream.current().clone();
         if current.kind == TokenKind::Equals {
+            state.stream.next();
+            let value = expressions::create(state)?;
+
             if let Some(modifier) = modifiers.get_readonly() {
+                // remove everything from the end of the variable up to the next `,` or `;`.
+                let start = variable.span.position + variable.name.len();
+                let end = state.stream.current().span.position;
+
                 let error = error::readonly_property_has_default_value(
                     state,
                     class_name,
                     &variable,
                     modifier.span(),
                     current.span,
+                    ParseErrorEdit::remove(start, end - start),
                 );
 
                 state.record(error);
             }
 
-            state.stream.next();
-            let value = expressions::create(state)?;
-
             entries.push(PropertyEntry::Initialized {
                 variable,
                 equals: current.span,
@@ -114,7 +120,7 @@ pub fn parse_var(
     state: &mut State,
     class_name: Option<&SimpleIdentifier>,
 ) -> ParseResult<VariableProperty> {
-    utils::skip(state, TokenKind::Var)?;
+    let var = utils::skip(state, TokenKind::Var)?;
 
     let ty = data_type::optional_data_type(state)?;
 
@@ -167,6 +173,7 @@ pub fn parse_var(
     Ok(VariableProperty {
         r#type: ty,
         attributes: state.get_attributes(),
+        var,
         entries,
         end,
     })
@@ -27,6 +27,8 @@ use crate::parser::state::State;
 
 #[inline(always)]
 pub fn interpolated(state: &mut State) -> ParseResult<Expression> {
+    // the first part of the string starts at its opening quote.
+    let start = state.stream.current().span;
     let mut parts = Vec::new();
 
     while state.stream.current().kind != TokenKind::DoubleQuote {
@@ -35,15 +37,17 @@ pub fn interpolated(state: &mut State) -> ParseResult<Expression> {
         }
     }
 
+    let end = state.stream.current().span;
     state.stream.next();
 
     Ok(Expression::InterpolatedString(
-        InterpolatedStringExpression { parts },
+        InterpolatedStringExpression { start, parts, end },
     ))
 }
 
 #[inline(always)]
 pub fn shell_exec(state: &mut State) -> ParseResult<Expression> {
+    let start = state.stream.current().span;
     state.stream.next();
 
     let mut parts = Vec::new();
@@ -54,9 +58,14 @@ pub fn shell_exec(state: &mut State) -> ParseResult<Expression> {
         }
     }
 
+    let end = state.stream.current().span;
     state.stream.next();
 
-    Ok(Expression::ShellExec(ShellExecExpression { parts }))
+    Ok(Expression::ShellExec(ShellExecExpression {
+        start,
+        parts,
+        end,
+    }))
 }
 
 #[inline(always)]
@@ -75,11 +84,12 @@ pub fn heredoc(state: &mut State) -> ParseResult<Expression> {
 
     let (indentation_type, indentation_amount) = match &state.stream.current().kind {
         TokenKind::EndDocString(indentation_type, indentation_amount) => {
-            (indentation_type.clone(), *indentation_amount)
+            (*indentation_type, *indentation_amount)
         }
         _ => unreachable!(),
     };
 
+    let end = state.stream.current().span;
     state.stream.next();
 
     let mut new_line = true;
@@ -142,7 +152,12 @@ pub fn heredoc(state: &mut State) -> ParseResult<Expression> {
         }
     }
 
-    Ok(Expression::Heredoc(HeredocExpression { label, parts }))
+    Ok(Expression::Heredoc(HeredocExpression {
+        start: span,
+        label,
+        parts,
+        end,
+    }))
 }
 
 #[inline(always)]
@@ -157,11 +172,12 @@ pub fn nowdoc(state: &mut State) -> ParseResult<Expression> {
 
     let (indentation_type, indentation_amount) = match &state.stream.current().kind {
         TokenKind::EndDocString(indentation_type, indentation_amount) => {
-            (indentation_type.clone(), *indentation_amount)
+            (*indentation_type, *indentation_amount)
         }
         _ => unreachable!(),
     };
 
+    let end = state.stream.current().span;
     state.stream.next();
 
     if indentation_type != DocStringIndentationKind::None {
@@ -225,8 +241,10 @@ pub fn nowdoc(state: &mut State) -> ParseResult<Expression> {
     }
 
     Ok(Expression::Nowdoc(NowdocExpression {
+        start: span,
         label,
         value: string_part,
+        end,
     }))
 }
 
@@ -267,7 +285,7 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
                 TokenKind::LeftBracket => {
                     let left_bracket = utils::skip_left_bracket(state)?;
 
-                    let current = state.stream.current();
+                    let current = state.stream.current().clone();
                     // Full expression syntax is not allowed here,
                     // so we can't call expression.
                     let index = match &current.kind {
@@ -282,7 +300,7 @@ fn part(state: &mut State) -> ParseResult<Option<StringPart>> {
                         TokenKind::Minus => {
                             let span = current.span;
                             state.stream.next();
-                            let literal = state.stream.current();
+                            let literal = state.stream.current().clone();
                             if let TokenKind::LiteralInteger = &literal.kind {
                                 state.stream.next();
 
@@ -51,7 +51,7 @@ pub fn usage(state: &mut State) -> ParseResult<TraitUsage> {
     }
 
     let mut adaptations = Vec::new();
-    if state.stream.current().kind == TokenKind::LeftBrace {
+    let end = if state.stream.current().kind == TokenKind::LeftBrace {
         utils::skip_left_brace(state)?;
 
         while state.stream.current().kind != TokenKind::RightBrace {
@@ -147,15 +147,16 @@ pub fn usage(state: &mut State) -> ParseResult<TraitUsage> {
             utils::skip_semicolon(state)?;
         }
 
-        utils::skip_right_brace(state)?;
+        utils::skip_right_brace(state)?
     } else {
-        utils::skip_semicolon(state)?;
-    }
+        utils::skip_semicolon(state)?
+    };
 
     Ok(TraitUsage {
         r#use: span,
         traits,
         adaptations,
+        end,
     })
 }
 
@@ -46,9 +46,7 @@ pub fn try_block(state: &mut State) -> ParseResult<Statement> {
 
         let catch_body = blocks::multiple_statements_until(state, &TokenKind::RightBrace)?;
 
-        utils::skip_right_brace(state)?;
-
-        let catch_end = state.stream.current().span;
+        let catch_end = utils::skip_right_brace(state)?;
 
         catches.push(CatchBlock {
             start: catch_start,
@@ -67,8 +65,7 @@ pub fn try_block(state: &mut State) -> ParseResult<Statement> {
 
         let finally_body = blocks::multiple_statements_until(state, &TokenKind::RightBrace)?;
 
-        utils::skip_right_brace(state)?;
-        let finally_end = state.stream.current().span;
+        let finally_end = utils::skip_right_brace(state)?;
 
         finally = Some(FinallyBlock {
             start: finally_start,
@@ -81,7 +78,11 @@ pub fn try_block(state: &mut State) -> ParseResult<Statement> {
         return Err(error::try_without_catch_or_finally(start, last_right_brace));
     }
 
-    let end = state.stream.current().span;
+    let end = match (&finally, catches.last()) {
+        (Some(finally), _) => finally.end,
+        (None, Some(catch)) => catch.end,
+        (None, None) => last_right_brace,
+    };
 
     Ok(Statement::Try(TryStatement {
         start,
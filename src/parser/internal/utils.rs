@@ -9,14 +9,16 @@ use crate::parser::state::State;
 pub fn skip_ending(state: &mut State) -> ParseResult<Ending> {
     let current = state.stream.current();
 
+    let span = current.span;
+
     if current.kind == TokenKind::CloseTag {
         state.stream.next();
 
-        Ok(Ending::CloseTag(current.span))
+        Ok(Ending::CloseTag(span))
     } else if current.kind == TokenKind::SemiColon {
         state.stream.next();
 
-        Ok(Ending::Semicolon(current.span))
+        Ok(Ending::Semicolon(span))
     } else {
         Err(error::unexpected_token(vec![";".to_string()], current))
     }
@@ -25,10 +27,12 @@ pub fn skip_ending(state: &mut State) -> ParseResult<Ending> {
 pub fn skip_semicolon(state: &mut State) -> ParseResult<Span> {
     let current = state.stream.current();
 
+    let span = current.span;
+
     if current.kind == TokenKind::SemiColon {
         state.stream.next();
 
-        Ok(current.span)
+        Ok(span)
     } else {
         Err(error::unexpected_token(vec!["`;`".to_string()], current))
     }
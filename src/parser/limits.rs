@@ -0,0 +1,69 @@
+use std::cell::Cell;
+use std::sync::atomic::Ordering;
+
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::parser::error;
+use crate::parser::error::ParseError;
+use crate::parser::options::Options;
+
+/// Reads tokens until there are more than `Options::max_tokens`, or until the parse is
+/// cancelled, in which case the error is stored in `exceeded` and no more tokens are read.
+pub struct Limited<'a, I> {
+    tokens: I,
+    options: &'a Options,
+    exceeded: &'a Cell<Option<ParseError>>,
+    read: usize,
+    stopped: bool,
+    // the span of the last token read, where a cancellation is reported.
+    span: Span,
+}
+
+impl<'a, I: Iterator<Item = Token>> Limited<'a, I> {
+    pub fn new(tokens: I, options: &'a Options, exceeded: &'a Cell<Option<ParseError>>) -> Self {
+        Self {
+            tokens,
+            options,
+            exceeded,
+            read: 0,
+            stopped: false,
+            span: Span::new(1, 1, 0),
+        }
+    }
+
+    fn exceed(&mut self, error: ParseError) -> Option<Token> {
+        self.exceeded.set(Some(error));
+        self.stopped = true;
+
+        None
+    }
+}
+
+impl<'a, I: Iterator<Item = Token>> Iterator for Limited<'a, I> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        if self.stopped {
+            return None;
+        }
+
+        // the flag is checked before lexing the next token, which can take a while.
+        if let Some(cancellation) = &self.options.cancellation {
+            if cancellation.load(Ordering::Relaxed) {
+                return self.exceed(error::parse_cancelled(self.span));
+            }
+        }
+
+        let token = self.tokens.next()?;
+        self.read += 1;
+        self.span = token.span;
+
+        if let Some(max_tokens) = self.options.max_tokens {
+            if self.read > max_tokens {
+                return self.exceed(error::too_many_tokens(max_tokens, &token));
+            }
+        }
+
+        Some(token)
+    }
+}
@@ -26,8 +26,7 @@ macro_rules! peek_token {
 #[macro_export]
 macro_rules! expect_token {
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? => $out:expr),+ $(,)? ], $state:expr, [ $($message:literal),+ $(,)? ]) => {{
-        let token = $state.stream.current();
-        $state.stream.next();
+        let token = $state.stream.take();
         match token.kind {
             $(
                 $( $pattern )|+ $( if $guard )? => {
@@ -37,7 +36,7 @@ macro_rules! expect_token {
             _ => {
                 return Err($crate::parser::error::unexpected_token(
                     vec![$($message.into(),)+],
-                    token,
+                    token.as_ref(),
                 ))
             }
         }
@@ -50,36 +49,34 @@ macro_rules! expect_token {
 #[macro_export]
 macro_rules! expect_literal {
     ($state:expr) => {{
-        let current = $state.stream.current();
-
-        match &current.kind {
+        match $state.stream.current().kind {
             TokenKind::LiteralInteger => {
-                $state.stream.next();
+                let current = $state.stream.take().into_owned();
 
                 $crate::parser::ast::literals::Literal::Integer(
                     $crate::parser::ast::literals::LiteralInteger {
                         span: current.span,
-                        value: current.value.clone(),
+                        value: current.value,
                     },
                 )
             }
             TokenKind::LiteralFloat => {
-                $state.stream.next();
+                let current = $state.stream.take().into_owned();
 
                 $crate::parser::ast::literals::Literal::Float(
                     $crate::parser::ast::literals::LiteralFloat {
                         span: current.span,
-                        value: current.value.clone(),
+                        value: current.value,
                     },
                 )
             }
             TokenKind::LiteralSingleQuotedString | TokenKind::LiteralDoubleQuotedString => {
-                $state.stream.next();
+                let current = $state.stream.take().into_owned();
 
                 $crate::parser::ast::literals::Literal::String(
                     $crate::parser::ast::literals::LiteralString {
                         span: current.span,
-                        value: current.value.clone(),
+                        value: current.value,
                         kind: if matches!(current.kind, TokenKind::LiteralSingleQuotedString) {
                             $crate::parser::ast::literals::LiteralStringKind::SingleQuoted
                         } else {
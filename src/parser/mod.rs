@@ -1,4 +1,7 @@
+use std::cell::Cell;
+
 use crate::expect_literal;
+use crate::lexer::error::SyntaxError;
 use crate::lexer::token::OpenTagKind;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
@@ -9,6 +12,7 @@ use crate::parser::ast::declares::DeclareEntryGroup;
 use crate::parser::ast::declares::DeclareStatement;
 use crate::parser::ast::variables::Variable;
 use crate::parser::ast::{Program, Statement, StaticVar};
+use crate::parser::error::ParseError;
 use crate::parser::error::ParseErrorStack;
 use crate::parser::error::ParseResult;
 use crate::parser::internal::attributes;
@@ -28,8 +32,12 @@ use crate::parser::internal::try_block;
 use crate::parser::internal::uses;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::limits::Limited;
+use crate::parser::options::Options;
 use crate::parser::state::State;
+use crate::parser::state::Stream;
 
+pub use crate::lexer::stream::LazyTokenStream;
 pub use crate::lexer::stream::TokenStream;
 
 use self::ast::ClosingTagStatement;
@@ -46,31 +54,135 @@ use self::ast::StaticStatement;
 use self::internal::precedences::Precedence;
 
 pub mod ast;
+pub mod codes;
 pub mod error;
+pub mod options;
+pub mod validation;
 
 mod expressions;
 mod internal;
+mod limits;
 mod macros;
 mod state;
 
 pub fn parse<B: ?Sized + AsRef<[u8]>>(input: &B) -> Result<Program, ParseErrorStack> {
-    let lexer = Lexer::new();
-    let tokens = match lexer.tokenize(input) {
-        Ok(tokens) => tokens,
-        Err(error) => {
+    parse_with(input, &Options::default())
+}
+
+/// Parse the given input, as `parse` does, using the given options.
+pub fn parse_with<B: ?Sized + AsRef<[u8]>>(
+    input: &B,
+    options: &Options,
+) -> Result<Program, ParseErrorStack> {
+    if let Some(max_input_size) = options.max_input_size {
+        let size = input.as_ref().len();
+        if size > max_input_size {
             return Err(ParseErrorStack {
-                errors: vec![error.into()],
-                partial: Vec::new(),
-            })
+                partial: Program::new(),
+                errors: vec![error::input_too_large(size, max_input_size)],
+            });
+        }
+    }
+
+    // the tokens are produced as the parser needs them, rather than all at once.
+    let mut tokens = Lexer::new().tokens(input);
+    let invalid = Cell::new(usize::MAX);
+    let exceeded = Cell::new(None);
+    let mut limited = Limited::new(tokens.by_ref(), options, &exceeded);
+
+    let result = construct_stream_with(
+        LazyTokenStream::from_tokens(limited.by_ref().inspect(|token| {
+            if token.kind == TokenKind::Error {
+                invalid.set(invalid.get().min(token.span.position));
+            }
+        })),
+        options,
+    );
+
+    // the parser stops at the first error it can't recover from, but the syntax errors
+    // of the whole input are reported.
+    for token in limited.by_ref() {
+        if token.kind == TokenKind::Error {
+            invalid.set(invalid.get().min(token.span.position));
         }
+    }
+
+    // the errors caused by the tokens ending early are not reported.
+    if let Some(error) = exceeded.take() {
+        let partial = match result {
+            Ok(program) => program,
+            Err(stack) => stack.partial,
+        };
+
+        return Err(ParseErrorStack {
+            partial,
+            errors: vec![error],
+        });
+    }
+
+    let syntax_errors = tokens.into_errors();
+    if syntax_errors.is_empty() {
+        return result;
+    }
+
+    // Parser errors found after an invalid token, or after the start of an unterminated
+    // string or comment, are most likely caused by it, so they are not reported.
+    let cutoff = syntax_errors
+        .iter()
+        .filter(|error| {
+            matches!(
+                error,
+                SyntaxError::UnexpectedEndOfFile(_)
+                    | SyntaxError::UnterminatedString(..)
+                    | SyntaxError::UnterminatedDocString(..)
+                    | SyntaxError::UnterminatedComment(..)
+                    | SyntaxError::UnterminatedInterpolation(..)
+            )
+        })
+        .map(|error| error.span().position)
+        .chain(std::iter::once(invalid.get()))
+        .min()
+        .unwrap_or(usize::MAX);
+
+    let (partial, parse_errors) = match result {
+        Ok(program) => (program, vec![]),
+        Err(stack) => (stack.partial, stack.errors),
     };
 
-    construct(&tokens)
+    let mut errors = syntax_errors
+        .into_iter()
+        .map(ParseError::from)
+        .chain(
+            parse_errors
+                .into_iter()
+                .filter(|error| error.span.position < cutoff),
+        )
+        .collect::<Vec<ParseError>>();
+
+    errors.sort_by_key(|error| error.span.position);
+
+    Err(ParseErrorStack { partial, errors })
 }
 
 pub fn construct(tokens: &[Token]) -> Result<Program, ParseErrorStack> {
-    let mut stream = TokenStream::new(tokens);
-    let mut state = State::new(&mut stream);
+    construct_from(TokenStream::new(tokens).into(), &Options::default())
+}
+
+/// Parse the tokens of a stream, such as one reading them from `Lexer::tokens()`.
+pub fn construct_stream(stream: LazyTokenStream) -> Result<Program, ParseErrorStack> {
+    construct_stream_with(stream, &Options::default())
+}
+
+/// Parse the tokens of a stream, as `construct_stream` does, using the given options.
+pub fn construct_stream_with(
+    stream: LazyTokenStream,
+    options: &Options,
+) -> Result<Program, ParseErrorStack> {
+    construct_from(stream.into(), options)
+}
+
+fn construct_from(stream: Stream, options: &Options) -> Result<Program, ParseErrorStack> {
+    let mut state = State::new(stream, options);
 
     let mut program = Program::new();
 
@@ -91,7 +203,11 @@ pub fn construct(tokens: &[Token]) -> Result<Program, ParseErrorStack> {
         program.push(statement);
     }
 
-    let errors = state.errors;
+    let mut errors = state.errors;
+    if errors.is_empty() && options.validate {
+        errors = validation::validate(&mut program);
+    }
+
     if !errors.is_empty() {
         return Err(ParseErrorStack {
             errors,
@@ -99,7 +215,7 @@ pub fn construct(tokens: &[Token]) -> Result<Program, ParseErrorStack> {
         });
     }
 
-    Ok(program.to_vec())
+    Ok(program)
 }
 
 fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
@@ -108,9 +224,10 @@ fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
         TokenKind::Use => uses::use_statement(state)?,
         TokenKind::Const => Statement::Constant(constants::parse(state)?),
         TokenKind::HaltCompiler => {
+            let halt_compiler = state.stream.current().span;
             state.stream.next();
 
-            let content = if let TokenKind::InlineHtml = state.stream.current().kind.clone() {
+            let content = if let TokenKind::InlineHtml = state.stream.current().kind {
                 let content = state.stream.current().value.clone();
                 state.stream.next();
                 Some(content)
@@ -118,7 +235,10 @@ fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
                 None
             };
 
-            Statement::HaltCompiler(HaltCompilerStatement { content })
+            Statement::HaltCompiler(HaltCompilerStatement {
+                halt_compiler,
+                content,
+            })
         }
         _ => statement(state)?,
     };
@@ -127,12 +247,16 @@ fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
 }
 
 fn statement(state: &mut State) -> ParseResult<Statement> {
+    state.nested(parse_statement)
+}
+
+fn parse_statement(state: &mut State) -> ParseResult<Statement> {
     let has_attributes = attributes::gather_attributes(state)?;
 
-    let current = state.stream.current();
+    let &Token { kind, span, .. } = state.stream.current();
     let peek = state.stream.peek();
     let statement = if has_attributes {
-        match &current.kind {
+        match &kind {
             TokenKind::Abstract => classes::parse(state)?,
             TokenKind::Readonly if peek.kind != TokenKind::LeftParen => classes::parse(state)?,
             TokenKind::Final => classes::parse(state)?,
@@ -172,27 +296,23 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
             }),
         }
     } else {
-        match &current.kind {
+        match &kind {
             TokenKind::OpenTag(OpenTagKind::Echo) => {
-                let span = current.span;
                 state.stream.next();
 
                 Statement::EchoOpeningTag(EchoOpeningTagStatement { span })
             }
             TokenKind::OpenTag(OpenTagKind::Full) => {
-                let span = current.span;
                 state.stream.next();
 
                 Statement::FullOpeningTag(FullOpeningTagStatement { span })
             }
             TokenKind::OpenTag(OpenTagKind::Short) => {
-                let span = current.span;
                 state.stream.next();
 
                 Statement::ShortOpeningTag(ShortOpeningTagStatement { span })
             }
             TokenKind::CloseTag => {
-                let span = current.span;
                 state.stream.next();
 
                 Statement::ClosingTag(ClosingTagStatement { span })
@@ -269,7 +389,7 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 };
 
-                let body = match state.stream.current().kind.clone() {
+                let body = match state.stream.current().kind {
                     TokenKind::SemiColon => {
                         let span = utils::skip_semicolon(state)?;
 
@@ -313,14 +433,13 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 };
 
-                Statement::Declare(DeclareStatement {
+                Statement::Declare(Box::new(DeclareStatement {
                     declare: span,
                     entries,
                     body,
-                })
+                }))
             }
             TokenKind::Global => {
-                let span = current.span;
                 state.stream.next();
 
                 let mut variables = vec![];
@@ -335,10 +454,11 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 }
 
-                utils::skip_semicolon(state)?;
+                let semicolon = utils::skip_semicolon(state)?;
                 Statement::Global(GlobalStatement {
                     global: span,
                     variables,
+                    semicolon,
                 })
             }
             TokenKind::Static if matches!(peek.kind, TokenKind::Variable) => {
@@ -369,15 +489,19 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                     }
                 }
 
-                utils::skip_semicolon(state)?;
+                let semicolon = utils::skip_semicolon(state)?;
 
-                Statement::Static(StaticStatement { vars })
+                Statement::Static(StaticStatement {
+                    r#static: span,
+                    vars,
+                    semicolon,
+                })
             }
             TokenKind::InlineHtml => {
                 let html = state.stream.current().value.clone();
                 state.stream.next();
 
-                Statement::InlineHtml(InlineHtmlStatement { html })
+                Statement::InlineHtml(InlineHtmlStatement { span, html })
             }
             TokenKind::Do => loops::do_while_statement(state)?,
             TokenKind::While => loops::while_statement(state)?,
@@ -390,7 +514,7 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
             TokenKind::Try => try_block::try_block(state)?,
             TokenKind::LeftBrace => blocks::block_statement(state)?,
             TokenKind::SemiColon => {
-                let start = current.span;
+                let start = span;
 
                 state.stream.next();
 
@@ -411,7 +535,7 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                 }
 
                 Statement::Echo(EchoStatement {
-                    echo: current.span,
+                    echo: span,
                     values,
                     ending: utils::skip_ending(state)?,
                 })
@@ -429,7 +553,7 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                 };
 
                 Statement::Return(ReturnStatement {
-                    r#return: current.span,
+                    r#return: span,
                     value,
                     ending: utils::skip_ending(state)?,
                 })
@@ -0,0 +1,300 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// The options of a parse, as given to [`crate::parse_with`] or [`crate::construct_stream_with`].
+///
+/// The limits are meant for parsing untrusted code: each of them stops the parse with
+/// its own error, and the statements parsed until then are kept as the partial program.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The maximum depth of nested statements and expressions.
+    ///
+    /// Parentheses, arrays, closures, and the operands parsed recursively, such as the
+    /// right side of an assignment, each go one level deeper, so `(($a))` is 3 levels
+    /// deep. A chain of left-associative operations, such as `$a . $b . $c` or
+    /// `$a->b()->c()`, stays at the level it starts at, and is limited by
+    /// `max_chain_length` instead.
+    ///
+    /// The parser grows its stack as needed, but the resulting tree is traversed
+    /// recursively, such as when it is cloned or serialized, so deeper code is reported
+    /// as an error rather than risking a stack overflow. The default fits the 2MB stack
+    /// of a spawned thread in an optimized build. Unoptimized builds use several times
+    /// more stack per level.
+    pub max_depth: usize,
+    /// The maximum number of operations chained one after another, such as the 2
+    /// concatenations of `$a . $b . $c`, including the ones of the enclosing expressions.
+    ///
+    /// Chains are parsed without recursing, but every operation wraps the ones before it
+    /// in the tree, so longer chains are reported as an error rather than risking a stack
+    /// overflow while traversing it. The default fits, along with `max_depth`, the 2MB
+    /// stack of a spawned thread in an optimized build.
+    pub max_chain_length: usize,
+    /// The maximum size of the input, in bytes.
+    ///
+    /// Only checked by `parse_with`, as `construct_stream_with` is given tokens.
+    pub max_input_size: Option<usize>,
+    /// The maximum number of tokens, comments included.
+    ///
+    /// Only checked by `parse_with`, as `construct_stream_with` reads the tokens from
+    /// a stream that can be limited by whoever creates it.
+    pub max_tokens: Option<usize>,
+    /// The maximum number of statements, expressions, and operations.
+    pub max_nodes: Option<usize>,
+    /// Stops the parse once set to `true`, from any thread.
+    ///
+    /// The flag is checked before every token is read, and before every statement and
+    /// expression is parsed.
+    pub cancellation: Option<Arc<AtomicBool>>,
+    /// Whether to check the program for the errors PHP reports at compile time, but that
+    /// are not part of its grammar, such as duplicate parameters, as
+    /// [`crate::parser::validation::validate`] does.
+    ///
+    /// Only done once the program parsed without errors. Off by default, so that only
+    /// syntax errors are reported.
+    pub validate: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_chain_length: 1024,
+            max_input_size: None,
+            max_tokens: None,
+            max_nodes: None,
+            cancellation: None,
+            validate: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(code: &str, options: Options) -> Vec<String> {
+        let code = code.to_string();
+
+        // unoptimized builds need more than the default test stack for nested statements.
+        std::thread::Builder::new()
+            .stack_size(16 * 1024 * 1024)
+            .spawn(move || match crate::parse_with(&code, &options) {
+                Ok(_) => vec![],
+                Err(stack) => stack.errors.into_iter().map(|error| error.id).collect(),
+            })
+            .unwrap()
+            .join()
+            .unwrap()
+    }
+
+    fn max_depth(max_depth: usize) -> Options {
+        Options {
+            max_depth,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let nested = |open: &str, close: &str, depth: usize| {
+            format!("<?php {}1{};", open.repeat(depth), close.repeat(depth))
+        };
+
+        assert!(codes(&nested("(", ")", 8), max_depth(10)).is_empty());
+        assert_eq!(codes(&nested("(", ")", 10), max_depth(10)), vec!["E075"]);
+        assert_eq!(
+            codes(&nested("[", "]", 100_000), max_depth(10)),
+            vec!["E075"]
+        );
+        assert_eq!(
+            codes(&nested("-", "", 100_000), max_depth(10)),
+            vec!["E075"]
+        );
+        assert_eq!(
+            codes(
+                &nested("function () { return ", "; };", 100_000),
+                max_depth(10)
+            ),
+            vec!["E075"]
+        );
+        assert_eq!(
+            codes(&nested("if (1) { ", "}", 100_000), max_depth(10)),
+            vec!["E075"]
+        );
+    }
+
+    #[test]
+    fn test_max_depth_of_operations() {
+        let chain =
+            |operation: &str, length: usize| format!("<?php $a{};", operation.repeat(length));
+
+        // chains of left-associative operations don't go any deeper.
+        assert!(codes(&chain(" . $a", 1_000), max_depth(10)).is_empty());
+        assert!(codes(&chain(" + $a * $a", 1_000), max_depth(10)).is_empty());
+        assert!(codes(&chain("->a()", 1_000), max_depth(10)).is_empty());
+        assert!(codes(&chain("[0]", 1_000), max_depth(10)).is_empty());
+
+        // right-associative ones, and the operands of each operation, do.
+        assert!(codes(&chain(" = $a", 7), max_depth(10)).is_empty());
+        assert_eq!(codes(&chain(" = $a", 100_000), max_depth(10)), vec!["E075"]);
+        assert_eq!(
+            codes(&chain(" ** $a", 100_000), max_depth(10)),
+            vec!["E075"]
+        );
+        assert_eq!(
+            codes(&chain(" . ($a", 100_000), max_depth(10)),
+            vec!["E075"]
+        );
+    }
+
+    #[test]
+    fn test_default_limits_fit_a_default_stack() {
+        let nested = |open: &str, inner: &str, close: &str, depth: usize| {
+            format!(
+                "<?php {}{inner}{};",
+                open.repeat(depth),
+                close.repeat(depth)
+            )
+        };
+        let chain =
+            |operation: &str, length: usize| format!("<?php $a{};", operation.repeat(length));
+
+        let limits = Options::default();
+        let codes = [
+            (
+                nested("if (1) { ", "1;", "}", limits.max_depth / 2 - 1),
+                None,
+            ),
+            (
+                nested(
+                    "function () { return ",
+                    "1",
+                    "; };",
+                    limits.max_depth / 2 - 1,
+                ),
+                None,
+            ),
+            (nested("[", "1", "]", limits.max_depth - 2), None),
+            (nested("(", "1", ")", limits.max_depth - 2), None),
+            (chain(" = $a", limits.max_depth - 2), None),
+            (chain(" . $a", limits.max_chain_length), None),
+            (chain("->a()", limits.max_chain_length), None),
+            (nested("if (1) { ", "1;", "}", 100_000), Some("E075")),
+            (nested("(", "1", ")", 100_000), Some("E075")),
+            (chain("->a()", 100_000), Some("E080")),
+        ];
+
+        // the size of the stack of a thread spawned with the default options.
+        std::thread::Builder::new()
+            .stack_size(2 * 1024 * 1024)
+            .spawn(move || {
+                for (code, expected) in codes {
+                    let errors = match crate::parse(&code) {
+                        Ok(_) => vec![],
+                        Err(stack) => stack.errors.into_iter().map(|error| error.id).collect(),
+                    };
+
+                    assert_eq!(errors, Vec::from_iter(expected));
+                }
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_max_chain_length() {
+        let options = Options {
+            max_chain_length: 10,
+            ..Options::default()
+        };
+        let chain =
+            |operation: &str, length: usize| format!("<?php $a{};", operation.repeat(length));
+
+        assert!(codes(&chain(" . $a", 10), options.clone()).is_empty());
+        assert_eq!(codes(&chain(" . $a", 11), options.clone()), vec!["E080"]);
+        assert_eq!(
+            codes(&chain("->a()", 100_000), options.clone()),
+            vec!["E080"]
+        );
+        assert_eq!(codes(&chain("[0]", 100_000), options.clone()), vec!["E080"]);
+
+        // the operations of the enclosing expressions count too.
+        assert!(codes("<?php $a . $a . [$a . $a . $a];", options.clone()).is_empty());
+        assert_eq!(
+            codes(
+                &format!("<?php $a{} . [$a{}];", " . $a".repeat(5), " . $a".repeat(5)),
+                options.clone()
+            ),
+            vec!["E080"]
+        );
+        assert!(codes(
+            &format!("<?php [$a{}] . $a{};", " . $a".repeat(5), " . $a".repeat(5)),
+            options
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_max_input_size() {
+        let options = Options {
+            max_input_size: Some(16),
+            ..Options::default()
+        };
+
+        assert!(codes("<?php $a = 1;", options.clone()).is_empty());
+        assert_eq!(codes("<?php $a = 1; $b = 2;", options), vec!["E076"]);
+    }
+
+    #[test]
+    fn test_max_tokens() {
+        let options = Options {
+            max_tokens: Some(6),
+            ..Options::default()
+        };
+
+        assert!(codes("<?php $a = 1;", options.clone()).is_empty());
+        // comments are tokens too.
+        assert_eq!(
+            codes("<?php /* a */ $a = 1;", options.clone()),
+            vec!["E077"]
+        );
+
+        let stack = crate::parse_with("<?php $a = 1; $b = 2;", &options).unwrap_err();
+
+        assert_eq!(stack.errors.len(), 1);
+        assert_eq!(stack.errors[0].id, "E077");
+        assert_eq!(stack.errors[0].span.position, 17);
+        assert_eq!(stack.partial.len(), 2);
+    }
+
+    #[test]
+    fn test_max_nodes() {
+        let options = Options {
+            max_nodes: Some(5),
+            ..Options::default()
+        };
+
+        assert!(codes("<?php $a = 1;", options.clone()).is_empty());
+        assert_eq!(codes("<?php $a = 1; $b = 2;", options), vec!["E078"]);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let cancellation = Arc::new(AtomicBool::new(false));
+        let options = Options {
+            cancellation: Some(cancellation.clone()),
+            ..Options::default()
+        };
+
+        assert!(codes("<?php $a = 1;", options.clone()).is_empty());
+
+        cancellation.store(true, std::sync::atomic::Ordering::Relaxed);
+
+        let stack = crate::parse_with("<?php $a = 1;", &options).unwrap_err();
+
+        assert_eq!(stack.errors.len(), 1);
+        assert_eq!(stack.errors[0].id, "E079");
+        assert!(stack.partial.is_empty());
+    }
+}
@@ -1,10 +1,25 @@
+use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::fmt::Display;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
+use crate::lexer::stream::LazyTokenStream;
 use crate::lexer::stream::TokenStream;
+use crate::lexer::token::Token;
 use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::comments::CommentGroup;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::error;
 use crate::parser::error::ParseError;
+use crate::parser::error::ParseResult;
+use crate::parser::options::Options;
+
+// The stack left below which it is grown before going one level deeper, which must fit
+// the frames of one level in an unoptimized build, and the size it is grown by.
+const RED_ZONE: usize = 256 * 1024;
+const STACK_GROWTH: usize = 2 * 1024 * 1024;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum NamespaceType {
@@ -21,21 +36,107 @@ pub enum Scope {
 #[derive(Debug)]
 pub struct State<'a> {
     pub stack: VecDeque<Scope>,
-    pub stream: &'a mut TokenStream<'a>,
+    pub stream: Stream<'a>,
     pub attributes: Vec<AttributeGroup>,
     pub namespace_type: Option<NamespaceType>,
     pub errors: Vec<ParseError>,
+    /// The number of statements and expressions enclosing the current token.
+    pub depth: usize,
+    pub max_depth: usize,
+    /// The number of operations chained before the current token, including the ones
+    /// of the enclosing expressions.
+    pub chain: usize,
+    pub max_chain_length: usize,
+    /// The number of statements, expressions, and operations parsed so far.
+    pub nodes: usize,
+    pub max_nodes: Option<usize>,
+    pub cancellation: Option<Arc<AtomicBool>>,
 }
 
 impl<'a> State<'a> {
-    pub fn new(tokens: &'a mut TokenStream<'a>) -> Self {
+    pub fn new(tokens: Stream<'a>, options: &Options) -> Self {
         Self {
             stack: VecDeque::with_capacity(32),
             stream: tokens,
             namespace_type: None,
             attributes: vec![],
             errors: vec![],
+            depth: 0,
+            max_depth: options.max_depth,
+            chain: 0,
+            max_chain_length: options.max_chain_length,
+            nodes: 0,
+            max_nodes: options.max_nodes,
+            cancellation: options.cancellation.clone(),
+        }
+    }
+
+    /// Go one level deeper, into a new statement or expression, failing instead if that
+    /// exceeds the limits of the options, or if the parse was cancelled.
+    pub fn deepen(&mut self) -> ParseResult<()> {
+        if self.depth >= self.max_depth {
+            return Err(error::nesting_too_deep(
+                self.max_depth,
+                self.stream.current(),
+            ));
+        }
+
+        self.count()?;
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    /// Chain one more operation to the current expression, at the same level, failing
+    /// instead if that exceeds the limits of the options, or if the parse was cancelled.
+    pub fn chain(&mut self) -> ParseResult<()> {
+        if self.chain >= self.max_chain_length {
+            return Err(error::chain_too_long(
+                self.max_chain_length,
+                self.stream.current(),
+            ));
+        }
+
+        self.count()?;
+        self.chain += 1;
+
+        Ok(())
+    }
+
+    fn count(&mut self) -> ParseResult<()> {
+        if let Some(max_nodes) = self.max_nodes {
+            if self.nodes >= max_nodes {
+                return Err(error::too_many_nodes(max_nodes, self.stream.current()));
+            }
         }
+
+        if let Some(cancellation) = &self.cancellation {
+            if cancellation.load(Ordering::Relaxed) {
+                return Err(error::parse_cancelled(self.stream.current().span));
+            }
+        }
+
+        self.nodes += 1;
+
+        Ok(())
+    }
+
+    /// Parse a nested statement or expression, one level deeper than the current one.
+    ///
+    /// The depth and the chain are restored afterwards, including any levels and
+    /// operations added by `parse` itself. The stack is grown as needed, so that the
+    /// depth is only limited by the options, whatever the stack of the current thread.
+    pub fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        let depth = self.depth;
+        let chain = self.chain;
+
+        self.deepen()?;
+        let result = stacker::maybe_grow(RED_ZONE, STACK_GROWTH, || parse(self));
+
+        self.depth = depth;
+        self.chain = chain;
+
+        result
     }
 
     pub fn attribute(&mut self, attr: AttributeGroup) {
@@ -95,3 +196,79 @@ impl<'a> State<'a> {
         self.stack.pop_back();
     }
 }
+
+/// The tokens being parsed, borrowed from a slice or read from an iterator.
+#[derive(Debug)]
+pub enum Stream<'a> {
+    Slice(TokenStream<'a>),
+    Lazy(LazyTokenStream<'a>),
+}
+
+impl<'a> Stream<'a> {
+    pub fn next(&mut self) {
+        match self {
+            Self::Slice(stream) => stream.next(),
+            Self::Lazy(stream) => stream.next(),
+        }
+    }
+
+    /// Move past the current token, returning it without copying it.
+    pub fn take(&mut self) -> Cow<'a, Token> {
+        match self {
+            Self::Slice(stream) => {
+                let token = stream.current();
+                stream.next();
+
+                Cow::Borrowed(token)
+            }
+            Self::Lazy(stream) => Cow::Owned(stream.take()),
+        }
+    }
+
+    pub fn current(&self) -> &Token {
+        match self {
+            Self::Slice(stream) => stream.current(),
+            Self::Lazy(stream) => stream.current(),
+        }
+    }
+
+    pub fn peek(&self) -> &Token {
+        match self {
+            Self::Slice(stream) => stream.peek(),
+            Self::Lazy(stream) => stream.peek(),
+        }
+    }
+
+    pub fn lookahead(&self, n: usize) -> &Token {
+        match self {
+            Self::Slice(stream) => stream.lookahead(n),
+            Self::Lazy(stream) => stream.lookahead(n),
+        }
+    }
+
+    pub fn is_eof(&self) -> bool {
+        match self {
+            Self::Slice(stream) => stream.is_eof(),
+            Self::Lazy(stream) => stream.is_eof(),
+        }
+    }
+
+    pub fn comments(&mut self) -> CommentGroup {
+        match self {
+            Self::Slice(stream) => stream.comments(),
+            Self::Lazy(stream) => stream.comments(),
+        }
+    }
+}
+
+impl<'a> From<TokenStream<'a>> for Stream<'a> {
+    fn from(stream: TokenStream<'a>) -> Self {
+        Self::Slice(stream)
+    }
+}
+
+impl<'a> From<LazyTokenStream<'a>> for Stream<'a> {
+    fn from(stream: LazyTokenStream<'a>) -> Self {
+        Self::Lazy(stream)
+    }
+}
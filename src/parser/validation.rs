@@ -0,0 +1,705 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use crate::downcast::downcast;
+use crate::lexer::token::Span;
+use crate::node::Node;
+use crate::parser::ast::classes::AnonymousClassExpression;
+use crate::parser::ast::classes::AnonymousClassMember;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::classes::ClassStatement;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::data_type::Type;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumStatement;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::enums::UnitEnumStatement;
+use crate::parser::ast::functions::AbstractConstructor;
+use crate::parser::ast::functions::AbstractMethod;
+use crate::parser::ast::functions::ArrowFunctionExpression;
+use crate::parser::ast::functions::ClosureExpression;
+use crate::parser::ast::functions::ClosureUseVariable;
+use crate::parser::ast::functions::ConcreteConstructor;
+use crate::parser::ast::functions::ConcreteMethod;
+use crate::parser::ast::functions::ConstructorParameterList;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::FunctionStatement;
+use crate::parser::ast::functions::ReturnType;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::interfaces::InterfaceStatement;
+use crate::parser::ast::loops::BreakStatement;
+use crate::parser::ast::loops::ContinueStatement;
+use crate::parser::ast::loops::DoWhileStatement;
+use crate::parser::ast::loops::ForStatement;
+use crate::parser::ast::loops::ForeachStatement;
+use crate::parser::ast::loops::ForeachStatementIterator;
+use crate::parser::ast::loops::Level;
+use crate::parser::ast::loops::WhileStatement;
+use crate::parser::ast::namespaces::NamespaceStatement;
+use crate::parser::ast::operators::ArithmeticOperationExpression;
+use crate::parser::ast::operators::AssignmentOperationExpression;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitStatement;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Ending;
+use crate::parser::ast::Expression;
+use crate::parser::ast::GlobalStatement;
+use crate::parser::ast::ListEntry;
+use crate::parser::ast::Program;
+use crate::parser::ast::ReturnStatement;
+use crate::parser::ast::StaticStatement;
+use crate::parser::ast::SwitchStatement;
+use crate::parser::error;
+use crate::parser::error::ParseError;
+use crate::traverser::Visitor;
+
+/// Check `program` for errors that PHP reports at compile time, but that are
+/// not part of its grammar, such as duplicate parameters or a `break` outside
+/// of a loop.
+///
+/// The program is only borrowed mutably because that is what `Visitor` requires;
+/// it is not modified.
+pub fn validate(program: &mut Program) -> Vec<ParseError> {
+    let mut validator = Validator::default();
+
+    for statement in program.iter_mut() {
+        let Ok(()) = validator.visit_node(statement);
+    }
+
+    validator.errors.sort_by_key(|error| error.span.position);
+    validator.errors
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Frame {
+    // a function, method, or closure, with the span of its `void` return type.
+    Function(Option<Span>),
+    // a loop or a `switch`, which can both be exited using `break` and `continue`.
+    Loop,
+}
+
+#[derive(Debug, Default)]
+struct Validator {
+    namespace: Option<String>,
+    frames: Vec<Frame>,
+    errors: Vec<ParseError>,
+}
+
+impl Validator {
+    fn named(&self, name: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}\\{}", namespace, name),
+            None => name.to_string(),
+        }
+    }
+
+    fn frame(node: &dyn Node) -> Option<Frame> {
+        if let Some(function) = downcast::<FunctionStatement>(node) {
+            return Some(Frame::Function(void(&function.return_type)));
+        }
+        if let Some(closure) = downcast::<ClosureExpression>(node) {
+            return Some(Frame::Function(void(&closure.return_type)));
+        }
+        if let Some(function) = downcast::<ArrowFunctionExpression>(node) {
+            return Some(Frame::Function(void(&function.return_type)));
+        }
+        if let Some(method) = downcast::<ConcreteMethod>(node) {
+            return Some(Frame::Function(void(&method.return_type)));
+        }
+        if let Some(method) = downcast::<AbstractMethod>(node) {
+            return Some(Frame::Function(void(&method.return_type)));
+        }
+        if downcast::<ConcreteConstructor>(node).is_some()
+            || downcast::<AbstractConstructor>(node).is_some()
+        {
+            return Some(Frame::Function(None));
+        }
+
+        if downcast::<WhileStatement>(node).is_some()
+            || downcast::<DoWhileStatement>(node).is_some()
+            || downcast::<ForStatement>(node).is_some()
+            || downcast::<ForeachStatement>(node).is_some()
+            || downcast::<SwitchStatement>(node).is_some()
+        {
+            return Some(Frame::Loop);
+        }
+
+        None
+    }
+
+    // the number of loops and `switch`es enclosing the current node, within the current function.
+    fn depth(&self) -> usize {
+        self.frames
+            .iter()
+            .rev()
+            .take_while(|frame| matches!(frame, Frame::Loop))
+            .count()
+    }
+
+    fn check_loop_control(&mut self, keyword: &str, span: Span, level: &Option<Level>) {
+        let depth = self.depth();
+        let level = level.as_ref().map(literal);
+
+        match level {
+            Some((Some(0), (level_span, level_length))) => {
+                self.errors.push(error::loop_control_level_not_positive(
+                    keyword,
+                    span,
+                    level_span,
+                    level_length,
+                ));
+            }
+            _ if depth == 0 => {
+                self.errors
+                    .push(error::loop_control_outside_of_loop(keyword, span));
+            }
+            Some((Some(value), (level_span, level_length))) if value > depth => {
+                self.errors.push(error::loop_control_level_too_deep(
+                    keyword,
+                    span,
+                    value,
+                    depth,
+                    level_span,
+                    level_length,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    fn check_return(&mut self, statement: &ReturnStatement) {
+        let void = match self.frames.iter().rev().find_map(|frame| match frame {
+            Frame::Function(void) => Some(void),
+            Frame::Loop => None,
+        }) {
+            Some(Some(void)) => *void,
+            _ => return,
+        };
+
+        if statement.value.is_some() {
+            let end = match &statement.ending {
+                Ending::Semicolon(span) | Ending::CloseTag(span) => *span,
+            };
+
+            self.errors.push(error::void_function_returns_value(
+                statement.r#return,
+                end,
+                void,
+            ));
+        }
+    }
+
+    fn check_parameters<'a>(
+        &mut self,
+        parameters: impl Iterator<Item = (&'a SimpleVariable, Option<Span>)>,
+    ) {
+        let mut seen: Vec<&SimpleVariable> = vec![];
+        let mut variadic: Option<Span> = None;
+
+        for (name, ellipsis) in parameters {
+            if name.name == b"$this" {
+                self.errors.push(error::this_used_as_parameter(name));
+            } else if let Some(previous) = seen.iter().find(|previous| previous.name == name.name) {
+                self.errors.push(error::duplicate_parameter(name, previous));
+            }
+
+            if let Some(variadic) = variadic.take() {
+                self.errors
+                    .push(error::variadic_parameter_is_not_last(variadic, name));
+            }
+
+            variadic = ellipsis;
+            seen.push(name);
+        }
+    }
+
+    fn check_assignment(&mut self, target: &Expression) {
+        match target {
+            Expression::Variable(Variable::SimpleVariable(variable))
+                if variable.name == b"$this" =>
+            {
+                self.errors.push(error::cannot_reassign_this(variable));
+            }
+            // `$this` could be re-assigned through a reference to it.
+            Expression::Reference(reference) => self.check_assignment(&reference.right),
+            Expression::List(list) => {
+                for entry in &list.items {
+                    match entry {
+                        ListEntry::Value { value } | ListEntry::KeyValue { value, .. } => {
+                            self.check_assignment(value)
+                        }
+                        ListEntry::Skipped => {}
+                    }
+                }
+            }
+            Expression::ShortArray(array) => {
+                for item in array.items.iter() {
+                    match item {
+                        ArrayItem::Value { value }
+                        | ArrayItem::ReferencedValue { value, .. }
+                        | ArrayItem::KeyValue { value, .. }
+                        | ArrayItem::ReferencedKeyValue { value, .. } => {
+                            self.check_assignment(value)
+                        }
+                        ArrayItem::Skipped | ArrayItem::SpreadValue { .. } => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_variable(&mut self, kind: &str, variable: &Variable) {
+        if let Variable::SimpleVariable(variable) = variable {
+            if variable.name == b"$this" {
+                self.errors
+                    .push(error::this_used_as_variable(kind, variable));
+            }
+        }
+    }
+
+    fn check_members(&mut self, class: &str, members: Vec<Member>) {
+        let mut seen: HashMap<(&str, Vec<u8>), Span> = HashMap::new();
+
+        for member in members {
+            // methods are case-insensitive, while enum cases share their names with constants.
+            let key = match member.kind {
+                "method" => ("method", member.name.to_ascii_lowercase().into_bytes()),
+                "property" => ("property", member.name.as_bytes().to_vec()),
+                _ => ("constant", member.name.as_bytes().to_vec()),
+            };
+
+            match seen.get(&key) {
+                Some(previous) => self.errors.push(error::duplicate_class_member(
+                    member.kind,
+                    class,
+                    &member.name,
+                    member.span,
+                    *previous,
+                )),
+                None => {
+                    seen.insert(key, member.span);
+                }
+            }
+        }
+    }
+
+    fn check(&mut self, node: &dyn Node) {
+        if let Some(statement) = downcast::<BreakStatement>(node) {
+            self.check_loop_control("break", statement.r#break, &statement.level);
+        } else if let Some(statement) = downcast::<ContinueStatement>(node) {
+            self.check_loop_control("continue", statement.r#continue, &statement.level);
+        } else if let Some(statement) = downcast::<ReturnStatement>(node) {
+            self.check_return(statement);
+        } else if let Some(parameters) = downcast::<FunctionParameterList>(node) {
+            self.check_parameters(
+                parameters
+                    .iter()
+                    .map(|parameter| (&parameter.name, parameter.ellipsis)),
+            );
+        } else if let Some(parameters) = downcast::<ConstructorParameterList>(node) {
+            self.check_parameters(
+                parameters
+                    .parameters
+                    .iter()
+                    .map(|parameter| (&parameter.name, parameter.ellipsis)),
+            );
+        } else if let Some(operation) = downcast::<AssignmentOperationExpression>(node) {
+            self.check_assignment(operation.left());
+
+            // `$a = &$this` binds `$a` to `$this`, so that assigning `$a` re-assigns it.
+            if let AssignmentOperationExpression::Assign { right, .. } = operation {
+                if let Expression::Reference(_) = right.as_ref() {
+                    self.check_assignment(right);
+                }
+            }
+        } else if let Some(operation) = downcast::<ArithmeticOperationExpression>(node) {
+            match operation {
+                ArithmeticOperationExpression::PreIncrement { right, .. }
+                | ArithmeticOperationExpression::PreDecrement { right, .. } => {
+                    self.check_assignment(right)
+                }
+                ArithmeticOperationExpression::PostIncrement { left, .. }
+                | ArithmeticOperationExpression::PostDecrement { left, .. } => {
+                    self.check_assignment(left)
+                }
+                _ => {}
+            }
+        } else if let Some(iterator) = downcast::<ForeachStatementIterator>(node) {
+            match iterator {
+                ForeachStatementIterator::Value { value, .. } => self.check_assignment(value),
+                ForeachStatementIterator::KeyAndValue { key, value, .. } => {
+                    self.check_assignment(key);
+                    self.check_assignment(value);
+                }
+            }
+        } else if let Some(catch) = downcast::<CatchBlock>(node) {
+            if let Some(variable) = &catch.var {
+                if variable.name == b"$this" {
+                    self.errors.push(error::cannot_reassign_this(variable));
+                }
+            }
+        } else if let Some(statement) = downcast::<GlobalStatement>(node) {
+            for variable in &statement.variables {
+                self.check_variable("global", variable);
+            }
+        } else if let Some(statement) = downcast::<StaticStatement>(node) {
+            for variable in &statement.vars {
+                self.check_variable("static", &variable.var);
+            }
+        } else if let Some(variable) = downcast::<ClosureUseVariable>(node) {
+            if variable.variable.name == b"$this" {
+                self.errors
+                    .push(error::this_used_as_variable("lexical", &variable.variable));
+            }
+        } else if let Some(class) = downcast::<ClassStatement>(node) {
+            let name = self.named(&class.name.to_string());
+            let members = class.body.members.iter().flat_map(Member::class).collect();
+
+            self.check_members(&name, members);
+        } else if let Some(class) = downcast::<AnonymousClassExpression>(node) {
+            let members = class
+                .body
+                .members
+                .iter()
+                .flat_map(Member::anonymous_class)
+                .collect();
+
+            self.check_members("class@anonymous", members);
+        } else if let Some(r#trait) = downcast::<TraitStatement>(node) {
+            let name = self.named(&r#trait.name.to_string());
+            let members = r#trait
+                .body
+                .members
+                .iter()
+                .flat_map(Member::r#trait)
+                .collect();
+
+            self.check_members(&name, members);
+        } else if let Some(interface) = downcast::<InterfaceStatement>(node) {
+            let name = self.named(&interface.name.to_string());
+            let members = interface
+                .body
+                .members
+                .iter()
+                .flat_map(Member::interface)
+                .collect();
+
+            self.check_members(&name, members);
+        } else if let Some(r#enum) = downcast::<UnitEnumStatement>(node) {
+            let name = self.named(&r#enum.name.to_string());
+            let members = r#enum
+                .body
+                .members
+                .iter()
+                .flat_map(Member::unit_enum)
+                .collect();
+
+            self.check_members(&name, members);
+        } else if let Some(r#enum) = downcast::<BackedEnumStatement>(node) {
+            let name = self.named(&r#enum.name.to_string());
+            let members = r#enum
+                .body
+                .members
+                .iter()
+                .flat_map(Member::backed_enum)
+                .collect();
+
+            self.check_members(&name, members);
+        }
+    }
+}
+
+impl Visitor<Infallible> for Validator {
+    fn visit_node(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        let frame = Validator::frame(node);
+        if let Some(frame) = frame {
+            self.frames.push(frame);
+        }
+
+        let namespace = downcast::<NamespaceStatement>(node).map(|namespace| {
+            let name = match namespace {
+                NamespaceStatement::Unbraced(namespace) => Some(namespace.name.to_string()),
+                NamespaceStatement::Braced(namespace) => {
+                    namespace.name.as_ref().map(|name| name.to_string())
+                }
+            };
+
+            std::mem::replace(&mut self.namespace, name)
+        });
+
+        self.visit(node)?;
+
+        for child in node.children() {
+            self.visit_node(child)?;
+        }
+
+        if let Some(namespace) = namespace {
+            self.namespace = namespace;
+        }
+
+        if frame.is_some() {
+            self.frames.pop();
+        }
+
+        Ok(())
+    }
+
+    fn visit(&mut self, node: &mut dyn Node) -> Result<(), Infallible> {
+        self.check(node);
+
+        Ok(())
+    }
+}
+
+// a named member of a class-like, as seen by the duplicate member check.
+struct Member {
+    kind: &'static str,
+    name: String,
+    span: Span,
+}
+
+impl Member {
+    fn new(kind: &'static str, name: impl ToString, span: Span) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            span,
+        }
+    }
+
+    fn constants(constant: &ClassishConstant) -> Vec<Member> {
+        constant
+            .entries
+            .iter()
+            .map(|entry| Member::new("constant", &entry.name, entry.name.span))
+            .collect()
+    }
+
+    fn properties(entries: &[PropertyEntry]) -> Vec<Member> {
+        entries
+            .iter()
+            .map(|entry| {
+                let variable = entry.variable();
+
+                Member::new("property", &variable.name, variable.span)
+            })
+            .collect()
+    }
+
+    fn method(name: &SimpleIdentifier) -> Vec<Member> {
+        vec![Member::new("method", name, name.span)]
+    }
+
+    fn class(member: &ClassMember) -> Vec<Member> {
+        match member {
+            ClassMember::Constant(constant) => Member::constants(constant),
+            ClassMember::TraitUsage(_) => vec![],
+            ClassMember::Property(property) => Member::properties(&property.entries),
+            ClassMember::VariableProperty(property) => Member::properties(&property.entries),
+            ClassMember::AbstractMethod(method) => Member::method(&method.name),
+            ClassMember::AbstractConstructor(method) => Member::method(&method.name),
+            ClassMember::ConcreteMethod(method) => Member::method(&method.name),
+            ClassMember::ConcreteConstructor(method) => Member::method(&method.name),
+        }
+    }
+
+    fn anonymous_class(member: &AnonymousClassMember) -> Vec<Member> {
+        match member {
+            AnonymousClassMember::Constant(constant) => Member::constants(constant),
+            AnonymousClassMember::TraitUsage(_) => vec![],
+            AnonymousClassMember::Property(property) => Member::properties(&property.entries),
+            AnonymousClassMember::VariableProperty(property) => {
+                Member::properties(&property.entries)
+            }
+            AnonymousClassMember::ConcreteMethod(method) => Member::method(&method.name),
+            AnonymousClassMember::ConcreteConstructor(method) => Member::method(&method.name),
+        }
+    }
+
+    fn r#trait(member: &TraitMember) -> Vec<Member> {
+        match member {
+            TraitMember::Constant(constant) => Member::constants(constant),
+            TraitMember::TraitUsage(_) => vec![],
+            TraitMember::Property(property) => Member::properties(&property.entries),
+            TraitMember::VariableProperty(property) => Member::properties(&property.entries),
+            TraitMember::AbstractMethod(method) => Member::method(&method.name),
+            TraitMember::AbstractConstructor(method) => Member::method(&method.name),
+            TraitMember::ConcreteMethod(method) => Member::method(&method.name),
+            TraitMember::ConcreteConstructor(method) => Member::method(&method.name),
+        }
+    }
+
+    fn interface(member: &InterfaceMember) -> Vec<Member> {
+        match member {
+            InterfaceMember::Constant(constant) => Member::constants(constant),
+            InterfaceMember::Constructor(method) => Member::method(&method.name),
+            InterfaceMember::Method(method) => Member::method(&method.name),
+        }
+    }
+
+    fn unit_enum(member: &UnitEnumMember) -> Vec<Member> {
+        match member {
+            UnitEnumMember::Case(case) => {
+                vec![Member::new("enum case", &case.name, case.name.span)]
+            }
+            UnitEnumMember::Method(method) => Member::method(&method.name),
+            UnitEnumMember::Constant(constant) => Member::constants(constant),
+            UnitEnumMember::TraitUsage(_) => vec![],
+        }
+    }
+
+    fn backed_enum(member: &BackedEnumMember) -> Vec<Member> {
+        match member {
+            BackedEnumMember::Case(case) => {
+                vec![Member::new("enum case", &case.name, case.name.span)]
+            }
+            BackedEnumMember::Method(method) => Member::method(&method.name),
+            BackedEnumMember::Constant(constant) => Member::constants(constant),
+            BackedEnumMember::TraitUsage(_) => vec![],
+        }
+    }
+}
+
+fn void(return_type: &Option<ReturnType>) -> Option<Span> {
+    match return_type {
+        Some(ReturnType {
+            data_type: Type::Void(span),
+            ..
+        }) => Some(*span),
+        _ => None,
+    }
+}
+
+// the value of a loop level, along with the position and length of its literal.
+fn literal(level: &Level) -> (Option<usize>, (Span, usize)) {
+    match level {
+        Level::Literal(literal) => (level.value(), (literal.span, literal.value.len())),
+        Level::Parenthesized { level, .. } => literal(level),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::options::Options;
+
+    fn codes(code: &str) -> Vec<String> {
+        let options = Options {
+            validate: true,
+            ..Options::default()
+        };
+
+        match crate::parse_with(code, &options) {
+            Ok(_) => vec![],
+            Err(stack) => stack.errors.into_iter().map(|error| error.id).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert_eq!(codes("<?php function a($a, $b, $a) {}"), vec!["E066"]);
+        assert_eq!(codes("<?php function a($this) {}"), vec!["E067"]);
+        assert_eq!(codes("<?php fn(...$a, $b) => 1;"), vec!["E074"]);
+        assert_eq!(
+            codes("<?php class A { function __construct(public $a, $a) {} }"),
+            vec!["E066"]
+        );
+        assert!(codes("<?php function a($a, ...$b) {}").is_empty());
+    }
+
+    #[test]
+    fn test_this_reassignment() {
+        assert_eq!(codes("<?php $this = 1;"), vec!["E068"]);
+        assert_eq!(codes("<?php $this .= 1;"), vec!["E068"]);
+        assert_eq!(codes("<?php $this++;"), vec!["E068"]);
+        assert_eq!(codes("<?php foreach ($a as $this) {}"), vec!["E068"]);
+        assert_eq!(
+            codes("<?php try {} catch (Exception $this) {}"),
+            vec!["E068"]
+        );
+        assert_eq!(codes("<?php list($a, $this) = $b;"), vec!["E068"]);
+        assert_eq!(codes("<?php [$a, [$this]] = $b;"), vec!["E068"]);
+        assert_eq!(codes("<?php ['a' => &$this] = $b;"), vec!["E068"]);
+        assert_eq!(codes("<?php foreach ($a as [$b, $this]) {}"), vec!["E068"]);
+        assert_eq!(codes("<?php foreach ($a as &$this) {}"), vec!["E068"]);
+        assert_eq!(codes("<?php $a = &$this;"), vec!["E068"]);
+        assert_eq!(codes("<?php $this = &$a;"), vec!["E068"]);
+        assert_eq!(codes("<?php global $a, $this;"), vec!["E081"]);
+        assert_eq!(codes("<?php static $this = 1;"), vec!["E081"]);
+        assert_eq!(codes("<?php function () use (&$this) {};"), vec!["E081"]);
+        assert!(codes("<?php $this->a = 1; $a = $this;").is_empty());
+        assert!(codes("<?php [$a, $b] = [$this, $this]; $c = &$this->d;").is_empty());
+    }
+
+    #[test]
+    fn test_parse_does_not_validate() {
+        assert!(crate::parse("<?php $this = 1; break;").is_ok());
+        assert!(codes("<?php $this = 1; break;").len() == 2);
+    }
+
+    #[test]
+    fn test_loop_control() {
+        assert_eq!(codes("<?php break;"), vec!["E069"]);
+        assert_eq!(codes("<?php if (true) { continue; }"), vec!["E069"]);
+        assert_eq!(
+            codes("<?php while (true) { function a() { break; } }"),
+            vec!["E069"]
+        );
+        assert_eq!(codes("<?php while (true) { break 2; }"), vec!["E070"]);
+        assert_eq!(codes("<?php while (true) { break 0; }"), vec!["E071"]);
+        assert!(codes(
+            "<?php foreach ($a as $b) { switch ($b) { case 1: continue 2; default: break; } }"
+        )
+        .is_empty());
+        assert!(codes("<?php do { for (;;) { break (2); } } while (true);").is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_members() {
+        assert_eq!(
+            codes("<?php class A { function a() {} function A() {} }"),
+            vec!["E072"]
+        );
+        assert_eq!(codes("<?php class A { public $a, $a; }"), vec!["E072"]);
+        assert_eq!(
+            codes("<?php interface A { const B = 1; const B = 2; }"),
+            vec!["E072"]
+        );
+        assert_eq!(codes("<?php enum A { case B; case B; }"), vec!["E072"]);
+        assert_eq!(
+            codes("<?php enum A: int { case B = 1; const B = 2; }"),
+            vec!["E072"]
+        );
+        assert!(codes("<?php class A { public $a; const a = 1; function a() {} }").is_empty());
+    }
+
+    #[test]
+    fn test_void_return() {
+        assert_eq!(
+            codes("<?php function a(): void { return 1; }"),
+            vec!["E073"]
+        );
+        assert!(codes("<?php function a(): void { return; }").is_empty());
+        assert!(codes("<?php function a(): void { $b = function () { return 1; }; }").is_empty());
+    }
+
+    #[test]
+    fn test_errors_are_named() {
+        let errors = crate::parse_with(
+            "<?php namespace Foo; class Bar { function a() {} function a() {} }",
+            &Options {
+                validate: true,
+                ..Options::default()
+            },
+        )
+        .unwrap_err()
+        .errors;
+
+        assert_eq!(errors[0].message, "cannot redeclare method `Foo\\Bar::a`");
+    }
+}
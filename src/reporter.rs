@@ -0,0 +1,834 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::line_index::ColumnUnit;
+use crate::line_index::LineColumn;
+use crate::line_index::LineIndex;
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseErrorAnnotationType;
+use crate::parser::error::ParseErrorSeverity;
+
+const TOOL_NAME: &str = "php-parser-rs";
+const TOOL_URI: &str = "https://github.com/php-rust-tools/php-parser";
+/// The id files that couldn't be processed are reported with.
+const FAILURE_ID: &str = "io-error";
+
+/// The output formats supported by [`report`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ReportFormat {
+    /// Human readable reports, rendered using `ariadne`.
+    #[default]
+    Text,
+    Json,
+    /// SARIF 2.1.0, as consumed by code scanning tools.
+    Sarif,
+    Checkstyle,
+    Junit,
+    /// GitHub Actions workflow commands, which are displayed as annotations.
+    Github,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 6] = [
+        ReportFormat::Text,
+        ReportFormat::Json,
+        ReportFormat::Sarif,
+        ReportFormat::Checkstyle,
+        ReportFormat::Junit,
+        ReportFormat::Github,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "text",
+            ReportFormat::Json => "json",
+            ReportFormat::Sarif => "sarif",
+            ReportFormat::Checkstyle => "checkstyle",
+            ReportFormat::Junit => "junit",
+            ReportFormat::Github => "github",
+        }
+    }
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReportFormat::ALL
+            .into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "unknown format `{}`, expecting one of: {}",
+                    s,
+                    ReportFormat::ALL.map(|format| format.as_str()).join(", ")
+                )
+            })
+    }
+}
+
+/// A file that has been parsed, along with the errors found in it.
+///
+/// Files without errors should be reported too, as some formats (e.g. JUnit)
+/// list passing files as well.
+#[derive(Debug, Clone, Copy)]
+pub struct ReportedFile<'a> {
+    pub path: &'a str,
+    pub source: &'a str,
+    pub errors: &'a [ParseError],
+    /// The byte offset into the file of the start of `source`, if the positions of the
+    /// errors, which are byte offsets into `source`, are byte offsets into the file too,
+    /// once shifted by it. See [`crate::source::SourceFile::file_offset`].
+    ///
+    /// Only the formats that report byte offsets use it, and they report lines and
+    /// columns alone when it is `None`.
+    pub file_offset: Option<usize>,
+    /// Why the file couldn't be processed, such as it being unreadable.
+    ///
+    /// Reported by the machine-readable formats only, as an error of the whole file.
+    pub failure: Option<&'a str>,
+}
+
+/// Render the errors of the given files in the given format.
+///
+/// Machine-readable formats use 1-based line numbers, and 1-based columns counted
+/// in Unicode scalar values rather than bytes.
+pub fn report(
+    format: ReportFormat,
+    files: &[ReportedFile],
+    colored: bool,
+    ascii: bool,
+) -> std::io::Result<String> {
+    let output = match format {
+        ReportFormat::Text => {
+            let mut reports = Vec::new();
+
+            for file in files {
+                let index = LineIndex::new(file.source);
+
+                for error in file.errors {
+                    reports.push(error.report_with_index(
+                        file.source,
+                        &index,
+                        Some(file.path),
+                        colored,
+                        ascii,
+                    )?);
+                }
+            }
+
+            reports.join("\n")
+        }
+        ReportFormat::Json => json(files),
+        ReportFormat::Sarif => sarif(files),
+        ReportFormat::Checkstyle => checkstyle(files),
+        ReportFormat::Junit => junit(files),
+        ReportFormat::Github => github(files),
+    };
+
+    Ok(output)
+}
+
+fn json(files: &[ReportedFile]) -> String {
+    let files = files
+        .iter()
+        .map(|file| {
+            let index = LineIndex::new(file.source);
+
+            json!({
+                "path": file.path,
+                "failure": file.failure,
+                "errors": file.errors.iter().map(|error| {
+                    let (line, column) = location(&index, error.span.position);
+
+                    json!({
+                        "id": error.id,
+                        "severity": error.severity,
+                        "message": error.message,
+                        "line": line,
+                        "column": column,
+                        "position": error.span.position,
+                        "annotations": error.annotations,
+                        "note": error.note,
+                        "fixes": error.fixes,
+                    })
+                }).collect::<Vec<Value>>(),
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    json!({ "files": files }).to_string()
+}
+
+fn sarif(files: &[ReportedFile]) -> String {
+    let mut rules: Vec<&str> = files
+        .iter()
+        .flat_map(|file| {
+            file.errors
+                .iter()
+                .map(|error| error.id.as_str())
+                .chain(file.failure.map(|_| FAILURE_ID))
+        })
+        .collect();
+    rules.sort_unstable();
+    rules.dedup();
+
+    let results = files
+        .iter()
+        .flat_map(|file| {
+            let lines = LineIndex::new(file.source);
+            let rules = &rules;
+
+            file.errors.iter().map(move |error| {
+                let related = error
+                    .annotations
+                    .iter()
+                    .enumerate()
+                    .map(|(index, annotation)| {
+                        let mut location = json!({
+                            "id": index,
+                            "physicalLocation": physical_location(
+                                file,
+                                &lines,
+                                annotation.position,
+                                Some(annotation.length),
+                            ),
+                        });
+
+                        if !annotation.message.is_empty() {
+                            location["message"] = json!({ "text": annotation.message });
+                        }
+
+                        location
+                    })
+                    .collect::<Vec<Value>>();
+
+                let fixes = error
+                    .fixes
+                    .iter()
+                    .map(|fix| {
+                        json!({
+                            "description": { "text": fix.description },
+                            "artifactChanges": [{
+                                "artifactLocation": { "uri": uri(file.path) },
+                                "replacements": fix.edits.iter().map(|edit| json!({
+                                    "deletedRegion": region(
+                                        file,
+                                        &lines,
+                                        edit.position,
+                                        edit.length,
+                                    ),
+                                    "insertedContent": { "text": edit.replacement },
+                                })).collect::<Vec<Value>>(),
+                            }],
+                        })
+                    })
+                    .collect::<Vec<Value>>();
+
+                let mut result = json!({
+                    "ruleId": error.id,
+                    "ruleIndex": rules.binary_search(&error.id.as_str()).unwrap_or_default(),
+                    "level": match error.severity {
+                        ParseErrorSeverity::Notice => "note",
+                        ParseErrorSeverity::Warning => "warning",
+                        ParseErrorSeverity::Error => "error",
+                    },
+                    "message": { "text": message_with_note(error) },
+                    "locations": [{
+                        "physicalLocation": physical_location(file, &lines, error.span.position, None),
+                    }],
+                    "relatedLocations": related,
+                });
+
+                if !fixes.is_empty() {
+                    result["fixes"] = json!(fixes);
+                }
+
+                result
+            })
+        })
+        .chain(files.iter().filter_map(|file| {
+            let failure = file.failure?;
+
+            Some(json!({
+                "ruleId": FAILURE_ID,
+                "ruleIndex": rules.binary_search(&FAILURE_ID).unwrap_or_default(),
+                "level": "error",
+                "message": { "text": failure },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri(file.path) },
+                    },
+                }],
+            }))
+        }))
+        .collect::<Vec<Value>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": TOOL_URI,
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<Value>>(),
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+    .to_string()
+}
+
+fn physical_location(
+    file: &ReportedFile,
+    index: &LineIndex,
+    position: usize,
+    length: Option<usize>,
+) -> Value {
+    let region = match length {
+        Some(length) => region(file, index, position, length),
+        None => {
+            let (line, column) = location(index, position);
+            let mut region = json!({ "startLine": line, "startColumn": column });
+
+            if let Some(offset) = file.file_offset {
+                region["byteOffset"] = json!(offset + position);
+            }
+
+            region
+        }
+    };
+
+    json!({
+        "artifactLocation": { "uri": uri(file.path) },
+        "region": region,
+    })
+}
+
+/// The SARIF region of the given range, with its byte offsets into the file when they
+/// are known.
+fn region(file: &ReportedFile, index: &LineIndex, position: usize, length: usize) -> Value {
+    let (start_line, start_column) = location(index, position);
+    let (end_line, end_column) = location(index, position + length);
+
+    let mut region = json!({
+        "startLine": start_line,
+        "startColumn": start_column,
+        "endLine": end_line,
+        "endColumn": end_column,
+    });
+
+    if let Some(offset) = file.file_offset {
+        region["byteOffset"] = json!(offset + position);
+        region["byteLength"] = json!(length);
+    }
+
+    region
+}
+
+fn checkstyle(files: &[ReportedFile]) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str("<checkstyle version=\"4.3\">\n");
+
+    for file in files {
+        output.push_str(&format!("  <file name=\"{}\">\n", xml_escape(file.path)));
+
+        if let Some(failure) = file.failure {
+            output.push_str(&format!(
+                "    <error severity=\"error\" message=\"{}\" source=\"{}.{}\"/>\n",
+                xml_escape(failure),
+                TOOL_NAME,
+                FAILURE_ID,
+            ));
+        }
+
+        let index = LineIndex::new(file.source);
+        for error in file.errors {
+            let (line, column) = location(&index, error.span.position);
+
+            output.push_str(&format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}.{}\"/>\n",
+                line,
+                column,
+                match error.severity {
+                    ParseErrorSeverity::Notice => "info",
+                    ParseErrorSeverity::Warning => "warning",
+                    ParseErrorSeverity::Error => "error",
+                },
+                xml_escape(&message_with_note(error)),
+                TOOL_NAME,
+                xml_escape(&error.id),
+            ));
+        }
+
+        output.push_str("  </file>\n");
+    }
+
+    output.push_str("</checkstyle>");
+    output
+}
+
+fn junit(files: &[ReportedFile]) -> String {
+    let tests = files.len();
+    let failures = files.iter().filter(|file| !file.errors.is_empty()).count();
+    let errors = files.iter().filter(|file| file.failure.is_some()).count();
+
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\">\n",
+        TOOL_NAME, tests, failures, errors
+    ));
+    output.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\">\n",
+        TOOL_NAME, tests, failures, errors
+    ));
+
+    for file in files {
+        let path = xml_escape(file.path);
+
+        if let Some(failure) = file.failure {
+            output.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\">\n",
+                path, path
+            ));
+            output.push_str(&format!(
+                "      <error type=\"{}\" message=\"{}\"/>\n",
+                FAILURE_ID,
+                xml_escape(failure),
+            ));
+            output.push_str("    </testcase>\n");
+
+            continue;
+        }
+
+        if file.errors.is_empty() {
+            output.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\"/>\n",
+                path, path
+            ));
+
+            continue;
+        }
+
+        output.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\">\n",
+            path, path
+        ));
+
+        let index = LineIndex::new(file.source);
+        for error in file.errors {
+            let (line, column) = location(&index, error.span.position);
+
+            output.push_str(&format!(
+                "      <failure type=\"{}\" message=\"{}\">{}:{}:{} [{}] {}</failure>\n",
+                xml_escape(&error.id),
+                xml_escape(&error.message),
+                path,
+                line,
+                column,
+                xml_escape(&error.id),
+                xml_escape(&message_with_note(error)),
+            ));
+        }
+
+        output.push_str("    </testcase>\n");
+    }
+
+    output.push_str("  </testsuite>\n");
+    output.push_str("</testsuites>");
+    output
+}
+
+fn github(files: &[ReportedFile]) -> String {
+    let mut lines = Vec::new();
+
+    for file in files {
+        if let Some(failure) = file.failure {
+            lines.push(format!(
+                "::error file={},title={}::{}",
+                github_escape_property(file.path),
+                FAILURE_ID,
+                github_escape_data(failure),
+            ));
+        }
+
+        let index = LineIndex::new(file.source);
+        for error in file.errors {
+            let (line, column) = location(&index, error.span.position);
+
+            // If the primary error annotation covers a single line, GitHub can
+            // underline exactly the offending code.
+            let end_column = error
+                .annotations
+                .iter()
+                .find(|annotation| annotation.r#type == ParseErrorAnnotationType::Error)
+                .filter(|annotation| annotation.position == error.span.position)
+                .map(|annotation| location(&index, annotation.position + annotation.length))
+                .filter(|(end_line, _)| *end_line == line)
+                .map(|(_, end_column)| format!(",endColumn={}", end_column))
+                .unwrap_or_default();
+
+            lines.push(format!(
+                "::{} file={},line={},col={}{},title={}::{}",
+                error.severity,
+                github_escape_property(file.path),
+                line,
+                column,
+                end_column,
+                github_escape_property(&error.id),
+                github_escape_data(&message_with_note(error)),
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Compute the 1-based line and 1-based character column of the given byte offset.
+fn location(index: &LineIndex, position: usize) -> (usize, usize) {
+    let LineColumn { line, column } = index.position(position, ColumnUnit::Char);
+
+    (line, column)
+}
+
+/// The given path as a URI reference, as SARIF expects.
+///
+/// Relative paths stay relative, while absolute ones become `file` URIs.
+fn uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./");
+    let drive = matches!(path.as_bytes(), [letter, b':', b'/', ..] if letter.is_ascii_alphabetic());
+
+    let mut uri = String::with_capacity(path.len());
+    if path.starts_with('/') {
+        uri.push_str("file://");
+    } else if drive {
+        uri.push_str("file:///");
+    }
+
+    for (index, byte) in path.bytes().enumerate() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            // a colon in the first segment of a relative reference would be read as a scheme.
+            b':' if drive && index == 1 => uri.push(':'),
+            byte => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    uri
+}
+
+fn message_with_note(error: &ParseError) -> String {
+    match &error.note {
+        Some(note) => format!("{}\nnote: {}", error.message, note),
+        None => error.message.clone(),
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            // other control characters are not allowed in XML 1.0.
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn github_escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn github_escape_property(value: &str) -> String {
+    github_escape_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::token::Span;
+    use crate::parser::error::ParseErrorEdit;
+    use crate::source::SourceFile;
+
+    fn errors(source: &str) -> Vec<ParseError> {
+        crate::parse(source).err().unwrap().errors
+    }
+
+    #[test]
+    fn test_location_counts_characters() {
+        let location = |source: &str, position| location(&LineIndex::new(source), position);
+
+        assert_eq!(location("<?php\n$a = 1;", 6), (2, 1));
+        assert_eq!(location("<?php\n'é' + ;", 14), (2, 8));
+        assert_eq!(location("<?php", 100), (1, 6));
+    }
+
+    #[test]
+    fn test_machine_readable_formats() {
+        let source = "<?php\nclass {}\n";
+        let errors = errors(source);
+        let files = [
+            ReportedFile {
+                path: "a&b.php",
+                source,
+                errors: &errors,
+                file_offset: Some(0),
+                failure: None,
+            },
+            ReportedFile {
+                path: "ok.php",
+                source: "<?php",
+                errors: &[],
+                file_offset: Some(0),
+                failure: None,
+            },
+        ];
+
+        let json: Value =
+            serde_json::from_str(&report(ReportFormat::Json, &files, false, true).unwrap())
+                .unwrap();
+        assert_eq!(json["files"][0]["errors"][0]["id"], "E005");
+        assert_eq!(json["files"][0]["errors"][0]["line"], 2);
+        assert_eq!(json["files"][0]["errors"][0]["column"], 7);
+
+        let sarif: Value =
+            serde_json::from_str(&report(ReportFormat::Sarif, &files, false, true).unwrap())
+                .unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif["runs"][0]["results"][0]["ruleId"], "E005");
+        assert_eq!(
+            sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"]
+                ["startColumn"],
+            7
+        );
+
+        let checkstyle = report(ReportFormat::Checkstyle, &files, false, true).unwrap();
+        assert!(checkstyle.contains("<file name=\"a&amp;b.php\">"));
+        assert!(checkstyle.contains("line=\"2\" column=\"7\" severity=\"error\""));
+
+        let junit = report(ReportFormat::Junit, &files, false, true).unwrap();
+        assert!(junit.contains("tests=\"2\" failures=\"1\""));
+        assert!(junit.contains("<testcase name=\"ok.php\" classname=\"ok.php\"/>"));
+
+        assert_eq!(
+            report(ReportFormat::Github, &files, false, true).unwrap(),
+            "::error file=a&b.php,line=2,col=7,endColumn=8,title=E005::unexpected token `{`, expecting an identifier"
+        );
+    }
+
+    #[test]
+    fn test_sarif_byte_offsets() {
+        let sarif = |bytes: &[u8]| -> Value {
+            let file = SourceFile::new(bytes.to_vec());
+            let errors = crate::parse(&file.contents)
+                .unwrap_err()
+                .errors
+                .into_iter()
+                .map(|error| file.text_error(error))
+                .collect::<Vec<ParseError>>();
+            let files = [ReportedFile {
+                path: "a.php",
+                source: &file.text(),
+                errors: &errors,
+                file_offset: file.file_offset(),
+                failure: None,
+            }];
+
+            serde_json::from_str(&report(ReportFormat::Sarif, &files, false, true).unwrap())
+                .unwrap()
+        };
+
+        // the byte order mark is part of the file, but not of the text.
+        let bom = sarif(b"\xEF\xBB\xBF<?php function f(?mixed $a) {}");
+        let result = &bom["runs"][0]["results"][0];
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["byteOffset"],
+            21
+        );
+        assert_eq!(
+            result["fixes"][0]["artifactChanges"][0]["replacements"][0]["deletedRegion"],
+            json!({
+                "startLine": 1,
+                "startColumn": 18,
+                "endLine": 1,
+                "endColumn": 19,
+                "byteOffset": 20,
+                "byteLength": 1,
+            })
+        );
+
+        // the text of a Windows-1252 file has more bytes than the file.
+        let windows1252 = sarif(b"<?php echo '\xE9'; function f(?mixed $a) {}");
+        let result = &windows1252["runs"][0]["results"][0];
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"],
+            json!({ "startLine": 1, "startColumn": 29 })
+        );
+        assert_eq!(
+            result["fixes"][0]["artifactChanges"][0]["replacements"][0]["deletedRegion"],
+            json!({
+                "startLine": 1,
+                "startColumn": 28,
+                "endLine": 1,
+                "endColumn": 29,
+            })
+        );
+    }
+
+    #[test]
+    fn test_failures() {
+        let files = [
+            ReportedFile {
+                path: "ok.php",
+                source: "<?php",
+                errors: &[],
+                file_offset: Some(0),
+                failure: None,
+            },
+            ReportedFile {
+                path: "./src/a b.php",
+                source: "",
+                errors: &[],
+                file_offset: None,
+                failure: Some("failed to read `./src/a b.php`: permission denied"),
+            },
+        ];
+
+        let sarif: Value =
+            serde_json::from_str(&report(ReportFormat::Sarif, &files, false, true).unwrap())
+                .unwrap();
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "io-error");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/a%20b.php"
+        );
+
+        let junit = report(ReportFormat::Junit, &files, false, true).unwrap();
+        assert!(junit.contains("tests=\"2\" failures=\"0\" errors=\"1\""));
+        assert!(junit.contains(
+            "<error type=\"io-error\" message=\"failed to read `./src/a b.php`: permission denied\"/>"
+        ));
+
+        let checkstyle = report(ReportFormat::Checkstyle, &files, false, true).unwrap();
+        assert!(checkstyle.contains("source=\"php-parser-rs.io-error\""));
+
+        assert_eq!(report(ReportFormat::Text, &files, false, true).unwrap(), "");
+    }
+
+    #[test]
+    fn test_uri() {
+        assert_eq!(uri("src/Foo.php"), "src/Foo.php");
+        assert_eq!(uri("./src/a b#1.php"), "src/a%20b%231.php");
+        assert_eq!(uri("src\\é.php"), "src/%C3%A9.php");
+        assert_eq!(uri("a:b.php"), "a%3Ab.php");
+        assert_eq!(uri("/home/a b.php"), "file:///home/a%20b.php");
+        assert_eq!(uri("C:\\src\\a.php"), "file:///C:/src/a.php");
+    }
+
+    #[test]
+    fn test_severity() {
+        let source = "<?php\n@foo();\n";
+        let errors = [ParseError::new(
+            "no-error-suppression",
+            "error suppression",
+            Span::new(2, 1, 6),
+        )
+        .severity(ParseErrorSeverity::Warning)];
+        let files = [ReportedFile {
+            path: "a.php",
+            source,
+            errors: &errors,
+            file_offset: Some(0),
+            failure: None,
+        }];
+
+        let json: Value =
+            serde_json::from_str(&report(ReportFormat::Json, &files, false, true).unwrap())
+                .unwrap();
+        assert_eq!(json["files"][0]["errors"][0]["severity"], "warning");
+
+        let sarif: Value =
+            serde_json::from_str(&report(ReportFormat::Sarif, &files, false, true).unwrap())
+                .unwrap();
+        assert_eq!(sarif["runs"][0]["results"][0]["level"], "warning");
+
+        let checkstyle = report(ReportFormat::Checkstyle, &files, false, true).unwrap();
+        assert!(checkstyle.contains("severity=\"warning\""));
+
+        assert!(report(ReportFormat::Github, &files, false, true)
+            .unwrap()
+            .starts_with("::warning file=a.php,line=2,col=1"));
+    }
+
+    #[test]
+    fn test_text_report_of_fixes() {
+        let source = "<?php\n@foo();\n";
+        let errors = [ParseError::new(
+            "no-error-suppression",
+            "error suppression",
+            Span::new(2, 1, 6),
+        )
+        .error("try removing this", 6, 1)
+        .fix("remove the `@`", vec![ParseErrorEdit::remove(6, 1)])
+        .suggestion(
+            "wrap the call in a `try` block",
+            vec![ParseErrorEdit::insert(6, "try { ")],
+        )];
+        let files = [ReportedFile {
+            path: "a.php",
+            source,
+            errors: &errors,
+            file_offset: Some(0),
+            failure: None,
+        }];
+
+        let text = report(ReportFormat::Text, &files, false, true).unwrap();
+        assert!(text.contains("remove the `@`, or wrap the call in a `try` block"));
+    }
+
+    #[test]
+    fn test_errors_without_fixes_deserialize() {
+        let mut json =
+            serde_json::to_value(ParseError::new("E001", "error", Span::new(1, 1, 0))).unwrap();
+        json.as_object_mut().unwrap().remove("fixes");
+        json.as_object_mut().unwrap().remove("severity");
+
+        let error: ParseError = serde_json::from_value(json).unwrap();
+        assert!(error.fixes.is_empty());
+    }
+}
@@ -0,0 +1,329 @@
+use std::borrow::Cow;
+use std::io;
+use std::path::Path;
+
+use crate::lexer::token::OpenTagKind;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::parser::error::ParseError;
+
+/// The byte order mark of UTF-8, which some editors write at the start of files.
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// The encodings PHP files can be decoded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Utf8,
+    /// ISO-8859-1, where every byte is the character of the same code point.
+    Latin1,
+    /// Windows-1252, which is Latin-1 with printable characters instead of most of the
+    /// control characters from `0x80` to `0x9F`.
+    Windows1252,
+}
+
+impl Encoding {
+    /// The encoding of the given name, as used by `declare(encoding='...')`.
+    ///
+    /// The name is case-insensitive, and dashes and underscores are ignored.
+    pub fn from_name(name: &[u8]) -> Option<Self> {
+        let name = name
+            .iter()
+            .filter(|byte| !matches!(byte, b'-' | b'_'))
+            .map(|byte| byte.to_ascii_lowercase())
+            .collect::<Vec<u8>>();
+
+        match name.as_slice() {
+            b"utf8" => Some(Self::Utf8),
+            b"iso88591" | b"latin1" => Some(Self::Latin1),
+            b"windows1252" | b"cp1252" => Some(Self::Windows1252),
+            _ => None,
+        }
+    }
+
+    /// Decode the given bytes.
+    ///
+    /// Bytes that aren't valid UTF-8 are replaced by `U+FFFD` when decoding UTF-8.
+    pub fn decode<'a>(&self, bytes: &'a [u8]) -> Cow<'a, str> {
+        match self {
+            Self::Utf8 => String::from_utf8_lossy(bytes),
+            _ if bytes.is_ascii() => Cow::Borrowed(std::str::from_utf8(bytes).unwrap()),
+            _ => Cow::Owned(bytes.iter().map(|byte| self.decode_byte(*byte)).collect()),
+        }
+    }
+
+    // the length of the given bytes once decoded.
+    fn decoded_len(&self, bytes: &[u8]) -> usize {
+        match self {
+            // every invalid sequence is replaced by a single `U+FFFD`.
+            Self::Utf8 => bytes
+                .utf8_chunks()
+                .map(|chunk| {
+                    chunk.valid().len()
+                        + if chunk.invalid().is_empty() {
+                            0
+                        } else {
+                            char::REPLACEMENT_CHARACTER.len_utf8()
+                        }
+                })
+                .sum(),
+            _ => bytes
+                .iter()
+                .map(|byte| self.decode_byte(*byte).len_utf8())
+                .sum(),
+        }
+    }
+
+    // decode a byte of a single-byte encoding.
+    fn decode_byte(&self, byte: u8) -> char {
+        if *self != Self::Windows1252 || !(0x80..=0x9F).contains(&byte) {
+            return byte as char;
+        }
+
+        // the 5 bytes Windows-1252 leaves undefined are decoded as Latin-1 ones.
+        match byte {
+            0x80 => '€',
+            0x82 => '‚',
+            0x83 => 'ƒ',
+            0x84 => '„',
+            0x85 => '…',
+            0x86 => '†',
+            0x87 => '‡',
+            0x88 => 'ˆ',
+            0x89 => '‰',
+            0x8A => 'Š',
+            0x8B => '‹',
+            0x8C => 'Œ',
+            0x8E => 'Ž',
+            0x91 => '‘',
+            0x92 => '’',
+            0x93 => '“',
+            0x94 => '”',
+            0x95 => '•',
+            0x96 => '–',
+            0x97 => '—',
+            0x98 => '˜',
+            0x99 => '™',
+            0x9A => 'š',
+            0x9B => '›',
+            0x9C => 'œ',
+            0x9E => 'ž',
+            0x9F => 'Ÿ',
+            byte => byte as char,
+        }
+    }
+}
+
+/// A PHP file, read as bytes, along with the encoding it is written in.
+///
+/// The parser works on bytes, so `contents` can be parsed whatever its encoding is,
+/// while `text()` is meant for displaying the code, such as in reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    /// The contents of the file, without its byte order mark.
+    ///
+    /// The positions of the tokens and nodes parsed from the file are byte offsets into
+    /// the contents, rather than into the file.
+    pub contents: Vec<u8>,
+    pub encoding: Encoding,
+    /// Whether the file starts with a UTF-8 byte order mark.
+    pub bom: bool,
+}
+
+impl SourceFile {
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(std::fs::read(path)?))
+    }
+
+    /// Detect the encoding of the given bytes.
+    ///
+    /// A byte order mark means the file is UTF-8. Otherwise, the encoding is the one
+    /// declared by a `declare(encoding='...')` statement at the start of the file, or
+    /// UTF-8 if the file is valid UTF-8, and Windows-1252 if it isn't.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        if let Some(contents) = bytes.strip_prefix(UTF8_BOM) {
+            return Self {
+                contents: contents.to_vec(),
+                encoding: Encoding::Utf8,
+                bom: true,
+            };
+        }
+
+        let encoding = declared_encoding(&bytes).unwrap_or_else(|| {
+            if std::str::from_utf8(&bytes).is_ok() {
+                Encoding::Utf8
+            } else {
+                Encoding::Windows1252
+            }
+        });
+
+        Self {
+            contents: bytes,
+            encoding,
+            bom: false,
+        }
+    }
+
+    /// The contents, decoded into UTF-8.
+    pub fn text(&self) -> Cow<'_, str> {
+        self.encoding.decode(&self.contents)
+    }
+
+    /// The byte offset into `text()` of the given byte offset into `contents`.
+    pub fn text_offset(&self, offset: usize) -> usize {
+        let offset = offset.min(self.contents.len());
+
+        self.encoding.decoded_len(&self.contents[..offset])
+    }
+
+    /// The byte offset into the file of the start of `text()`, if the byte offsets into
+    /// `text()` are byte offsets into the file too, once shifted by it.
+    ///
+    /// They aren't when decoding changed the bytes of the file, such as for files in
+    /// another encoding than UTF-8, or containing invalid UTF-8.
+    pub fn file_offset(&self) -> Option<usize> {
+        match self.text() {
+            Cow::Borrowed(_) if self.bom => Some(UTF8_BOM.len()),
+            Cow::Borrowed(_) => Some(0),
+            Cow::Owned(_) => None,
+        }
+    }
+
+    /// Move the positions of the given error, which are byte offsets into `contents`,
+    /// to the matching byte offsets into `text()`, so that it can be reported.
+    pub fn text_error(&self, mut error: ParseError) -> ParseError {
+        let line_start = error.span.position.saturating_sub(error.span.column - 1);
+
+        error.span.position = self.text_offset(error.span.position);
+        error.span.column = error.span.position - self.text_offset(line_start) + 1;
+
+        for annotation in &mut error.annotations {
+            let end = self.text_offset(annotation.position + annotation.length);
+
+            annotation.position = self.text_offset(annotation.position);
+            annotation.length = end - annotation.position;
+        }
+
+        for edit in error.fixes.iter_mut().flat_map(|fix| &mut fix.edits) {
+            let end = self.text_offset(edit.position + edit.length);
+
+            edit.position = self.text_offset(edit.position);
+            edit.length = end - edit.position;
+        }
+
+        error
+    }
+
+    /// The bytes of the file, with the given contents, such as fixed ones.
+    ///
+    /// The byte order mark is kept, if the file had one.
+    pub fn file_bytes(&self, contents: &[u8]) -> Vec<u8> {
+        if self.bom {
+            [UTF8_BOM, contents].concat()
+        } else {
+            contents.to_vec()
+        }
+    }
+}
+
+// the encoding declared by `<?php declare(encoding='...')`, which must be the first
+// statement of the file.
+fn declared_encoding(bytes: &[u8]) -> Option<Encoding> {
+    let mut tokens = Lexer::new().tokens(bytes).filter(|token| {
+        !matches!(
+            token.kind,
+            TokenKind::SingleLineComment
+                | TokenKind::MultiLineComment
+                | TokenKind::HashMarkComment
+                | TokenKind::DocumentComment
+        )
+    });
+
+    fn expect(tokens: &mut impl Iterator<Item = Token>, kind: TokenKind) -> Option<Token> {
+        tokens.next().filter(|token| token.kind == kind)
+    }
+
+    expect(&mut tokens, TokenKind::OpenTag(OpenTagKind::Full))?;
+    expect(&mut tokens, TokenKind::Declare)?;
+    expect(&mut tokens, TokenKind::LeftParen)?;
+
+    loop {
+        let key = expect(&mut tokens, TokenKind::Identifier)?;
+        expect(&mut tokens, TokenKind::Equals)?;
+        let value = tokens.next()?;
+
+        if key.value.eq_ignore_ascii_case(b"encoding") {
+            return match value.kind {
+                TokenKind::LiteralSingleQuotedString | TokenKind::LiteralDoubleQuotedString => {
+                    Encoding::from_name(&value.value)
+                }
+                _ => None,
+            };
+        }
+
+        expect(&mut tokens, TokenKind::Comma)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_order_mark() {
+        let file = SourceFile::new(b"\xEF\xBB\xBF<?php $a = 1;".to_vec());
+
+        assert!(file.bom);
+        assert_eq!(file.encoding, Encoding::Utf8);
+        assert_eq!(file.contents, b"<?php $a = 1;");
+        assert!(crate::parse(&file.contents).is_ok());
+        assert_eq!(file.file_bytes(b"<?php"), b"\xEF\xBB\xBF<?php");
+        assert_eq!(file.file_offset(), Some(3));
+    }
+
+    #[test]
+    fn test_declared_encoding() {
+        let encoding = |code: &[u8]| SourceFile::new(code.to_vec()).encoding;
+
+        assert_eq!(
+            encoding(b"<?php declare(encoding='ISO-8859-1'); echo 'caf\xE9';"),
+            Encoding::Latin1
+        );
+        assert_eq!(
+            encoding(b"<?php\n// legacy\ndeclare(strict_types=1, ENCODING=\"latin1\");"),
+            Encoding::Latin1
+        );
+        assert_eq!(
+            encoding(b"<?php declare(encoding='utf-8'); echo 'caf\xC3\xA9';"),
+            Encoding::Utf8
+        );
+        assert_eq!(encoding(b"<?php echo 'caf\xE9';"), Encoding::Windows1252);
+        assert_eq!(encoding(b"<?php echo 'caf\xC3\xA9';"), Encoding::Utf8);
+        assert_eq!(
+            encoding(b"<?php $a = 1; declare(encoding='latin1');"),
+            Encoding::Utf8
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let file = SourceFile::new(b"<?php echo '\x80 caf\xE9' . ;".to_vec());
+
+        assert_eq!(file.text(), "<?php echo '€ café' . ;");
+        assert_eq!(file.file_offset(), None);
+        assert_eq!(
+            SourceFile::new(b"<?php echo 'caf\xC3\xA9';".to_vec()).file_offset(),
+            Some(0)
+        );
+
+        let error = crate::parse(&file.contents).unwrap_err().errors.remove(0);
+        assert_eq!(error.span.position, 22);
+
+        let error = file.text_error(error);
+        assert_eq!(error.span.position, 25);
+        assert_eq!(error.span.column, 26);
+        assert_eq!(&file.text()[error.span.position..], ";");
+        assert_eq!(error.annotations[0].position, 25);
+        assert_eq!(error.annotations[0].length, 1);
+    }
+}
@@ -0,0 +1,5 @@
+<?php
+
+$a = <<<EOT
+    foo
+    bar
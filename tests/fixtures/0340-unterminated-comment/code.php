@@ -0,0 +1,7 @@
+<?php
+
+foo();
+
+/* bar();
+
+baz();
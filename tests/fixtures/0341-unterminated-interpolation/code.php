@@ -0,0 +1,3 @@
+<?php
+
+$a = "foo {$bar->baz
@@ -0,0 +1,5 @@
+<?php
+
+$a = "\u{zz}";
+$b = "\400";
+$c = ;
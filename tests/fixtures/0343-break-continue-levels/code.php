@@ -0,0 +1,12 @@
+<?php
+
+foreach ($a as $b) {
+    while (true) {
+        switch ($b) {
+            case 1:
+                continue 3;
+            default:
+                break 2;
+        }
+    }
+}
@@ -0,0 +1,15 @@
+<?php
+
+namespace App;
+
+final class User
+{
+    public function rename(string $name, string $name): void
+    {
+        return $this;
+    }
+
+    public function Rename(): void
+    {
+    }
+}
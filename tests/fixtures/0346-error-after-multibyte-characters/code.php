@@ -0,0 +1,3 @@
+<?php
+
+$greeting = 'héllo wörld 👋' . ;
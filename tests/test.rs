@@ -76,15 +76,22 @@ fn test_fixtures() -> io::Result<()> {
 
     entries.sort();
 
-    for entry in entries {
-        let test_fixture = TestFixture::new(entry);
-
-        test_fixture.validate()?;
-
-        run_test(&test_fixture)?;
-    }
-
-    Ok(())
+    // unoptimized builds need more than the default test stack for long chains.
+    std::thread::Builder::new()
+        .stack_size(16 * 1024 * 1024)
+        .spawn(move || {
+            for entry in entries {
+                let test_fixture = TestFixture::new(entry);
+
+                test_fixture.validate()?;
+
+                run_test(&test_fixture)?;
+            }
+
+            Ok(())
+        })?
+        .join()
+        .unwrap()
 }
 
 fn run_test(test_fixture: &TestFixture) -> io::Result<()> {